@@ -0,0 +1,5 @@
+fn main() {
+    // Generates the `grpc` service from its definition. Requires `protoc` to be installed.
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/arloader.proto").unwrap();
+}
@@ -1,4 +1,4 @@
-use arloader::OraclePrice;
+use arloader::Arweave;
 use futures::future::try_join;
 use num_bigint::BigUint;
 use num_traits::cast::ToPrimitive;
@@ -8,16 +8,7 @@ const BUNDLE_SIZE: usize = usize::pow(1024, 2) * 256;
 
 #[tokio::main]
 async fn main() {
-    let prices = reqwest::get(
-        "https://api.coingecko.com/api/v3/simple/price?ids=arweave,solana&vs_currencies=usd",
-    )
-    .await
-    .unwrap()
-    .json::<OraclePrice>()
-    .await
-    .unwrap();
-
-    let usd_per_ar: BigUint = BigUint::from((prices.arweave.usd * 100.0).floor() as u32);
+    let (usd_per_ar, _): (BigUint, _) = Arweave::default().get_fiat_prices().await.unwrap();
 
     println!(
         "Price in USD to upload {} MB of files of various sizes in KB (${:.2} USD per AR):\n",
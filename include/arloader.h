@@ -0,0 +1,74 @@
+/*
+ * C interface of arloader, built with the `ffi` feature:
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Functions returning int32_t return ARLOADER_OK or a negative error code. The message of the
+ * last error on the calling thread is returned by arloader_last_error.
+ */
+#ifndef ARLOADER_H
+#define ARLOADER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ARLOADER_OK 0
+/* A pointer was null or a string wasn't valid UTF-8. */
+#define ARLOADER_ERROR_INVALID_ARGUMENT -1
+/* The request failed. See arloader_last_error. */
+#define ARLOADER_ERROR_FAILED -2
+/* Arloader panicked. See arloader_last_error. */
+#define ARLOADER_ERROR_PANIC -3
+
+typedef enum ArloaderStatusCode {
+    ARLOADER_STATUS_SUBMITTED = 0,
+    ARLOADER_STATUS_PENDING = 1,
+    ARLOADER_STATUS_CONFIRMED = 2,
+    ARLOADER_STATUS_NOT_FOUND = 3,
+    ARLOADER_STATUS_BLOCKED = 4,
+    ARLOADER_STATUS_FAILED = 5,
+    ARLOADER_STATUS_DROPPED = 6,
+    ARLOADER_STATUS_SIGNED = 7,
+} ArloaderStatusCode;
+
+/* Status of an uploaded file. Free its strings with arloader_status_free. */
+typedef struct ArloaderStatus {
+    /* Id of the transaction, or of the data item for bundled files. */
+    char *id;
+    ArloaderStatusCode status;
+    uint64_t confirmations;
+    /* Reward paid in winstons, UINT64_MAX if it doesn't fit. */
+    uint64_t reward;
+} ArloaderStatus;
+
+typedef struct ArloaderClient ArloaderClient;
+
+/* Creates a client uploading with the keypair at keypair_path to base_url, or
+ * https://arweave.net/ if it is NULL, writing statuses to the existing directory log_dir.
+ * Returns NULL if the client couldn't be created. */
+ArloaderClient *arloader_client_new(const char *keypair_path, const char *base_url,
+                                    const char *log_dir);
+
+void arloader_client_free(ArloaderClient *client);
+
+/* Uploads the file at file_path, blocking until it is posted, and fills status. */
+int32_t arloader_upload_path(const ArloaderClient *client, const char *file_path,
+                             ArloaderStatus *status);
+
+/* Updates the status of the file uploaded from file_path from the network and fills status. */
+int32_t arloader_poll_status(const ArloaderClient *client, const char *file_path,
+                             ArloaderStatus *status);
+
+void arloader_status_free(ArloaderStatus *status);
+
+/* Valid until the next call that fails on the same thread. NULL if there wasn't an error. */
+const char *arloader_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ARLOADER_H */
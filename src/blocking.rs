@@ -0,0 +1,181 @@
+//! Synchronous versions of the upload, status and price functions of [`crate::Arweave`].
+
+use crate::{
+    error::Error,
+    status::{Status, TxStatusResponse},
+    transaction::{Base64, Tag},
+};
+use num_bigint::BigUint;
+use std::path::PathBuf;
+use tokio::runtime::{Builder, Runtime};
+use url::Url;
+
+/// Wraps a [`crate::Arweave`], blocking on each of its functions on a runtime of its own. Must
+/// not be used from within an async context, where blocking on the runtime panics.
+pub struct Arweave {
+    inner: crate::Arweave,
+    runtime: Runtime,
+}
+
+impl Arweave {
+    pub fn new(inner: crate::Arweave) -> Result<Self, Error> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { inner, runtime })
+    }
+
+    pub fn from_keypair_path(keypair_path: PathBuf, base_url: Url) -> Result<Self, Error> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        let inner = runtime.block_on(crate::Arweave::from_keypair_path(keypair_path, base_url))?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// Returns the wrapped [`crate::Arweave`], e.g. to read or change its settings.
+    pub fn inner(&self) -> &crate::Arweave {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut crate::Arweave {
+        &mut self.inner
+    }
+
+    /// See [`crate::Arweave::get_price`].
+    pub fn get_price(&self, bytes: &u64) -> Result<(BigUint, BigUint, BigUint), Error> {
+        self.runtime.block_on(self.inner.get_price(bytes))
+    }
+
+    /// See [`crate::Arweave::get_price_terms`].
+    pub fn get_price_terms(&self, reward_mult: f32) -> Result<(u64, u64), Error> {
+        self.runtime
+            .block_on(self.inner.get_price_terms(reward_mult))
+    }
+
+    /// See [`crate::Arweave::get_wallet_balance`].
+    pub fn get_wallet_balance(&self, wallet_address: Option<String>) -> Result<BigUint, Error> {
+        self.runtime
+            .block_on(self.inner.get_wallet_balance(wallet_address))
+    }
+
+    /// See [`crate::Arweave::upload_file_from_path`].
+    pub fn upload_file_from_path(
+        &self,
+        file_path: PathBuf,
+        log_dir: Option<PathBuf>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+        content_type: Option<&str>,
+    ) -> Result<Status, Error> {
+        self.runtime.block_on(self.inner.upload_file_from_path(
+            file_path,
+            log_dir,
+            additional_tags,
+            last_tx,
+            price_terms,
+            content_type,
+        ))
+    }
+
+    /// See [`crate::Arweave::upload_files_from_paths`].
+    pub fn upload_files_from_paths<IP, IT>(
+        &self,
+        paths_iter: IP,
+        log_dir: Option<PathBuf>,
+        tags_iter: Option<IT>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+    ) -> Result<Vec<Status>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+        IT: Iterator<Item = Option<Vec<Tag<Base64>>>> + Send,
+    {
+        self.runtime.block_on(self.inner.upload_files_from_paths(
+            paths_iter,
+            log_dir,
+            tags_iter,
+            last_tx,
+            price_terms,
+        ))
+    }
+
+    /// See [`crate::Arweave::get_status`].
+    pub fn get_status(&self, id: &Base64) -> Result<Status, Error> {
+        self.runtime.block_on(self.inner.get_status(id))
+    }
+
+    /// See [`crate::Arweave::get_tx_status`].
+    pub fn get_tx_status(&self, id: &Base64) -> Result<TxStatusResponse, Error> {
+        self.runtime.block_on(self.inner.get_tx_status(id))
+    }
+
+    /// See [`crate::Arweave::read_status`].
+    pub fn read_status(&self, file_path: PathBuf, log_dir: PathBuf) -> Result<Status, Error> {
+        self.runtime
+            .block_on(self.inner.read_status(file_path, log_dir))
+    }
+
+    /// See [`crate::Arweave::update_status`].
+    pub fn update_status(&self, file_path: PathBuf, log_dir: PathBuf) -> Result<Status, Error> {
+        self.runtime
+            .block_on(self.inner.update_status(file_path, log_dir))
+    }
+
+    /// See [`crate::Arweave::update_statuses`].
+    pub fn update_statuses<IP>(
+        &self,
+        paths_iter: IP,
+        log_dir: PathBuf,
+    ) -> Result<Vec<Status>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        self.runtime
+            .block_on(self.inner.update_statuses(paths_iter, log_dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arweave;
+    use crate::{
+        error::Error,
+        simulation::SimulatedGateway,
+        status::StatusCode,
+        transaction::{Base64, Tag},
+        utils::TempDir,
+    };
+    use std::{path::PathBuf, sync::Arc, time::Duration};
+
+    #[test]
+    fn test_blocking_upload() -> Result<(), Error> {
+        let arweave = Arweave::new(crate::Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..crate::Arweave::default()
+        })?;
+        let temp_dir = arweave.runtime.block_on(TempDir::from_str("./tests/"))?;
+        let paths = vec![
+            PathBuf::from("tests/fixtures/0.png"),
+            PathBuf::from("tests/fixtures/1.png"),
+        ];
+
+        let price_terms = arweave.get_price_terms(1.0)?;
+        let statuses = arweave.upload_files_from_paths(
+            paths.clone().into_iter(),
+            Some(temp_dir.0.clone()),
+            None::<std::vec::IntoIter<Option<Vec<Tag<Base64>>>>>,
+            None,
+            price_terms,
+        )?;
+        assert!(statuses.iter().all(|s| s.status == StatusCode::Submitted));
+
+        let statuses = arweave.update_statuses(paths.into_iter(), temp_dir.0.clone())?;
+        assert!(statuses.iter().all(|s| s.status == StatusCode::Confirmed));
+        assert_eq!(
+            arweave.get_status(&statuses[0].id)?.status,
+            StatusCode::Confirmed
+        );
+        Ok(())
+    }
+}
@@ -7,6 +7,22 @@ use bytes::BufMut;
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 
+/// ANS-104 signature type of [`DataItem`]s signed with Arweave RSA keys.
+pub const ARWEAVE_SIGNATURE_TYPE: u16 = 1;
+
+/// ANS-104 signature type of [`DataItem`]s signed with Ethereum secp256k1 keys.
+pub const ETHEREUM_SIGNATURE_TYPE: u16 = 3;
+
+/// Returns the byte lengths of the signature and owner of [`DataItem`]s with `signature_type`,
+/// or `None` if the signature type isn't supported.
+pub fn signature_lengths(signature_type: u16) -> Option<(usize, usize)> {
+    match signature_type {
+        ARWEAVE_SIGNATURE_TYPE => Some((512, 512)),
+        ETHEREUM_SIGNATURE_TYPE => Some((65, 65)),
+        _ => None,
+    }
+}
+
 /// Returns [`avro_rs::Schema`] for [`DataItem`] [`Tag`]s.
 pub fn get_tags_schema() -> Schema {
     let schema = r#"
@@ -43,7 +59,7 @@ impl Default for DataItem {
     fn default() -> Self {
         Self {
             id: Base64(Vec::with_capacity(32)),
-            signature_type: 1,
+            signature_type: ARWEAVE_SIGNATURE_TYPE,
             signature: Base64(Vec::with_capacity(512)),
             owner: Base64(Vec::with_capacity(512)),
             target: Base64(Vec::with_capacity(32)),
@@ -56,8 +72,10 @@ impl Default for DataItem {
 
 impl DataItem {
     pub fn serialize(&self) -> Result<Vec<u8>, Error> {
-        if self.signature.0.len() != 512 {
-            return Err(Error::UnsignedTransaction);
+        match signature_lengths(self.signature_type) {
+            Some((signature_len, _)) if self.signature.0.len() == signature_len => {}
+            Some(_) => return Err(Error::UnsignedTransaction),
+            None => return Err(Error::InvalidDataItem),
         }
         let mut buf = Vec::new().writer();
         buf.write(&self.signature_type.to_le_bytes())?;
@@ -101,16 +119,19 @@ impl DataItem {
 
         let result = [(); 2].map(|_| iter.next().unwrap());
         data_item.signature_type = u16::from_le_bytes(result);
-        if data_item.signature_type != 1 {
-            println!("invalid signature_type");
-            return Err(Error::InvalidDataItem);
-        }
+        let (signature_len, owner_len) = match signature_lengths(data_item.signature_type) {
+            Some(lengths) => lengths,
+            None => {
+                println!("invalid signature_type");
+                return Err(Error::InvalidDataItem);
+            }
+        };
 
-        for _ in 0..512 {
+        for _ in 0..signature_len {
             data_item.signature.0.push(iter.next().unwrap());
         }
 
-        for _ in 0..512 {
+        for _ in 0..owner_len {
             data_item.owner.0.push(iter.next().unwrap());
         }
 
@@ -156,6 +177,14 @@ impl DataItem {
         Ok(data_item)
     }
 
+    /// Returns true if the data item is tagged as a bundle, with other data items nested in its
+    /// data.
+    pub fn is_bundle(&self) -> bool {
+        self.tags
+            .iter()
+            .any(|t| t.name == "Bundle-Format" && t.value == "binary")
+    }
+
     /// Header is 64 bytes with first 32 for the size of the bytes le. Second
     /// 32 is id, which is the hashed signature.
     pub fn to_bundle_item(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
@@ -195,7 +224,7 @@ impl<'a> ToItems<'a, DataItem> for DataItem {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
 mod tests {
     use super::DataItem;
     use crate::{
@@ -0,0 +1,172 @@
+//! Uploading [`DataItem`]s to Bundlr nodes and verifying the receipts they return.
+
+use crate::{
+    bundle::DataItem,
+    crypto::Provider,
+    error::Error,
+    transaction::{Base64, DeepHashItem, ToItems},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Default Bundlr node used for uploading data items.
+pub const BUNDLR_NODE_URL: &str = "https://node1.bundlr.network/";
+
+/// Number of times the balance on a Bundlr node is polled after a funding transaction is
+/// confirmed before giving up on it being credited.
+pub const BUNDLR_FUNDING_RETRIES: u64 = 20;
+
+/// Information about a Bundlr node, as returned by its `info` endpoint. Funding transactions
+/// are sent to the address of the node for the currency.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct BundlrInfo {
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub addresses: HashMap<String, String>,
+}
+
+/// Balance of an address on a Bundlr node in winstons, as returned by its
+/// `account/balance/arweave` endpoint, which encodes it as either a string or a number.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct BundlrBalance {
+    pub balance: Value,
+}
+
+impl BundlrBalance {
+    pub fn winstons(&self) -> Result<u128, Error> {
+        match &self.balance {
+            Value::String(s) => s
+                .parse::<u128>()
+                .map_err(|_| Error::InvalidBundlrBalance(s.clone())),
+            Value::Number(n) => n
+                .as_u64()
+                .map(u128::from)
+                .ok_or_else(|| Error::InvalidBundlrBalance(n.to_string())),
+            v => Err(Error::InvalidBundlrBalance(v.to_string())),
+        }
+    }
+}
+
+/// Receipt returned by a Bundlr node when it accepts a [`DataItem`]. The node signs the receipt
+/// with its own key, committing to include the item in a bundle before `deadline_height`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlrReceipt {
+    pub id: Base64,
+    pub timestamp: u64,
+    pub version: String,
+    pub public: Base64,
+    pub signature: Base64,
+    pub deadline_height: u64,
+    #[serde(default)]
+    pub block: Option<u64>,
+}
+
+impl BundlrReceipt {
+    /// Verifies the receipt signature against the public key of the node included in the
+    /// receipt and checks that the receipt is for the expected [`DataItem`].
+    pub fn verify(&self, data_item: &DataItem, crypto: &Provider) -> Result<(), Error> {
+        if self.id != data_item.id {
+            return Err(Error::InvalidReceipt);
+        }
+        let deep_hash = crypto.deep_hash(self.to_deep_hash_item()?)?;
+        crypto
+            .verify_with_modulus(&self.public.0, &self.signature.0, &deep_hash)
+            .map_err(|_| Error::InvalidReceipt)
+    }
+}
+
+impl<'a> ToItems<'a, BundlrReceipt> for BundlrReceipt {
+    fn to_deep_hash_item(&'a self) -> Result<DeepHashItem, Error> {
+        let children: Vec<DeepHashItem> = vec![
+            "Bundlr".as_bytes(),
+            self.version.as_bytes(),
+            self.id.to_string().as_bytes(),
+            self.deadline_height.to_string().as_bytes(),
+            self.timestamp.to_string().as_bytes(),
+        ]
+        .into_iter()
+        .map(DeepHashItem::from_item)
+        .collect();
+
+        Ok(DeepHashItem::List(children))
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::{BundlrBalance, BundlrInfo, BundlrReceipt};
+    use crate::{
+        bundle::DataItem,
+        crypto::Provider,
+        error::Error,
+        transaction::{Base64, ToItems},
+    };
+    use matches::assert_matches;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_verify_receipt() -> Result<(), Error> {
+        let node =
+            Provider::from_keypair_path(PathBuf::from("tests/fixtures/test_key0.json")).await?;
+        let data_item = DataItem {
+            id: Base64(vec![7; 32]),
+            ..DataItem::default()
+        };
+
+        let mut receipt = BundlrReceipt {
+            id: data_item.id.clone(),
+            timestamp: 1640995200000,
+            version: "1.0.0".to_string(),
+            public: node.keypair_modulus()?,
+            signature: Base64::default(),
+            deadline_height: 850000,
+            block: None,
+        };
+        let deep_hash = node.deep_hash(receipt.to_deep_hash_item()?)?;
+        receipt.signature = Base64(node.sign(&deep_hash)?);
+
+        receipt.verify(&data_item, &Provider::default())?;
+
+        receipt.deadline_height += 1;
+        assert_matches!(
+            receipt.verify(&data_item, &Provider::default()),
+            Err(Error::InvalidReceipt)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundlr_balance_and_info() -> Result<(), Error> {
+        let balance: BundlrBalance = serde_json::from_str(r#"{"balance":"1000000000000"}"#)?;
+        assert_eq!(balance.winstons()?, 1000000000000);
+        let balance: BundlrBalance = serde_json::from_str(r#"{"balance":42}"#)?;
+        assert_eq!(balance.winstons()?, 42);
+        let balance: BundlrBalance = serde_json::from_str(r#"{"balance":"lots"}"#)?;
+        assert_matches!(balance.winstons(), Err(Error::InvalidBundlrBalance(_)));
+
+        let info: BundlrInfo = serde_json::from_str(
+            r#"{"version":"0.2.0","addresses":{"arweave":"OXcT1sVRSA5eGwt2k6Yuz8-3e3g9WJi5uSE99CWqsBs"},"gateway":"arweave.net"}"#,
+        )?;
+        assert_eq!(
+            info.addresses.get("arweave").map(String::as_str),
+            Some("OXcT1sVRSA5eGwt2k6Yuz8-3e3g9WJi5uSE99CWqsBs")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fund_bundlr_poll_secs() {
+        // Nothing listens on the url, so anything but the poll interval check would fail
+        // differently.
+        let bundlr_url = url::Url::parse("http://127.0.0.1:1/").unwrap();
+        assert_matches!(
+            crate::Arweave::default()
+                .fund_bundlr(1, bundlr_url, 0)
+                .await,
+            Err(Error::InvalidPollInterval)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Caches of gateway responses and transaction anchors.
+
+use crate::{
+    error::Error,
+    fetch_tx_anchor,
+    rt::{self, fs},
+    transaction::Base64,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tracing::debug;
+use url::Url;
+
+/// Default time a fetched anchor is reused for. Anchors are accepted for 50 blocks, about 100
+/// minutes, so this leaves plenty of margin for transactions that are slow to post.
+pub const ANCHOR_TTL_SECS: u64 = 600;
+
+/// Caches response bodies in `dir`, keyed by the BLAKE3 hash of the request url. Entries older
+/// than `ttl` are ignored and overwritten by the next response for the same url.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    pub dir: PathBuf,
+    pub ttl: Duration,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheEntry {
+    url: String,
+    fetched_at: DateTime<Utc>,
+    body: String,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    /// Returns the cached body for `url` if there is an entry younger than `ttl`.
+    pub async fn get(&self, url: &Url) -> Option<String> {
+        let data = fs::read_to_string(self.entry_path(url)).await.ok()?;
+        let entry: CacheEntry = serde_json::from_str(&data).ok()?;
+        let age = (Utc::now() - entry.fetched_at).to_std().ok()?;
+        if entry.url == url.as_str() && age < self.ttl {
+            Some(entry.body)
+        } else {
+            None
+        }
+    }
+
+    /// Writes the body of a response for `url` to the cache.
+    pub async fn insert(&self, url: &Url, body: &str) -> Result<(), Error> {
+        let entry = CacheEntry {
+            url: url.to_string(),
+            fetched_at: Utc::now(),
+            body: body.to_string(),
+        };
+        fs::create_dir_all(&self.dir).await?;
+        fs::write(self.entry_path(url), serde_json::to_string(&entry)?).await?;
+        Ok(())
+    }
+
+    fn entry_path(&self, url: &Url) -> PathBuf {
+        self.dir
+            .join(blake3::hash(url.as_str().as_bytes()).to_string())
+            .with_extension("json")
+    }
+}
+
+/// Shares one `tx_anchor` between all transactions created within `ttl` of fetching it. Clones
+/// share the same anchor.
+#[derive(Debug, Clone)]
+pub struct AnchorCache {
+    pub ttl: Duration,
+    anchor: Arc<Mutex<Option<(Base64, Instant)>>>,
+    refreshing: Arc<AtomicBool>,
+}
+
+impl AnchorCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            anchor: Arc::new(Mutex::new(None)),
+            refreshing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the cached anchor and its age if it is younger than `ttl`.
+    pub fn get(&self) -> Option<(Base64, Duration)> {
+        let anchor = self.anchor.lock().unwrap();
+        anchor
+            .as_ref()
+            .map(|(anchor, fetched_at)| (anchor.clone(), fetched_at.elapsed()))
+            .filter(|(_, age)| *age < self.ttl)
+    }
+
+    pub fn insert(&self, anchor: Base64) {
+        *self.anchor.lock().unwrap() = Some((anchor, Instant::now()));
+        self.refreshing.store(false, Ordering::SeqCst);
+    }
+
+    /// Claims the background refresh of the anchor, returning false if another refresh is
+    /// already running.
+    pub fn start_refresh(&self) -> bool {
+        !self.refreshing.swap(true, Ordering::SeqCst)
+    }
+
+    /// Releases the claim on the background refresh after it failed.
+    pub fn cancel_refresh(&self) {
+        self.refreshing.store(false, Ordering::SeqCst);
+    }
+
+    /// Spawns a task that fetches an anchor from `url` with `client` right away and then every
+    /// `interval`, until the returned handle is dropped. Failed fetches are logged and leave the
+    /// cached anchor as is, and rounds that find a refresh already claimed with
+    /// [`AnchorCache::start_refresh`] are skipped. With `interval` under half of `ttl`, uploads
+    /// never wait on an anchor request.
+    pub fn spawn_refresher(
+        &self,
+        client: reqwest::Client,
+        url: Url,
+        interval: Duration,
+    ) -> AnchorRefresher {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (anchor_cache, task_stopped) = (self.clone(), stopped.clone());
+        rt::spawn(async move {
+            while !task_stopped.load(Ordering::SeqCst) {
+                if anchor_cache.start_refresh() {
+                    match fetch_tx_anchor(&client, url.clone()).await {
+                        Ok(anchor) => anchor_cache.insert(anchor),
+                        Err(e) => {
+                            debug!("failed to refresh tx_anchor: {}", e);
+                            anchor_cache.cancel_refresh();
+                        }
+                    }
+                }
+                rt::sleep(interval).await;
+            }
+        });
+        AnchorRefresher { stopped }
+    }
+}
+
+/// Handle of the task spawned by [`AnchorCache::spawn_refresher`], which stops once the handle
+/// is dropped.
+#[derive(Debug)]
+pub struct AnchorRefresher {
+    stopped: Arc<AtomicBool>,
+}
+
+impl AnchorRefresher {
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Stops the task after its current fetch, if any.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for AnchorRefresher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnchorCache, ResponseCache};
+    use crate::{error::Error, transaction::Base64, utils::TempDir};
+    use std::{str::FromStr, time::Duration};
+    use url::Url;
+
+    #[test]
+    fn test_anchor_cache() {
+        let cache = AnchorCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get(), None);
+        cache.insert(Base64(vec![1; 32]));
+        let (anchor, age) = cache.clone().get().unwrap();
+        assert_eq!(anchor, Base64(vec![1; 32]));
+        assert!(age < Duration::from_secs(60));
+
+        assert!(cache.start_refresh());
+        assert!(!cache.start_refresh());
+        cache.insert(Base64(vec![2; 32]));
+        assert!(cache.start_refresh());
+
+        let expired = AnchorCache::new(Duration::from_secs(0));
+        expired.insert(Base64(vec![1; 32]));
+        assert_eq!(expired.get(), None);
+    }
+
+    #[tokio::test]
+    async fn test_anchor_refresher() {
+        use crate::utils::{http_response, serve_http};
+        use std::sync::{
+            atomic::{AtomicU8, Ordering},
+            Arc,
+        };
+
+        // yields instead of sleeping so that paused time only moves with `advance`
+        async fn wait_until(condition: impl Fn() -> bool) {
+            while !condition() {
+                tokio::task::yield_now().await;
+            }
+        }
+        async fn settle() {
+            for _ in 0..100 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        tokio::time::pause();
+        let requests = Arc::new(AtomicU8::new(0));
+        let served = requests.clone();
+        let (base_url, _) = serve_http(usize::MAX, move |_| {
+            let n = served.fetch_add(1, Ordering::SeqCst) + 1;
+            http_response(200, Base64(vec![n; 32]).to_string())
+        });
+        let url = base_url.join("tx_anchor").unwrap();
+        let interval = Duration::from_secs(60);
+        // timers round their deadlines up to the next millisecond
+        let step = interval + Duration::from_millis(1);
+        let anchor_of = |cache: &AnchorCache| cache.get().map(|(anchor, _)| anchor.0[0]);
+
+        let cache = AnchorCache::new(Duration::from_secs(600));
+        let refresher = cache.spawn_refresher(reqwest::Client::new(), url, interval);
+        wait_until(|| anchor_of(&cache) == Some(1)).await;
+
+        tokio::time::advance(step).await;
+        wait_until(|| anchor_of(&cache) == Some(2)).await;
+
+        // a refresh claimed elsewhere skips the round
+        assert!(cache.start_refresh());
+        tokio::time::advance(step).await;
+        settle().await;
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert_eq!(anchor_of(&cache), Some(2));
+
+        cache.cancel_refresh();
+        tokio::time::advance(step).await;
+        wait_until(|| anchor_of(&cache) == Some(3)).await;
+
+        drop(refresher);
+        tokio::time::advance(step).await;
+        settle().await;
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_response_cache() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let url = Url::from_str("https://arweave.net/tx/pending").unwrap();
+
+        let cache = ResponseCache::new(temp_dir.0.clone(), Duration::from_secs(60));
+        assert_eq!(cache.get(&url).await, None);
+        cache.insert(&url, "[]").await?;
+        assert_eq!(cache.get(&url).await, Some("[]".to_string()));
+
+        let expired = ResponseCache::new(temp_dir.0.clone(), Duration::from_secs(0));
+        assert_eq!(expired.get(&url).await, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,571 @@
+//! Configuration for the HTTP client used by [`crate::Arweave`] for network requests.
+
+use crate::{error::Error, rt::sleep};
+use chrono::{DateTime, Utc};
+use futures::Future;
+use num_bigint::BigUint;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Certificate, Client, Proxy,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::{
+    mpsc::UnboundedSender, Mutex as AsyncMutex, Notify, OwnedSemaphorePermit, Semaphore,
+};
+use tracing::{info, warn};
+use url::Url;
+
+/// Seconds between probes of a gateway under maintenance that didn't send a `Retry-After`.
+pub const MAINTENANCE_PROBE_SECS: u64 = 30;
+
+/// Options used to build the [`Client`] stored on [`crate::Arweave`]. The default config
+/// produces the same client as [`Client::new`], which picks up proxies from the standard
+/// `HTTP_PROXY` and `HTTPS_PROXY` environment variables.
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    /// Timeout applied to each request from when it starts connecting until the response body
+    /// has been read.
+    pub timeout: Option<Duration>,
+    /// Timeout applied to the connect phase of each request.
+    pub connect_timeout: Option<Duration>,
+    /// Proxy that all requests are routed through.
+    pub proxy: Option<Url>,
+    /// Paths of additional PEM encoded root certificates to trust, e.g. for corporate proxies
+    /// that terminate TLS.
+    pub root_certificate_paths: Vec<PathBuf>,
+    /// Maximum number of idle connections kept open to each host for reuse, unlimited if not
+    /// set.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long idle connections are kept open for reuse, 90 seconds if not set.
+    pub pool_idle_timeout: Option<Duration>,
+}
+
+impl ClientConfig {
+    /// Builds a [`Client`] from the config.
+    pub fn build(&self) -> Result<Client, Error> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy.clone())?);
+        }
+        for path in &self.root_certificate_paths {
+            let pem = fs::read(path)?;
+            builder = builder.add_root_certificate(Certificate::from_pem(&pem)?);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Limits the number of concurrent posts to each host. Every host gets its own semaphore, so a
+/// slow host only holds up requests to itself while requests to other hosts keep their full
+/// concurrency. Set it on [`crate::Arweave::host_limiter`].
+#[derive(Debug, Clone)]
+pub struct HostLimiter {
+    /// Maximum number of concurrent requests to a single host.
+    pub permits_per_host: usize,
+    semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl HostLimiter {
+    pub fn new(permits_per_host: usize) -> Self {
+        Self {
+            permits_per_host,
+            semaphores: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits for a permit for the host of `url`. The permit is released when it is dropped.
+    pub async fn acquire(&self, url: &Url) -> OwnedSemaphorePermit {
+        let semaphore = self
+            .semaphores
+            .lock()
+            .unwrap()
+            .entry(url.host_str().unwrap_or_default().to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.permits_per_host)))
+            .clone();
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("host semaphores are never closed")
+    }
+}
+
+/// Limits the number of concurrent uploads of files and bundles by the error rate of posts
+/// instead of a fixed buffer. The limit is halved when the gateway responds with
+/// `429 Too Many Requests` or a `5xx` error, and raised by one after as many successful posts in
+/// a row as the limit, up to `max`. `503 Service Unavailable` is left to
+/// [`MaintenanceGate`] and not counted. Set it on [`crate::Arweave::adaptive_concurrency`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveConcurrency {
+    /// Limit that concurrency starts at and ramps back up to.
+    pub max: usize,
+    state: Arc<Mutex<AdaptiveState>>,
+    released: Arc<Notify>,
+}
+
+#[derive(Debug)]
+struct AdaptiveState {
+    limit: usize,
+    in_flight: usize,
+    successes: usize,
+}
+
+/// Permit for an upload from [`AdaptiveConcurrency::acquire`], released when it is dropped.
+#[derive(Debug)]
+pub struct AdaptivePermit {
+    concurrency: AdaptiveConcurrency,
+}
+
+impl Drop for AdaptivePermit {
+    fn drop(&mut self) {
+        self.concurrency.state.lock().unwrap().in_flight -= 1;
+        self.concurrency.released.notify_waiters();
+    }
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            max,
+            state: Arc::new(Mutex::new(AdaptiveState {
+                limit: max,
+                in_flight: 0,
+                successes: 0,
+            })),
+            released: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Current limit on concurrent uploads.
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// Waits until fewer uploads than the current limit are in flight.
+    pub async fn acquire(&self) -> AdaptivePermit {
+        loop {
+            let released = self.released.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.in_flight < state.limit {
+                    state.in_flight += 1;
+                    return AdaptivePermit {
+                        concurrency: self.clone(),
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+
+    /// Records the outcome of a post, `false` for rate limiting and server errors.
+    pub fn record(&self, succeeded: bool) {
+        let mut state = self.state.lock().unwrap();
+        if succeeded {
+            state.successes += 1;
+            if state.successes >= state.limit && state.limit < self.max {
+                state.limit += 1;
+                state.successes = 0;
+                drop(state);
+                self.released.notify_waiters();
+            }
+        } else {
+            state.successes = 0;
+            if state.limit > 1 {
+                state.limit /= 2;
+                warn!(
+                    "gateway is struggling, reducing concurrency to {}",
+                    state.limit
+                );
+            }
+        }
+    }
+}
+
+/// Change of state of a [`MaintenanceGate`], sent to the channel set with
+/// [`MaintenanceGate::with_events`] to report progress.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaintenanceEvent {
+    /// The gateway responded with `503 Service Unavailable` and posts are paused.
+    Paused { retry_after: Option<Duration> },
+    /// The gateway responded normally again after being paused for `paused_for`.
+    Resumed { paused_for: Duration },
+}
+
+/// Pauses posts while a gateway is under maintenance instead of failing them. Posts that get a
+/// `503 Service Unavailable` response wait for its `Retry-After`, or `probe_interval` without
+/// one, and are then sent again as probes until the gateway responds normally, while every other
+/// post waits for them to get through. Clones share the same state. Set it on
+/// [`crate::Arweave::maintenance`]; without it, maintenance responses fail with
+/// [`Error::GatewayUnavailable`].
+#[derive(Debug, Clone)]
+pub struct MaintenanceGate {
+    pub probe_interval: Duration,
+    paused_since: Arc<Mutex<Option<Instant>>>,
+    resumed: Arc<Notify>,
+    events: Option<UnboundedSender<MaintenanceEvent>>,
+}
+
+impl MaintenanceGate {
+    pub fn new(probe_interval: Duration) -> Self {
+        Self {
+            probe_interval,
+            paused_since: Arc::new(Mutex::new(None)),
+            resumed: Arc::new(Notify::new()),
+            events: None,
+        }
+    }
+
+    /// Sends [`MaintenanceEvent`]s to `events` when posts are paused and resumed.
+    pub fn with_events(mut self, events: UnboundedSender<MaintenanceEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_since.lock().unwrap().is_some()
+    }
+
+    /// Waits until posts are resumed, returning immediately if they aren't paused.
+    pub async fn wait_until_resumed(&self) {
+        loop {
+            // Created before checking, so that a resume in between isn't missed.
+            let resumed = self.resumed.notified();
+            if !self.is_paused() {
+                return;
+            }
+            resumed.await;
+        }
+    }
+
+    /// Records a maintenance response, returning how long to wait before probing again.
+    pub fn pause(&self, retry_after: Option<Duration>) -> Duration {
+        let mut paused_since = self.paused_since.lock().unwrap();
+        if paused_since.is_none() {
+            *paused_since = Some(Instant::now());
+            warn!("gateway under maintenance, pausing posts");
+            self.send(MaintenanceEvent::Paused { retry_after });
+        }
+        retry_after.unwrap_or(self.probe_interval)
+    }
+
+    /// Records a normal response, resuming posts if they were paused.
+    pub fn resume(&self) {
+        if let Some(paused_since) = self.paused_since.lock().unwrap().take() {
+            let paused_for = paused_since.elapsed();
+            info!("gateway back from maintenance after {:?}", paused_for);
+            self.send(MaintenanceEvent::Resumed { paused_for });
+            self.resumed.notify_waiters();
+        }
+    }
+
+    fn send(&self, event: MaintenanceEvent) {
+        if let Some(events) = &self.events {
+            // Nobody listening for progress is not a reason to fail posts.
+            let _ = events.send(event);
+        }
+    }
+}
+
+/// What a [`BalanceGuard`] does when the balance of the wallet drops below the projected cost of
+/// the files left to upload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LowBalanceAction {
+    /// Holds uploads until the wallet is topped up, checking again every `check_interval`.
+    Pause,
+    /// Fails the remaining uploads with [`Error::InsufficientBalance`].
+    Abort,
+}
+
+/// Re-checks the balance of the wallet every `check_interval` during an upload run against the
+/// projected cost of the files left to upload, so that a run that outgrows its wallet stops
+/// cleanly instead of failing midway with gateway errors. Clones share the same state. Set it on
+/// [`crate::Arweave::balance_guard`].
+#[derive(Debug, Clone)]
+pub struct BalanceGuard {
+    pub check_interval: Duration,
+    pub action: LowBalanceAction,
+    remaining: Arc<Mutex<u128>>,
+    last_checked: Arc<AsyncMutex<Option<Instant>>>,
+}
+
+impl BalanceGuard {
+    pub fn new(check_interval: Duration, action: LowBalanceAction) -> Self {
+        Self {
+            check_interval,
+            action,
+            remaining: Arc::new(Mutex::new(0)),
+            last_checked: Arc::new(AsyncMutex::new(None)),
+        }
+    }
+
+    /// Projected cost in winstons of the files left to upload.
+    pub fn remaining(&self) -> u128 {
+        *self.remaining.lock().unwrap()
+    }
+
+    /// Adds the projected cost of files queued for upload.
+    pub fn add_remaining(&self, cost: u128) {
+        *self.remaining.lock().unwrap() += cost;
+    }
+
+    /// Removes the projected cost of a file whose upload finished, failed or was aborted.
+    pub fn record_uploaded(&self, cost: u128) {
+        let mut remaining = self.remaining.lock().unwrap();
+        *remaining = remaining.saturating_sub(cost);
+    }
+
+    /// Gets the balance with `get_balance` if `check_interval` has passed since the last check
+    /// and compares it with the remaining cost, pausing or failing as `action` says if it is
+    /// short. Concurrent uploads wait for a check in progress, and so for the wallet to be
+    /// topped up while paused.
+    pub async fn check<F, Fut>(&self, get_balance: F) -> Result<(), Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<BigUint, Error>>,
+    {
+        let mut last_checked = self.last_checked.lock().await;
+        if matches!(*last_checked, Some(checked_at) if checked_at.elapsed() < self.check_interval) {
+            return Ok(());
+        }
+        loop {
+            let available = get_balance().await?;
+            *last_checked = Some(Instant::now());
+            let required = BigUint::from(self.remaining());
+            if available >= required {
+                return Ok(());
+            }
+            match self.action {
+                LowBalanceAction::Abort => {
+                    // Checked again by the next upload, which fails too unless topped up.
+                    *last_checked = None;
+                    return Err(Error::InsufficientBalance {
+                        required,
+                        available,
+                    });
+                }
+                LowBalanceAction::Pause => {
+                    warn!(
+                        "balance of {} winstons is below the {} winstons needed for the \
+                        remaining files, pausing uploads",
+                        available, required
+                    );
+                    sleep(self.check_interval).await;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the delay requested by the `Retry-After` header in `headers`, given either in seconds
+/// or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        retry_after, AdaptiveConcurrency, BalanceGuard, ClientConfig, HostLimiter,
+        LowBalanceAction, MaintenanceEvent, MaintenanceGate, RETRY_AFTER,
+    };
+    use crate::error::Error;
+    use matches::assert_matches;
+    use num_bigint::BigUint;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use std::{
+        path::PathBuf,
+        str::FromStr,
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+    use tokio::sync::mpsc::unbounded_channel;
+    use url::Url;
+
+    #[test]
+    fn test_build_client() -> Result<(), Error> {
+        let config = ClientConfig {
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(5)),
+            proxy: Some(Url::from_str("http://localhost:3128").unwrap()),
+            pool_max_idle_per_host: Some(32),
+            pool_idle_timeout: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        config.build()?;
+
+        let config = ClientConfig {
+            root_certificate_paths: vec![PathBuf::from("tests/fixtures/missing.pem")],
+            ..Default::default()
+        };
+        assert_matches!(config.build(), Err(Error::IOError(_)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_host_limiter() {
+        let limiter = HostLimiter::new(1);
+        let primary = Url::from_str("https://arweave.net/tx/").unwrap();
+        let fallback = Url::from_str("https://arweave.dev/tx/").unwrap();
+
+        let permit = limiter.acquire(&fallback).await;
+        let wait = Duration::from_millis(50);
+        assert!(tokio::time::timeout(wait, limiter.acquire(&primary))
+            .await
+            .is_ok());
+        assert!(tokio::time::timeout(wait, limiter.acquire(&fallback))
+            .await
+            .is_err());
+        drop(permit);
+        assert!(tokio::time::timeout(wait, limiter.acquire(&fallback))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency() {
+        let concurrency = AdaptiveConcurrency::new(4);
+        let wait = Duration::from_millis(50);
+        let mut permits = Vec::new();
+        for _ in 0..4 {
+            permits.push(concurrency.acquire().await);
+        }
+        assert!(tokio::time::timeout(wait, concurrency.acquire())
+            .await
+            .is_err());
+
+        concurrency.record(false);
+        assert_eq!(concurrency.limit(), 2);
+        permits.truncate(2);
+        assert!(tokio::time::timeout(wait, concurrency.acquire())
+            .await
+            .is_err());
+
+        concurrency.record(true);
+        concurrency.record(true);
+        assert_eq!(concurrency.limit(), 3);
+        assert!(tokio::time::timeout(wait, concurrency.acquire())
+            .await
+            .is_ok());
+        for _ in 0..10 {
+            concurrency.record(true);
+        }
+        assert_eq!(concurrency.limit(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_balance_guard() -> Result<(), Error> {
+        let guard = BalanceGuard::new(Duration::from_millis(50), LowBalanceAction::Abort);
+        guard.add_remaining(1000);
+        guard.check(|| async { Ok(BigUint::from(1000u32)) }).await?;
+        // not checked again within the interval
+        guard.check(|| async { Ok(BigUint::from(0u32)) }).await?;
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_matches!(
+            guard.check(|| async { Ok(BigUint::from(999u32)) }).await,
+            Err(Error::InsufficientBalance { .. })
+        );
+        assert_matches!(
+            guard.check(|| async { Ok(BigUint::from(999u32)) }).await,
+            Err(Error::InsufficientBalance { .. })
+        );
+        guard.record_uploaded(500);
+        assert_eq!(guard.remaining(), 500);
+        guard.check(|| async { Ok(BigUint::from(999u32)) }).await?;
+
+        let paused = BalanceGuard::new(Duration::from_millis(10), LowBalanceAction::Pause);
+        paused.add_remaining(1000);
+        let checks = AtomicU32::new(0);
+        paused
+            .check(|| {
+                let n = checks.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(BigUint::from(n * 500)) }
+            })
+            .await?;
+        assert_eq!(checks.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(0)));
+    }
+
+    #[test]
+    fn test_maintenance_gate() {
+        let (tx, mut rx) = unbounded_channel();
+        let gate = MaintenanceGate::new(Duration::from_secs(30)).with_events(tx);
+
+        assert_eq!(gate.pause(None), Duration::from_secs(30));
+        assert_eq!(
+            gate.pause(Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        assert!(gate.is_paused());
+        gate.resume();
+        gate.resume();
+        assert!(!gate.is_paused());
+
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            MaintenanceEvent::Paused { retry_after: None }
+        );
+        assert_matches!(rx.try_recv(), Ok(MaintenanceEvent::Resumed { .. }));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_gate_holds_other_posts() {
+        let gate = MaintenanceGate::new(Duration::from_secs(30));
+        gate.wait_until_resumed().await;
+
+        gate.pause(None);
+        let waiting = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.wait_until_resumed().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+        gate.resume();
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -1,56 +1,341 @@
 //! Functions for Cli commands comprised of library functions.
 
 use crate::{
+    apply_reward_mult,
+    client::MaintenanceEvent,
+    download_stream,
     error::Error,
     file_stem_is_valid_txid,
+    format::NumberFormat,
+    report::ReportFormat,
     solana::{FLOOR, SOLANA_MAIN_URL, SOL_AR_BASE_URL},
-    status::{OutputFormat, StatusCode},
+    source::UploadSource,
+    status::{OutputFormat, StatusCode, StatusFilter, VerifyOutcome},
     transaction::{Base64, Tag},
-    update_bundle_statuses_stream, update_statuses_stream, upload_bundles_stream,
-    upload_bundles_stream_with_sol, upload_files_stream, upload_files_with_sol_stream, Arweave,
-    PathsChunk, BLOCK_SIZE, WINSTONS_PER_AR,
+    update_all_statuses_stream, update_bundle_statuses_stream, update_statuses_stream,
+    upload_bundles_stream, upload_bundles_stream_with_sol, upload_files_dedup_stream,
+    upload_files_stream, upload_files_with_sol_stream, upload_urls_stream, verify_uploads_stream,
+    Arweave, PathsChunk, BLOCK_SIZE, MAX_TX_DATA, WINSTONS_PER_AR,
 };
 
+#[cfg(feature = "serve")]
+use crate::serve::UploadServer;
+#[cfg(feature = "progress")]
+use crate::UploadProgress;
 use futures::{
     future::{try_join, try_join_all},
     StreamExt,
 };
 use glob::glob;
+#[cfg(feature = "progress")]
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use num_traits::cast::ToPrimitive;
+use regex::Regex;
+use serde_json::{json, Value};
 use solana_sdk::signer::keypair;
-use std::{path::PathBuf, str::FromStr};
+#[cfg(feature = "progress")]
+use std::collections::HashMap;
+#[cfg(feature = "serve")]
+use std::net::SocketAddr;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use tokio::{
     fs,
+    sync::mpsc::UnboundedReceiver,
     time::{sleep, Duration},
 };
 use url::Url;
 
 pub type CommandResult = Result<(), Error>;
 
+/// Prints pauses and resumptions of posts while the gateway is under maintenance.
+pub async fn print_maintenance_events(
+    mut events: UnboundedReceiver<MaintenanceEvent>,
+    output_format: OutputFormat,
+) {
+    while let Some(event) = events.recv().await {
+        match event {
+            MaintenanceEvent::Paused { .. } => output_format
+                .print_message("Gateway is under maintenance. Pausing uploads until it is back..."),
+            MaintenanceEvent::Resumed { paused_for } => output_format.print_message(format!(
+                "Gateway is back. Resuming uploads after {} seconds.",
+                paused_for.as_secs()
+            )),
+        }
+    }
+}
+
+/// Transaction being posted by an upload shown by [`show_upload_progress`].
+#[cfg(feature = "progress")]
+struct TransactionProgress {
+    files: u64,
+    data_size: u64,
+    posted: u64,
+    chunks_bar: Option<ProgressBar>,
+}
+
+/// Shows a progress bar of the upload of `files` files, with the bytes posted, rate and time
+/// left, and a bar for each transaction whose data is being posted in chunks. The byte total
+/// grows by the data size of each transaction as it starts, and a line is printed above the
+/// bars as each finishes. Returns once every file has been posted or skipped, or `events`
+/// closes.
+#[cfg(feature = "progress")]
+pub async fn show_upload_progress(mut events: UnboundedReceiver<UploadProgress>, files: u64) {
+    let multi = MultiProgress::new();
+    let total_bar = multi.add(ProgressBar::new(0));
+    total_bar.set_style(
+        ProgressStyle::with_template(
+            "{msg} files [{wide_bar}] {bytes}/{total_bytes} {binary_bytes_per_sec} eta {eta}",
+        )
+        .unwrap()
+        .progress_chars("=> "),
+    );
+    let chunks_style = ProgressStyle::with_template(" {prefix} [{wide_bar}] {bytes}/{total_bytes}")
+        .unwrap()
+        .progress_chars("=> ");
+
+    let mut files_done = 0;
+    total_bar.set_message(format!("{}/{}", files_done, files));
+    let mut transactions = HashMap::new();
+    while files_done < files {
+        let event = match events.recv().await {
+            Some(event) => event,
+            None => break,
+        };
+        match event {
+            UploadProgress::Started {
+                id,
+                files,
+                data_size,
+            } => {
+                total_bar.inc_length(data_size);
+                let progress = TransactionProgress {
+                    files,
+                    data_size,
+                    posted: 0,
+                    chunks_bar: None,
+                };
+                transactions.insert(id.to_string(), progress);
+            }
+            UploadProgress::ChunkPosted { id, bytes } => {
+                if let Some(progress) = transactions.get_mut(&id.to_string()) {
+                    progress.posted += bytes;
+                    total_bar.inc(bytes);
+                    let data_size = progress.data_size;
+                    progress
+                        .chunks_bar
+                        .get_or_insert_with(|| {
+                            let bar = multi.add(ProgressBar::new(data_size));
+                            bar.set_style(chunks_style.clone());
+                            bar.set_prefix(id.to_string());
+                            bar
+                        })
+                        .inc(bytes);
+                }
+            }
+            UploadProgress::Finished { id, posted } => {
+                if let Some(progress) = transactions.remove(&id.to_string()) {
+                    total_bar.inc(progress.data_size.saturating_sub(progress.posted));
+                    if let Some(chunks_bar) = progress.chunks_bar {
+                        chunks_bar.finish_and_clear();
+                        multi.remove(&chunks_bar);
+                    }
+                    files_done += progress.files;
+                    let _ = multi.println(format!(
+                        "{} {} {} file(s)",
+                        id,
+                        if posted { "posted" } else { "failed to post" },
+                        progress.files
+                    ));
+                }
+            }
+            UploadProgress::Skipped { files } => files_done += files,
+        }
+        total_bar.set_message(format!("{}/{}", files_done, files));
+    }
+    total_bar.finish();
+}
+
 /// Gets cost of uploading a list of files.
 pub async fn command_files(paths: Option<Vec<PathBuf>>) -> CommandResult {
     println!("{:?}", paths);
     Ok(())
 }
-/// Gets cost of uploading a list of files.
-pub async fn command_get_cost<IP>(
+/// Uploads `file_path` as a SmartWeave contract with the initial state in `init_state_path`,
+/// uploading the contract source from `contract_src_path` first if no `contract_src` is given.
+pub async fn command_deploy_contract(
+    arweave: &Arweave,
+    file_path: PathBuf,
+    init_state_path: PathBuf,
+    contract_src: Option<Base64>,
+    contract_src_path: Option<PathBuf>,
+    log_dir: Option<PathBuf>,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let init_state: Value = serde_json::from_slice(&fs::read(init_state_path).await?)?;
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+
+    let contract_src = if let Some(contract_src) = contract_src {
+        contract_src
+    } else {
+        let contract_src_path = contract_src_path.ok_or(Error::MissingFilePath)?;
+        let status = arweave
+            .deploy_contract_source(contract_src_path, log_dir.clone(), price_terms)
+            .await?;
+        if status.status.needs_reupload() {
+            output_format.print_message(format!(
+                "Failed to upload contract source: {}",
+                status.status
+            ));
+            return Ok(());
+        }
+        output_format.print_message(format!("Uploaded contract source {}.", status.id));
+        status.id
+    };
+
+    let status = arweave
+        .upload_atomic_asset(
+            file_path,
+            log_dir,
+            tags,
+            &contract_src,
+            &init_state,
+            price_terms,
+        )
+        .await?;
+    output_format.print_header(&status.header_string(output_format));
+    print!("{}", output_format.formatted_string(&status));
+    output_format.print_message(format!(
+        "\nRun `arloader get-status {}` to confirm the contract transaction.",
+        status.id
+    ));
+    Ok(())
+}
+
+/// Prints a digest of the statuses in `log_dir` and sends it to the webhook or email address
+/// configured on `arweave`.
+pub async fn command_digest(arweave: &Arweave, log_dir: PathBuf) -> CommandResult {
+    let digest = arweave.batch_digest(log_dir).await?;
+    print!("{}", digest);
+    arweave.send_digest(&digest).await
+}
+
+/// Downloads a transaction, or every file in a manifest, to `output_dir`, verifying the data
+/// against its transaction. `source` is either a transaction id or the path of a manifest file.
+pub async fn command_download(
+    arweave: &Arweave,
+    source: &str,
+    output_dir: PathBuf,
+    is_manifest: bool,
+    output_format: &OutputFormat,
+    buffer: usize,
+) -> CommandResult {
+    let source_path = PathBuf::from(source);
+    let downloads = if source_path.is_file() {
+        let manifest = serde_json::from_str(&fs::read_to_string(source_path).await?)?;
+        arweave.manifest_download_paths(&manifest, &output_dir)?
+    } else {
+        let id = Base64::from_str(source)?;
+        if is_manifest {
+            let (data, _) = arweave.get_verified_transaction_data(&id).await?;
+            let manifest = serde_json::from_slice(&data)?;
+            arweave.manifest_download_paths(&manifest, &output_dir)?
+        } else {
+            vec![(id.clone(), output_dir.join(id.to_string()))]
+        }
+    };
+
+    let mut stream = download_stream(arweave, downloads.into_iter(), buffer);
+    let mut counter = 0;
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(verification) => {
+                if counter == 0 {
+                    output_format.print_header(&verification.header_string(&output_format));
+                }
+                print!("{}", output_format.formatted_string(&verification));
+                counter += 1;
+            }
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
+        }
+    }
+    output_format.print_message(format!(
+        "\nDownloaded {} file(s) to {}.",
+        counter,
+        output_dir.display()
+    ));
+    Ok(())
+}
+
+/// Estimated cost of uploading files, as computed by [`estimate_upload_cost`].
+struct UploadCostEstimate {
+    transactions: usize,
+    files: usize,
+    bytes: u64,
+    /// Cost in winstons, or lamports if paid with SOL.
+    cost: u64,
+    fiat_cost: f32,
+}
+
+impl UploadCostEstimate {
+    fn units(with_sol: bool) -> &'static str {
+        match with_sol {
+            true => "lamports",
+            false => "winstons",
+        }
+    }
+
+    /// Cost in AR, or SOL if paid with SOL.
+    fn amount(&self, with_sol: bool) -> f64 {
+        match with_sol {
+            true => self.cost as f64 / 1e9,
+            false => self.cost as f64 / WINSTONS_PER_AR as f64,
+        }
+    }
+
+    fn summary(&self, arweave: &Arweave, with_sol: bool, number_format: &NumberFormat) -> String {
+        let units = Self::units(with_sol);
+        let cost_str = match with_sol {
+            true => format!("{} {}", number_format.integer(self.cost), units),
+            false => format!(
+                "{} {} ({} AR)",
+                number_format.integer(self.cost),
+                units,
+                number_format.winstons_as_ar(self.cost)
+            ),
+        };
+        format!(
+            "{} files with {} total bytes in {} transaction(s) is {} ({}{})",
+            number_format.integer(self.files),
+            number_format.integer(self.bytes),
+            number_format.integer(self.transactions),
+            cost_str,
+            arweave.currency.symbol(),
+            number_format.decimal(self.fiat_cost as f64, 4)
+        )
+    }
+}
+
+/// Estimates the cost of uploading `paths_iter`, either in bundles of up to `bundle_size` bytes
+/// or one transaction per file if `no_bundle`.
+async fn estimate_upload_cost<IP>(
     arweave: &Arweave,
     paths_iter: IP,
     reward_mult: f32,
     with_sol: bool,
     bundle_size: u64,
     no_bundle: bool,
-) -> CommandResult
+) -> Result<UploadCostEstimate, Error>
 where
     IP: Iterator<Item = PathBuf> + Send + Sync,
 {
     let (base, incremental) = arweave.get_price_terms(reward_mult).await?;
-    let (_, usd_per_ar, usd_per_sol) = arweave.get_price(&1).await?;
-
-    let units = match with_sol {
-        true => "lamports",
-        false => "winstons",
-    };
+    let (_, fiat_per_ar, fiat_per_sol) = arweave.get_price(&1).await?;
 
     let (num_trans, num_files, cost, bytes) = if no_bundle {
         paths_iter.fold((0, 0, 0, 0), |(n_t, n_f, c, b), p| {
@@ -95,20 +380,226 @@ where
         )
     };
 
-    // get usd cost based on calculated cost
-    let usd_cost = match with_sol {
-        true => (&cost * &usd_per_sol).to_f32().unwrap() / 1e11_f32,
-        false => (&cost * &usd_per_ar).to_f32().unwrap() / 1e14_f32,
+    // get fiat cost based on calculated cost
+    let fiat_cost = match with_sol {
+        true => (&cost * &fiat_per_sol).to_f32().unwrap() / 1e11_f32,
+        false => (&cost * &fiat_per_ar).to_f32().unwrap() / 1e14_f32,
     };
 
+    Ok(UploadCostEstimate {
+        transactions: num_trans,
+        files: num_files,
+        bytes,
+        cost,
+        fiat_cost,
+    })
+}
+
+/// Prints the estimated cost of uploading the files of `paths_iter`, in bundles of up to
+/// `bundle_size` bytes unless `no_bundle`, in winstons or, paid with SOL, lamports.
+pub async fn command_get_cost<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    reward_mult: f32,
+    with_sol: bool,
+    bundle_size: u64,
+    no_bundle: bool,
+    number_format: &NumberFormat,
+    output_format: &OutputFormat,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    let estimate = estimate_upload_cost(
+        arweave,
+        paths_iter,
+        reward_mult,
+        with_sol,
+        bundle_size,
+        no_bundle,
+    )
+    .await?;
+
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "files": estimate.files,
+                "bytes": estimate.bytes,
+                "transactions": estimate.transactions,
+                "cost": estimate.cost,
+                "units": UploadCostEstimate::units(with_sol),
+                "currency": arweave.currency.to_string(),
+                "fiat_cost": estimate.fiat_cost,
+            })
+        );
+        return Ok(());
+    }
+
     println!(
-        "The price to upload {} files with {} total bytes in {} transaction(s) is {} {} (${:.4}).",
-        num_files, bytes, num_trans, cost, units, usd_cost
+        "The price to upload {}.",
+        estimate.summary(arweave, with_sol, number_format)
     );
 
     Ok(())
 }
 
+/// Prints the estimated cost of uploading `paths` and asks for confirmation before anything is
+/// posted, returning [`Error::UploadCancelled`] if it isn't given. Uploads costing no more than
+/// `confirm_above` AR, or SOL if paid with SOL, go ahead without asking.
+pub async fn command_confirm_upload(
+    arweave: &Arweave,
+    paths: &[PathBuf],
+    reward_mult: f32,
+    with_sol: bool,
+    bundle_size: u64,
+    no_bundle: bool,
+    confirm_above: Option<f64>,
+    number_format: &NumberFormat,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let estimate = estimate_upload_cost(
+        arweave,
+        paths.iter().cloned(),
+        reward_mult,
+        with_sol,
+        bundle_size,
+        no_bundle,
+    )
+    .await?;
+    if confirm_above.map_or(false, |above| estimate.amount(with_sol) <= above) {
+        return Ok(());
+    }
+
+    output_format.print_message(&format!(
+        "Uploading {}.",
+        estimate.summary(arweave, with_sol, number_format)
+    ));
+    eprint!("Continue? [Y/n] ");
+    let mut answer = String::new();
+    // Closed input, e.g. in scripts, doesn't count as an empty answer.
+    match std::io::stdin().read_line(&mut answer)? > 0 && is_confirmation(&answer) {
+        true => Ok(()),
+        false => Err(Error::UploadCancelled),
+    }
+}
+
+/// Whether `answer` to a Y/n prompt confirms, an empty answer defaulting to yes.
+fn is_confirmation(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes")
+}
+
+/// Prints the price of uploading `bytes_or_glob`, either a number of bytes in one transaction
+/// or the files matching a glob in bundles of up to [`MAX_TX_DATA`] bytes.
+pub async fn command_get_price(
+    arweave: &Arweave,
+    bytes_or_glob: &str,
+    reward_mult: f32,
+    number_format: &NumberFormat,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let bytes = match bytes_or_glob.parse::<u64>() {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let paths_iter = glob(bytes_or_glob)?
+                .filter_map(Result::ok)
+                .filter(|p| p.is_file());
+            return command_get_cost(
+                arweave,
+                paths_iter,
+                reward_mult,
+                false,
+                MAX_TX_DATA,
+                false,
+                number_format,
+                output_format,
+            )
+            .await;
+        }
+    };
+
+    let (winstons, fiat_per_ar, _) = arweave.get_price(&bytes).await?;
+    let winstons = apply_reward_mult(winstons.to_u128().unwrap(), reward_mult);
+    let fiat_cost = (&fiat_per_ar * winstons).to_f64().unwrap() / 1e14_f64;
+
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "bytes": bytes,
+                "cost": winstons,
+                "units": arweave.units,
+                "currency": arweave.currency.to_string(),
+                "fiat_cost": fiat_cost,
+            })
+        );
+        return Ok(());
+    }
+
+    println!(
+        "The price to upload {} bytes in one transaction is {} {} ({} AR) ({}{}).",
+        number_format.integer(bytes),
+        number_format.integer(winstons),
+        arweave.units,
+        number_format.winstons_as_ar(winstons),
+        arweave.currency.symbol(),
+        number_format.decimal(fiat_cost, 4)
+    );
+    Ok(())
+}
+
+/// Aborts if any of the signing wallets has pending transactions that were not created from
+/// `log_dir`.
+pub async fn command_check_pending(
+    arweave: &Arweave,
+    log_dir: Option<PathBuf>,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let unknown_ids = arweave.get_unknown_pending_ids(log_dir).await?;
+    if unknown_ids.is_empty() {
+        return Ok(());
+    }
+
+    output_format.print_message(format!(
+        "Found {} pending transaction(s) for the signing wallets that were not created from the log directory:",
+        unknown_ids.len()
+    ));
+    for id in &unknown_ids {
+        output_format.print_message(format!(" {}", id));
+    }
+    output_format.print_message(
+        "Wait for them to be confirmed before uploading, or run without `--check-pending`.",
+    );
+    Err(Error::UnknownPendingTransactions(unknown_ids.len()))
+}
+
+/// Returns the number of uploads to post at a time, `buffer` paced by
+/// [`crate::network::NetworkConditions::upload_buffer`], printing a warning if the node is
+/// syncing or its mempool is saturated. Uploads go ahead with `buffer` if the node doesn't report
+/// its state.
+pub async fn command_check_network(
+    arweave: &Arweave,
+    buffer: usize,
+    output_format: &OutputFormat,
+) -> usize {
+    match arweave.get_network_conditions().await {
+        Ok(conditions) => {
+            for warning in conditions.warnings() {
+                output_format.print_message(format!("Warning: {}.", warning));
+            }
+            let paced_buffer = conditions.upload_buffer(buffer);
+            if paced_buffer < buffer {
+                output_format.print_message(format!(
+                    "Posting {} upload(s) at a time instead of {}.",
+                    paced_buffer, buffer
+                ));
+            }
+            paced_buffer
+        }
+        Err(_) => buffer,
+    }
+}
+
 /// Displays pending transaction count every second for one minute.
 pub async fn command_get_pending_count(arweave: &Arweave) -> CommandResult {
     println!(" {}\n{:-<84}", "pending tx", "");
@@ -138,6 +629,10 @@ pub async fn command_get_status(
 ) -> CommandResult {
     let id = Base64::from_str(id)?;
     let status = arweave.get_status(&id).await?;
+    if !matches!(output_format, OutputFormat::Display) {
+        print!("{}", output_format.formatted_string(&status));
+        return Ok(());
+    }
     println!(
         "{}",
         status
@@ -151,6 +646,69 @@ pub async fn command_get_status(
     Ok(())
 }
 
+/// Writes the chunks and data paths of every confirmed upload in `log_dir` to `output_dir`.
+pub async fn command_export_chunks(
+    arweave: &Arweave,
+    log_dir: PathBuf,
+    output_dir: PathBuf,
+) -> CommandResult {
+    let chunks_dirs = arweave
+        .export_confirmed_chunks(log_dir, &output_dir)
+        .await?;
+    for chunks_dir in &chunks_dirs {
+        println!("{}", chunks_dir.display());
+    }
+    println!(
+        "\nExported chunks of {} confirmed transaction(s) to {}.",
+        chunks_dirs.len(),
+        output_dir.display()
+    );
+    Ok(())
+}
+
+/// Prints projected daily and weekly spend for continuous uploads logged to `log_dir`.
+pub async fn command_forecast(
+    arweave: &Arweave,
+    log_dir: PathBuf,
+    window_days: u32,
+    number_format: &NumberFormat,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let forecast = match arweave.forecast_spend(log_dir.clone(), window_days).await? {
+        Some(forecast) => forecast,
+        None => {
+            output_format.print_message(format!(
+                "Didn't find any paid uploads in {}.",
+                log_dir.display()
+            ));
+            return Ok(());
+        }
+    };
+    if output_format.is_json() {
+        println!("{}", serde_json::to_string(&forecast)?);
+        return Ok(());
+    }
+    println!(
+        "Uploaded {} bytes per day over the last {} day(s) at {} winstons per byte.\n",
+        number_format.integer(forecast.bytes_per_day),
+        forecast.window_days,
+        number_format.decimal(forecast.winstons_per_byte, 2)
+    );
+    println!(" {:<10}  {:>30}", "period", "projected spend (AR)");
+    println!("{:-<43}", "");
+    println!(
+        " {:<10}  {:>30}",
+        "Daily",
+        number_format.winstons_as_ar(forecast.daily_winstons)
+    );
+    println!(
+        " {:<10}  {:>30}",
+        "Weekly",
+        number_format.winstons_as_ar(forecast.weekly_winstons)
+    );
+    Ok(())
+}
+
 /// Retrieves transaction from the network.
 pub async fn command_get_transaction(arweave: &Arweave, id: &str) -> CommandResult {
     let id = Base64::from_str(id)?;
@@ -159,13 +717,15 @@ pub async fn command_get_transaction(arweave: &Arweave, id: &str) -> CommandResu
     Ok(())
 }
 
-/// Lists transaction statuses, filtered by statuses and max confirmations if provided.
+/// Lists transaction statuses, filtered by statuses, max confirmations, tags and file paths if
+/// provided. Lists every status in `log_dir` if `paths_iter` is `None`.
 pub async fn command_list_statuses<IP>(
     arweave: &Arweave,
-    paths_iter: IP,
+    paths_iter: Option<IP>,
     log_dir: &str,
     statuses: Option<Vec<StatusCode>>,
     max_confirms: Option<u64>,
+    filter: Option<StatusFilter>,
     output_format: &OutputFormat,
 ) -> CommandResult
 where
@@ -174,77 +734,210 @@ where
     let log_dir_str = log_dir;
     let log_dir = PathBuf::from(log_dir_str);
 
-    let all_statuses = arweave.read_statuses(paths_iter, log_dir).await;
+    let all_statuses = match paths_iter {
+        Some(paths_iter) => arweave.read_statuses(paths_iter, log_dir).await,
+        None => arweave.read_all_statuses(log_dir).await,
+    };
     if let Ok(all_statuses) = all_statuses {
         let mut counter = 0;
         for status in arweave
-            .filter_statuses(all_statuses, statuses, max_confirms)?
+            .filter_statuses_with(all_statuses, statuses, max_confirms, filter.as_ref())?
             .iter()
         {
             if counter == 0 {
-                println!("{}", status.header_string(&output_format));
+                output_format.print_header(&status.header_string(&output_format));
             }
             print!("{}", output_format.formatted_string(status));
             counter += 1;
         }
         if counter == 0 {
-            println!("Didn't find any matching statuses.");
+            output_format.print_message("Didn't find any matching statuses.");
         } else {
-            println!("Found {} files matching filter criteria.", counter);
+            output_format
+                .print_message(format!("Found {} files matching filter criteria.", counter));
         }
     } else {
-        println!(
+        output_format.print_message(format!(
             "Didn't find statuses for one or more file paths in {}.",
             log_dir_str
-        );
+        ));
+    }
+    Ok(())
+}
+
+/// Lists the transactions of `wallet_address`, or the wallet if `None`, that have all of `tags`,
+/// newest first. A page is printed at a time when output goes to a terminal, asking before
+/// getting the next one.
+pub async fn command_list_transactions(
+    arweave: &Arweave,
+    wallet_address: Option<String>,
+    tags: Vec<Tag<String>>,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let paginate = !output_format.is_json() && std::io::stdout().is_terminal();
+    let mut after_cursor = None;
+    let mut counter = 0;
+    loop {
+        let page = arweave
+            .get_wallet_transactions_with_tags(wallet_address.clone(), &tags, after_cursor)
+            .await?;
+        for transaction in &page.transactions {
+            if counter == 0 {
+                output_format.print_header(&transaction.header_string(output_format));
+            }
+            print!("{}", output_format.formatted_string(transaction));
+            counter += 1;
+        }
+        after_cursor = page.next_cursor();
+        if after_cursor.is_none() {
+            break;
+        }
+        if paginate {
+            eprint!(
+                "-- {} transactions listed. Press Enter for more, q to quit --",
+                counter
+            );
+            let mut answer = String::new();
+            if std::io::stdin().read_line(&mut answer)? == 0 || answer.trim() == "q" {
+                break;
+            }
+        }
+    }
+    if counter == 0 {
+        output_format.print_message("Didn't find any matching transactions.");
     }
     Ok(())
 }
 
-/// Lists transaction statuses, filtered by statuses and max confirmations if provided.
+/// Lists bundle statuses, filtered by statuses, max confirmations and the paths of their files if
+/// provided.
 pub async fn command_list_bundle_statuses(
     arweave: &Arweave,
     log_dir: &str,
     statuses: Option<Vec<StatusCode>>,
     max_confirms: Option<u64>,
+    filter: Option<StatusFilter>,
     output_format: &OutputFormat,
 ) -> CommandResult {
     let mut counter = 0;
     let all_statuses = arweave.read_bundle_statuses(log_dir).await?;
 
     for status in arweave
-        .filter_statuses(all_statuses, statuses, max_confirms)?
+        .filter_statuses_with(all_statuses, statuses, max_confirms, filter.as_ref())?
         .iter()
     {
         if counter == 0 {
-            println!("{}", status.header_string(&output_format));
+            output_format.print_header(&status.header_string(&output_format));
         }
         print!("{}", output_format.formatted_string(status));
         counter += 1;
     }
     if counter == 0 {
-        println!("Didn't find any matching statuses.");
+        output_format.print_message("Didn't find any matching statuses.");
     } else {
-        println!("Found {} files matching filter criteria.", counter);
+        output_format.print_message(format!("Found {} files matching filter criteria.", counter));
     }
     Ok(())
 }
 
-/// Prints a count of transactions by status.
+/// Prints a count of transactions by status, or a table of the files with links to them if a
+/// `report_format` is given.
 pub async fn command_status_report<IP>(
     arweave: &Arweave,
-    paths_iter: IP,
+    paths_iter: Option<IP>,
     log_dir: &str,
+    report_format: Option<ReportFormat>,
 ) -> CommandResult
 where
     IP: Iterator<Item = PathBuf> + Send + Sync,
 {
     let log_dir = PathBuf::from(log_dir);
-    let summary = arweave.status_summary(paths_iter, log_dir).await?;
+    if let Some(report_format) = report_format {
+        let report = arweave
+            .status_report(paths_iter, log_dir, arweave.base_url.clone())
+            .await?;
+        print!("{}", report.render(report_format));
+        return Ok(());
+    }
+    let summary = match paths_iter {
+        Some(paths_iter) => arweave.status_summary(paths_iter, log_dir).await?,
+        None => arweave.status_summary_all(log_dir).await?,
+    };
     println!("{}", summary);
     Ok(())
 }
 
+/// Verifies uploaded files against their transactions and prints a summary.
+pub async fn command_verify<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    log_dir: PathBuf,
+    download: bool,
+    output_format: &OutputFormat,
+    buffer: usize,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    let mut stream = verify_uploads_stream(arweave, paths_iter, log_dir, download, buffer);
+
+    let mut verifications = Vec::new();
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(verification) => {
+                if verifications.is_empty() {
+                    output_format.print_header(&verification.header_string(&output_format));
+                }
+                print!("{}", output_format.formatted_string(&verification));
+                verifications.push(verification);
+            }
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
+        }
+    }
+
+    if verifications.is_empty() {
+        output_format.print_message(
+            "The <FILE_PATHS> and <LOG_DIR> combination you provided didn't return any statuses.",
+        );
+    } else {
+        output_format.print_message(format!(
+            "\n{}",
+            arweave.verification_summary(&verifications)?
+        ));
+    }
+
+    // Pending uploads can't be verified yet, but haven't failed either.
+    let pending = verifications
+        .iter()
+        .filter(|v| v.outcome == VerifyOutcome::Pending)
+        .count();
+    let failed = verifications
+        .iter()
+        .filter(|v| !matches!(v.outcome, VerifyOutcome::Verified | VerifyOutcome::Pending))
+        .count();
+    if failed > 0 {
+        output_format.print_message(format!(
+            "FAIL: {} of {} file(s) could not be verified.",
+            failed,
+            verifications.len()
+        ));
+        return Err(Error::VerificationFailed(failed));
+    }
+    if pending > 0 {
+        output_format.print_message(format!(
+            "PENDING: {} of {} file(s) are not confirmed yet. Run again once they are to verify them.",
+            pending,
+            verifications.len()
+        ));
+    } else if !verifications.is_empty() {
+        output_format.print_message(format!(
+            "PASS: all {} file(s) verified.",
+            verifications.len()
+        ));
+    }
+    Ok(())
+}
+
 /// Updates bundle statuses for provided files in provided directory.
 pub async fn command_update_bundle_statuses(
     arweave: &Arweave,
@@ -260,18 +953,18 @@ pub async fn command_update_bundle_statuses(
     let mut counter = 0;
     while let Some(Ok(status)) = stream.next().await {
         if counter == 0 {
-            println!("{}", status.header_string(&output_format));
+            output_format.print_header(&status.header_string(&output_format));
         }
         print!("{}", output_format.formatted_string(&status));
         counter += 1;
     }
     if counter == 0 {
-        println!(
+        output_format.print_message(format!(
             "The <LOG_DIR> you provided, {}, didn't have any statuses in it.",
             log_dir.display().to_string()
-        );
+        ));
     } else {
-        println!("Updated {} statuses.", counter);
+        output_format.print_message(format!("Updated {} statuses.", counter));
     }
 
     Ok(())
@@ -311,13 +1004,13 @@ pub async fn command_update_nft_statuses(
     let asset_manifest_txid = get_manifest_id_from_log_dir(&log_dir_assets);
     let metadata_manifest_txid = get_manifest_id_from_log_dir(&log_dir_metadata);
 
-    println!("\n\nUpdating asset bundle statuses...\n");
+    output_format.print_message("\n\nUpdating asset bundle statuses...\n");
     command_update_bundle_statuses(&arweave, log_dir_assets, output_format, buffer).await?;
-    println!("\n\nUpdating metadata bundle statuses...\n");
+    output_format.print_message("\n\nUpdating metadata bundle statuses...\n");
     command_update_bundle_statuses(&arweave, log_dir_metadata, output_format, buffer).await?;
-    println!("\n\nUpdating asset manifest status...\n");
+    output_format.print_message("\n\nUpdating asset manifest status...\n");
     command_get_status(&arweave, &asset_manifest_txid, output_format).await?;
-    println!("\n\nUpdating metadata manifest status...\n");
+    output_format.print_message("\n\nUpdating metadata manifest status...\n");
     command_get_status(&arweave, &metadata_manifest_txid, output_format).await?;
     Ok(())
 }
@@ -325,7 +1018,7 @@ pub async fn command_update_nft_statuses(
 /// Updates statuses for provided files in provided directory.
 pub async fn command_update_statuses<IP>(
     arweave: &Arweave,
-    paths_iter: IP,
+    paths_iter: Option<IP>,
     log_dir: PathBuf,
     output_format: &OutputFormat,
     buffer: usize,
@@ -335,21 +1028,181 @@ where
 {
     let log_dir = PathBuf::from(log_dir);
 
-    let mut stream = update_statuses_stream(arweave, paths_iter, log_dir.clone(), buffer);
+    let mut stream = match paths_iter {
+        Some(paths_iter) => {
+            update_statuses_stream(arweave, paths_iter, log_dir.clone(), buffer).boxed_local()
+        }
+        None => update_all_statuses_stream(arweave, log_dir.clone(), buffer)?.boxed_local(),
+    };
     let mut counter = 0;
+    let mut confirmed = Vec::new();
     while let Some(Ok(status)) = stream.next().await {
         if counter == 0 {
-            println!("{}", status.header_string(output_format));
+            output_format.print_header(&status.header_string(output_format));
         }
         print!("{}", output_format.formatted_string(&status));
         counter += 1;
+        if status.status == StatusCode::Confirmed {
+            confirmed.push(status);
+        }
+        // Keep assets.json current while long status updates run.
+        if confirmed.len() >= buffer {
+            arweave
+                .update_assets_file(&confirmed, log_dir.clone())
+                .await?;
+            confirmed.clear();
+        }
     }
     if counter == 0 {
-        println!("The <GLOB> and <LOG_DIR> combination you provided didn't return any statuses.");
+        output_format.print_message(
+            "The <GLOB> and <LOG_DIR> combination you provided didn't return any statuses.",
+        );
+    } else {
+        let assets_path = arweave
+            .update_assets_file(&confirmed, log_dir.clone())
+            .await?;
+        output_format.print_message(format!(
+            "Updated {} statuses. Confirmed uploads are listed in {}.",
+            counter,
+            assets_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Writes unsigned transactions for files to `output_dir`, to be signed offline.
+pub async fn command_create_unsigned<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    output_dir: PathBuf,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+    let last_tx = arweave.get_tx_anchor().await?;
+    fs::create_dir_all(&output_dir).await?;
+    let mut counter = 0;
+    for file_path in paths_iter {
+        let path = arweave
+            .write_unsigned_transaction(
+                file_path.clone(),
+                &output_dir,
+                tags.clone(),
+                Some(last_tx.clone()),
+                price_terms,
+            )
+            .await?;
+        print_written_path(&file_path, &path, output_format);
+        counter += 1;
+    }
+    output_format.print_message(format!(
+        "\nWrote {} unsigned transaction(s). Run `arloader sign {} --ar-keypair-path <AR_KEYPAIR_PATH> --output-dir <OUTPUT_DIR>` to sign them, and post them within 50 blocks.",
+        counter,
+        output_dir.join("*").display()
+    ));
+    Ok(())
+}
+
+/// Serves the HTTP API of [`UploadServer`] on `addr`, uploading submitted files inside
+/// `root_dir` with `arweave` and writing their statuses to `log_dir`.
+#[cfg(feature = "serve")]
+pub async fn command_serve(
+    arweave: Arweave,
+    log_dir: PathBuf,
+    reward_mult: f32,
+    root_dir: PathBuf,
+    token: String,
+    addr: SocketAddr,
+) -> CommandResult {
+    println!(
+        "Serving uploads from {} at http://{}/ with statuses written to {}.",
+        arweave.crypto.wallet_address()?,
+        addr,
+        log_dir.display()
+    );
+    UploadServer::new(arweave, log_dir, reward_mult, root_dir, token)
+        .serve(addr)
+        .await
+}
+
+/// Signs unsigned transactions written by [`command_create_unsigned`], writing them to
+/// `output_dir`.
+pub async fn command_sign<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    output_dir: PathBuf,
+    output_format: &OutputFormat,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    fs::create_dir_all(&output_dir).await?;
+    let mut counter = 0;
+    for path in paths_iter {
+        let signed_path = arweave.sign_transaction_file(&path, &output_dir).await?;
+        print_written_path(&path, &signed_path, output_format);
+        counter += 1;
+    }
+    output_format.print_message(format!("\nSigned {} transaction(s).", counter));
+    Ok(())
+}
+
+/// Prints that `path` was written to `written_path`, as a json object with `path` and
+/// `written_path` keys for json output.
+fn print_written_path(path: &Path, written_path: &Path, output_format: &OutputFormat) {
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "path": path,
+                "written_path": written_path,
+            })
+        );
     } else {
-        println!("Updated {} statuses.", counter);
+        println!("{} -> {}", path.display(), written_path.display());
     }
+}
 
+/// Posts signed transactions written by [`command_sign`] or persisted by uploads.
+pub async fn command_post_signed<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    buffer: usize,
+    output_format: &OutputFormat,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    let mut counter = 0;
+    for path in paths_iter {
+        match arweave.post_signed_transaction_file(&path, buffer).await {
+            Ok((id, reward)) => {
+                if output_format.is_json() {
+                    println!(
+                        "{}",
+                        json!({
+                            "path": path,
+                            "id": id.to_string(),
+                            "reward": reward,
+                        })
+                    );
+                } else {
+                    println!("{} {:>12}", id, reward);
+                }
+                counter += 1;
+            }
+            Err(e) => output_format.print_message(format!("{}: {:#?}", path.display(), e)),
+        }
+    }
+    output_format.print_message(format!(
+        "\nPosted {} transaction(s). Run `arloader get-status <ID>` to confirm them.",
+        counter
+    ));
     Ok(())
 }
 
@@ -358,6 +1211,7 @@ pub async fn command_upload<IP>(
     arweave: &Arweave,
     paths_iter: IP,
     log_dir: Option<PathBuf>,
+    skip_existing: bool,
     tags: Option<Vec<Tag<Base64>>>,
     reward_mult: f32,
     output_format: &OutputFormat,
@@ -367,12 +1221,23 @@ where
     IP: Iterator<Item = PathBuf> + Send + Sync,
 {
     let price_terms = arweave.get_price_terms(reward_mult).await?;
+    let paths = paths_iter.collect::<Vec<PathBuf>>();
+    arweave
+        .check_balance(
+            paths
+                .iter()
+                .filter_map(|p| p.metadata().ok())
+                .map(|m| m.len()),
+            price_terms,
+        )
+        .await?;
 
     let mut stream = upload_files_stream(
         arweave,
-        paths_iter,
+        paths.into_iter(),
         tags,
         log_dir.clone(),
+        skip_existing,
         None,
         price_terms,
         buffer,
@@ -384,27 +1249,176 @@ where
             Ok(status) => {
                 if counter == 0 {
                     if let Some(log_dir) = &log_dir {
-                        println!("Logging statuses to {}", &log_dir.display());
+                        output_format
+                            .print_message(format!("Logging statuses to {}", &log_dir.display()));
                     }
-                    println!("{}", status.header_string(&output_format));
+                    output_format.print_header(&status.header_string(&output_format));
                 }
                 print!("{}", output_format.formatted_string(&status));
                 counter += 1;
             }
-            Err(e) => println!("{:#?}", e),
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
         }
     }
 
     if counter == 0 {
-        println!("<FILE_PATHS> didn't match any files.");
+        output_format.print_message("<FILE_PATHS> didn't match any files.");
     } else {
-        println!(
+        output_format.print_message(format!(
             "Uploaded {} files. Run `arloader update-status {} --file-paths <FILE_PATHS>` to confirm transaction(s).",
             counter,
             &log_dir.unwrap_or(PathBuf::from("")).display(),
-        );
+        ));
+    }
+
+    Ok(())
+}
+
+/// Uploads the objects of `sources` to Arweave without writing them to disk.
+pub async fn command_upload_urls(
+    arweave: &Arweave,
+    sources: Vec<UploadSource>,
+    log_dir: Option<PathBuf>,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+    buffer: usize,
+) -> CommandResult {
+    let urls = try_join_all(sources.iter().map(|s| s.urls(&arweave.client)))
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<Url>>();
+    if urls.is_empty() {
+        output_format.print_message("<SOURCES> didn't include any objects.");
+        return Ok(());
+    }
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+
+    let mut stream = upload_urls_stream(
+        arweave,
+        urls.into_iter(),
+        tags,
+        log_dir.clone(),
+        price_terms,
+        buffer,
+    );
+
+    let mut counter = 0;
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(status) => {
+                if counter == 0 {
+                    if let Some(log_dir) = &log_dir {
+                        output_format
+                            .print_message(format!("Logging statuses to {}", &log_dir.display()));
+                    }
+                    output_format.print_header(&status.header_string(&output_format));
+                }
+                print!("{}", output_format.formatted_string(&status));
+                counter += 1;
+            }
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
+        }
     }
 
+    output_format.print_message(format!(
+        "Uploaded {} objects. Run `arloader update-status {} --no-bundle` to confirm transaction(s).",
+        counter,
+        &log_dir.unwrap_or(PathBuf::from("")).display(),
+    ));
+    Ok(())
+}
+
+/// Uploads files to Arweave, uploading byte-identical files only once.
+pub async fn command_upload_dedup<IP>(
+    arweave: &Arweave,
+    paths_iter: IP,
+    log_dir: Option<PathBuf>,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+    buffer: usize,
+) -> CommandResult
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync,
+{
+    let paths_groups = arweave.group_file_paths_by_hash(paths_iter)?;
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+
+    let mut stream = upload_files_dedup_stream(
+        arweave,
+        paths_groups,
+        tags,
+        log_dir.clone(),
+        price_terms,
+        buffer,
+    );
+
+    let mut counter = 0;
+    let mut uploaded = 0;
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(status) => {
+                if counter == 0 {
+                    if let Some(log_dir) = &log_dir {
+                        output_format
+                            .print_message(format!("Logging statuses to {}", &log_dir.display()));
+                    }
+                    output_format.print_header(&status.header_string(&output_format));
+                }
+                print!("{}", output_format.formatted_string(&status));
+                counter += 1;
+                uploaded += (status.reward > 0) as usize;
+            }
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
+        }
+    }
+
+    if counter == 0 {
+        output_format.print_message("<FILE_PATHS> didn't match any files.");
+    } else {
+        output_format.print_message(format!(
+            "Uploaded {} of {} files, the rest were duplicates. Run `arloader update-status {} --file-paths <FILE_PATHS>` to confirm transaction(s).",
+            uploaded,
+            counter,
+            &log_dir.unwrap_or(PathBuf::from("")).display(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Uploads a directory tree and a manifest that reproduces its folder hierarchy.
+pub async fn command_upload_dir(
+    arweave: &Arweave,
+    root: PathBuf,
+    log_dir: PathBuf,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+    buffer: usize,
+) -> CommandResult {
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+    let (manifest_id, statuses) = arweave
+        .upload_dir(root.clone(), log_dir.clone(), tags, price_terms, buffer)
+        .await?;
+
+    if let Some(status) = statuses.first() {
+        output_format.print_message(format!("Logging statuses to {}", &log_dir.display()));
+        output_format.print_header(&status.header_string(&output_format));
+    }
+    for status in &statuses {
+        print!("{}", output_format.formatted_string(status));
+    }
+
+    output_format.print_message(format!(
+        "Uploaded {} files from {} and wrote manifest to {}manifest_{id}.json.\n\nRun `arloader get-status {id}` to confirm manifest transaction.",
+        statuses.len(),
+        root.display(),
+        log_dir.display(),
+        id = manifest_id
+    ));
     Ok(())
 }
 
@@ -419,11 +1433,14 @@ pub async fn command_upload_bundles(
     buffer: usize,
 ) -> CommandResult {
     if path_chunks.len() == 0 {
-        println!("<FILE_PATHS> didn't match any files.");
+        output_format.print_message("<FILE_PATHS> didn't match any files.");
         return Ok(());
     } else {
         let tags = tags.unwrap_or(Vec::new());
         let price_terms = arweave.get_price_terms(reward_mult).await?;
+        arweave
+            .check_balance(path_chunks.iter().map(|c| c.1), price_terms)
+            .await?;
         let log_dir = if let Some(log_dir) = log_dir {
             log_dir
         } else {
@@ -435,14 +1452,21 @@ pub async fn command_upload_bundles(
             .iter()
             .fold((0, 0), |(f, d), c| (f + c.0.len(), d + c.1));
 
-        println!(
+        output_format.print_message(format!(
             "Uploading {} files with {} KB of data in {} bundle transactions...\n",
             num_files,
             data_size / 1_000,
             path_chunks.len(),
-        );
+        ));
 
-        let mut stream = upload_bundles_stream(arweave, path_chunks, tags, price_terms, buffer);
+        let mut stream = upload_bundles_stream(
+            arweave,
+            path_chunks,
+            tags,
+            Some(log_dir.clone()),
+            price_terms,
+            buffer,
+        );
 
         let mut counter = 0;
         let mut number_of_files = 0;
@@ -454,27 +1478,22 @@ pub async fn command_upload_bundles(
                     number_of_files += status.number_of_files;
                     data_size += status.data_size;
                     if counter == 0 {
-                        println!("{}", status.header_string(&output_format));
+                        output_format.print_header(&status.header_string(&output_format));
                     }
                     print!("{}", output_format.formatted_string(&status));
-                    fs::write(
-                        log_dir.join(status.id.to_string()).with_extension("json"),
-                        serde_json::to_string(&status)?,
-                    )
-                    .await?;
                     counter += 1;
                 }
-                Err(e) => println!("{:#?}", e),
+                Err(e) => output_format.print_message(format!("{:#?}", e)),
             }
         }
 
-        println!(
+        output_format.print_message(format!(
             "\nUploaded {} KB in {} files in {} bundle transactions. Run `arloader update-status {}` to update statuses.",
             data_size / 1000,
             number_of_files,
             counter,
             log_dir.display().to_string()
-        );
+        ));
     }
     Ok(())
 }
@@ -491,7 +1510,7 @@ pub async fn command_upload_bundles_with_sol(
     sol_keypair_path: PathBuf,
 ) -> CommandResult {
     if path_chunks.len() == 0 {
-        println!("<FILE_PATHS> didn't match any files.");
+        output_format.print_message("<FILE_PATHS> didn't match any files.");
         return Ok(());
     } else {
         let tags = tags.unwrap_or(Vec::new());
@@ -510,17 +1529,18 @@ pub async fn command_upload_bundles_with_sol(
             .iter()
             .fold((0, 0), |(f, d), c| (f + c.0.len(), d + c.1));
 
-        println!(
+        output_format.print_message(format!(
             "Uploading {} files with {} KB of data in {} bundle transactions...\n",
             num_files,
             data_size / 1_000,
             path_chunks.len(),
-        );
+        ));
 
         let mut stream = upload_bundles_stream_with_sol(
             arweave,
             path_chunks,
             tags,
+            Some(log_dir.clone()),
             price_terms,
             buffer,
             solana_url,
@@ -537,32 +1557,27 @@ pub async fn command_upload_bundles_with_sol(
                     number_of_files += status.number_of_files;
                     data_size += status.data_size;
                     if counter == 0 {
-                        println!("{}", status.header_string(&output_format));
+                        output_format.print_header(&status.header_string(&output_format));
                     }
                     print!("{}", output_format.formatted_string(&status));
-                    fs::write(
-                        log_dir.join(status.id.to_string()).with_extension("json"),
-                        serde_json::to_string(&status)?,
-                    )
-                    .await?;
                     counter += 1;
                 }
-                Err(e) => println!("{:#?}", e),
+                Err(e) => output_format.print_message(format!("{:#?}", e)),
             }
         }
 
-        println!(
+        output_format.print_message(format!(
             "\nUploaded {} KB in {} files in {} bundle transaction(s). Run `arloader update-status {}` to update statuses.",
             data_size / 1000,
             number_of_files,
             counter,
             log_dir.display().to_string()
-        );
+        ));
     }
     Ok(())
 }
 
-/// Re-uploads files from status and max confirmations criteria.
+/// Re-uploads files from status, max confirmations, tag and file path criteria.
 pub async fn command_reupload<IP>(
     arweave: &Arweave,
     log_dir: PathBuf,
@@ -571,6 +1586,7 @@ pub async fn command_reupload<IP>(
     reward_mult: f32,
     statuses: Option<Vec<StatusCode>>,
     max_confirms: Option<u64>,
+    filter: Option<StatusFilter>,
     output_format: &OutputFormat,
     buffer: usize,
     sol_keypair_path: Option<PathBuf>,
@@ -590,7 +1606,7 @@ where
         .filter(|p| !all_statuses.iter().any(|s| s.file_path.as_ref() == Some(p)));
 
     let filtered_paths_iter = arweave
-        .filter_statuses(all_statuses_copy, statuses, max_confirms)?
+        .filter_statuses_with(all_statuses_copy, statuses, max_confirms, filter.as_ref())?
         .into_iter()
         .filter_map(|f| f.file_path);
 
@@ -601,6 +1617,7 @@ where
             arweave,
             paths_iter,
             Some(log_dir),
+            false,
             tags,
             reward_mult,
             output_format,
@@ -613,6 +1630,7 @@ where
             arweave,
             paths_iter,
             Some(log_dir),
+            false,
             tags,
             reward_mult,
             output_format,
@@ -622,7 +1640,7 @@ where
     }
 }
 
-/// Re-uploads files from status and max confirmations criteria.
+/// Re-uploads files from status, max confirmations, tag and file path criteria.
 ///
 /// Includes any file paths not present in bundle statuses. Collects file paths from bundle
 /// statuses to be re-uploaded based on filter criteria, removes existing bundle statuses files,
@@ -636,6 +1654,7 @@ pub async fn command_reupload_bundles<IP>(
     reward_mult: f32,
     statuses: Option<Vec<StatusCode>>,
     max_confirms: Option<u64>,
+    filter: Option<StatusFilter>,
     output_format: OutputFormat,
     buffer: usize,
     sol_keypair_path: Option<PathBuf>,
@@ -659,7 +1678,8 @@ where
     let missing_paths_iter =
         paths_iter.filter(|p| !all_paths_map.contains_key(&p.display().to_string()));
 
-    let filtered_statuses = arweave.filter_statuses(all_statuses, statuses, max_confirms)?;
+    let filtered_statuses =
+        arweave.filter_statuses_with(all_statuses, statuses, max_confirms, filter.as_ref())?;
     let mut bundle_status_paths = Vec::new();
 
     let filtered_paths_map =
@@ -716,6 +1736,7 @@ pub async fn command_upload_nfts<IP>(
     buffer: usize,
     sol_keypair_path: Option<PathBuf>,
     link_file: bool,
+    index_pattern: &Regex,
 ) -> CommandResult
 where
     IP: Iterator<Item = PathBuf> + Send + Sync,
@@ -746,7 +1767,7 @@ where
     .await?;
 
     // Upload images
-    println!("\n\nUploading assets...\n");
+    output_format.print_message("\n\nUploading assets...\n");
     if let Some(sol_keypair_path) = sol_keypair_path.clone() {
         command_upload_bundles_with_sol(
             &arweave,
@@ -773,12 +1794,14 @@ where
     }
 
     // Upload manifest
-    println!("\n\nUploading manifest for images...\n");
+    output_format.print_message("\n\nUploading manifest for images...\n");
     command_upload_manifest(
         &arweave,
         &log_dir_assets.display().to_string(),
         reward_mult,
         sol_keypair_path.clone().map(|s| s.display().to_string()),
+        None,
+        output_format,
     )
     .await?;
 
@@ -792,7 +1815,7 @@ where
     .unwrap();
 
     // Update metadata with links to uploaded images.
-    println!("\n\nUpdating metadata with links from manifest...\n");
+    output_format.print_message("\n\nUpdating metadata with links from manifest...\n");
     command_update_metadata(
         &arweave,
         paths_vec.clone().into_iter(),
@@ -802,7 +1825,7 @@ where
     .await?;
 
     // Upload metadata.
-    println!("\n\nUploading updated metadata files...\n");
+    output_format.print_message("\n\nUploading updated metadata files...\n");
     if let Some(sol_keypair_path) = sol_keypair_path.clone() {
         command_upload_bundles_with_sol(
             &arweave,
@@ -828,12 +1851,14 @@ where
         .await?;
     }
 
-    println!("\n\nUploading manifest for metadata...\n");
+    output_format.print_message("\n\nUploading manifest for metadata...\n");
     command_upload_manifest(
         &arweave,
         &log_dir_metadata_string,
         reward_mult,
         sol_keypair_path.map(|s| s.display().to_string()),
+        None,
+        output_format,
     )
     .await?;
     let metadata_manifest_path = glob(&format!("{}manifest*.json", &log_dir_metadata_string))
@@ -842,15 +1867,28 @@ where
         .nth(0)
         .unwrap();
 
-    println!(
+    let uris_path = arweave
+        .write_metaplex_uris(
+            paths_vec.into_iter().map(|p| p.with_extension("json")),
+            metadata_manifest_path.clone(),
+            link_file,
+            index_pattern,
+        )
+        .await?;
+
+    output_format.print_message(format!(
         "\n\nUpload complete! Links to your uploaded metadata files can be found in `{}`",
         metadata_manifest_path.display().to_string()
-    );
+    ));
+    output_format.print_message(format!(
+        "Candy machine uris, ordered by file index, written to `{}`.",
+        uris_path.display().to_string()
+    ));
 
-    println!(
+    output_format.print_message(format!(
         "Run `arloader update-nft-status {}` to confirm all transactions.",
         log_dir.display().to_string()
-    );
+    ));
     Ok(())
 }
 
@@ -860,23 +1898,105 @@ pub async fn command_upload_manifest(
     log_dir: &str,
     reward_mult: f32,
     sol_keypair_path: Option<String>,
+    arns_record: Option<(Base64, String)>,
+    output_format: &OutputFormat,
 ) -> CommandResult {
     let solana_url = SOLANA_MAIN_URL.parse::<Url>()?;
     let sol_ar_url = SOL_AR_BASE_URL.parse::<Url>()?.join("sol")?;
     let from_keypair = sol_keypair_path.map(|s| keypair::read_keypair_file(s).unwrap());
 
     let price_terms = arweave.get_price_terms(reward_mult).await?;
-    let output = arweave
-        .upload_manifest_from_bundle_log_dir(
+
+    let (manifest_id, num_files) = match arweave
+        .post_manifest_from_bundle_log_dir(
             log_dir,
             price_terms,
             solana_url,
             sol_ar_url,
             from_keypair,
         )
-        .await?;
+        .await?
+    {
+        Some(manifest) => manifest,
+        None => {
+            output_format.print_message(format!("No bundle statuses found in {}", log_dir));
+            return Ok(());
+        }
+    };
+    let interaction_id = match arns_record {
+        Some((ant_contract_id, sub_domain)) => Some((
+            arweave
+                .set_arns_record(&ant_contract_id, &sub_domain, &manifest_id, price_terms)
+                .await?,
+            ant_contract_id,
+            sub_domain,
+        )),
+        None => None,
+    };
+
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "id": manifest_id.to_string(),
+                "files": num_files,
+                "arns_interaction_id": interaction_id.map(|(id, _, _)| id.to_string()),
+            })
+        );
+        return Ok(());
+    }
 
-    println!("{}", output);
+    println!(
+        "Uploaded manifest for {} files and wrote to {}manifest_{}.json.",
+        num_files, log_dir, manifest_id
+    );
+    if let Some((interaction_id, ant_contract_id, sub_domain)) = interaction_id {
+        println!(
+            "Pointed ArNS record {} of {} at manifest with interaction {}.",
+            sub_domain, ant_contract_id, interaction_id
+        );
+    }
+    println!(
+        "\nRun `arloader get-status {}` to confirm manifest transaction.",
+        manifest_id
+    );
+    Ok(())
+}
+
+/// Uploads a manifest of the confirmed statuses in `log_dir` and prints the url it serves the
+/// files at.
+pub async fn command_create_manifest(
+    arweave: &Arweave,
+    log_dir: PathBuf,
+    root: Option<PathBuf>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+) -> CommandResult {
+    let price_terms = arweave.get_price_terms(reward_mult).await?;
+    match arweave
+        .post_manifest_from_log_dir(log_dir.clone(), root.as_deref(), price_terms)
+        .await?
+    {
+        Some((id, num_files)) if output_format.is_json() => println!(
+            "{}",
+            json!({
+                "id": id.to_string(),
+                "files": num_files,
+                "url": arweave.base_url.join(&format!("{}/", id))?.to_string(),
+            })
+        ),
+        Some((id, num_files)) => println!(
+            "Uploaded manifest for {} files and wrote to {}manifest_{id}.json.\n\nFiles are served at {}{id}/\n\nRun `arloader get-status {id}` to confirm manifest transaction.",
+            num_files,
+            log_dir.display(),
+            arweave.base_url,
+            id = id
+        ),
+        None => output_format.print_message(format!(
+            "No confirmed statuses found in {}",
+            log_dir.display()
+        )),
+    }
     Ok(())
 }
 
@@ -885,6 +2005,7 @@ pub async fn command_upload_with_sol<IP>(
     arweave: &Arweave,
     paths_iter: IP,
     log_dir: Option<PathBuf>,
+    skip_existing: bool,
     tags: Option<Vec<Tag<Base64>>>,
     reward_mult: f32,
     output_format: &OutputFormat,
@@ -905,6 +2026,7 @@ where
         paths_iter,
         tags,
         log_dir.clone(),
+        skip_existing,
         None,
         price_terms,
         solana_url,
@@ -919,25 +2041,26 @@ where
             Ok(status) => {
                 if counter == 0 {
                     if let Some(log_dir) = &log_dir {
-                        println!("Logging statuses to {}", &log_dir.display());
+                        output_format
+                            .print_message(format!("Logging statuses to {}", &log_dir.display()));
                     }
-                    println!("{}", status.header_string(&output_format));
+                    output_format.print_header(&status.header_string(&output_format));
                 }
                 print!("{}", output_format.formatted_string(&status));
                 counter += 1;
             }
-            Err(e) => println!("{:#?}", e),
+            Err(e) => output_format.print_message(format!("{:#?}", e)),
         }
     }
 
     if counter == 0 {
-        println!("<FILE_PATHS> didn't match any files.");
+        output_format.print_message("<FILE_PATHS> didn't match any files.");
     } else {
-        println!(
+        output_format.print_message(format!(
             "Uploaded {} files. Run `arloader update-status {} --file-paths <FILE_PATHS>` to confirm transaction(s).",
             counter,
             &log_dir.unwrap_or(PathBuf::from("")).display(),
-        );
+        ));
     }
 
     Ok(())
@@ -947,6 +2070,8 @@ where
 pub async fn command_wallet_balance(
     arweave: &Arweave,
     wallet_address: Option<String>,
+    number_format: &NumberFormat,
+    output_format: &OutputFormat,
 ) -> CommandResult {
     let mb = u64::pow(1024, 2);
     let result = tokio::join!(
@@ -954,34 +2079,81 @@ pub async fn command_wallet_balance(
         arweave.get_price(&mb)
     );
     let balance = result.0?;
-    let (winstons_per_kb, usd_per_ar, _) = result.1?;
+    let (winstons_per_kb, fiat_per_ar, _) = result.1?;
 
-    let balance_usd = &balance.to_f32().unwrap() / &WINSTONS_PER_AR.to_f32().unwrap()
-        * &usd_per_ar.to_f32().unwrap()
+    let balance_fiat = &balance.to_f32().unwrap() / &WINSTONS_PER_AR.to_f32().unwrap()
+        * &fiat_per_ar.to_f32().unwrap()
         / 100_f32;
 
-    let usd_per_kb = (&winstons_per_kb * &usd_per_ar).to_f32().unwrap() / 1e14_f32;
+    let fiat_per_kb = (&winstons_per_kb * &fiat_per_ar).to_f32().unwrap() / 1e14_f32;
+
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "balance": balance.to_u128(),
+                "units": arweave.units,
+                "currency": arweave.currency.to_string(),
+                "fiat_balance": balance_fiat,
+                "fiat_per_ar": fiat_per_ar.to_f64().unwrap() / 100_f64,
+                "price_per_mb": winstons_per_kb.to_u128(),
+                "fiat_price_per_mb": fiat_per_kb,
+            })
+        );
+        return Ok(());
+    }
 
     println!(
-            "Wallet balance is {} {units} (${balance_usd:.2} at ${ar_price:.2} USD per AR). At the current price of {price} {units} per MB (${usd_price:.4}), you can upload {max} MB of data.",
-            &balance,
+            "Wallet balance is {} {units} ({balance_ar} AR, {symbol}{balance_fiat} at {symbol}{ar_price} {currency} per AR). At the current price of {price} {units} per MB ({symbol}{fiat_price}), you can upload {max} MB of data.",
+            number_format.integer(&balance),
             units = arweave.units,
-            max = &balance / &winstons_per_kb,
-            price = &winstons_per_kb,
-            balance_usd = balance_usd,
-            ar_price = &usd_per_ar.to_f32().unwrap()
-            / 100_f32,
-            usd_price = usd_per_kb
+            symbol = arweave.currency.symbol(),
+            currency = arweave.currency,
+            balance_ar = number_format.winstons_as_ar(&balance),
+            max = number_format.integer(&balance / &winstons_per_kb),
+            price = number_format.integer(&winstons_per_kb),
+            balance_fiat = number_format.decimal(balance_fiat as f64, 2),
+            ar_price = number_format.decimal(fiat_per_ar.to_f64().unwrap() / 100_f64, 2),
+            fiat_price = number_format.decimal(fiat_per_kb as f64, 4)
     );
     Ok(())
 }
 
+/// Watches a directory and uploads files as they appear or change, printing their statuses.
+pub async fn command_watch(
+    arweave: &Arweave,
+    dir: PathBuf,
+    log_dir: PathBuf,
+    tags: Option<Vec<Tag<Base64>>>,
+    reward_mult: f32,
+    output_format: &OutputFormat,
+    debounce: Duration,
+) -> CommandResult {
+    output_format.print_message(format!(
+        "Watching {} for new and modified files and logging statuses to {}. Press Ctrl-C to stop.",
+        dir.display(),
+        log_dir.display()
+    ));
+    let mut counter = 0;
+    arweave
+        .watch_and_upload(dir, log_dir, tags, reward_mult, debounce, |status| {
+            if counter == 0 {
+                output_format.print_header(&status.header_string(output_format));
+            }
+            print!("{}", output_format.formatted_string(status));
+            counter += 1;
+        })
+        .await?;
+    Ok(())
+}
+
 /// Writes metaplex link items used to create NFTs with candy machine program.
 pub async fn command_write_metaplex_items(
     arweave: &Arweave,
     glob_str: &str,
     manifest_str: &str,
     link_file: bool,
+    output_format: &OutputFormat,
 ) -> CommandResult {
     let paths_iter = glob(glob_str)?.filter_map(Result::ok);
     let num_paths: usize = paths_iter.collect::<Vec<PathBuf>>().len();
@@ -997,6 +2169,16 @@ pub async fn command_write_metaplex_items(
         )
         .await?;
 
+    if output_format.is_json() {
+        println!(
+            "{}",
+            json!({
+                "files": num_paths,
+                "path": metaplex_items_path,
+            })
+        );
+        return Ok(());
+    }
     println!(
         "Successfully wrote metaplex items for {} metadata files to {}",
         num_paths,
@@ -0,0 +1,134 @@
+//! Compression of data before upload, recorded with a `Content-Encoding` tag.
+
+use crate::error::Error;
+use flate2::{read::GzDecoder, write::GzEncoder};
+use std::{
+    io::{Read, Write},
+    str::FromStr,
+};
+
+/// Buffer size used by the brotli encoder and decoder.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// Compression algorithms that can be applied to data before upload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Gzip,
+    Brotli,
+}
+
+impl Compression {
+    /// Value of the `Content-Encoding` tag added to compressed uploads.
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Brotli => "br",
+        }
+    }
+
+    /// Returns the [`Compression`] for the value of a `Content-Encoding` tag.
+    pub fn from_content_encoding(content_encoding: &str) -> Result<Self, Error> {
+        match content_encoding {
+            "gzip" => Ok(Compression::Gzip),
+            "br" => Ok(Compression::Brotli),
+            _ => Err(Error::UnsupportedEncoding(content_encoding.to_string())),
+        }
+    }
+
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?)
+            }
+            Compression::Brotli => {
+                let mut compressed = Vec::new();
+                {
+                    let mut encoder =
+                        brotli::CompressorWriter::new(&mut compressed, BROTLI_BUFFER_SIZE, 9, 22);
+                    encoder.write_all(data)?;
+                }
+                Ok(compressed)
+            }
+        }
+    }
+
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut decompressed = Vec::new();
+        match self {
+            Compression::Gzip => {
+                GzDecoder::new(data).read_to_end(&mut decompressed)?;
+            }
+            Compression::Brotli => {
+                brotli::Decompressor::new(data, BROTLI_BUFFER_SIZE)
+                    .read_to_end(&mut decompressed)?;
+            }
+        }
+        Ok(decompressed)
+    }
+}
+
+impl FromStr for Compression {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gzip" => Ok(Compression::Gzip),
+            "brotli" => Ok(Compression::Brotli),
+            _ => Err(Error::UnsupportedEncoding(s.to_string())),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::Compression;
+    use crate::{error::Error, transaction::Tag, Arweave};
+    use matches::assert_matches;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_compression_round_trip() -> Result<(), Error> {
+        let data = "{\"name\": \"arloader\"}".repeat(100).into_bytes();
+        for compression in [Compression::Gzip, Compression::Brotli] {
+            let compressed = compression.compress(&data)?;
+            assert!(compressed.len() < data.len());
+            assert_eq!(compression.decompress(&compressed)?, data);
+            assert_eq!(
+                Compression::from_content_encoding(compression.content_encoding())?,
+                compression
+            );
+        }
+        assert_matches!(
+            Compression::from_content_encoding("zstd"),
+            Err(Error::UnsupportedEncoding(_))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compressed_data_item() -> Result<(), Error> {
+        let arweave = Arweave {
+            compression: Some(Compression::Brotli),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.json");
+        let (data_item, _) = arweave
+            .create_data_item_from_file_path(file_path.clone(), Vec::new())
+            .await?;
+
+        let tag = |name: &str, value: &str| Tag {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        assert!(data_item
+            .tags
+            .contains(&tag("Content-Type", "application/json")));
+        assert!(data_item.tags.contains(&tag("Content-Encoding", "br")));
+        assert_eq!(
+            Compression::Brotli.decompress(&data_item.data.0)?,
+            std::fs::read(file_path)?
+        );
+        Ok(())
+    }
+}
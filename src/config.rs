@@ -0,0 +1,167 @@
+//! Defaults for the flags of the cli read from a [`CONFIG_FILE_NAME`] file.
+
+use crate::{
+    error::Error,
+    transaction::{validate_tags, FromUtf8Strs, Tag, MAX_DATA_ITEM_TAGS_BYTES},
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Name of the config file looked for in the current directory and in the `arloader` directory
+/// of the user's config directory.
+pub const CONFIG_FILE_NAME: &str = "arloader.toml";
+
+/// Settings read from a config file, used as the defaults of the matching flags.
+///
+/// ```toml
+/// ar_keypair_path = "~/.arweave/keypair.json"
+/// base_url = "https://arweave.net/"
+/// log_dir = "status/"
+/// buffer = 10
+/// reward_multiplier = 1.5
+///
+/// [tags]
+/// App-Name = "gallery"
+/// ```
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub ar_keypair_path: Option<String>,
+    pub base_url: Option<String>,
+    /// Directory that the `upload` command writes statuses to.
+    pub log_dir: Option<String>,
+    pub buffer: Option<usize>,
+    pub reward_multiplier: Option<f32>,
+    /// Tags added to uploaded files unless `--tags` is passed, by name.
+    pub tags: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Reads the config at `path`, expanding a leading `~` in its paths to the home directory
+    /// and checking its tags against the limits of transaction tags.
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        let data = std::fs::read_to_string(path)?;
+        let invalid =
+            |e: &dyn std::fmt::Display| Error::InvalidConfig(format!("{}: {}", path.display(), e));
+        let mut config: Self = toml::from_str(&data).map_err(|e| invalid(&e))?;
+        config.ar_keypair_path = config.ar_keypair_path.map(expand_home);
+        config.log_dir = config.log_dir.map(expand_home);
+        if let Some(tags) = config.tags::<Tag<String>>()? {
+            validate_tags(&tags, MAX_DATA_ITEM_TAGS_BYTES).map_err(|e| invalid(&e))?;
+        }
+        Ok(config)
+    }
+
+    /// Loads the config at `path` if given, otherwise the first [`Config::default_paths`] that
+    /// exists, or the default config if none do.
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        if let Some(path) = path {
+            return Self::from_path(path);
+        }
+        match Self::default_paths().into_iter().find(|p| p.is_file()) {
+            Some(path) => Self::from_path(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Paths config files are loaded from when no path is given, in order of precedence.
+    pub fn default_paths() -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from(CONFIG_FILE_NAME)];
+        if let Some(config_dir) = dirs_next::config_dir() {
+            paths.push(config_dir.join("arloader").join(CONFIG_FILE_NAME));
+        }
+        paths
+    }
+
+    /// Returns [`Config::tags`] as tags for uploads, `None` if there are none.
+    pub fn tags<T>(&self) -> Result<Option<Vec<T>>, Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        if self.tags.is_empty() {
+            return Ok(None);
+        }
+        self.tags
+            .iter()
+            .map(|(name, value)| T::from_utf8_strs(name, value))
+            .collect::<Result<Vec<T>, Error>>()
+            .map(Some)
+    }
+}
+
+/// Replaces a leading `~` of `path` with the home directory.
+fn expand_home(path: String) -> String {
+    match (path.strip_prefix('~'), dirs_next::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            format!("{}{}", home.display(), rest)
+        }
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_home, Config};
+    use crate::{
+        error::Error,
+        transaction::{Base64, Tag},
+    };
+    use matches::assert_matches;
+
+    #[test]
+    fn test_config() -> Result<(), Error> {
+        let config: Config = toml::from_str(
+            r#"
+            ar_keypair_path = "~/.arweave/keypair.json"
+            buffer = 10
+            reward_multiplier = 1.5
+
+            [tags]
+            App-Name = "gallery"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.buffer, Some(10));
+        assert_eq!(config.reward_multiplier, Some(1.5));
+        assert_eq!(config.base_url, None);
+        let tags: Vec<Tag<String>> = config.tags()?.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].value, "gallery");
+        assert_eq!(Config::default().tags::<Tag<Base64>>()?, None);
+
+        assert!(toml::from_str::<Config>("bufer = 10").is_err());
+        let home = dirs_next::home_dir().unwrap().display().to_string();
+        assert_eq!(
+            expand_home("~/.arweave/keypair.json".to_string()),
+            format!("{}/.arweave/keypair.json", home)
+        );
+        assert_eq!(
+            expand_home("~user/keypair.json".to_string()),
+            "~user/keypair.json"
+        );
+        assert_eq!(expand_home("status/".to_string()), "status/");
+
+        let temp_dir = std::env::temp_dir().join("arloader-config-test");
+        std::fs::create_dir_all(&temp_dir)?;
+        let path = temp_dir.join(super::CONFIG_FILE_NAME);
+        std::fs::write(&path, "ar_keypair_path = \"~/keypair.json\"\n")?;
+        assert_eq!(
+            Config::load(Some(&path))?.ar_keypair_path,
+            Some(format!("{}/keypair.json", home))
+        );
+        std::fs::write(
+            &path,
+            format!("[tags]\nApp-Name = \"{}\"\n", "a".repeat(4096)),
+        )?;
+        assert_matches!(Config::load(Some(&path)), Err(Error::InvalidConfig(_)));
+        std::fs::remove_dir_all(&temp_dir)?;
+        assert_matches!(
+            Config::load(Some("tests/fixtures/0.json".as_ref())),
+            Err(Error::InvalidConfig(_))
+        );
+        Ok(())
+    }
+}
@@ -1,19 +1,25 @@
 //! Functionality for creating and verifying signatures and hashing.
 
+#[cfg(feature = "native")]
+use crate::rt::fs;
 use crate::{
     error::Error,
     transaction::{Base64, DeepHashItem},
 };
 use jsonwebkey::JsonWebKey;
-use log::debug;
+use num_bigint::BigUint;
 use ring::{
     digest::{Context, SHA256, SHA384},
+    hmac, pbkdf2,
     rand::{self, SecureRandom},
     signature::{self, KeyPair, RsaKeyPair},
 };
 use std::fs as fsSync;
+use std::num::NonZeroU32;
 use std::path::PathBuf;
-use tokio::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "native")]
+use tracing::debug;
 
 /// Struct for for crypto methods.
 pub struct Provider {
@@ -34,27 +40,80 @@ impl Default for Provider {
 impl Provider {
     /// Reads a [`JsonWebKey`] from a [`PathBuf`] and stores it as a [`signature::RsaKeyPair`] in
     /// the `keypair` property of [`Provider`] for future use in signing and funding transactions.
+    #[cfg(feature = "native")]
     pub async fn from_keypair_path(keypair_path: PathBuf) -> Result<Provider, Error> {
         debug!("{:?}", keypair_path);
         let data = fs::read_to_string(keypair_path).await?;
-
-        let jwk_parsed: JsonWebKey = data.parse().unwrap();
-        Ok(Self {
-            keypair: signature::RsaKeyPair::from_pkcs8(&jwk_parsed.key.as_ref().to_der())?,
-            sr: rand::SystemRandom::new(),
-        })
+        Self::from_jwk_str(&data)
     }
     /// Sync version of [`Provider::from_keypair_path`].
     pub fn from_keypair_path_sync(keypair_path: PathBuf) -> Result<Provider, Error> {
         let data = fsSync::read_to_string(keypair_path)?;
+        Self::from_jwk_str(&data)
+    }
 
-        let jwk_parsed: JsonWebKey = data.parse().unwrap();
+    /// Parses a [`JsonWebKey`] from the contents of a keypair file, for keys that are passed in
+    /// by a secrets manager rather than stored on disk.
+    pub fn from_jwk_str(jwk: &str) -> Result<Provider, Error> {
+        let jwk_parsed: JsonWebKey = jwk
+            .parse()
+            .map_err(|e| Error::InvalidJwk(format!("{}", e)))?;
         Ok(Self {
             keypair: signature::RsaKeyPair::from_pkcs8(&jwk_parsed.key.as_ref().to_der())?,
             sr: rand::SystemRandom::new(),
         })
     }
 
+    /// Reads a [`JsonWebKey`] from the environment variable `var`, e.g. `ARWEAVE_KEYPAIR`, so
+    /// that containers can be given a key without writing it to disk.
+    pub fn from_env(var: &str) -> Result<Provider, Error> {
+        let jwk = std::env::var(var).map_err(|_| Error::MissingEnvVar(var.to_string()))?;
+        Self::from_jwk_str(&jwk)
+    }
+
+    /// Generates a new random 4096 bit keypair. Returns the [`Provider`] together with the
+    /// keypair encoded as a [`JsonWebKey`] json value so that it can be written to file and
+    /// loaded again with [`Provider::from_keypair_path`].
+    #[cfg(feature = "session-wallet")]
+    pub fn generate() -> Result<(Provider, serde_json::Value), Error> {
+        use rsa::{
+            pkcs8::EncodePrivateKey,
+            rand_core::OsRng,
+            traits::{PrivateKeyParts, PublicKeyParts},
+            BigUint, RsaPrivateKey,
+        };
+
+        let key = RsaPrivateKey::new(&mut OsRng, 4096)?;
+        let encode = |n: &BigUint| Base64(n.to_bytes_be()).to_string();
+        let jwk = serde_json::json!({
+            "kty": "RSA",
+            "n": encode(key.n()),
+            "e": encode(key.e()),
+            "d": encode(key.d()),
+            "p": encode(&key.primes()[0]),
+            "q": encode(&key.primes()[1]),
+            "dp": encode(key.dp().ok_or(rsa::Error::Internal)?),
+            "dq": encode(key.dq().ok_or(rsa::Error::Internal)?),
+            "qi": encode(&key.crt_coefficient().ok_or(rsa::Error::Internal)?),
+        });
+
+        let provider = Self {
+            keypair: signature::RsaKeyPair::from_pkcs8(key.to_pkcs8_der()?.as_bytes())?,
+            sr: rand::SystemRandom::new(),
+        };
+        Ok((provider, jwk))
+    }
+
+    /// Derives the keypair of a BIP39 mnemonic phrase in the same way as
+    /// [arweave-mnemonic-keys](https://github.com/ar-io/arweave-mnemonic-keys), so that wallets
+    /// backed up as a seed phrase instead of a JWK file can be used. The same phrase always
+    /// gives the same 4096 bit keypair. Only phrases from the English word list are supported,
+    /// and deriving the keypair takes a few seconds.
+    pub fn from_mnemonic(phrase: &str) -> Result<Provider, Error> {
+        let seed = mnemonic_to_seed(phrase, "")?;
+        Self::from_jwk_str(&rsa_jwk_from_seed(&seed, MNEMONIC_KEY_BITS).to_string())
+    }
+
     /// Returns the full modulus of the stored keypair. Encoded as a Base64Url String,
     /// represents the associated network address. Also used in the calculation of transaction
     /// signatures.
@@ -128,6 +187,23 @@ impl Provider {
         Ok(())
     }
 
+    /// Verifies that a message was signed by the keypair with the provided public modulus, as
+    /// included in the `owner` field of transactions and data items. Arweave keys all use the
+    /// standard public exponent of 65537.
+    pub fn verify_with_modulus(
+        &self,
+        modulus: &[u8],
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), Error> {
+        let public_key = signature::RsaPublicKeyComponents {
+            n: modulus,
+            e: &PUBLIC_EXPONENT[..],
+        };
+        public_key.verify(&signature::RSA_PSS_2048_8192_SHA256, message, signature)?;
+        Ok(())
+    }
+
     pub fn hash_sha256(&self, message: &[u8]) -> Result<[u8; 32], Error> {
         let mut context = Context::new(&SHA256);
         context.update(message);
@@ -204,6 +280,268 @@ impl Provider {
     }
 }
 
+/// Keypairs that uploads are signed with in turn, so that large upload runs aren't throttled by
+/// the limits the network puts on pending transactions per wallet. Set it on
+/// [`crate::Arweave::key_ring`].
+pub struct KeyRing {
+    providers: Vec<Provider>,
+    next: AtomicUsize,
+}
+
+impl KeyRing {
+    pub fn new(providers: Vec<Provider>) -> Result<Self, Error> {
+        if providers.is_empty() {
+            return Err(Error::EmptyKeyRing);
+        }
+        Ok(Self {
+            providers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    #[cfg(feature = "native")]
+    pub async fn from_keypair_paths<IP>(keypair_paths: IP) -> Result<Self, Error>
+    where
+        IP: IntoIterator<Item = PathBuf>,
+    {
+        let mut providers = Vec::new();
+        for keypair_path in keypair_paths {
+            providers.push(Provider::from_keypair_path(keypair_path).await?);
+        }
+        Self::new(providers)
+    }
+
+    /// Returns the next keypair in turn.
+    pub fn next(&self) -> &Provider {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.providers.len();
+        &self.providers[i]
+    }
+
+    pub fn wallet_addresses(&self) -> Result<Vec<Base64>, Error> {
+        self.providers.iter().map(|p| p.wallet_address()).collect()
+    }
+}
+
+/// Public exponent (65537) shared by all Arweave RSA keys.
+const PUBLIC_EXPONENT: [u8; 3] = [1, 0, 1];
+
+/// Size of the modulus of keypairs derived from mnemonic phrases.
+const MNEMONIC_KEY_BITS: usize = 4096;
+
+/// Steps between candidates of the form 30k + i with gcd(30, i) = 1, starting from 30k + 1, as
+/// in the prime search of node-forge.
+const GCD_30_DELTA: [u32; 8] = [6, 4, 2, 4, 2, 4, 6, 2];
+
+/// Primes below 1000, for trial division of candidates and as Miller-Rabin bases.
+const SMALL_PRIMES: [u32; 168] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+    311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 373, 379, 383, 389, 397, 401, 409, 419, 421,
+    431, 433, 439, 443, 449, 457, 461, 463, 467, 479, 487, 491, 499, 503, 509, 521, 523, 541, 547,
+    557, 563, 569, 571, 577, 587, 593, 599, 601, 607, 613, 617, 619, 631, 641, 643, 647, 653, 659,
+    661, 673, 677, 683, 691, 701, 709, 719, 727, 733, 739, 743, 751, 757, 761, 769, 773, 787, 797,
+    809, 811, 821, 823, 827, 829, 839, 853, 857, 859, 863, 877, 881, 883, 887, 907, 911, 919, 929,
+    937, 941, 947, 953, 967, 971, 977, 983, 991, 997,
+];
+
+/// BIP39 English word list, one word per line.
+const BIP39_ENGLISH: &str = include_str!("bip39_english.txt");
+
+/// Returns the 64 byte BIP39 seed of `phrase`, salted with `passphrase`, after checking that
+/// its words are in the English word list and that they end with the checksum of the rest.
+fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], Error> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if !phrase.is_ascii() || ![12, 15, 18, 21, 24].contains(&words.len()) {
+        return Err(Error::InvalidMnemonic(format!(
+            "expected 12, 15, 18, 21 or 24 words from the English word list, got {}",
+            words.len()
+        )));
+    }
+    let word_list: Vec<&str> = BIP39_ENGLISH.lines().collect();
+    let mut bits = Vec::with_capacity(words.len() * 11);
+    for word in &words {
+        let index = word_list
+            .binary_search(word)
+            .map_err(|_| Error::InvalidMnemonic(format!("{} is not in the word list", word)))?;
+        bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+    }
+    let checksum_len = bits.len() / 33;
+    let (entropy_bits, checksum) = bits.split_at(bits.len() - checksum_len);
+    let entropy: Vec<u8> = entropy_bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, bit| acc << 1 | *bit as u8))
+        .collect();
+    let hash = ring::digest::digest(&SHA256, &entropy);
+    if (0..checksum_len).any(|i| checksum[i] != ((hash.as_ref()[0] >> (7 - i)) & 1 == 1)) {
+        return Err(Error::InvalidMnemonic("checksum doesn't match".to_string()));
+    }
+    let mut seed = [0u8; 64];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA512,
+        NonZeroU32::new(2048).unwrap(),
+        format!("mnemonic{}", passphrase).as_bytes(),
+        words.join(" ").as_bytes(),
+        &mut seed,
+    );
+    Ok(seed)
+}
+
+/// Generates an RSA keypair with node-forge's algorithm, drawing random numbers from an
+/// [`HmacDrbg`] seeded with `seed`, and returns it as a [`JsonWebKey`] json value.
+fn rsa_jwk_from_seed(seed: &[u8], bits: usize) -> serde_json::Value {
+    let mut drbg = HmacDrbg::new(seed);
+    let e = BigUint::from_bytes_be(&PUBLIC_EXPONENT);
+    let one = BigUint::from(1u32);
+    let (p_bits, q_bits) = (bits - (bits >> 1), bits >> 1);
+
+    let mut p = find_prime(p_bits, &mut drbg);
+    let mut q = find_prime(q_bits, &mut drbg);
+    let n = loop {
+        if p < q {
+            std::mem::swap(&mut p, &mut q);
+        }
+        // e is prime, so p - 1 and q - 1 are coprime with it unless they are multiples of it
+        if ((&p - &one) % &e).bits() == 0 {
+            p = find_prime(p_bits, &mut drbg);
+            continue;
+        }
+        if ((&q - &one) % &e).bits() == 0 {
+            q = find_prime(q_bits, &mut drbg);
+            continue;
+        }
+        let n = &p * &q;
+        if n.bits() as usize != bits {
+            q = find_prime(q_bits, &mut drbg);
+            continue;
+        }
+        break n;
+    };
+
+    let (p1, q1) = (&p - &one, &q - &one);
+    let d = e.modinv(&(&p1 * &q1)).expect("e is coprime with phi");
+    let qi = q.modinv(&p).expect("p and q are distinct primes");
+    let encode = |n: &BigUint| Base64(n.to_bytes_be()).to_string();
+    serde_json::json!({
+        "kty": "RSA",
+        "n": encode(&n),
+        "e": encode(&e),
+        "d": encode(&d),
+        "p": encode(&p),
+        "q": encode(&q),
+        "dp": encode(&(&d % &p1)),
+        "dq": encode(&(&d % &q1)),
+        "qi": encode(&qi),
+    })
+}
+
+/// Returns the first probable prime of `bits` bits at or after a random 30k + 1 candidate.
+fn find_prime(bits: usize, drbg: &mut HmacDrbg) -> BigUint {
+    let mut num = random_candidate(bits, drbg);
+    let mut delta_idx = 0;
+    loop {
+        if num.bits() as usize > bits {
+            num = random_candidate(bits, drbg);
+        }
+        if is_probable_prime(&num) {
+            return num;
+        }
+        num += GCD_30_DELTA[delta_idx % 8];
+        delta_idx += 1;
+    }
+}
+
+/// Random number of `bits` bits with the top bit set, aligned on a 30k + 1 boundary.
+fn random_candidate(bits: usize, drbg: &mut HmacDrbg) -> BigUint {
+    let mut bytes = drbg.generate((bits >> 3) + 1);
+    match bits & 7 {
+        0 => bytes[0] = 0,
+        t => bytes[0] &= (1 << t) - 1,
+    }
+    let mut num = BigUint::from_bytes_be(&bytes) | (BigUint::from(1u32) << (bits - 1));
+    let rem = (&num % 30u32).to_u32_digits().first().copied().unwrap_or(0);
+    num += 31 - rem;
+    num
+}
+
+/// Trial division by the primes below 1000 followed by Miller-Rabin rounds.
+fn is_probable_prime(n: &BigUint) -> bool {
+    for p in &SMALL_PRIMES {
+        if n == &BigUint::from(*p) {
+            return true;
+        }
+        if (n % *p).bits() == 0 {
+            return false;
+        }
+    }
+
+    let one = BigUint::from(1u32);
+    let n1 = n - &one;
+    let s = n1.trailing_zeros().unwrap_or(0);
+    let d = &n1 >> s;
+    'bases: for a in SMALL_PRIMES.iter().take(8) {
+        let mut x = BigUint::from(*a).modpow(&d, n);
+        if x == one || x == n1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&BigUint::from(2u32), n);
+            if x == n1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// HMAC_DRBG with SHA-256, per NIST SP 800-90A, without reseeding or additional input. Matches
+/// the `hmac-drbg` package used by arweave-mnemonic-keys, which updates its state after every
+/// call to generate, so the output depends on the sizes requested as well as the seed.
+struct HmacDrbg {
+    k: Vec<u8>,
+    v: Vec<u8>,
+}
+
+impl HmacDrbg {
+    fn new(entropy: &[u8]) -> Self {
+        let mut drbg = Self {
+            k: vec![0; 32],
+            v: vec![1; 32],
+        };
+        drbg.update(Some(entropy));
+        drbg
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut context = hmac::Context::with_key(&hmac::Key::new(hmac::HMAC_SHA256, &self.k));
+        for d in data {
+            context.update(d);
+        }
+        context.sign().as_ref().to_vec()
+    }
+
+    fn update(&mut self, seed: Option<&[u8]>) {
+        self.k = self.hmac(&[&self.v, &[0x00], seed.unwrap_or_default()]);
+        self.v = self.hmac(&[&self.v]);
+        if let Some(seed) = seed {
+            self.k = self.hmac(&[&self.v, &[0x01], seed]);
+            self.v = self.hmac(&[&self.v]);
+        }
+    }
+
+    fn generate(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len + 32);
+        while bytes.len() < len {
+            self.v = self.hmac(&[&self.v]);
+            bytes.extend_from_slice(&self.v);
+        }
+        bytes.truncate(len);
+        self.update(None);
+        bytes
+    }
+}
+
 const DEFAULT_KEYPAIR: &str = r##"{
     "kty": "RSA",
     "n": "vUS-Urn9wBomxlKPhzZrjcsLZaGqPawdFRxHuy9sCUEF2zkRwbVLUf4vstz04Tis8tbd8TbGbmGxFxfybTFCEltwbfAMPmgAyvu4NZztkcFTg8XmsmADxPF5wOc0lpmwcSbec-r69_zNx6WXEM7qVng2nrufM_yR3ociBCSrG9_jnuhDaLxLayCkbD4gViNTIPPUJCQPCmy3PuRx-DITj7VFwi8u-KdWWjVN5cJ-pLLNKQjlpo0BOYMSc11S6N1s1Od6EG-LdL_gG1rfDX2hWzEtH2kHolN3UTSv1UU6980kG-e1BLIJHm7tHIBqxpwMR6m8HD6e3bDlcVQm23qxq6D3sIdauz4RNOl4yVFlI1o5tLeH_ot9uyWKkqGcknc4FgJ1CcVMwZsSl6S-BcTgZgns9AgfnJApZzWdyIpcyuqHBTaBOtcViGTupbn-LdY-lf1CwJZOgp5uDBFfU34ZhEcyCTLTEd5dCw9kQmO7TTqAJEO4kbtczxHUaNrAW8SViFNeG7SNlZ9uwqNMy7R1wswX_baarVjRzF3yUGkdSkzBMJfYs0lFLTiPY8gcuRsz03GNISi6AFuk25LhS19llIaz9-uucP8T0fnXzwHJqe85ygVLEOPcL72Z4VlRDvrdJMba4GKqcbwU5D17Q1lA9cPX7DmVtRJ7PCX2M_ezLQ0",
@@ -216,13 +554,16 @@ const DEFAULT_KEYPAIR: &str = r##"{
     "qi": "QRTwMaZiz-IbZXr0bJCic6iHK1R4y2Yw-RVYCvFolVhyJORBVkqvu9XhJr1sRQlsqONSXa3T7hZwLi_vYhz2v5lKTdIy7aCW0M7HNc-MmpoEJckPJ5ps0gx5RhriK7dLWb4Jm9nixeyp19KPn-PKbo6pTszaaJGU_fG0r6jf8nAxBAT2nfHkB9SrqbDVko1gswFg8W_rqtesJHngqu-_RYSltkz6yzJ4zJZAyOyFlwwGyEnEPVwxWgy5oxuMPPTU5T0mBGWskDR1o4w78ZS42YLwAKQm48qfZmthTTHBnizW40AFMOJTFwEMZD1dV7YAMm1dQHO8ybQbZk1w7ybiiQ"
 }"##;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
 mod tests {
-    use super::Provider;
+    use super::{mnemonic_to_seed, rsa_jwk_from_seed, Provider, DEFAULT_KEYPAIR};
     use crate::{
+        transaction::Base64,
         Arweave, Error,
         {transaction::Transaction, ToItems},
     };
+    use jsonwebkey::JsonWebKey;
+    use ring::signature::RsaKeyPair;
     use std::path::PathBuf;
     use std::str::FromStr;
     use url::Url;
@@ -262,4 +603,107 @@ mod tests {
             "jA6UzKJ1cIvL2vUIct7Qf90QhC5b1UttvwknaGGBtjI"
         );
     }
+
+    #[test]
+    fn test_from_jwk_str_and_env() -> Result<(), Error> {
+        let provider = Provider::from_jwk_str(DEFAULT_KEYPAIR)?;
+        assert_eq!(
+            provider.wallet_address()?,
+            Provider::default().wallet_address()?
+        );
+        assert!(matches!(
+            Provider::from_jwk_str("{}"),
+            Err(Error::InvalidJwk(_))
+        ));
+
+        std::env::set_var("ARLOADER_TEST_KEYPAIR", DEFAULT_KEYPAIR);
+        let provider = Provider::from_env("ARLOADER_TEST_KEYPAIR")?;
+        assert_eq!(
+            provider.wallet_address()?,
+            Provider::default().wallet_address()?
+        );
+        assert!(matches!(
+            Provider::from_env("ARLOADER_TEST_KEYPAIR_UNSET"),
+            Err(Error::MissingEnvVar(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mnemonic_to_seed() -> Result<(), Error> {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+            abandon abandon about";
+        let seed = mnemonic_to_seed(phrase, "TREZOR")?;
+        assert_eq!(
+            Base64(seed.to_vec()),
+            Base64::from_str(
+                "xVJXw2DAfHICmuvBtTwF7QNiraOOrT4-nvo3COU0lVMfCaaYdZnRgmTB4ckvLPFBYwx6PEq3yBsvABaY50Y7BA"
+            )?
+        );
+        let phrase = "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong";
+        let seed = mnemonic_to_seed(phrase, "TREZOR")?;
+        assert_eq!(
+            Base64(seed.to_vec()),
+            Base64::from_str(
+                "rCdJVIAiUiIHnXvhgVg3UehvVxAnsEl7W10RIY4KihMzJXKRfw-OWliWIMbxWxHGHe4ydlGhTDThgjEFLkjAaQ"
+            )?
+        );
+
+        assert!(mnemonic_to_seed("abandon about", "").is_err());
+        assert!(matches!(
+            mnemonic_to_seed(&"abandon ".repeat(12), ""),
+            Err(Error::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            mnemonic_to_seed(&format!("{}abandonn", "abandon ".repeat(11)), ""),
+            Err(Error::InvalidMnemonic(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rsa_jwk_from_seed() -> Result<(), Error> {
+        // Derived keys are 4096 bits, 2048 keeps the test fast with the same algorithm.
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank \
+            yellow";
+        let seed = mnemonic_to_seed(phrase, "")?;
+        let jwk = rsa_jwk_from_seed(&seed, 2048);
+        assert_eq!(
+            jwk,
+            rsa_jwk_from_seed(&mnemonic_to_seed(&format!(" {}\n", phrase), "")?, 2048)
+        );
+
+        let jwk_parsed: JsonWebKey = jwk.to_string().parse().unwrap();
+        let keypair = RsaKeyPair::from_pkcs8(&jwk_parsed.key.as_ref().to_der())?;
+        assert_eq!(keypair.public_modulus_len(), 256);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "derives a 4096 bit keypair, which is slow without optimizations"]
+    fn test_from_mnemonic_wallet_address() -> Result<(), Error> {
+        // Pins the wallet of a phrase at the key size of arweave-mnemonic-keys, so that a change
+        // to the derivation can't silently give users a different wallet.
+        let provider = Provider::from_mnemonic(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        )?;
+        assert_eq!(
+            provider.wallet_address()?.to_string(),
+            "4gko33o-g-_iAJ-EaBQT_MX1m3GqW380z6Nz3HuszAk"
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "session-wallet")]
+    #[tokio::test]
+    async fn test_generate_keypair() -> Result<(), Error> {
+        let (provider, jwk) = Provider::generate()?;
+        let temp_dir = crate::utils::TempDir::from_str("./tests/").await?;
+        let keypair_path = temp_dir.0.join("session.json");
+        crate::rt::fs::write(&keypair_path, serde_json::to_string(&jwk)?).await?;
+
+        let loaded = Provider::from_keypair_path(keypair_path).await?;
+        assert_eq!(provider.wallet_address()?, loaded.wallet_address()?);
+        Ok(())
+    }
 }
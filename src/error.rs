@@ -1,10 +1,11 @@
 //! Errors propagated by library functions.
 use base64::DecodeError;
 use glob;
+use num_bigint::BigUint;
 use reqwest;
 use ring::error::{KeyRejected, Unspecified};
 use serde_json;
-use std::string::FromUtf8Error;
+use std::{path::PathBuf, string::FromUtf8Error};
 use thiserror::Error;
 use url::ParseError;
 
@@ -15,6 +16,11 @@ pub enum Error {
     ArweaveGetPriceError(reqwest::Error),
     #[error("error posting arweave transaction: {0}")]
     ArweavePostError(reqwest::Error),
+    #[cfg(feature = "parquet-export")]
+    #[error("arrow: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[error("{0} asset(s) not confirmed")]
+    AssetsNotConfirmed(usize),
     #[error("avro deserialize: {0}")]
     AvroDeError(#[from] avro_rs::DeError),
     #[error("base64 decode: {0}")]
@@ -22,23 +28,96 @@ pub enum Error {
     #[error("bincode: {0}")]
     Bincode(#[from] Box<bincode::ErrorKind>),
     #[error("unhandled boxed dyn error {0}")]
-    BoxedDynStd(#[from] Box<dyn std::error::Error>),
+    BoxedDynStd(Box<dyn std::error::Error + Send + Sync>),
+    #[error("bundlr node at {0} has no arweave address")]
+    BundlrAddressNotFound(String),
+    #[error("funding transaction {0} wasn't credited by bundlr node")]
+    BundlrFundingNotCredited(String),
+    #[error("downloaded data doesn't match transaction {0}")]
+    DataMismatch(String),
+    #[cfg(feature = "email")]
+    #[error("email: {0}")]
+    Email(String),
+    #[error("key ring has no keypairs")]
+    EmptyKeyRing,
     #[error("formatting error")]
     FormatError(#[from] std::fmt::Error),
     #[error("from utf8: {0}")]
     FromUtf8(#[from] FromUtf8Error),
+    #[error("gateway unavailable, retry after {0:?}")]
+    GatewayUnavailable(Option<std::time::Duration>),
     #[error("glob patters: {0}")]
     GlobPattern(#[from] glob::PatternError),
+    #[error("graphql: {0}")]
+    GraphQl(String),
+    #[cfg(feature = "serve")]
+    #[error("hyper: {0}")]
+    Hyper(#[from] hyper::Error),
+    #[error("anchor refresh interval must be at least 1 block")]
+    InvalidAnchorRefreshInterval,
+    #[error("invalid app tags: {0}")]
+    InvalidAppTags(String),
+    #[error("invalid bundlr balance: {0}")]
+    InvalidBundlrBalance(String),
+    #[error("{1} bytes from offset {0} are outside of the data")]
+    InvalidByteRange(u64, u64),
+    #[error("no chunk at index {0}")]
+    InvalidChunkIndex(usize),
     #[error("invalid bunlde item binary")]
     InvalidDataItem,
+    #[error("data doesn't match data root {0}")]
+    InvalidDataRoot(String),
+    #[error("invalid ethereum key: {0}")]
+    InvalidEthereumKey(String),
+    #[error("invalid ffi argument: {0}")]
+    InvalidFfiArgument(String),
     #[error("hashing failed")]
     InvalidHash,
+    #[error("no unique index in file name: {0}")]
+    InvalidFileIndex(String),
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
+    #[error("invalid content disposition: {0}")]
+    InvalidContentDisposition(String),
+    #[error("invalid jwk: {0}")]
+    InvalidJwk(String),
+    #[error("invalid locale: {0}")]
+    InvalidLocale(String),
+    #[error("invalid manifest path: {0}")]
+    InvalidManifestPath(String),
+    #[error("invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    #[error("poll interval must be at least 1 second")]
+    InvalidPollInterval,
     #[error("invalid proof")]
     InvalidProof,
+    #[error("signature doesn't match transaction and owner")]
+    InvalidSignature,
+    #[error("invalid bundlr receipt")]
+    InvalidReceipt,
+    #[error("invalid report format: {0}")]
+    InvalidReportFormat(String),
+    #[error("invalid spl token: {0}")]
+    InvalidSplToken(String),
+    #[error("invalid tag pattern")]
+    InvalidTagPattern,
     #[error("invalid tags")]
     InvalidTags,
+    #[error("transaction id {0} isn't the hash of its signature")]
+    InvalidTransactionId(String),
+    #[error("offset of transaction {0} is smaller than its size")]
+    InvalidTxOffset(String),
+    #[error("invalid upload source: {0}")]
+    InvalidUploadSource(String),
+    #[error("insufficient balance: {required} winstons required, {available} available")]
+    InsufficientBalance {
+        required: BigUint,
+        available: BigUint,
+    },
     #[error("insufficient sol funds")]
     InsufficientSolFunds,
+    #[error("insufficient token funds")]
+    InsufficientTokenFunds,
     #[error("io: {0}")]
     IOError(#[from] std::io::Error),
     #[error("keypair not provided")]
@@ -47,30 +126,154 @@ pub enum Error {
     KeyRejected(#[from] KeyRejected),
     #[error("manifest not found")]
     ManifestNotFound,
+    #[error("environment variable {0} not set")]
+    MissingEnvVar(String),
     #[error("file path not provided")]
     MissingFilePath,
     #[error("missing trailing slash")]
     MissingTrailingSlash,
     #[error("no bundle statuses found")]
     NoBundleStatusesFound,
+    #[cfg(feature = "native")]
+    #[error("notify: {0}")]
+    Notify(#[from] notify::Error),
     #[error("error getting oracle prices: {0}")]
     OracleGetPriceError(reqwest::Error),
+    #[error("oracle price not found: {0}")]
+    OraclePriceNotFound(String),
+    #[error("{0} is outside of the root directory")]
+    PathOutsideRoot(PathBuf),
+    #[cfg(feature = "parquet-export")]
+    #[error("parquet: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "metrics")]
+    #[error("prometheus: {0}")]
+    Prometheus(#[from] prometheus::Error),
+    #[cfg(feature = "session-wallet")]
+    #[error("pkcs8: {0}")]
+    Pkcs8(#[from] rsa::pkcs8::Error),
+    #[error("request body is larger than {0} bytes")]
+    RequestBodyTooLarge(usize),
     #[error("reqwest: {0}")]
     Reqwest(#[from] reqwest::Error),
+    #[cfg(feature = "session-wallet")]
+    #[error("rsa: {0}")]
+    Rsa(#[from] rsa::Error),
     #[error("ring unspecified: {0}")]
     RingUnspecified(#[from] Unspecified),
     #[error("serde json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("status not found")]
     StatusNotFound,
+    #[cfg(feature = "native")]
     #[error("solana hash parse {0}")]
     SolanaHashParse(#[from] solana_sdk::hash::ParseHashError),
     #[error("solana network error")]
     SolanaNetworkError,
+    #[cfg(feature = "native")]
+    #[error("solana pubkey parse {0}")]
+    SolanaPubkeyParse(#[from] solana_sdk::pubkey::ParsePubkeyError),
+    #[error("strip prefix: {0}")]
+    StripPrefix(#[from] std::path::StripPrefixError),
+    #[error("tags exceed protocol limits: {0}")]
+    TagLimitExceeded(String),
+    #[cfg(feature = "native")]
     #[error("solana hash parse {0}")]
     TokioJoinError(#[from] tokio::task::JoinError),
+    #[cfg(feature = "grpc")]
+    #[error("tonic transport: {0}")]
+    TonicTransport(#[from] tonic::transport::Error),
+    #[error("unexpected status response {0}: {1}")]
+    UnexpectedStatusResponse(u16, String),
+    #[error("{0} pending transaction(s) not created from log directory")]
+    UnknownPendingTransactions(usize),
+    #[error("unsupported currency: {0}")]
+    UnsupportedCurrency(String),
+    #[error("unsupported content encoding: {0}")]
+    UnsupportedEncoding(String),
     #[error("transaction is not signed")]
     UnsignedTransaction,
+    #[error("upload cancelled, nothing was posted")]
+    UploadCancelled,
     #[error("url parse error: {0}")]
     UrlParse(#[from] ParseError),
+    #[error("{0} file(s) failed verification")]
+    VerificationFailed(usize),
+}
+
+impl Error {
+    /// Whether the error is transient, like a dropped connection, a timeout, or a `429 Too Many
+    /// Requests` or `5xx` response, so that the request that failed may succeed if retried.
+    /// Other errors, like invalid tags, an unsigned transaction or an insufficient balance, fail
+    /// the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ArweaveGetPriceError(e)
+            | Error::ArweavePostError(e)
+            | Error::OracleGetPriceError(e)
+            | Error::Reqwest(e) => match e.status() {
+                Some(status) => is_retryable_status(status.as_u16()),
+                None => e.is_timeout() || e.is_request() || e.is_body(),
+            },
+            Error::GatewayUnavailable(_) | Error::SolanaNetworkError => true,
+            Error::IOError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            Error::UnexpectedStatusResponse(status, _) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+}
+
+/// Keeps [`Error`] `Send` and `Sync`, so that uploads can run on other threads, e.g. in the
+/// handlers of the `grpc` feature, by keeping only the message of errors that aren't.
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(error: Box<dyn std::error::Error>) -> Self {
+        Error::BoxedDynStd(error.to_string().into())
+    }
+}
+
+/// Whether a response with `status` may succeed if the request is sent again.
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 408 || status == 429 || status >= 500
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_error_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Error>();
+        let error = Error::from(Box::<dyn std::error::Error>::from("invalid keypair"));
+        assert_eq!(
+            error.to_string(),
+            "unhandled boxed dyn error invalid keypair"
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::GatewayUnavailable(None).is_retryable());
+        assert!(Error::UnexpectedStatusResponse(429, String::new()).is_retryable());
+        assert!(Error::UnexpectedStatusResponse(503, String::new()).is_retryable());
+        assert!(Error::from(std::io::Error::from(std::io::ErrorKind::TimedOut)).is_retryable());
+
+        assert!(!Error::UnexpectedStatusResponse(400, String::new()).is_retryable());
+        assert!(!Error::from(std::io::Error::from(std::io::ErrorKind::NotFound)).is_retryable());
+        assert!(!Error::InvalidTags.is_retryable());
+        assert!(!Error::UnsignedTransaction.is_retryable());
+        assert!(!Error::InsufficientBalance {
+            required: BigUint::from(2u8),
+            available: BigUint::from(1u8),
+        }
+        .is_retryable());
+    }
 }
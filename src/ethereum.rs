@@ -0,0 +1,176 @@
+//! Signing of [`DataItem`]s with Ethereum secp256k1 keys, ANS-104 signature type 3.
+
+use crate::{
+    bundle::{DataItem, ETHEREUM_SIGNATURE_TYPE},
+    crypto::Provider,
+    error::Error,
+    transaction::{Base64, ToItems},
+};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+/// Signs [`DataItem`]s with an Ethereum private key.
+pub struct EthereumSigner {
+    signing_key: SigningKey,
+}
+
+impl EthereumSigner {
+    /// Creates a signer from a 32 byte private key.
+    pub fn from_bytes(private_key: &[u8]) -> Result<Self, Error> {
+        let signing_key = SigningKey::from_slice(private_key)
+            .map_err(|e| Error::InvalidEthereumKey(e.to_string()))?;
+        Ok(Self { signing_key })
+    }
+
+    /// Creates a signer from a hex encoded private key, with or without a `0x` prefix, as
+    /// exported by wallets.
+    pub fn from_hex(private_key: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex(private_key.trim())?)
+    }
+
+    /// Uncompressed public key, used as the owner of signed [`DataItem`]s.
+    pub fn public_key(&self) -> Base64 {
+        Base64(
+            self.signing_key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+        )
+    }
+
+    /// Checksum-less `0x` prefixed address of the wallet.
+    pub fn address(&self) -> String {
+        let hash = Keccak256::digest(&self.public_key().0[1..]);
+        format!("0x{}", encode_hex(&hash[12..]))
+    }
+
+    /// Signs `message` as an Ethereum personal message, returning the 65 byte `r || s || v`
+    /// signature.
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let (signature, recovery_id) = self
+            .signing_key
+            .sign_digest_recoverable(personal_message_digest(message))
+            .map_err(|_| Error::InvalidSignature)?;
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte() + 27);
+        Ok(bytes)
+    }
+
+    /// Sets the owner and signature type of `data_item`, signs it and sets its id to the hash
+    /// of the signature. The deep hash of the item is calculated with `crypto`.
+    pub fn sign_data_item(
+        &self,
+        crypto: &Provider,
+        mut data_item: DataItem,
+    ) -> Result<DataItem, Error> {
+        data_item.signature_type = ETHEREUM_SIGNATURE_TYPE;
+        data_item.owner = self.public_key();
+        let deep_hash = crypto.deep_hash(data_item.to_deep_hash_item()?)?;
+        let signature = self.sign(&deep_hash)?;
+        let id = crypto.hash_sha256(&signature)?;
+
+        data_item.signature = Base64(signature);
+        data_item.id = Base64(id.to_vec());
+        Ok(data_item)
+    }
+}
+
+/// Verifies that `signature` of `message` was made by the key of the uncompressed `public_key`.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), Error> {
+    if signature.len() != 65 {
+        return Err(Error::InvalidSignature);
+    }
+    let recovery_id = match signature[64] {
+        v @ 27..=28 => v - 27,
+        v => v,
+    };
+    let recovery_id = RecoveryId::from_byte(recovery_id).ok_or(Error::InvalidSignature)?;
+    let signature = Signature::from_slice(&signature[..64]).map_err(|_| Error::InvalidSignature)?;
+    let recovered = VerifyingKey::recover_from_digest(
+        personal_message_digest(message),
+        &signature,
+        recovery_id,
+    )
+    .map_err(|_| Error::InvalidSignature)?;
+
+    if recovered.to_encoded_point(false).as_bytes() == public_key {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+/// Verifies the signature of a [`DataItem`] signed with [`EthereumSigner::sign_data_item`].
+pub fn verify_data_item(crypto: &Provider, data_item: &DataItem) -> Result<(), Error> {
+    let deep_hash = crypto.deep_hash(data_item.to_deep_hash_item()?)?;
+    verify(&data_item.owner.0, &deep_hash, &data_item.signature.0)
+}
+
+fn personal_message_digest(message: &[u8]) -> Keccak256 {
+    Keccak256::new()
+        .chain_update(format!("\x19Ethereum Signed Message:\n{}", message.len()))
+        .chain_update(message)
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(Error::InvalidEthereumKey(hex.to_string()));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| Error::InvalidEthereumKey(hex.to_string()))
+        })
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify, verify_data_item, EthereumSigner};
+    use crate::{
+        bundle::{DataItem, ETHEREUM_SIGNATURE_TYPE},
+        crypto::Provider,
+        transaction::{Base64, FromUtf8Strs, Tag},
+    };
+
+    #[test]
+    fn test_sign_data_item() {
+        // Well known test key, e.g. the first hardhat account.
+        let signer = EthereumSigner::from_hex(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        assert_eq!(
+            signer.address(),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+
+        let crypto = Provider::default();
+        let data_item = DataItem {
+            data: Base64::from_utf8_str("tasty").unwrap(),
+            tags: vec![Tag::<String>::from_utf8_strs("Content-Type", "text/plain").unwrap()],
+            ..DataItem::default()
+        };
+        let data_item = signer.sign_data_item(&crypto, data_item).unwrap();
+        assert_eq!(data_item.signature_type, ETHEREUM_SIGNATURE_TYPE);
+        assert_eq!(data_item.signature.0.len(), 65);
+        assert_eq!(data_item.owner.0.len(), 65);
+        assert_eq!(
+            data_item.id.0,
+            crypto.hash_sha256(&data_item.signature.0).unwrap()
+        );
+        verify_data_item(&crypto, &data_item).unwrap();
+
+        let mut tampered = data_item.clone();
+        tampered.data = Base64::from_utf8_str("bland").unwrap();
+        assert!(verify_data_item(&crypto, &tampered).is_err());
+        assert!(verify(&data_item.owner.0, b"message", &data_item.signature.0).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Export of statuses to Parquet. Enabled with the `parquet-export` feature.
+
+use crate::{
+    error::Error,
+    status::{BundleStatus, Status},
+};
+use arrow::{
+    array::{ArrayRef, Decimal128Array, StringArray, TimestampMillisecondArray, UInt64Array},
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use parquet::arrow::ArrowWriter;
+use std::{fs::File, path::Path, sync::Arc};
+
+/// Columns shared by [`Status`] and [`BundleStatus`] rows.
+struct StatusRow {
+    id: String,
+    file_path: Option<String>,
+    status: String,
+    created_at: i64,
+    last_modified: i64,
+    reward: u128,
+    /// Size of the file on disk, if it still exists, for [`Status`] rows.
+    data_size: Option<u64>,
+    number_of_files: u64,
+    block_height: Option<u64>,
+    number_of_confirmations: Option<u64>,
+}
+
+impl From<&Status> for StatusRow {
+    fn from(status: &Status) -> Self {
+        Self {
+            id: status.id.to_string(),
+            file_path: status.file_path.as_ref().map(|p| p.display().to_string()),
+            status: status.status.to_string(),
+            created_at: status.created_at.timestamp_millis(),
+            last_modified: status.last_modified.timestamp_millis(),
+            reward: status.reward,
+            data_size: status
+                .file_path
+                .as_ref()
+                .and_then(|p| p.metadata().ok())
+                .map(|m| m.len()),
+            number_of_files: 1,
+            block_height: status.raw_status.as_ref().map(|r| r.block_height),
+            number_of_confirmations: status
+                .raw_status
+                .as_ref()
+                .map(|r| r.number_of_confirmations),
+        }
+    }
+}
+
+impl From<&BundleStatus> for StatusRow {
+    fn from(status: &BundleStatus) -> Self {
+        Self {
+            id: status.id.to_string(),
+            file_path: None,
+            status: status.status.to_string(),
+            created_at: status.created_at.timestamp_millis(),
+            last_modified: status.last_modified.timestamp_millis(),
+            reward: status.reward,
+            data_size: Some(status.data_size),
+            number_of_files: status.number_of_files,
+            block_height: status.raw_status.as_ref().map(|r| r.block_height),
+            number_of_confirmations: status
+                .raw_status
+                .as_ref()
+                .map(|r| r.number_of_confirmations),
+        }
+    }
+}
+
+/// Schema of the exported record batches.
+pub fn status_schema() -> Schema {
+    let timestamp = DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()));
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("file_path", DataType::Utf8, true),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("created_at", timestamp.clone(), false),
+        Field::new("last_modified", timestamp, false),
+        Field::new("reward", DataType::Decimal128(38, 0), false),
+        Field::new("data_size", DataType::UInt64, true),
+        Field::new("number_of_files", DataType::UInt64, false),
+        Field::new("block_height", DataType::UInt64, true),
+        Field::new("number_of_confirmations", DataType::UInt64, true),
+    ])
+}
+
+fn record_batch(rows: Vec<StatusRow>) -> Result<RecordBatch, Error> {
+    let timestamps = |f: fn(&StatusRow) -> i64| {
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(rows.iter().map(f)).with_timezone("UTC"),
+        ) as ArrayRef
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|r| &r.id))),
+        Arc::new(StringArray::from_iter(
+            rows.iter().map(|r| r.file_path.as_ref()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| &r.status),
+        )),
+        timestamps(|r| r.created_at),
+        timestamps(|r| r.last_modified),
+        Arc::new(
+            Decimal128Array::from_iter_values(rows.iter().map(|r| r.reward as i128))
+                .with_precision_and_scale(38, 0)?,
+        ),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|r| r.data_size))),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|r| r.number_of_files),
+        )),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|r| r.block_height))),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|r| r.number_of_confirmations),
+        )),
+    ];
+    Ok(RecordBatch::try_new(Arc::new(status_schema()), columns)?)
+}
+
+fn write_parquet(batch: RecordBatch, output_path: &Path) -> Result<(), Error> {
+    let file = File::create(output_path)?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Converts [`Status`]es to a [`RecordBatch`] with the [`status_schema`].
+pub fn statuses_to_record_batch(statuses: &[Status]) -> Result<RecordBatch, Error> {
+    record_batch(statuses.iter().map(StatusRow::from).collect())
+}
+
+/// Converts [`BundleStatus`]es to a [`RecordBatch`] with the [`status_schema`].
+pub fn bundle_statuses_to_record_batch(statuses: &[BundleStatus]) -> Result<RecordBatch, Error> {
+    record_batch(statuses.iter().map(StatusRow::from).collect())
+}
+
+/// Writes [`Status`]es to a Parquet file at `output_path`.
+pub fn write_statuses_parquet(statuses: &[Status], output_path: &Path) -> Result<(), Error> {
+    write_parquet(statuses_to_record_batch(statuses)?, output_path)
+}
+
+/// Writes [`BundleStatus`]es to a Parquet file at `output_path`.
+pub fn write_bundle_statuses_parquet(
+    statuses: &[BundleStatus],
+    output_path: &Path,
+) -> Result<(), Error> {
+    write_parquet(bundle_statuses_to_record_batch(statuses)?, output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_statuses_parquet;
+    use crate::{
+        error::Error,
+        status::{RawStatus, Status, StatusCode},
+        transaction::Base64,
+        utils::TempDir,
+    };
+    use arrow::array::{Array, StringArray, UInt64Array};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::{fs::File, path::PathBuf, str::FromStr};
+
+    #[tokio::test]
+    async fn test_write_statuses_parquet() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let statuses = vec![
+            Status {
+                id: Base64(vec![1; 32]),
+                file_path: Some(PathBuf::from("tests/fixtures/0.png")),
+                status: StatusCode::Confirmed,
+                reward: 100,
+                raw_status: Some(RawStatus {
+                    block_height: 10,
+                    block_indep_hash: Base64(vec![0; 48]),
+                    number_of_confirmations: 25,
+                }),
+                ..Status::default()
+            },
+            Status {
+                id: Base64(vec![2; 32]),
+                reward: 200,
+                ..Status::default()
+            },
+        ];
+        let output_path = temp_dir.0.join("statuses.parquet");
+        write_statuses_parquet(&statuses, &output_path)?;
+
+        let batch = ParquetRecordBatchReaderBuilder::try_new(File::open(output_path)?)?
+            .build()?
+            .next()
+            .unwrap()?;
+        assert_eq!(batch.num_rows(), 2);
+        let status = batch
+            .column_by_name("status")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(status.value(0), "Confirmed");
+        let data_size = batch
+            .column_by_name("data_size")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(
+            data_size.value(0),
+            PathBuf::from("tests/fixtures/0.png").metadata()?.len()
+        );
+        assert!(data_size.is_null(1));
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+//! C-compatible functions declared in `include/arloader.h`. Enabled with the `ffi` feature.
+
+use crate::{
+    blocking::Arweave,
+    error::Error,
+    status::{Status, StatusCode},
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr,
+    str::FromStr,
+};
+use url::Url;
+
+pub const ARLOADER_OK: i32 = 0;
+/// A pointer was null or a string wasn't valid UTF-8.
+pub const ARLOADER_ERROR_INVALID_ARGUMENT: i32 = -1;
+/// The request failed. See [`arloader_last_error`].
+pub const ARLOADER_ERROR_FAILED: i32 = -2;
+/// Arloader panicked. See [`arloader_last_error`].
+pub const ARLOADER_ERROR_PANIC: i32 = -3;
+
+/// Gateway used when [`arloader_client_new`] isn't given one.
+const DEFAULT_BASE_URL: &str = "https://arweave.net/";
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Status of an upload, as [`StatusCode`] without the failure reason.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArloaderStatusCode {
+    Submitted = 0,
+    Pending = 1,
+    Confirmed = 2,
+    NotFound = 3,
+    Blocked = 4,
+    Failed = 5,
+    Dropped = 6,
+    Signed = 7,
+}
+
+impl From<&StatusCode> for ArloaderStatusCode {
+    fn from(status: &StatusCode) -> Self {
+        match status {
+            StatusCode::Submitted => Self::Submitted,
+            StatusCode::Pending => Self::Pending,
+            StatusCode::Confirmed => Self::Confirmed,
+            StatusCode::NotFound => Self::NotFound,
+            StatusCode::Blocked => Self::Blocked,
+            StatusCode::Failed { .. } => Self::Failed,
+            StatusCode::Dropped => Self::Dropped,
+            StatusCode::Signed => Self::Signed,
+        }
+    }
+}
+
+/// Status of an uploaded file. Strings are owned by the status until it is freed with
+/// [`arloader_status_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArloaderStatus {
+    /// Id of the transaction, or of the data item for bundled files.
+    pub id: *mut c_char,
+    pub status: ArloaderStatusCode,
+    pub confirmations: u64,
+    /// Reward paid in winstons, `u64::MAX` if it doesn't fit.
+    pub reward: u64,
+}
+
+impl ArloaderStatus {
+    fn from_status(status: &Status) -> Self {
+        Self {
+            id: to_c_string(&status.id.to_string()),
+            status: ArloaderStatusCode::from(&status.status),
+            confirmations: status
+                .raw_status
+                .as_ref()
+                .map_or(0, |raw| raw.number_of_confirmations),
+            reward: u64::try_from(status.reward).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Uploads files with a wallet, writing their statuses to a log directory.
+pub struct ArloaderClient {
+    arweave: Arweave,
+    log_dir: PathBuf,
+}
+
+/// Creates a client that uploads with the keypair at `keypair_path` to `base_url`, or
+/// `https://arweave.net/` if it is null, writing statuses to the existing directory `log_dir`.
+/// Returns null if the client couldn't be created.
+///
+/// # Safety
+///
+/// Strings must be null or nul-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn arloader_client_new(
+    keypair_path: *const c_char,
+    base_url: *const c_char,
+    log_dir: *const c_char,
+) -> *mut ArloaderClient {
+    catch_panic(ptr::null_mut(), || {
+        let client = (|| -> Result<ArloaderClient, Error> {
+            let keypair_path = PathBuf::from(from_c_str(keypair_path)?);
+            let base_url = match base_url.is_null() {
+                true => Url::from_str(DEFAULT_BASE_URL)?,
+                false => Url::from_str(from_c_str(base_url)?)?,
+            };
+            let log_dir = PathBuf::from(from_c_str(log_dir)?);
+            Ok(ArloaderClient {
+                arweave: Arweave::from_keypair_path(keypair_path, base_url)?,
+                log_dir,
+            })
+        })();
+        match client {
+            Ok(client) => Box::into_raw(Box::new(client)),
+            Err(error) => {
+                set_last_error(&error);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Frees a client created with [`arloader_client_new`].
+///
+/// # Safety
+///
+/// `client` must be null or returned by [`arloader_client_new`], and not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn arloader_client_free(client: *mut ArloaderClient) {
+    catch_panic((), || {
+        if !client.is_null() {
+            drop(Box::from_raw(client));
+        }
+    })
+}
+
+/// Uploads the file at `file_path`, blocking until it is posted, and fills `status` with its
+/// status.
+///
+/// # Safety
+///
+/// `client` must be returned by [`arloader_client_new`], `file_path` nul-terminated and `status`
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn arloader_upload_path(
+    client: *const ArloaderClient,
+    file_path: *const c_char,
+    status: *mut ArloaderStatus,
+) -> i32 {
+    with_status(client, file_path, status, |client, file_path| {
+        let price_terms = client.arweave.get_price_terms(1.0)?;
+        client.arweave.upload_file_from_path(
+            file_path,
+            Some(client.log_dir.clone()),
+            None,
+            None,
+            price_terms,
+            None,
+        )
+    })
+}
+
+/// Updates the status of the file uploaded from `file_path` from the network and fills `status`
+/// with it.
+///
+/// # Safety
+///
+/// As for [`arloader_upload_path`].
+#[no_mangle]
+pub unsafe extern "C" fn arloader_poll_status(
+    client: *const ArloaderClient,
+    file_path: *const c_char,
+    status: *mut ArloaderStatus,
+) -> i32 {
+    with_status(client, file_path, status, |client, file_path| {
+        client
+            .arweave
+            .update_status(file_path, client.log_dir.clone())
+    })
+}
+
+/// Frees the strings of a status filled by [`arloader_upload_path`] or
+/// [`arloader_poll_status`], leaving the status itself to the caller.
+///
+/// # Safety
+///
+/// `status` must be null or filled by one of those functions, and not freed already.
+#[no_mangle]
+pub unsafe extern "C" fn arloader_status_free(status: *mut ArloaderStatus) {
+    catch_panic((), || {
+        if let Some(status) = status.as_mut() {
+            if !status.id.is_null() {
+                drop(CString::from_raw(status.id));
+                status.id = ptr::null_mut();
+            }
+        }
+    })
+}
+
+/// Returns the message of the last error on the calling thread, or null if there wasn't one. The
+/// message is valid until the next call that fails on the same thread.
+#[no_mangle]
+pub extern "C" fn arloader_last_error() -> *const c_char {
+    catch_panic(ptr::null(), || {
+        LAST_ERROR.with(|last_error| {
+            last_error
+                .borrow()
+                .as_ref()
+                .map_or(ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// Runs `f`, returning `on_panic` and setting the last error if it panics, since unwinding into
+/// the caller of an `extern "C"` function is undefined behavior.
+fn catch_panic<T, F: FnOnce() -> T>(on_panic: T, f: F) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        set_last_error(format!("arloader panicked: {}", panic_message(&*payload)));
+        on_panic
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Runs `f` with the client and path, writing the status it returns to `status`.
+unsafe fn with_status<F>(
+    client: *const ArloaderClient,
+    file_path: *const c_char,
+    status: *mut ArloaderStatus,
+    f: F,
+) -> i32
+where
+    F: FnOnce(&ArloaderClient, PathBuf) -> Result<Status, Error>,
+{
+    catch_panic(ARLOADER_ERROR_PANIC, || {
+        let (client, file_path) = match (client.as_ref(), from_c_str(file_path)) {
+            (Some(client), Ok(file_path)) if !status.is_null() => {
+                (client, PathBuf::from(file_path))
+            }
+            _ => {
+                set_last_error("client, file path or status is null or invalid");
+                return ARLOADER_ERROR_INVALID_ARGUMENT;
+            }
+        };
+        match f(client, file_path) {
+            Ok(result) => {
+                status.write(ArloaderStatus::from_status(&result));
+                ARLOADER_OK
+            }
+            Err(error) => {
+                set_last_error(&error);
+                ARLOADER_ERROR_FAILED
+            }
+        }
+    })
+}
+
+unsafe fn from_c_str<'a>(s: *const c_char) -> Result<&'a str, Error> {
+    if s.is_null() {
+        return Err(Error::InvalidFfiArgument("null string".to_string()));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| Error::InvalidFfiArgument(e.to_string()))
+}
+
+fn to_c_string(s: &str) -> *mut c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+fn set_last_error<D: std::fmt::Display>(error: D) {
+    let message = CString::new(error.to_string().replace('\0', " ")).ok();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arloader_client_free, arloader_client_new, arloader_last_error, arloader_poll_status,
+        arloader_status_free, arloader_upload_path, with_status, ArloaderClient, ArloaderStatus,
+        ArloaderStatusCode, ARLOADER_ERROR_FAILED, ARLOADER_ERROR_INVALID_ARGUMENT,
+        ARLOADER_ERROR_PANIC, ARLOADER_OK,
+    };
+    use crate::{blocking::Arweave, error::Error, simulation::SimulatedGateway, utils::TempDir};
+    use std::{
+        ffi::{CStr, CString},
+        mem::MaybeUninit,
+        ptr,
+        sync::Arc,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_ffi_upload() -> Result<(), Error> {
+        let arweave = Arweave::new(crate::Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..crate::Arweave::default()
+        })?;
+        let temp_dir = tokio::runtime::Runtime::new()?.block_on(TempDir::from_str("./tests/"))?;
+        let client = Box::into_raw(Box::new(ArloaderClient {
+            arweave,
+            log_dir: temp_dir.0.clone(),
+        }));
+        let file_path = CString::new("tests/fixtures/0.png").unwrap();
+
+        unsafe {
+            let mut status = MaybeUninit::<ArloaderStatus>::uninit();
+            let code = arloader_upload_path(client, file_path.as_ptr(), status.as_mut_ptr());
+            assert_eq!(code, ARLOADER_OK);
+            let mut status = status.assume_init();
+            assert_eq!(status.status, ArloaderStatusCode::Submitted);
+            let id = CStr::from_ptr(status.id).to_str().unwrap().to_string();
+            arloader_status_free(&mut status);
+            assert!(status.id.is_null());
+
+            let code = arloader_poll_status(client, file_path.as_ptr(), &mut status);
+            assert_eq!(code, ARLOADER_OK);
+            assert_eq!(status.status, ArloaderStatusCode::Confirmed);
+            assert_eq!(CStr::from_ptr(status.id).to_str().unwrap(), id);
+            arloader_status_free(&mut status);
+
+            let missing = CString::new("tests/fixtures/1.png").unwrap();
+            let code = arloader_poll_status(client, missing.as_ptr(), &mut status);
+            assert_eq!(code, ARLOADER_ERROR_FAILED);
+            assert!(!arloader_last_error().is_null());
+            assert_eq!(
+                arloader_poll_status(client, ptr::null(), &mut status),
+                ARLOADER_ERROR_INVALID_ARGUMENT
+            );
+            let code = with_status(client, file_path.as_ptr(), &mut status, |_, _| {
+                panic!("broken client")
+            });
+            assert_eq!(code, ARLOADER_ERROR_PANIC);
+            assert_eq!(
+                CStr::from_ptr(arloader_last_error()).to_str().unwrap(),
+                "arloader panicked: broken client"
+            );
+
+            arloader_client_free(client);
+            assert!(arloader_client_new(ptr::null(), ptr::null(), ptr::null()).is_null());
+        }
+        Ok(())
+    }
+}
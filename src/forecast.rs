@@ -0,0 +1,136 @@
+//! Projections of AR spend for continuous ingestion.
+
+use crate::status::{BundleStatus, Status};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Number of most recent uploads that the fee rate is averaged over.
+pub const FORECAST_FEE_SAMPLES: usize = 50;
+
+/// Size and reward of one upload, taken from its status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendSample {
+    pub created_at: DateTime<Utc>,
+    pub data_size: u64,
+    pub reward: u128,
+}
+
+impl From<&BundleStatus> for SpendSample {
+    fn from(status: &BundleStatus) -> Self {
+        Self {
+            created_at: status.created_at,
+            data_size: status.data_size,
+            reward: status.reward,
+        }
+    }
+}
+
+impl SpendSample {
+    /// Returns a sample for `status` with its [`Status::uploaded_size`], or `None` if it isn't
+    /// known.
+    pub fn from_status(status: &Status) -> Option<Self> {
+        Some(Self {
+            created_at: status.created_at,
+            data_size: status.uploaded_size()?,
+            reward: status.reward,
+        })
+    }
+}
+
+/// Projected AR spend, in winstons, at the observed ingestion rate and recent fees.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpendForecast {
+    /// Days of uploads the ingestion rate was observed over.
+    pub window_days: u32,
+    pub bytes_per_day: u64,
+    pub winstons_per_byte: f64,
+    pub daily_winstons: u128,
+    pub weekly_winstons: u128,
+}
+
+impl SpendForecast {
+    /// Projects spend from the `samples` created in the `window_days` before `now`. Returns
+    /// `None` if there are no paid uploads to take a fee rate from.
+    pub fn from_samples(
+        samples: &[SpendSample],
+        now: DateTime<Utc>,
+        window_days: u32,
+    ) -> Option<Self> {
+        let window_start = now - Duration::days(window_days as i64);
+        let window_bytes: u64 = samples
+            .iter()
+            .filter(|s| s.created_at > window_start && s.created_at <= now)
+            .map(|s| s.data_size)
+            .sum();
+        let bytes_per_day = window_bytes / window_days.max(1) as u64;
+
+        let mut paid: Vec<&SpendSample> = samples
+            .iter()
+            .filter(|s| s.reward > 0 && s.data_size > 0)
+            .collect();
+        paid.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        paid.truncate(FORECAST_FEE_SAMPLES);
+        let (sampled_rewards, sampled_bytes) = paid.iter().fold((0u128, 0u128), |(r, b), s| {
+            (r + s.reward, b + s.data_size as u128)
+        });
+        if sampled_bytes == 0 {
+            return None;
+        }
+        let winstons_per_byte = sampled_rewards as f64 / sampled_bytes as f64;
+
+        let daily_winstons = (bytes_per_day as f64 * winstons_per_byte).ceil() as u128;
+        Some(Self {
+            window_days,
+            bytes_per_day,
+            winstons_per_byte,
+            daily_winstons,
+            weekly_winstons: daily_winstons * 7,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpendForecast, SpendSample};
+    use crate::status::Status;
+    use chrono::{Duration, Utc};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_spend_forecast() {
+        let now = Utc::now();
+        let sample = |days_ago: i64, data_size: u64, reward: u128| SpendSample {
+            created_at: now - Duration::days(days_ago) + Duration::minutes(1),
+            data_size,
+            reward,
+        };
+        let samples = vec![
+            sample(1, 1_000, 2_000),
+            sample(2, 3_000, 6_000),
+            // Outside the window, but still a recent fee sample.
+            sample(10, 4_000, 8_000),
+        ];
+
+        let forecast = SpendForecast::from_samples(&samples, now, 2).unwrap();
+        assert_eq!(forecast.bytes_per_day, 2_000);
+        assert_eq!(forecast.winstons_per_byte, 2.0);
+        assert_eq!(forecast.daily_winstons, 4_000);
+        assert_eq!(forecast.weekly_winstons, 28_000);
+
+        assert!(SpendForecast::from_samples(&[sample(1, 1_000, 0)], now, 2).is_none());
+
+        // Compressed uploads are sized by what was uploaded, not by the file on disk.
+        let status = Status {
+            file_path: Some(PathBuf::from("tests/fixtures/0.png")),
+            data_size: Some(500),
+            reward: 1_000,
+            ..Status::default()
+        };
+        assert_eq!(SpendSample::from_status(&status).unwrap().data_size, 500);
+        let status = Status {
+            file_path: Some(PathBuf::from("tests/fixtures/missing.png")),
+            ..status
+        };
+        assert_eq!(SpendSample::from_status(&status).unwrap().data_size, 500);
+    }
+}
@@ -0,0 +1,137 @@
+//! Locale aware formatting of cost and size figures in reports.
+
+use crate::error::Error;
+use num_format::{Grouping, Locale};
+use std::fmt::Display;
+
+/// Number of decimal places in one AR denominated in winstons.
+const AR_DECIMALS: usize = 12;
+
+/// Formats numbers using the digit separators and decimal point of `locale`. Without a locale
+/// numbers are formatted as before, without separators and with a `.` decimal point.
+#[derive(Debug, Clone, Copy)]
+pub struct NumberFormat {
+    pub locale: Option<Locale>,
+    /// Number of decimal places AR amounts are truncated to.
+    pub ar_decimals: usize,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            locale: None,
+            ar_decimals: AR_DECIMALS,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Creates a [`NumberFormat`] from a locale name, like `en`, `de` or `fr-CA`.
+    pub fn new(locale_name: Option<&str>, ar_decimals: usize) -> Result<Self, Error> {
+        let locale = locale_name
+            .map(|n| Locale::from_name(n).map_err(|_| Error::InvalidLocale(n.to_string())))
+            .transpose()?;
+        Ok(Self {
+            locale,
+            ar_decimals: ar_decimals.min(AR_DECIMALS),
+        })
+    }
+
+    /// Formats an integer, grouping digits with the separator of the locale.
+    pub fn integer<T: Display>(&self, n: T) -> String {
+        let s = n.to_string();
+        match s.strip_prefix('-') {
+            Some(digits) => format!("-{}", self.group(digits)),
+            None => self.group(&s),
+        }
+    }
+
+    /// Formats a float rounded to `decimals` places.
+    pub fn decimal(&self, n: f64, decimals: usize) -> String {
+        let s = format!("{:.*}", decimals, n);
+        match s.split_once('.') {
+            Some((int, frac)) => format!("{}{}{}", self.integer(int), self.decimal_point(), frac),
+            None => self.integer(s),
+        }
+    }
+
+    /// Formats an amount of winstons as AR, truncated to `ar_decimals` places.
+    pub fn winstons_as_ar<T: Display>(&self, winstons: T) -> String {
+        let digits = format!("{:0>width$}", winstons.to_string(), width = AR_DECIMALS + 1);
+        let (int, frac) = digits.split_at(digits.len() - AR_DECIMALS);
+        let frac = &frac[..self.ar_decimals];
+        if frac.is_empty() {
+            self.integer(int)
+        } else {
+            format!("{}{}{}", self.integer(int), self.decimal_point(), frac)
+        }
+    }
+
+    fn decimal_point(&self) -> &str {
+        self.locale.as_ref().map(|l| l.decimal()).unwrap_or(".")
+    }
+
+    fn group(&self, digits: &str) -> String {
+        let locale = match &self.locale {
+            Some(locale) => locale,
+            None => return digits.to_string(),
+        };
+        let (head, tail) = match locale.grouping() {
+            Grouping::Posix => return digits.to_string(),
+            Grouping::Standard => (3, 3),
+            Grouping::Indian => (3, 2),
+        };
+        if digits.len() <= head {
+            return digits.to_string();
+        }
+
+        let (rest, last) = digits.split_at(digits.len() - head);
+        let mut groups = vec![last];
+        let mut rest = rest;
+        while rest.len() > tail {
+            let (r, g) = rest.split_at(rest.len() - tail);
+            groups.push(g);
+            rest = r;
+        }
+        groups.push(rest);
+        groups.reverse();
+        groups.join(locale.separator())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+    use crate::error::Error;
+    use matches::assert_matches;
+
+    #[test]
+    fn test_number_format() -> Result<(), Error> {
+        let plain = NumberFormat::default();
+        assert_eq!(plain.integer(1234567), "1234567");
+        assert_eq!(
+            plain.winstons_as_ar(1234567890123456789_u64),
+            "1234567.890123456789"
+        );
+
+        let en = NumberFormat::new(Some("en"), 6)?;
+        assert_eq!(en.integer(1234567), "1,234,567");
+        assert_eq!(en.integer(-123), "-123");
+        assert_eq!(en.decimal(1234.5678, 2), "1,234.57");
+        assert_eq!(
+            en.winstons_as_ar(1234567890123456789_u64),
+            "1,234,567.890123"
+        );
+        assert_eq!(en.winstons_as_ar(5), "0.000000");
+
+        let de = NumberFormat::new(Some("de"), 0)?;
+        assert_eq!(de.decimal(1234567.891, 2), "1.234.567,89");
+        assert_eq!(de.winstons_as_ar(1234567890123456789_u64), "1.234.567");
+
+        assert_matches!(
+            NumberFormat::new(Some("xx-nope"), 2),
+            Err(Error::InvalidLocale(_))
+        );
+        Ok(())
+    }
+}
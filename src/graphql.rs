@@ -0,0 +1,323 @@
+//! Queries of the GraphQL endpoint of a gateway.
+
+#[cfg(feature = "native")]
+use crate::status::{OutputFormat, QuietDisplay, VerboseDisplay};
+use crate::{
+    error::Error,
+    transaction::{Base64, Tag},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+#[cfg(feature = "native")]
+use std::fmt;
+
+/// Number of transactions requested per page.
+pub const GRAPHQL_PAGE_SIZE: u64 = 100;
+
+/// Name of the tag holding the BLAKE3 hash of the contents of an upload, so that it can be found
+/// again with [`content_hash_query`].
+pub const CONTENT_HASH_TAG: &str = "Content-Hash";
+
+const WALLET_TRANSACTIONS_QUERY: &str =
+    "query($owners: [String!], $tags: [TagFilter!], $first: Int, $after: String) {
+  transactions(owners: $owners, tags: $tags, first: $first, after: $after, sort: HEIGHT_DESC) {
+    pageInfo { hasNextPage }
+    edges {
+      cursor
+      node {
+        id
+        tags { name value }
+        data { size }
+        block { id height timestamp }
+      }
+    }
+  }
+}";
+
+/// Block a transaction was mined in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlockInfo {
+    pub id: String,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+/// Transaction of a wallet, with the cursor to pass to get the transactions after it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WalletTransaction {
+    pub id: Base64,
+    pub tags: Vec<Tag<String>>,
+    pub data_size: u64,
+    /// `None` while the transaction is pending.
+    pub block: Option<BlockInfo>,
+    pub cursor: String,
+}
+
+/// Page of the transactions of a wallet, newest first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TransactionPage {
+    pub transactions: Vec<WalletTransaction>,
+    pub has_next_page: bool,
+}
+
+impl TransactionPage {
+    /// Cursor to request the next page with, if there is one.
+    pub fn next_cursor(&self) -> Option<String> {
+        if self.has_next_page {
+            self.transactions.last().map(|t| t.cursor.clone())
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the body of a request for a page of the transactions owned by `wallet_address`,
+/// starting after `after_cursor`.
+pub fn wallet_transactions_query(wallet_address: &str, after_cursor: Option<&str>) -> Value {
+    wallet_transactions_with_tags_query(wallet_address, &[], after_cursor)
+}
+
+/// Same as [`wallet_transactions_query`], only including transactions that have all of `tags`.
+pub fn wallet_transactions_with_tags_query(
+    wallet_address: &str,
+    tags: &[Tag<String>],
+    after_cursor: Option<&str>,
+) -> Value {
+    let tags: Vec<Value> = tags
+        .iter()
+        .map(|tag| json!({ "name": tag.name, "values": [tag.value] }))
+        .collect();
+    json!({
+        "query": WALLET_TRANSACTIONS_QUERY,
+        "variables": {
+            "owners": [wallet_address],
+            "tags": if tags.is_empty() { Value::Null } else { Value::from(tags) },
+            "first": GRAPHQL_PAGE_SIZE,
+            "after": after_cursor,
+        }
+    })
+}
+
+/// Returns the body of a request for the latest transaction owned by any of `wallet_addresses`
+/// that is tagged with `content_hash` as its [`CONTENT_HASH_TAG`].
+pub fn content_hash_query(wallet_addresses: &[String], content_hash: &str) -> Value {
+    json!({
+        "query": WALLET_TRANSACTIONS_QUERY,
+        "variables": {
+            "owners": wallet_addresses,
+            "tags": [{ "name": CONTENT_HASH_TAG, "values": [content_hash] }],
+            "first": 1,
+        }
+    })
+}
+
+/// Returns the body of a request for a page of the transactions owned by any of
+/// `wallet_addresses`, pending ones first, starting after `after_cursor`.
+pub fn pending_transactions_query(
+    wallet_addresses: &[String],
+    after_cursor: Option<&str>,
+) -> Value {
+    json!({
+        "query": WALLET_TRANSACTIONS_QUERY,
+        "variables": {
+            "owners": wallet_addresses,
+            "first": GRAPHQL_PAGE_SIZE,
+            "after": after_cursor,
+        }
+    })
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<GraphQlData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlData {
+    transactions: GraphQlTransactions,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlTransactions {
+    page_info: GraphQlPageInfo,
+    edges: Vec<GraphQlEdge>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPageInfo {
+    has_next_page: bool,
+}
+
+#[derive(Deserialize)]
+struct GraphQlEdge {
+    cursor: String,
+    node: GraphQlNode,
+}
+
+#[derive(Deserialize)]
+struct GraphQlNode {
+    id: Base64,
+    tags: Vec<Tag<String>>,
+    data: GraphQlDataSize,
+    block: Option<BlockInfo>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlDataSize {
+    #[serde(deserialize_with = "deserialize_size")]
+    size: u64,
+}
+
+/// Sizes are returned as strings by the gateway, but as numbers by some other indexers.
+fn deserialize_size<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+        Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| serde::de::Error::custom("invalid data size")),
+        v => Err(serde::de::Error::custom(format!("invalid data size {}", v))),
+    }
+}
+
+/// Parses the response to [`wallet_transactions_query`], returning [`Error::GraphQl`] with
+/// the messages of any errors in it.
+pub fn parse_transaction_page(body: &str) -> Result<TransactionPage, Error> {
+    let response: GraphQlResponse = serde_json::from_str(body)?;
+    if !response.errors.is_empty() {
+        let messages: Vec<String> = response.errors.into_iter().map(|e| e.message).collect();
+        return Err(Error::GraphQl(messages.join("; ")));
+    }
+    let transactions = response
+        .data
+        .ok_or_else(|| Error::GraphQl("response has no data".to_string()))?
+        .transactions;
+
+    Ok(TransactionPage {
+        has_next_page: transactions.page_info.has_next_page,
+        transactions: transactions
+            .edges
+            .into_iter()
+            .map(|edge| WalletTransaction {
+                id: edge.node.id,
+                tags: edge.node.tags,
+                data_size: edge.node.data.size,
+                block: edge.node.block,
+                cursor: edge.cursor,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(feature = "native")]
+impl WalletTransaction {
+    pub fn header_string(&self, output_format: &OutputFormat) -> String {
+        match output_format {
+            OutputFormat::Display => {
+                format!(
+                    " {:<43}  {:>8}  {:>12}  {}
+{:-<86}",
+                    "id", "height", "data_size", "tags", ""
+                )
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl QuietDisplay for WalletTransaction {
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w, "{}", self.id)
+    }
+}
+
+#[cfg(feature = "native")]
+impl VerboseDisplay for WalletTransaction {}
+
+#[cfg(feature = "native")]
+impl fmt::Display for WalletTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = match &self.block {
+            Some(block) => block.height.to_string(),
+            None => "pending".to_string(),
+        };
+        let tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|tag| format!("{}={}", tag.name, tag.value))
+            .collect();
+        writeln!(
+            f,
+            " {:<43}  {:>8}  {:>12}  {}",
+            self.id,
+            height,
+            self.data_size,
+            tags.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        content_hash_query, parse_transaction_page, wallet_transactions_query,
+        wallet_transactions_with_tags_query, CONTENT_HASH_TAG, GRAPHQL_PAGE_SIZE,
+    };
+    use crate::{
+        error::Error,
+        transaction::{FromUtf8Strs, Tag},
+    };
+    use matches::assert_matches;
+
+    #[test]
+    fn test_parse_transaction_page() -> Result<(), Error> {
+        let query = wallet_transactions_query("owner", Some("cursor"));
+        assert_eq!(query["variables"]["owners"][0], "owner");
+        assert_eq!(query["variables"]["first"], GRAPHQL_PAGE_SIZE);
+        assert_eq!(query["variables"]["after"], "cursor");
+        assert!(query["variables"]["tags"].is_null());
+        let tags = vec![Tag::<String>::from_utf8_strs("App-Name", "arloader")?];
+        let query = wallet_transactions_with_tags_query("owner", &tags, None);
+        assert!(query["variables"]["after"].is_null());
+        assert_eq!(query["variables"]["tags"][0]["name"], "App-Name");
+        assert_eq!(query["variables"]["tags"][0]["values"][0], "arloader");
+        let owners = ["owner".to_string(), "rotated".to_string()];
+        let query = content_hash_query(&owners, "hash");
+        assert_eq!(query["variables"]["owners"][1], "rotated");
+        assert_eq!(query["variables"]["tags"][0]["name"], CONTENT_HASH_TAG);
+        assert_eq!(query["variables"]["tags"][0]["values"][0], "hash");
+
+        let page = parse_transaction_page(
+            r#"{"data":{"transactions":{"pageInfo":{"hasNextPage":true},"edges":[
+            {"cursor":"WyIyMDIyIiwxXQ==","node":{"id":"kZb9p8hS8MYRKD2WLjbO8A2pBpHQ-ckmVqL8n4C3ZrU",
+            "tags":[{"name":"Content-Type","value":"image/png"}],"data":{"size":"1024"},
+            "block":{"id":"blockid","height":1014240,"timestamp":1660000000}}},
+            {"cursor":"WyIyMDIyIiwyXQ==","node":{"id":"Qf7mJ9lbh0Dx9VgZkB0Yq2Uq1JxA8sH3tnfR2bZ5Xw0",
+            "tags":[],"data":{"size":0},"block":null}}]}}}"#,
+        )?;
+        assert_eq!(page.transactions.len(), 2);
+        assert_eq!(page.transactions[0].data_size, 1024);
+        assert_eq!(page.transactions[0].tags[0].value, "image/png");
+        assert_eq!(page.transactions[0].block.as_ref().unwrap().height, 1014240);
+        assert!(page.transactions[1].block.is_none());
+        assert_eq!(page.next_cursor().as_deref(), Some("WyIyMDIyIiwyXQ=="));
+
+        assert_matches!(
+            parse_transaction_page(r#"{"errors":[{"message":"invalid owner"}]}"#),
+            Err(Error::GraphQl(_))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,414 @@
+//! gRPC service for bulk uploads, defined in `proto/arloader.proto`, with the `grpc` feature.
+
+use crate::{
+    error::Error,
+    is_bearer_token, path_under_root,
+    status::{Status, StatusCode},
+    transaction::{FromUtf8Strs, Tag},
+    update_statuses_stream, upload_files_stream, Arweave,
+};
+use futures::{stream, Future, Stream, StreamExt};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+    time::Instant,
+};
+use tonic::{transport::Server, Request, Response};
+
+/// Types generated from `proto/arloader.proto`.
+pub mod proto {
+    tonic::include_proto!("arloader");
+}
+
+use proto::uploader_server::{Uploader, UploaderServer};
+
+/// Number of files posted concurrently when an upload request doesn't set one.
+pub const GRPC_UPLOAD_BUFFER: usize = 5;
+
+/// Seconds between status updates when a watch request doesn't set them.
+pub const GRPC_WATCH_INTERVAL_SECS: u64 = 30;
+
+/// Seconds a watch request streams updates for when it doesn't set them.
+pub const GRPC_WATCH_TIMEOUT_SECS: u64 = 60 * 60;
+
+/// Stream of statuses returned by the RPCs of [`UploaderService`].
+pub type StatusStream =
+    Pin<Box<dyn Stream<Item = Result<proto::Status, tonic::Status>> + Send + 'static>>;
+
+/// Uploads files with [`UploaderService::arweave`], writing their statuses to
+/// [`UploaderService::log_dir`].
+#[derive(Clone)]
+pub struct UploaderService {
+    pub arweave: Arc<Arweave>,
+    pub log_dir: PathBuf,
+    /// Directory that files have to be in.
+    pub root_dir: PathBuf,
+    /// Token that requests have to send as `authorization: Bearer <TOKEN>` metadata.
+    pub token: String,
+}
+
+impl UploaderService {
+    pub fn new(arweave: Arweave, log_dir: PathBuf, root_dir: PathBuf, token: String) -> Self {
+        Self {
+            arweave: Arc::new(arweave),
+            log_dir,
+            root_dir,
+            token,
+        }
+    }
+
+    /// Serves the service on `addr` until the process is stopped.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), Error> {
+        Server::builder()
+            .add_service(UploaderServer::new(self))
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+
+    /// Fails with `UNAUTHENTICATED` unless `request` has the bearer token of the service.
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), tonic::Status> {
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok());
+        if is_bearer_token(authorization, &self.token) {
+            Ok(())
+        } else {
+            Err(tonic::Status::unauthenticated(
+                "missing or invalid bearer token",
+            ))
+        }
+    }
+
+    /// Returns the canonical paths of `file_paths`, failing with `PERMISSION_DENIED` if any of
+    /// them are outside [`UploaderService::root_dir`].
+    fn paths_under_root(&self, file_paths: Vec<String>) -> Result<Vec<PathBuf>, tonic::Status> {
+        file_paths
+            .iter()
+            .map(|p| path_under_root(&self.root_dir, Path::new(p)))
+            .collect::<Result<_, _>>()
+            .map_err(to_grpc_status)
+    }
+}
+
+#[tonic::async_trait]
+impl Uploader for UploaderService {
+    type UploadStream = StatusStream;
+    type WatchStatusStream = StatusStream;
+
+    async fn upload(
+        &self,
+        request: Request<proto::UploadRequest>,
+    ) -> Result<Response<StatusStream>, tonic::Status> {
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let paths = self.paths_under_root(request.file_paths)?;
+        let tags = request
+            .tags
+            .iter()
+            .map(|tag| Tag::from_utf8_strs(&tag.name, &tag.value))
+            .collect::<Result<Vec<_>, Error>>()
+            .map_err(to_grpc_status)?;
+        let reward_mult = match request.reward_multiplier {
+            m if m > 0.0 => m,
+            _ => 1.0,
+        };
+        let buffer = match request.buffer {
+            0 => GRPC_UPLOAD_BUFFER,
+            b => b as usize,
+        };
+        let price_terms = self
+            .arweave
+            .get_price_terms(reward_mult)
+            .await
+            .map_err(to_grpc_status)?;
+
+        let arweave = self.arweave.clone();
+        let log_dir = self.log_dir.clone();
+        Ok(Response::new(spawn_stream(move |tx| async move {
+            let mut statuses = Box::pin(upload_files_stream(
+                &arweave,
+                paths.into_iter(),
+                (!tags.is_empty()).then(|| tags),
+                Some(log_dir),
+                false,
+                None,
+                price_terms,
+                buffer,
+            ));
+            while let Some(result) = statuses.next().await {
+                if tx.send(to_grpc_result(result)).is_err() {
+                    break;
+                }
+            }
+        })))
+    }
+
+    async fn watch_status(
+        &self,
+        request: Request<proto::WatchStatusRequest>,
+    ) -> Result<Response<StatusStream>, tonic::Status> {
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let mut paths = self.paths_under_root(request.file_paths)?;
+        let interval = Duration::from_secs(match request.interval_secs {
+            0 => GRPC_WATCH_INTERVAL_SECS,
+            s => s,
+        });
+        let deadline = Instant::now()
+            + Duration::from_secs(match request.timeout_secs {
+                0 => GRPC_WATCH_TIMEOUT_SECS,
+                s => s,
+            });
+
+        let arweave = self.arweave.clone();
+        let log_dir = self.log_dir.clone();
+        Ok(Response::new(spawn_stream(move |tx| async move {
+            while !paths.is_empty() {
+                let mut settled = Vec::new();
+                let mut statuses = Box::pin(update_statuses_stream(
+                    &arweave,
+                    paths.clone().into_iter(),
+                    log_dir.clone(),
+                    GRPC_UPLOAD_BUFFER,
+                ));
+                while let Some(result) = statuses.next().await {
+                    if let Ok(status) = &result {
+                        if is_settled(&status.status) {
+                            settled.extend(status.file_path.clone());
+                        }
+                    }
+                    if tx.send(to_grpc_result(result)).is_err() {
+                        return;
+                    }
+                }
+                paths.retain(|p| !settled.contains(p));
+                if paths.is_empty() {
+                    break;
+                }
+                if Instant::now() + interval > deadline {
+                    let _ = tx.send(Err(tonic::Status::deadline_exceeded(format!(
+                        "{} file(s) not settled before the timeout",
+                        paths.len()
+                    ))));
+                    break;
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })))
+    }
+}
+
+impl From<Status> for proto::Status {
+    fn from(status: Status) -> Self {
+        let reason = match &status.status {
+            StatusCode::Failed { reason } => reason.clone(),
+            _ => status.blocked_reason.clone().unwrap_or_default(),
+        };
+        Self {
+            id: status.id.to_string(),
+            status: status.status.to_string(),
+            file_path: status
+                .file_path
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            content_type: status.content_type,
+            confirmations: status
+                .raw_status
+                .map_or(0, |raw| raw.number_of_confirmations),
+            reward: status.reward.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Runs `produce` on its own task, returning the stream of what it sends, so that responses
+/// don't borrow the service.
+fn spawn_stream<F, Fut>(produce: F) -> StatusStream
+where
+    F: FnOnce(UnboundedSender<Result<proto::Status, tonic::Status>>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = unbounded_channel();
+    tokio::spawn(produce(tx));
+    Box::pin(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }))
+}
+
+/// Whether a watched file won't change status again without being uploaded again.
+fn is_settled(status: &StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::Confirmed
+            | StatusCode::Blocked
+            | StatusCode::Failed { .. }
+            | StatusCode::Dropped
+    )
+}
+
+fn to_grpc_result(result: Result<Status, Error>) -> Result<proto::Status, tonic::Status> {
+    result.map(proto::Status::from).map_err(to_grpc_status)
+}
+
+fn to_grpc_status(error: Error) -> tonic::Status {
+    match error {
+        Error::StatusNotFound => tonic::Status::not_found(error.to_string()),
+        Error::IOError(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tonic::Status::not_found(e.to_string())
+        }
+        Error::InvalidTags => tonic::Status::invalid_argument(error.to_string()),
+        Error::PathOutsideRoot(_) => tonic::Status::permission_denied(error.to_string()),
+        _ => tonic::Status::internal(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        proto::{self, uploader_server::Uploader},
+        UploaderService,
+    };
+    use crate::{error::Error, simulation::SimulatedGateway, utils::TempDir, Arweave};
+    use futures::StreamExt;
+    use std::{path::PathBuf, sync::Arc, time::Duration};
+    use tonic::{Code, Request};
+
+    fn service(confirm_after: Duration, log_dir: PathBuf) -> UploaderService {
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                confirm_after,
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        UploaderService::new(
+            arweave,
+            log_dir,
+            PathBuf::from("tests/fixtures"),
+            "token".to_string(),
+        )
+    }
+
+    fn authorized<T>(message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer token".parse().unwrap());
+        request
+    }
+
+    fn upload_request(file_paths: Vec<String>) -> proto::UploadRequest {
+        proto::UploadRequest {
+            file_paths,
+            tags: vec![proto::Tag {
+                name: "App-Name".to_string(),
+                value: "arloader".to_string(),
+            }],
+            ..proto::UploadRequest::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_uploader_service() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let service = service(Duration::from_secs(0), temp_dir.0.clone());
+        let file_paths = vec![
+            "tests/fixtures/0.png".to_string(),
+            "tests/fixtures/1.png".to_string(),
+        ];
+
+        let statuses: Vec<proto::Status> = service
+            .upload(authorized(upload_request(file_paths.clone())))
+            .await
+            .unwrap()
+            .into_inner()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses.iter().all(|s| s.status == "Submitted"));
+
+        let statuses: Vec<proto::Status> = service
+            .watch_status(authorized(proto::WatchStatusRequest {
+                file_paths,
+                interval_secs: 1,
+                ..proto::WatchStatusRequest::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert!(statuses.iter().all(|s| s.status == "Confirmed"));
+        assert_eq!(statuses.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_uploader_service_rejects_requests() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let service = service(Duration::from_secs(0), temp_dir.0.clone());
+
+        let result = service
+            .upload(Request::new(upload_request(vec![
+                "tests/fixtures/0.png".to_string()
+            ])))
+            .await;
+        assert_eq!(result.err().unwrap().code(), Code::Unauthenticated);
+        let result = service
+            .watch_status(Request::new(proto::WatchStatusRequest::default()))
+            .await;
+        assert_eq!(result.err().unwrap().code(), Code::Unauthenticated);
+
+        let result = service
+            .upload(authorized(upload_request(vec![
+                "tests/fixtures/0.png".to_string(),
+                "tests/fixtures/../../Cargo.toml".to_string(),
+            ])))
+            .await;
+        assert_eq!(result.err().unwrap().code(), Code::PermissionDenied);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_status_times_out() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let service = service(Duration::from_secs(3600), temp_dir.0.clone());
+        let file_paths = vec!["tests/fixtures/0.png".to_string()];
+        service
+            .upload(authorized(upload_request(file_paths.clone())))
+            .await
+            .unwrap()
+            .into_inner()
+            .collect::<Vec<_>>()
+            .await;
+
+        let results: Vec<Result<proto::Status, tonic::Status>> = service
+            .watch_status(authorized(proto::WatchStatusRequest {
+                file_paths,
+                interval_secs: 1,
+                timeout_secs: 1,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .collect()
+            .await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().status, "Pending");
+        assert_eq!(
+            results[1].as_ref().unwrap_err().code(),
+            Code::DeadlineExceeded
+        );
+        Ok(())
+    }
+}
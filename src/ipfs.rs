@@ -0,0 +1,229 @@
+//! IPFS CIDv1s of files, computed as `ipfs add --cid-version 1` does.
+
+use crate::{crypto::Provider, error::Error};
+use std::io::Read;
+
+/// Name of the tag holding the CID of the data of a transaction.
+pub const IPFS_ADD_TAG: &str = "IPFS-Add";
+
+/// Size of the chunks files are split into, the default of `ipfs add`.
+pub const IPFS_CHUNK_SIZE: usize = 262144;
+
+/// Maximum number of links of a node of the tree.
+pub const IPFS_MAX_LINKS: usize = 174;
+
+const RAW_CODEC: u8 = 0x55;
+const DAG_PB_CODEC: u8 = 0x70;
+const SHA2_256_CODE: u8 = 0x12;
+
+/// Node of the tree, as linked to by its parent.
+struct Link {
+    cid: Vec<u8>,
+    file_size: u64,
+    /// Size of the node and all of its descendants.
+    tsize: u64,
+}
+
+/// Computes the CIDv1 of data that is passed to it in pieces of any size, e.g. as the chunks of
+/// a transaction are read from disk, holding at most one [`IPFS_CHUNK_SIZE`] chunk at a time.
+pub struct CidBuilder<'a> {
+    crypto: &'a Provider,
+    chunk: Vec<u8>,
+    leaves: Vec<Link>,
+}
+
+impl<'a> CidBuilder<'a> {
+    pub fn new(crypto: &'a Provider) -> Self {
+        Self {
+            crypto,
+            chunk: Vec::with_capacity(IPFS_CHUNK_SIZE),
+            leaves: Vec::new(),
+        }
+    }
+
+    /// Adds `data` to the end of the data.
+    pub fn update(&mut self, mut data: &[u8]) -> Result<(), Error> {
+        while !data.is_empty() {
+            let len = (IPFS_CHUNK_SIZE - self.chunk.len()).min(data.len());
+            self.chunk.extend_from_slice(&data[..len]);
+            data = &data[len..];
+            if self.chunk.len() == IPFS_CHUNK_SIZE {
+                self.push_leaf()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the base32 CIDv1 of the data.
+    pub fn finish(mut self) -> Result<String, Error> {
+        if !self.chunk.is_empty() || self.leaves.is_empty() {
+            self.push_leaf()?;
+        }
+        let mut links = self.leaves;
+        while links.len() > 1 {
+            links = links
+                .chunks(IPFS_MAX_LINKS)
+                .map(|children| parent_link(children, self.crypto))
+                .collect::<Result<Vec<Link>, Error>>()?;
+        }
+        Ok(encode_base32(&links[0].cid))
+    }
+
+    fn push_leaf(&mut self) -> Result<(), Error> {
+        let len = self.chunk.len() as u64;
+        self.leaves.push(Link {
+            cid: cid_bytes(RAW_CODEC, &self.crypto.hash_sha256(&self.chunk)?),
+            file_size: len,
+            tsize: len,
+        });
+        self.chunk.clear();
+        Ok(())
+    }
+}
+
+/// Returns the base32 CIDv1 of `data`.
+pub fn cid(data: &[u8], crypto: &Provider) -> Result<String, Error> {
+    let mut builder = CidBuilder::new(crypto);
+    builder.update(data)?;
+    builder.finish()
+}
+
+/// Returns the base32 CIDv1 of the contents of `reader`, read a chunk at a time.
+pub fn file_cid<R: Read>(mut reader: R, crypto: &Provider) -> Result<String, Error> {
+    let mut builder = CidBuilder::new(crypto);
+    let mut chunk = vec![0; IPFS_CHUNK_SIZE];
+    loop {
+        match read_chunk(&mut reader, &mut chunk)? {
+            0 => break,
+            len => builder.update(&chunk[..len])?,
+        }
+    }
+    builder.finish()
+}
+
+/// Fills `buf` from `reader`, returning fewer bytes only at the end of the data.
+fn read_chunk<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(len)
+}
+
+/// Encodes the dag-pb node linking to `children`, with links before data as go-ipfs does.
+fn parent_link(children: &[Link], crypto: &Provider) -> Result<Link, Error> {
+    let file_size: u64 = children.iter().map(|c| c.file_size).sum();
+
+    // UnixFS Data message: Type = File, filesize, blocksizes.
+    let mut data = vec![0x08, 0x02, 0x18];
+    push_varint(&mut data, file_size);
+    for child in children {
+        data.push(0x20);
+        push_varint(&mut data, child.file_size);
+    }
+
+    let mut node = Vec::new();
+    for child in children {
+        // PBLink message: Hash, empty Name, Tsize.
+        let mut link = vec![0x0a];
+        push_varint(&mut link, child.cid.len() as u64);
+        link.extend_from_slice(&child.cid);
+        link.extend_from_slice(&[0x12, 0x00, 0x18]);
+        push_varint(&mut link, child.tsize);
+
+        node.push(0x12);
+        push_varint(&mut node, link.len() as u64);
+        node.extend(link);
+    }
+    node.push(0x0a);
+    push_varint(&mut node, data.len() as u64);
+    node.extend(data);
+
+    Ok(Link {
+        cid: cid_bytes(DAG_PB_CODEC, &crypto.hash_sha256(&node)?),
+        file_size,
+        tsize: node.len() as u64 + children.iter().map(|c| c.tsize).sum::<u64>(),
+    })
+}
+
+fn cid_bytes(codec: u8, sha256: &[u8; 32]) -> Vec<u8> {
+    let mut cid = vec![0x01, codec, SHA2_256_CODE, 0x20];
+    cid.extend_from_slice(sha256);
+    cid
+}
+
+fn push_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+/// Lowercase unpadded base32 with the `b` multibase prefix.
+fn encode_base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut encoded = String::from("b");
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cid, file_cid, CidBuilder, IPFS_CHUNK_SIZE};
+    use crate::crypto::Provider;
+
+    #[test]
+    fn test_cid() {
+        let crypto = Provider::default();
+        assert_eq!(
+            cid(b"", &crypto).unwrap(),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        assert_eq!(
+            cid(b"hello world", &crypto).unwrap(),
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+
+        let data = vec![7; IPFS_CHUNK_SIZE];
+        assert!(cid(&data, &crypto).unwrap().starts_with("bafkrei"));
+        let data = vec![7; IPFS_CHUNK_SIZE + 1];
+        let root = cid(&data, &crypto).unwrap();
+        assert!(root.starts_with("bafybei"));
+        assert_eq!(file_cid(&data[..], &crypto).unwrap(), root);
+    }
+
+    #[test]
+    fn test_multi_chunk_cid() {
+        let crypto = Provider::default();
+        let data: Vec<u8> = (0..2 * IPFS_CHUNK_SIZE + 100)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        // Computed independently from the dag-pb and UnixFS specs: three raw leaves under a
+        // dag-pb File node, as `ipfs add --cid-version 1 --raw-leaves` builds it.
+        let expected = "bafybeicmzf22samzthl4zsngndgudpeosnuowdzpud65c7w7yj2qivo4ky";
+        assert_eq!(cid(&data, &crypto).unwrap(), expected);
+        assert_eq!(file_cid(&data[..], &crypto).unwrap(), expected);
+
+        let mut builder = CidBuilder::new(&crypto);
+        for piece in data.chunks(100_000) {
+            builder.update(piece).unwrap();
+        }
+        assert_eq!(builder.finish().unwrap(), expected);
+    }
+}
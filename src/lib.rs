@@ -69,56 +69,187 @@
 //!
 //! #### Solana
 //! The functions for allowing payment to be made in SOL can be found in the [`solana`] module.
+//!
+//! #### Ethereum
+//! With the `ethereum` feature, [`DataItem`]s can be signed with Ethereum keys by the signer in the `ethereum` module.
+//!
+//! #### WebAssembly
+//! Everything that touches the file system, the network or an async runtime is behind the default `native` feature.
+//! Building with `default-features = false` leaves the [`transaction`], [`bundle`], [`merkle`] and [`crypto`] modules,
+//! so that transactions and data items can be created and signed on `wasm32-unknown-unknown`.
 
 #![feature(derive_default_enum)]
+#[cfg(feature = "native")]
 use blake3;
-use chrono::Utc;
+#[cfg(feature = "native")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "native")]
 use futures::{
-    future::{try_join, try_join_all},
-    stream, Stream, StreamExt,
+    future::{join_all, try_join, try_join_all},
+    stream, Stream, StreamExt, TryStreamExt,
 };
+#[cfg(feature = "native")]
 use glob::glob;
+#[cfg(feature = "native")]
 use infer;
-use log::debug;
+#[cfg(feature = "native")]
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+#[cfg(feature = "native")]
 use num_bigint::BigUint;
+#[cfg(feature = "session-wallet")]
+use num_traits::ToPrimitive;
+#[cfg(feature = "native")]
 use rayon::prelude::*;
+#[cfg(feature = "native")]
+use regex::Regex;
+#[cfg(feature = "native")]
 use reqwest::{
     self,
     header::{ACCEPT, CONTENT_TYPE},
     StatusCode as ResponseStatusCode,
 };
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "native")]
+use rt::{fs, sleep, Duration};
+#[cfg(feature = "native")]
 use serde_json::{json, Value};
+#[cfg(feature = "native")]
 use solana_sdk::signer::keypair::Keypair;
+#[cfg(feature = "session-wallet")]
+use std::future::Future;
+#[cfg(feature = "native")]
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Write,
+    sync::Arc,
+};
+use std::{
     path::{Path, PathBuf},
     str::FromStr,
 };
-use tokio::{
-    fs,
-    time::{sleep, Duration},
-};
+#[cfg(feature = "native")]
+use tokio::sync::{mpsc::UnboundedSender, OwnedSemaphorePermit, Semaphore};
+#[cfg(feature = "native")]
+use tracing::{debug, field::display, instrument, warn, Span};
+#[cfg(feature = "native")]
 use url::Url;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod bundle;
+pub mod bundlr;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
+pub mod client;
+#[cfg(feature = "native")]
 pub mod commands;
+pub mod compression;
+#[cfg(feature = "native")]
+pub mod config;
 pub mod crypto;
+#[cfg(feature = "native")]
+pub mod digest;
 pub mod error;
+#[cfg(feature = "ethereum")]
+pub mod ethereum;
+#[cfg(feature = "parquet-export")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "native")]
+pub mod forecast;
+pub mod format;
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod ipfs;
 pub mod merkle;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod network;
+#[cfg(feature = "native")]
+pub mod oracle;
+#[cfg(feature = "native")]
+pub mod report;
+#[cfg(feature = "native")]
+pub mod rt;
+#[cfg(feature = "native")]
+pub mod scan;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "native")]
+pub mod simulation;
+#[cfg(feature = "native")]
 pub mod solana;
+#[cfg(feature = "native")]
+pub mod source;
+#[cfg(feature = "native")]
 pub mod status;
+#[cfg(feature = "native")]
+pub mod store;
 pub mod transaction;
+#[cfg(feature = "native")]
+pub mod transport;
+#[cfg(feature = "native")]
 pub mod utils;
 
+#[cfg(feature = "native")]
 use bundle::DataItem;
-use error::Error;
-use merkle::{generate_data_root, generate_leaves, resolve_proofs};
-use solana::{create_sol_transaction, get_sol_ar_signature, SigResponse, FLOOR};
-use status::{BundleStatus, Filterable, Status, StatusCode};
-use transaction::{Base64, Chunk, FromUtf8Strs, Tag, ToItems, Transaction};
+#[cfg(feature = "native")]
+use bundlr::{BundlrBalance, BundlrInfo, BundlrReceipt, BUNDLR_FUNDING_RETRIES};
+#[cfg(feature = "native")]
+use compression::Compression;
+#[cfg(feature = "native")]
+use digest::BatchDigest;
+#[cfg(feature = "native")]
+use error::{is_retryable_status, Error};
+#[cfg(feature = "native")]
+use forecast::{SpendForecast, SpendSample};
+#[cfg(feature = "native")]
+use graphql::{
+    content_hash_query, parse_transaction_page, pending_transactions_query,
+    wallet_transactions_with_tags_query, TransactionPage, WalletTransaction, CONTENT_HASH_TAG,
+};
+#[cfg(feature = "native")]
+use merkle::{
+    chunk_ranges, generate_data_root, generate_leaf, generate_leaves, resolve_proofs,
+    validate_downloaded_chunk, LeafIndex, Node,
+};
+#[cfg(feature = "native")]
+use network::{NetworkConditions, NetworkInfo};
+#[cfg(feature = "native")]
+use oracle::{CoinGecko, Currency, PriceOracle};
+#[cfg(feature = "native")]
+use report::StatusReport;
+#[cfg(feature = "native")]
+use scan::{FileScanner, ScanVerdict};
+#[cfg(feature = "native")]
+use simulation::SimulatedGateway;
+#[cfg(feature = "native")]
+use solana::{
+    create_sol_transaction_with_client, create_spl_token_transaction_with_client,
+    get_sol_ar_signature_with_client, SigResponse, SplToken, FLOOR,
+};
+#[cfg(feature = "native")]
+use status::{
+    BundleStatus, Filterable, ManifestVersion, Status, StatusCode, StatusFilter,
+    StatusNotification, TxStatusResponse, Verification, VerifyOutcome,
+};
+#[cfg(feature = "native")]
+use store::{FileStatusStore, StatusStore};
+use transaction::Base64;
+#[cfg(feature = "native")]
+use transaction::{
+    validate_tags, AppTags, Chunk, ContentDisposition, FromUtf8Strs, SignedTransaction, Tag,
+    TagPattern, ToItems, Transaction, TxOffset, UnsignedTransaction, MAX_DATA_ITEM_TAGS_BYTES,
+    MAX_TX_TAGS_BYTES,
+};
+#[cfg(feature = "native")]
+use transport::{ArweaveTransport, HttpTransport};
+#[cfg(feature = "native")]
+use utils::TempDir;
 
+#[cfg(feature = "native")]
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
 /// Winstons are a sub unit of the native Arweave network token, AR. There are 10<sup>12</sup> Winstons per AR.
@@ -140,6 +271,51 @@ pub const CHUNKS_RETRIES: u16 = 10;
 /// Number of seconds to wait between retying to post a failed chunk.
 pub const CHUNKS_RETRY_SLEEP: u64 = 1;
 
+/// Number of times a transaction can be not found while waiting for it to be confirmed.
+pub const CONFIRMATION_NOT_FOUND_RETRIES: u16 = 10;
+
+/// Approximate number of seconds between blocks, used to convert ages in blocks to durations.
+pub const BLOCK_TIME_SECS: u64 = 120;
+
+/// Number of seconds to wait between polls while funding and sweeping a session wallet.
+pub const SESSION_POLL_SECS: u64 = 30;
+
+/// Name of the file in a log directory that maps the paths of confirmed uploads to their ids and
+/// gateway urls.
+pub const ASSETS_FILE_NAME: &str = "assets.json";
+
+/// Name of the file in a log directory that [`Arweave::compact_statuses`] moves status files
+/// into, mapping the stems of the status files to their statuses.
+pub const STATUS_ARCHIVE_FILE_NAME: &str = "status_archive.json";
+
+/// Name of the file in a log directory that [`Arweave::append_to_manifest`] records the
+/// [`ManifestVersion`]s it posts in. Not prefixed with `manifest`, so that it isn't mistaken for
+/// a manifest file.
+pub const MANIFEST_LINEAGE_FILE_NAME: &str = "lineage.json";
+
+/// Name of the directory in a log directory that holds [`LeafIndex`]es of uploaded files.
+pub const LEAVES_DIR_NAME: &str = "leaves";
+
+/// Number of seconds between status updates of files uploaded by [`Arweave::watch_and_upload`].
+pub const WATCH_STATUS_POLL_SECS: u64 = 60;
+
+/// Number of debounce periods after which [`Arweave::watch_and_upload`] uploads a file that is
+/// still being written to.
+pub const WATCH_MAX_DEBOUNCES: u32 = 10;
+
+/// Time to live in seconds of ArNS records set with [`Arweave::set_arns_record`].
+pub const ARNS_TTL_SECONDS: u64 = 3600;
+
+/// Version of the SmartWeave protocol that contracts and interactions are tagged with.
+pub const SMARTWEAVE_VERSION: &str = "0.3.0";
+
+/// Number of milliseconds a peer has to answer `info` in to be considered healthy by
+/// [`Arweave::discover_peers`].
+pub const PEER_PROBE_TIMEOUT_MILLIS: u64 = 2000;
+
+/// Number of peers probed at a time by [`Arweave::discover_peers`].
+pub const PEER_PROBE_BUFFER: usize = 20;
+
 //=========================
 // Streams
 //=========================
@@ -147,10 +323,16 @@ pub const CHUNKS_RETRY_SLEEP: u64 = 1;
 /// Uploads a stream of bundles from [`Vec<PathsChunk>`]s.
 ///
 /// Buffers bundles if using the tx/ endpoint, else buffers chunks with bundles processed serially.
+/// If `log_dir` is set, each bundle and its files are logged as [`StatusCode::Signed`] before
+/// the bundle is posted, with [`Arweave::write_bundle_status`], and logged again once posted.
+/// With an [`Arweave::balance_guard`], the balance of the wallet is checked against the cost of
+/// the bundles left to upload before each is posted.
+#[cfg(feature = "native")]
 pub fn upload_bundles_stream<'a>(
     arweave: &'a Arweave,
     paths_chunks: Vec<PathsChunk>,
     tags: Vec<Tag<String>>,
+    log_dir: Option<PathBuf>,
     price_terms: (u64, u64),
     buffer: usize,
 ) -> impl Stream<Item = Result<BundleStatus, Error>> + 'a {
@@ -158,22 +340,46 @@ pub fn upload_bundles_stream<'a>(
     let (bundles_buffer, chunks_buffer) = if bundle_size > MAX_TX_DATA {
         (1, buffer * CHUNKS_BUFFER_FACTOR)
     } else {
-        (buffer, 1)
+        (arweave.upload_buffer(buffer), 1)
     };
+    if let Some(guard) = &arweave.balance_guard {
+        guard.add_remaining(
+            paths_chunks
+                .iter()
+                .map(|p| price_for_data_size(p.1, price_terms))
+                .sum(),
+        );
+    }
 
     stream::iter(paths_chunks)
         .map(move |p| {
-            arweave.post_bundle_transaction_from_file_paths(
-                p,
-                tags.clone(),
-                price_terms,
-                chunks_buffer,
-            )
+            let (tags, log_dir) = (tags.clone(), log_dir.clone());
+            async move {
+                let _permit = arweave.acquire_upload_permit().await;
+                let cost = price_for_data_size(p.1, price_terms);
+                let result = match arweave.check_balance_guard().await {
+                    Ok(()) => {
+                        arweave
+                            .post_logged_bundle_from_file_paths(
+                                p,
+                                tags,
+                                price_terms,
+                                chunks_buffer,
+                                log_dir.as_deref(),
+                            )
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                arweave.release_balance_guard(cost);
+                result
+            }
         })
         .buffer_unordered(bundles_buffer)
 }
 
 /// Queries network and updates locally stored [`BundleStatus`] structs.
+#[cfg(feature = "native")]
 pub fn update_bundle_statuses_stream<'a, IP>(
     arweave: &'a Arweave,
     paths_iter: IP,
@@ -187,11 +393,14 @@ where
         .buffer_unordered(buffer)
 }
 
-/// Uploads a stream of bundles from [`Vec<PathsChunk>`]s, paying with SOL.
+/// Uploads a stream of bundles from [`Vec<PathsChunk>`]s, paying with SOL, logging them to
+/// `log_dir` like [`upload_bundles_stream`].
+#[cfg(feature = "native")]
 pub fn upload_bundles_stream_with_sol<'a>(
     arweave: &'a Arweave,
     paths_chunks: Vec<PathsChunk>,
     tags: Vec<Tag<String>>,
+    log_dir: Option<PathBuf>,
     price_terms: (u64, u64),
     buffer: usize,
     solana_url: Url,
@@ -202,70 +411,311 @@ pub fn upload_bundles_stream_with_sol<'a>(
     let (bundles_buffer, chunks_buffer) = if bundle_size > MAX_TX_DATA {
         (1, buffer * CHUNKS_BUFFER_FACTOR)
     } else {
-        (buffer, 1)
+        (arweave.upload_buffer(buffer), 1)
     };
 
     stream::iter(paths_chunks)
         .map(move |p| {
-            arweave.post_bundle_transaction_from_file_paths_with_sol(
-                p,
-                tags.clone(),
-                price_terms,
-                chunks_buffer,
-                solana_url.clone(),
-                sol_ar_url.clone(),
-                from_keypair,
-            )
+            let (tags, log_dir) = (tags.clone(), log_dir.clone());
+            let (solana_url, sol_ar_url) = (solana_url.clone(), sol_ar_url.clone());
+            async move {
+                let _permit = arweave.acquire_upload_permit().await;
+                arweave
+                    .post_logged_bundle_from_file_paths_with_sol(
+                        p,
+                        tags,
+                        price_terms,
+                        chunks_buffer,
+                        solana_url,
+                        sol_ar_url,
+                        from_keypair,
+                        log_dir.as_deref(),
+                    )
+                    .await
+            }
         })
         .buffer_unordered(bundles_buffer)
 }
 
+/// Uploads files as individual [`DataItem`]s to a Bundlr node, returning a stream of [`Status`]
+/// structs that include verified receipts.
+#[cfg(feature = "native")]
+pub fn upload_files_to_bundlr_stream<'a, IP>(
+    arweave: &'a Arweave,
+    paths_iter: IP,
+    tags: Vec<Tag<String>>,
+    log_dir: Option<PathBuf>,
+    bundlr_url: Url,
+    buffer: usize,
+) -> impl Stream<Item = Result<Status, Error>> + 'a
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync + 'a,
+{
+    stream::iter(paths_iter)
+        .map(move |p| {
+            arweave.upload_file_to_bundlr(p, log_dir.clone(), tags.clone(), bundlr_url.clone())
+        })
+        .buffer_unordered(buffer)
+}
+
 /// Uploads a stream of chunks from [`Vec<Chunk>`]s.
+#[cfg(feature = "native")]
 pub fn upload_transaction_chunks_stream<'a>(
     arweave: &'a Arweave,
-    signed_transaction: Transaction,
+    signed_transaction: &'a Transaction,
     buffer: usize,
 ) -> impl Stream<Item = Result<usize, Error>> + 'a {
     stream::iter(0..signed_transaction.chunks.len())
-        .map(move |i| {
+        .map(move |i| async move {
             let chunk = signed_transaction.get_chunk(i).unwrap();
-            arweave.post_chunk_with_retries(chunk)
+            let bytes = chunk.chunk.0.len() as u64;
+            let posted = arweave.post_chunk_with_retries(chunk).await?;
+            arweave.send_progress(UploadProgress::ChunkPosted {
+                id: signed_transaction.id.clone(),
+                bytes,
+            });
+            Ok(posted)
         })
         .buffer_unordered(buffer)
 }
 
-/// Uploads files matching glob pattern, returning a stream of [`Status`] structs.
+/// Uploads files matching glob pattern, returning a stream of [`Status`] structs. If
+/// `skip_existing` is true, files with a status in `log_dir` that doesn't
+/// [`StatusCode::needs_reupload`] are skipped. With an [`Arweave::bundle_policy`], files below
+/// its threshold are gathered into bundles that are uploaded with
+/// [`Arweave::upload_files_in_bundles`] as they fill up, their statuses following once each is
+/// posted. With an [`Arweave::adaptive_concurrency`], up to its `max` files or bundles are
+/// uploaded at once in place of `buffer`, as its limit allows. With an
+/// [`Arweave::balance_guard`], the balance of the wallet is checked against the cost of the files
+/// left to upload as they are uploaded.
+#[cfg(feature = "native")]
 pub fn upload_files_stream<'a, IP>(
     arweave: &'a Arweave,
     paths_iter: IP,
     tags: Option<Vec<Tag<Base64>>>,
     log_dir: Option<PathBuf>,
+    skip_existing: bool,
     last_tx: Option<Base64>,
     price_terms: (u64, u64),
     buffer: usize,
 ) -> impl Stream<Item = Result<Status, Error>> + 'a
+where
+    IP: Iterator<Item = PathBuf> + Send + Sync + 'a,
+{
+    let skip_log_dir = log_dir.clone().filter(|_| skip_existing);
+    let jobs = stream::iter(paths_iter)
+        .filter(move |p| Box::pin(arweave.skip_if_uploaded(p.clone(), skip_log_dir.clone())))
+        .inspect(move |p| {
+            if let Some(guard) = &arweave.balance_guard {
+                guard.add_remaining(arweave.projected_reward(p, price_terms));
+            }
+        })
+        .map(Some)
+        .chain(stream::iter([None]))
+        .scan(
+            (Vec::new(), 0),
+            move |(bundled, bundled_size): &mut (Vec<PathBuf>, u64), path| {
+                let policy = arweave.bundle_policy.as_ref();
+                let jobs = match path {
+                    Some(path) => match policy.zip(
+                        path.metadata()
+                            .ok()
+                            .map(|m| m.len())
+                            .filter(|len| policy.map_or(false, |p| *len < p.threshold)),
+                    ) {
+                        Some((policy, len)) => {
+                            let mut jobs = Vec::new();
+                            if !bundled.is_empty() && *bundled_size + len > policy.bundle_size {
+                                jobs.push(UploadJob::Bundle(std::mem::take(bundled)));
+                                *bundled_size = 0;
+                            }
+                            bundled.push(path);
+                            *bundled_size += len;
+                            jobs
+                        }
+                        None => vec![UploadJob::File(path)],
+                    },
+                    None if !bundled.is_empty() => {
+                        vec![UploadJob::Bundle(std::mem::take(bundled))]
+                    }
+                    None => Vec::new(),
+                };
+                futures::future::ready(Some(stream::iter(jobs)))
+            },
+        )
+        .flatten();
+
+    jobs.map(move |job| {
+        let (log_dir, tags, last_tx) = (log_dir.clone(), tags.clone(), last_tx.clone());
+        async move {
+            // Bundles take their permits in `upload_bundles_stream`.
+            let _permit = match job {
+                UploadJob::File(_) => arweave.acquire_upload_permit().await,
+                UploadJob::Bundle(_) => None,
+            };
+            let cost = match &job {
+                UploadJob::File(path) => arweave.projected_reward(path, price_terms),
+                UploadJob::Bundle(paths) => paths
+                    .iter()
+                    .map(|p| arweave.projected_reward(p, price_terms))
+                    .sum(),
+            };
+            let results = match arweave.check_balance_guard().await {
+                Ok(()) => match job {
+                    UploadJob::File(path) => vec![
+                        arweave
+                            .upload_file_from_path(path, log_dir, tags, last_tx, price_terms, None)
+                            .await,
+                    ],
+                    UploadJob::Bundle(paths) => match arweave
+                        .upload_files_in_bundles(paths, log_dir, tags, price_terms, 1)
+                        .await
+                    {
+                        Ok(statuses) => statuses.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    },
+                },
+                Err(e) => vec![Err(e)],
+            };
+            arweave.release_balance_guard(cost);
+            results
+        }
+    })
+    .buffer_unordered(arweave.upload_buffer(buffer))
+    .flat_map(stream::iter)
+}
+
+/// Upload of [`upload_files_stream`], of a file in a transaction of its own or of files in a
+/// bundle.
+#[cfg(feature = "native")]
+enum UploadJob {
+    File(PathBuf),
+    Bundle(Vec<PathBuf>),
+}
+
+/// Uploads the objects at `urls_iter`, e.g. from [`source::UploadSource::urls`], returning a
+/// stream of [`Status`] structs.
+#[cfg(feature = "native")]
+pub fn upload_urls_stream<'a, IU>(
+    arweave: &'a Arweave,
+    urls_iter: IU,
+    tags: Option<Vec<Tag<Base64>>>,
+    log_dir: Option<PathBuf>,
+    price_terms: (u64, u64),
+    buffer: usize,
+) -> impl Stream<Item = Result<Status, Error>> + 'a
+where
+    IU: Iterator<Item = Url> + Send + Sync + 'a,
+{
+    stream::iter(urls_iter)
+        .map(move |u| {
+            arweave.upload_file_from_url(u, log_dir.clone(), tags.clone(), None, price_terms)
+        })
+        .buffer_unordered(buffer)
+}
+
+/// Uploads one file from each [`PathsGroup`] of byte-identical files, returning a stream of
+/// [`Status`] structs for every path in each group. With an [`Arweave::balance_guard`], the
+/// balance of the wallet is checked against the cost of the groups left to upload before each is
+/// uploaded.
+#[cfg(feature = "native")]
+pub fn upload_files_dedup_stream<'a>(
+    arweave: &'a Arweave,
+    paths_groups: Vec<PathsGroup>,
+    tags: Option<Vec<Tag<Base64>>>,
+    log_dir: Option<PathBuf>,
+    price_terms: (u64, u64),
+    buffer: usize,
+) -> impl Stream<Item = Result<Status, Error>> + 'a {
+    let group_cost = move |g: &PathsGroup| {
+        g.1.first()
+            .map_or(0, |p| arweave.projected_reward(p, price_terms))
+    };
+    if let Some(guard) = &arweave.balance_guard {
+        guard.add_remaining(paths_groups.iter().map(group_cost).sum());
+    }
+
+    stream::iter(paths_groups)
+        .map(move |g| {
+            let (log_dir, tags) = (log_dir.clone(), tags.clone());
+            async move {
+                let _permit = arweave.acquire_upload_permit().await;
+                let cost = group_cost(&g);
+                let result = match arweave.check_balance_guard().await {
+                    Ok(()) => {
+                        arweave
+                            .upload_paths_group(g, log_dir, tags, price_terms)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                arweave.release_balance_guard(cost);
+                result
+            }
+        })
+        .buffer_unordered(arweave.upload_buffer(buffer))
+        .flat_map(|result| {
+            let results: Vec<Result<Status, Error>> = match result {
+                Ok(statuses) => statuses.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(results)
+        })
+}
+
+/// Verifies uploaded files against the transactions recorded in their statuses, returning a
+/// stream of [`Verification`] structs. With `download`, the data of each transaction is
+/// downloaded and compared with the file, with [`Arweave::verify_download`].
+#[cfg(feature = "native")]
+pub fn verify_uploads_stream<'a, IP>(
+    arweave: &'a Arweave,
+    paths_iter: IP,
+    log_dir: PathBuf,
+    download: bool,
+    buffer: usize,
+) -> impl Stream<Item = Result<Verification, Error>> + 'a
 where
     IP: Iterator<Item = PathBuf> + Send + Sync + 'a,
 {
     stream::iter(paths_iter)
         .map(move |p| {
-            arweave.upload_file_from_path(
-                p,
-                log_dir.clone(),
-                tags.clone(),
-                last_tx.clone(),
-                price_terms,
-            )
+            let log_dir = log_dir.clone();
+            async move {
+                if download {
+                    arweave.verify_download(p, log_dir).await
+                } else {
+                    arweave.verify_upload(p, log_dir).await
+                }
+            }
         })
         .buffer_unordered(buffer)
 }
 
+/// Downloads the data of each transaction id to the paired path, returning a stream of
+/// [`Verification`] structs.
+#[cfg(feature = "native")]
+pub fn download_stream<'a, ID>(
+    arweave: &'a Arweave,
+    downloads_iter: ID,
+    buffer: usize,
+) -> impl Stream<Item = Result<Verification, Error>> + 'a
+where
+    ID: Iterator<Item = (Base64, PathBuf)> + Send + Sync + 'a,
+{
+    stream::iter(downloads_iter)
+        .map(move |(id, file_path)| arweave.download_transaction(id, file_path))
+        .buffer_unordered(buffer)
+}
+
 /// Uploads files matching glob pattern, returning a stream of [`Status`] structs, paying with SOL.
+/// Skips files already uploaded to `log_dir` in the same way as [`upload_files_stream`].
+#[cfg(feature = "native")]
 pub fn upload_files_with_sol_stream<'a, IP>(
     arweave: &'a Arweave,
     paths_iter: IP,
     tags: Option<Vec<Tag<Base64>>>,
     log_dir: Option<PathBuf>,
+    skip_existing: bool,
     last_tx: Option<Base64>,
     price_terms: (u64, u64),
     solana_url: Url,
@@ -276,23 +726,50 @@ pub fn upload_files_with_sol_stream<'a, IP>(
 where
     IP: Iterator<Item = PathBuf> + Send + Sync + 'a,
 {
+    let skip_log_dir = log_dir.clone().filter(|_| skip_existing);
     stream::iter(paths_iter)
+        .filter(move |p| Box::pin(arweave.skip_if_uploaded(p.clone(), skip_log_dir.clone())))
         .map(move |p| {
-            arweave.upload_file_from_path_with_sol(
-                p,
-                log_dir.clone(),
-                tags.clone(),
-                last_tx.clone(),
-                price_terms,
-                solana_url.clone(),
-                sol_ar_url.clone(),
-                from_keypair,
-            )
+            let (log_dir, tags, last_tx) = (log_dir.clone(), tags.clone(), last_tx.clone());
+            let (solana_url, sol_ar_url) = (solana_url.clone(), sol_ar_url.clone());
+            async move {
+                let _permit = arweave.acquire_upload_permit().await;
+                arweave
+                    .upload_file_from_path_with_sol(
+                        p,
+                        log_dir,
+                        tags,
+                        last_tx,
+                        price_terms,
+                        solana_url,
+                        sol_ar_url,
+                        from_keypair,
+                    )
+                    .await
+            }
         })
-        .buffer_unordered(buffer)
+        .buffer_unordered(arweave.upload_buffer(buffer))
+}
+
+/// Queries network and updates every [`Status`] in `log_dir`, without needing the paths of the
+/// uploaded files. Statuses without a file path are updated by id.
+#[cfg(feature = "native")]
+pub fn update_all_statuses_stream<'a>(
+    arweave: &'a Arweave,
+    log_dir: PathBuf,
+    buffer: usize,
+) -> Result<impl Stream<Item = Result<Status, Error>> + 'a, Error> {
+    Ok(arweave
+        .list_all_statuses(log_dir.clone())?
+        .map(move |result| {
+            let log_dir = log_dir.clone();
+            async move { arweave.refresh_status(result?, log_dir).await }
+        })
+        .buffer_unordered(buffer))
 }
 
 /// Queries network and updates locally stored [`Status`] structs.
+#[cfg(feature = "native")]
 pub fn update_statuses_stream<'a, IP>(
     arweave: &'a Arweave,
     paths_iter: IP,
@@ -302,7 +779,7 @@ pub fn update_statuses_stream<'a, IP>(
 where
     IP: Iterator<Item = PathBuf> + Send + Sync + 'a,
 {
-    stream::iter(paths_iter)
+    paced(paths_iter, arweave.status_updates.delay)
         .map(move |p| arweave.update_status(p, log_dir.clone()))
         .buffer_unordered(buffer)
 }
@@ -311,44 +788,446 @@ where
 // Helpers
 //=========================
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct OraclePrice {
-    pub arweave: OraclePricePair,
-    pub solana: OraclePricePair,
+/// Yields the items of `iter`, waiting `delay` before each but the first.
+#[cfg(feature = "native")]
+fn paced<I>(iter: I, delay: Duration) -> impl Stream<Item = I::Item>
+where
+    I: Iterator,
+{
+    stream::iter(iter)
+        .enumerate()
+        .then(move |(i, item)| async move {
+            if i > 0 && !delay.is_zero() {
+                sleep(delay).await;
+            }
+            item
+        })
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct OraclePricePair {
-    pub usd: f32,
+#[cfg(feature = "native")]
+pub(crate) async fn fetch_tx_anchor(client: &reqwest::Client, url: Url) -> Result<Base64, Error> {
+    let resp = client.get(url).send().await?;
+    debug!("last_tx: {}", resp.status());
+    let last_tx_str = resp.text().await?;
+    Ok(Base64::from_str(&last_tx_str)?)
 }
 
 /// Tuple struct includes two elements: chunk of paths and aggregatge data size of paths.
+#[cfg(feature = "native")]
 #[derive(Clone, Debug)]
 pub struct PathsChunk(Vec<PathBuf>, u64);
 
+/// Tuple struct includes two elements: BLAKE3 hash of file contents and paths of files with
+/// those contents.
+#[cfg(feature = "native")]
+#[derive(Clone, Debug)]
+pub struct PathsGroup(pub String, pub Vec<PathBuf>);
+
+/// Files smaller than `threshold` bytes are packed into bundles of up to `bundle_size` bytes by
+/// [`upload_files_stream`], while larger files are uploaded as transactions of their own. Set it
+/// on [`Arweave::bundle_policy`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundlePolicy {
+    pub threshold: u64,
+    pub bundle_size: u64,
+}
+
+impl Default for BundlePolicy {
+    /// Bundles files smaller than one [`BLOCK_SIZE`], which are priced as a full block when
+    /// uploaded on their own.
+    fn default() -> Self {
+        Self {
+            threshold: BLOCK_SIZE,
+            bundle_size: MAX_TX_DATA,
+        }
+    }
+}
+
+/// Limits the file data that uploads hold in memory to `max_chunks` chunks of up to
+/// [`merkle::MAX_CHUNK_SIZE`] bytes, shared by every upload of an [`Arweave`]. With a budget,
+/// [`Arweave::upload_file_from_path`] hashes files and posts their chunks one chunk at a time,
+/// reading each from disk when it is needed, instead of reading whole files into memory. Set it
+/// on [`Arweave::chunk_budget`].
+///
+/// Bundles aren't covered: their data items are signed over the whole file data and the bundle
+/// is built in memory, so files uploaded in bundles, including those below the
+/// [`BundlePolicy`] threshold, are read whole and don't count against the budget.
+#[cfg(feature = "native")]
+#[derive(Clone, Debug)]
+pub struct ChunkBudget {
+    pub max_chunks: usize,
+    permits: Arc<Semaphore>,
+}
+
+#[cfg(feature = "native")]
+impl ChunkBudget {
+    pub fn new(max_chunks: usize) -> Self {
+        Self {
+            max_chunks,
+            permits: Arc::new(Semaphore::new(max_chunks)),
+        }
+    }
+
+    /// Waits until another chunk can be held in memory. It counts against the budget until the
+    /// permit is dropped.
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("chunk budgets are never closed")
+    }
+
+    /// Number of chunks that can be read without waiting.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+}
+
+/// Progress of the posts of upload transactions, sent to [`Arweave::progress`] if set. Posts of
+/// transactions with more data than [`MAX_TX_DATA`], or of chunks read from disk with a
+/// [`ChunkBudget`], report each chunk as it is posted. Files left out of an upload are reported
+/// as skipped, so that every file is accounted for.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadProgress {
+    /// Posting of transaction `id`, with `data_size` bytes of data for `files` files, started.
+    Started {
+        id: Base64,
+        files: u64,
+        data_size: u64,
+    },
+    /// A chunk of `bytes` bytes of the data of transaction `id` was posted.
+    ChunkPosted { id: Base64, bytes: u64 },
+    /// Posting of transaction `id` finished, with all of its data if `posted`.
+    Finished { id: Base64, posted: bool },
+    /// `files` files were left out without being posted, having already been uploaded or been
+    /// blocked by the [`FileScanner`].
+    Skipped { files: u64 },
+}
+
+/// Number of statuses [`Arweave::update_statuses`] requests at a time by default.
+pub const STATUS_UPDATE_BUFFER: usize = 10;
+
+/// Limits status requests of [`Arweave::update_statuses`] and [`update_statuses_stream`] to
+/// spare the gateway: at most `buffer` are in flight at a time, at least one, and each starts at
+/// least `delay` after the one before. Set it on [`Arweave::status_updates`].
+#[cfg(feature = "native")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusUpdatePolicy {
+    pub buffer: usize,
+    pub delay: Duration,
+}
+
+#[cfg(feature = "native")]
+impl Default for StatusUpdatePolicy {
+    fn default() -> Self {
+        Self {
+            buffer: STATUS_UPDATE_BUFFER,
+            delay: Duration::from_secs(0),
+        }
+    }
+}
+
+/// Path served at the root url of a manifest and the file served for paths not in it, for
+/// manifests of static sites. `fallback` is either a path in the manifest or a transaction id.
+/// Set it on [`Arweave::manifest_index`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManifestIndex {
+    pub index: Option<String>,
+    pub fallback: Option<String>,
+}
+
+/// Returns `manifest` as an `arweave/paths` manifest, converting the manifest files written by
+/// [`Arweave::write_manifest`] back to one. Entries without a content type get
+/// `application/octet-stream`.
+#[cfg(feature = "native")]
+fn normalize_manifest(manifest: Value) -> Result<Value, Error> {
+    if manifest.get("paths").map_or(false, Value::is_object) {
+        let mut manifest = manifest;
+        for entry in manifest["paths"].as_object_mut().unwrap().values_mut() {
+            if entry.get("content_type").is_none() {
+                entry["content_type"] = json!(mime_guess::mime::OCTET_STREAM.to_string());
+            }
+        }
+        return Ok(manifest);
+    }
+
+    let paths = manifest
+        .as_object()
+        .ok_or(Error::ManifestNotFound)?
+        .iter()
+        .map(|(path, entry)| {
+            let id = entry["id"]
+                .as_str()
+                .ok_or_else(|| Error::InvalidManifestPath(path.clone()))?;
+            let content_type = entry["files"][0]["type"]
+                .as_str()
+                .unwrap_or(mime_guess::mime::OCTET_STREAM.as_ref());
+            Ok((
+                path.clone(),
+                json!({"id": id, "content_type": content_type}),
+            ))
+        })
+        .collect::<Result<serde_json::Map<String, Value>, Error>>()?;
+
+    Ok(json!({
+        "manifest": "arweave/paths",
+        "version": "0.1.0",
+        "paths": Value::Object(paths)
+    }))
+}
+
 /// Used in updating [`BundleStatus`]s to determine whether a file stem includes a valid transaction id.
 pub fn file_stem_is_valid_txid(file_path: &PathBuf) -> bool {
-    match Base64::from_str(file_path.file_stem().unwrap().to_str().unwrap()) {
-        Ok(txid) => match txid.0.len() {
+    match file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(Base64::from_str)
+    {
+        Some(Ok(txid)) => match txid.0.len() {
             32 => true,
             _ => false,
         },
-        Err(_) => false,
+        _ => false,
+    }
+}
+
+/// Returns the BLAKE3 hash that the files of `file_path` in a log directory, such as its
+/// [`Status`], are named by. Paths are hashed by their bytes on Unix, so that paths that aren't
+/// valid UTF-8 can be logged, and with `/` separators elsewhere, so that logs written on Windows
+/// can be read on other platforms.
+#[cfg(feature = "native")]
+pub fn hash_file_path(file_path: &Path) -> String {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        std::borrow::Cow::Borrowed(file_path.as_os_str().as_bytes())
+    };
+    #[cfg(not(unix))]
+    let bytes: std::borrow::Cow<[u8]> = file_path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .into_bytes()
+        .into();
+    blake3::hash(&bytes).to_string()
+}
+
+/// Returns the hash that versions before [`hash_file_path`] named the files of `file_path` by,
+/// the hash of the path as a string, if it differs from that of [`hash_file_path`], i.e. for
+/// paths with `\` separators on Windows, so that their logs can still be read.
+#[cfg(feature = "native")]
+fn legacy_hash_file_path(file_path: &Path) -> Option<String> {
+    let hash = blake3::hash(file_path.to_str()?.as_bytes()).to_string();
+    (hash != hash_file_path(file_path)).then_some(hash)
+}
+
+/// Returns the canonical path of `file_path` if it is inside `root_dir`, or
+/// [`Error::PathOutsideRoot`] if it isn't, so that services only upload files under the
+/// directory they were started with. Relative paths are resolved from the working directory.
+#[cfg(feature = "native")]
+pub fn path_under_root(root_dir: &Path, file_path: &Path) -> Result<PathBuf, Error> {
+    let root_dir = root_dir.canonicalize()?;
+    let file_path = file_path.canonicalize()?;
+    if file_path.starts_with(&root_dir) {
+        Ok(file_path)
+    } else {
+        Err(Error::PathOutsideRoot(file_path))
+    }
+}
+
+/// Whether `authorization`, the value of an `Authorization` header, is `Bearer <token>`,
+/// compared in constant time.
+pub fn is_bearer_token(authorization: Option<&str>, token: &str) -> bool {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map_or(false, |bearer| {
+            ring::constant_time::verify_slices_are_equal(bearer.as_bytes(), token.as_bytes())
+                .is_ok()
+        })
+}
+
+/// Used to tell [`Status`] files, named by the BLAKE3 hash of their file path or `txid_<id>` when
+/// they have none, from bundle statuses, manifests and other files in a log directory.
+pub fn file_stem_is_status(file_path: &Path) -> bool {
+    match file_path.file_stem().and_then(|s| s.to_str()) {
+        Some(stem) => {
+            (stem.len() == 64 && stem.chars().all(|c| c.is_ascii_hexdigit()))
+                || stem.starts_with("txid_")
+        }
+        None => false,
+    }
+}
+
+/// Returns the BLAKE3 hash of the contents of the file at `file_path`, read a block at a time.
+#[cfg(feature = "native")]
+fn hash_file_contents(file_path: &Path) -> Result<String, std::io::Error> {
+    let mut hasher = blake3::Hasher::new();
+    std::io::copy(&mut std::fs::File::open(file_path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_string())
+}
+
+/// Gets the content type of `data` from its [magic numbers](https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types),
+/// which are at the start of the data, so the first chunk is enough.
+#[cfg(feature = "native")]
+fn infer_content_type(data: &[u8]) -> &'static str {
+    infer::get(data)
+        .map(|kind| kind.mime_type())
+        .unwrap_or("application/octet-stream")
+}
+
+/// Replaces any `Content-Type` tag in `tags` with one for `content_type`, for callers that know
+/// the content type better than the extension or magic numbers of a file.
+#[cfg(feature = "native")]
+fn override_content_type(tags: &mut Vec<Tag<Base64>>, content_type: &str) -> Result<(), Error> {
+    tags.retain(|tag| tag.name.0 != b"Content-Type");
+    tags.push(Tag::from_utf8_strs("Content-Type", content_type)?);
+    Ok(())
+}
+
+/// Returns the value of the `Content-Encoding` tag in `tags`, if there is one.
+#[cfg(feature = "native")]
+fn content_encoding(tags: &[Tag<String>]) -> Option<&str> {
+    tags.iter()
+        .find(|tag| tag.name == "Content-Encoding")
+        .map(|tag| tag.value.as_str())
+}
+
+/// Reads the file at `file_path` and compresses it as the `Content-Encoding` tag in `tags` says,
+/// to get the data that was uploaded from it.
+#[cfg(feature = "native")]
+async fn read_uploaded_data(file_path: &Path, tags: &[Tag<String>]) -> Result<Vec<u8>, Error> {
+    let data = fs::read(file_path).await?;
+    match content_encoding(tags) {
+        Some(content_encoding) => {
+            Compression::from_content_encoding(content_encoding)?.compress(&data)
+        }
+        None => Ok(data),
     }
 }
 
+/// Multiplies `winstons` by `reward_mult` to the nearest millionth of the multiplier in integer
+/// math, so that prices beyond the precision of a float aren't rounded.
+pub fn apply_reward_mult(winstons: u128, reward_mult: f32) -> u128 {
+    let millionths = (reward_mult as f64 * 1_000_000.0).round() as u128;
+    winstons * millionths / 1_000_000
+}
+
+/// Computes the price in winstons of `data_size` bytes of data from the base and incremental
+/// prices of a block, as returned by [`Arweave::get_price_terms`].
+pub fn price_for_data_size(data_size: u64, price_terms: (u64, u64)) -> u128 {
+    let blocks_len = data_size / BLOCK_SIZE + (data_size % BLOCK_SIZE != 0) as u64;
+    price_terms.0 as u128 + price_terms.1 as u128 * blocks_len.saturating_sub(1) as u128
+}
+
+/// Gets deep hash, signs with `crypto` and sets signature and id.
+#[cfg(feature = "native")]
+fn sign_transaction_with(
+    crypto: &crypto::Provider,
+    mut transaction: Transaction,
+) -> Result<Transaction, Error> {
+    let deep_hash = crypto.deep_hash(transaction.to_deep_hash_item()?)?;
+    let signature = crypto.sign(&deep_hash)?;
+    let id = crypto.hash_sha256(&signature)?;
+    transaction.signature = Base64(signature);
+    transaction.id = Base64(id.to_vec());
+    Ok(transaction)
+}
+
 //=========================
 // Arweave
 //=========================
 
 /// Struct with methods for interacting with the Arweave network.
+#[cfg(feature = "native")]
 pub struct Arweave {
     pub name: String,
     pub units: String,
     pub base_url: Url,
     pub crypto: crypto::Provider,
+    /// Keypairs that uploaded files and bundles are signed with in turn instead of
+    /// [`Arweave::crypto`], if set.
+    pub key_ring: Option<crypto::KeyRing>,
+    /// Client every request to the gateway, peers and Solana is sent with, so that connections
+    /// are pooled and reused across requests instead of opened for each.
+    pub client: reqwest::Client,
+    /// Pattern used to add tags derived from the directories of uploaded files.
+    pub tag_pattern: Option<TagPattern>,
+    /// Disposition uploaded files are tagged with, along with their file names, if set.
+    pub content_disposition: Option<ContentDisposition>,
+    /// Cache for responses to transaction requests.
+    pub cache: Option<cache::ResponseCache>,
+    /// Check run on every file before it is uploaded.
+    pub scanner: Option<Arc<dyn FileScanner>>,
+    /// Compression applied to file data before upload.
+    pub compression: Option<Compression>,
+    /// Gateway that posts and status requests go to instead of the network, if set.
+    pub simulation: Option<Arc<SimulatedGateway>>,
+    /// Price, anchor, post and status requests are sent with, [`HttpTransport`] by default.
+    pub transport: Arc<dyn ArweaveTransport>,
+    /// Cache that lets transactions share one fetched `tx_anchor`.
+    pub anchor_cache: Option<cache::AnchorCache>,
+    /// Cache for responses to network and fiat price requests, kept apart from [`Arweave::cache`]
+    /// so that prices can expire sooner than transactions.
+    pub price_cache: Option<cache::ResponseCache>,
+    /// Per host limits on concurrent posts of transactions and chunks, if set.
+    pub host_limiter: Option<client::HostLimiter>,
+    /// Limit on concurrent uploads of files and bundles, by [`upload_files_stream`],
+    /// [`upload_bundles_stream`] and their variants, that adapts to the error rate of posts, if
+    /// set, in place of their fixed `buffer`.
+    pub adaptive_concurrency: Option<client::AdaptiveConcurrency>,
+    /// Pauses posts while the gateway is under maintenance, if set.
+    pub maintenance: Option<client::MaintenanceGate>,
+    /// Periodic check of the balance of the wallet during [`upload_files_stream`],
+    /// [`upload_bundles_stream`] and [`upload_files_dedup_stream`], if set.
+    pub balance_guard: Option<client::BalanceGuard>,
+    /// Channel [`UploadProgress`] of uploads is sent to, if set.
+    pub progress: Option<UnboundedSender<UploadProgress>>,
+    /// Directory signed transactions are written to before they are posted, if set, so that
+    /// failed posts can be retried with [`Arweave::post_signed_transaction_file`].
+    pub signed_tx_dir: Option<PathBuf>,
+    /// Additional nodes that posted transactions and their chunks are pushed to, for faster
+    /// propagation. Peers that accept them are recorded in the status.
+    pub seed_peers: Vec<Url>,
+    /// Size threshold below which [`upload_files_stream`] bundles files, if set.
+    pub bundle_policy: Option<BundlePolicy>,
+    /// Number of chunks of file data that uploads may hold in memory at once, if set. Files
+    /// uploaded in bundles are read whole regardless.
+    pub chunk_budget: Option<ChunkBudget>,
+    /// Index and fallback of the manifests posted by [`Arweave::upload_dir`] and
+    /// [`Arweave::post_manifest_from_bundle_log_dir`], if set.
+    pub manifest_index: Option<ManifestIndex>,
+    /// Concurrency and delay of the status requests of [`Arweave::update_statuses`].
+    pub status_updates: StatusUpdatePolicy,
+    /// Where statuses are written and read, [`FileStatusStore`] by default. Bundle statuses are
+    /// files in the log directory with any store.
+    pub status_store: Arc<dyn StatusStore>,
+    /// Endpoint that [`StatusNotification`]s and [`BatchDigest`]s are posted to, if set.
+    pub webhook_url: Option<Url>,
+    /// SMTP relay that [`BatchDigest`]s are emailed with, if set.
+    #[cfg(feature = "email")]
+    pub smtp_digest: Option<digest::SmtpDigest>,
+    /// Source of AR and SOL prices in fiat currencies.
+    pub oracle: Arc<dyn PriceOracle>,
+    /// Fiat currency prices are shown in.
+    pub currency: Currency,
+    /// SPL token that uploads funded with SOL pay in instead of SOL, if set, converted at the
+    /// price of SOL in [`Arweave::currency`], e.g. USDC with [`Currency::Usd`].
+    pub sol_payment_token: Option<SplToken>,
+    /// Whether uploads, in transactions of their own or in bundles, are tagged with the hash of
+    /// their contents and files already uploaded with the same hash are skipped, so that re-runs
+    /// don't pay for them again even without a `log_dir`. Uploads are found in the statuses of
+    /// `log_dir`, if set, and through GraphQL, see [`Arweave::find_uploaded`].
+    pub chain_dedup: bool,
+    /// Application that every transaction and data item created is tagged with, if set.
+    pub app_tags: Option<AppTags>,
+    /// Whether uploaded files are tagged with their IPFS CID as their [`ipfs::IPFS_ADD_TAG`], so
+    /// that IPFS-bridging gateways can find them by it. The CID is of the data uploaded, i.e. of
+    /// the compressed data if [`Arweave::compression`] is set.
+    pub ipfs_cid: bool,
 }
 
+#[cfg(feature = "native")]
 impl Default for Arweave {
     fn default() -> Self {
         Self {
@@ -356,10 +1235,45 @@ impl Default for Arweave {
             units: String::from("winstons"),
             base_url: Url::from_str("https://arweave.net/").unwrap(),
             crypto: crypto::Provider::default(),
+            key_ring: None,
+            client: reqwest::Client::new(),
+            tag_pattern: None,
+            content_disposition: None,
+            cache: None,
+            scanner: None,
+            compression: None,
+            simulation: None,
+            transport: Arc::new(HttpTransport),
+            anchor_cache: Some(cache::AnchorCache::new(Duration::from_secs(
+                cache::ANCHOR_TTL_SECS,
+            ))),
+            price_cache: None,
+            host_limiter: None,
+            adaptive_concurrency: None,
+            maintenance: None,
+            balance_guard: None,
+            progress: None,
+            signed_tx_dir: None,
+            seed_peers: Vec::new(),
+            bundle_policy: None,
+            chunk_budget: None,
+            manifest_index: None,
+            status_updates: StatusUpdatePolicy::default(),
+            status_store: Arc::new(FileStatusStore::default()),
+            webhook_url: None,
+            #[cfg(feature = "email")]
+            smtp_digest: None,
+            oracle: Arc::new(CoinGecko),
+            currency: Currency::default(),
+            sol_payment_token: None,
+            chain_dedup: false,
+            app_tags: None,
+            ipfs_cid: false,
         }
     }
 }
 
+#[cfg(feature = "native")]
 impl Arweave {
     pub async fn from_keypair_path(keypair_path: PathBuf, base_url: Url) -> Result<Arweave, Error> {
         let crypto = crypto::Provider::from_keypair_path(keypair_path).await?;
@@ -383,58 +1297,382 @@ impl Arweave {
         Ok(arweave)
     }
 
+    /// Creates an [`Arweave`] from the contents of a keypair file, see
+    /// [`crypto::Provider::from_jwk_str`].
+    pub fn from_jwk_str(jwk: &str, base_url: Url) -> Result<Arweave, Error> {
+        let crypto = crypto::Provider::from_jwk_str(jwk)?;
+        let arweave = Arweave {
+            base_url,
+            crypto,
+            ..Default::default()
+        };
+
+        Ok(arweave)
+    }
+
+    /// Creates an [`Arweave`] from a keypair in the environment variable `var`, see
+    /// [`crypto::Provider::from_env`].
+    pub fn from_env(var: &str, base_url: Url) -> Result<Arweave, Error> {
+        let crypto = crypto::Provider::from_env(var)?;
+        let arweave = Arweave {
+            base_url,
+            crypto,
+            ..Default::default()
+        };
+
+        Ok(arweave)
+    }
+
+    /// Replaces the [`reqwest::Client`] used for network requests, e.g. with one built from a
+    /// [`client::ClientConfig`] to set timeouts, a proxy or custom root certificates.
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
     //-------------------------
     // Get Request
     //-------------------------
 
+    /// Sends a GET request, returning the response status code and body. Bodies of successful
+    /// responses are served from and written to [`Arweave::cache`] if set. Errors reading or
+    /// writing the cache are ignored so that they never fail the request.
+    pub async fn get_with_cache(
+        &self,
+        url: Url,
+    ) -> Result<(ResponseStatusCode, String), reqwest::Error> {
+        self.get_with(self.cache.as_ref(), url).await
+    }
+
+    /// Sends a GET request for a price like [`Arweave::get_with_cache`], going through
+    /// [`Arweave::price_cache`] instead.
+    pub async fn get_price_with_cache(
+        &self,
+        url: Url,
+    ) -> Result<(ResponseStatusCode, String), reqwest::Error> {
+        self.get_with(self.price_cache.as_ref(), url).await
+    }
+
+    async fn get_with(
+        &self,
+        cache: Option<&cache::ResponseCache>,
+        url: Url,
+    ) -> Result<(ResponseStatusCode, String), reqwest::Error> {
+        if let Some(cache) = cache {
+            if let Some(body) = cache.get(&url).await {
+                debug!("cache hit {}", url);
+                return Ok((ResponseStatusCode::OK, body));
+            }
+        }
+
+        let resp = self.client.get(url.clone()).send().await?;
+        let status = resp.status();
+        let body = resp.text().await?;
+        if let (Some(cache), ResponseStatusCode::OK) = (cache, status) {
+            let _ = cache.insert(&url, &body).await;
+        }
+        Ok((status, body))
+    }
+
     /// Get pending network transaction count.
     pub async fn get_pending_count(&self) -> Result<usize, Error> {
         let url = self.base_url.join("tx/pending")?;
-        let tx_ids: Vec<String> = reqwest::get(url).await?.json().await?;
+        let tx_ids: Vec<String> = self.client.get(url).send().await?.json().await?;
         Ok(tx_ids.len())
     }
 
-    /// Returns price of uploading data to the network in winstons and USD per AR and USD per SOL
-    /// as a BigUint with two decimals.
-    pub async fn get_price(&self, bytes: &u64) -> Result<(BigUint, BigUint, BigUint), Error> {
-        let url = self.base_url.join("price/")?.join(&bytes.to_string())?;
-        let winstons_per_bytes = reqwest::get(url)
-            .await
-            .map_err(|e| Error::ArweaveGetPriceError(e))?
-            .json::<u64>()
-            .await?;
-        let winstons_per_bytes = BigUint::from(winstons_per_bytes);
+    /// Gets ids of pending network transactions.
+    pub async fn get_pending_ids(&self) -> Result<Vec<Base64>, Error> {
+        let url = self.base_url.join("tx/pending")?;
+        let tx_ids: Vec<Base64> = self.client.get(url).send().await?.json().await?;
+        Ok(tx_ids)
+    }
 
-        let oracle_url =
-            "https://api.coingecko.com/api/v3/simple/price?ids=arweave,solana&vs_currencies=usd";
-        let prices = reqwest::get(oracle_url)
-            .await
-            .map_err(|e| Error::OracleGetPriceError(e))?
-            .json::<OraclePrice>()
+    /// Gets the height, current block and sync state of [`Arweave::base_url`].
+    pub async fn get_network_info(&self) -> Result<NetworkInfo, Error> {
+        let url = self.base_url.join("info")?;
+        let info = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
             .await?;
-
-        let usd_per_ar: BigUint = BigUint::from((prices.arweave.usd * 100.0).floor() as u32);
-        let usd_per_sol: BigUint = BigUint::from((prices.solana.usd * 100.0).floor() as u32);
-
-        Ok((winstons_per_bytes, usd_per_ar, usd_per_sol))
+        Ok(info)
     }
 
-    /// Gets base and incremental prices for a 256 KB block of data.
+    /// Gets the network info and pending transaction count of [`Arweave::base_url`] together,
+    /// logging a warning if the node is syncing or the mempool is saturated.
+    pub async fn get_network_conditions(&self) -> Result<NetworkConditions, Error> {
+        let (info, pending_count) =
+            try_join(self.get_network_info(), self.get_pending_count()).await?;
+        let conditions = NetworkConditions {
+            info,
+            pending_count,
+        };
+        for warning in conditions.warnings() {
+            warn!("{}", warning);
+        }
+        Ok(conditions)
+    }
+
+    /// Returns price of uploading data to the network in winstons and the price of AR and SOL in
+    /// [`Arweave::currency`], as BigUints with two decimals. The price in winstons is computed
+    /// from the price terms of [`Arweave::get_network_price_terms`], which are reused from
+    /// [`Arweave::price_cache`] if set, instead of requested for each size.
+    pub async fn get_price(&self, bytes: &u64) -> Result<(BigUint, BigUint, BigUint), Error> {
+        let price_terms = self.get_network_price_terms().await?;
+        let winstons_per_bytes = BigUint::from(price_for_data_size(*bytes, price_terms));
+        let (fiat_per_ar, fiat_per_sol) = self.get_fiat_prices().await?;
+
+        Ok((winstons_per_bytes, fiat_per_ar, fiat_per_sol))
+    }
+
+    /// Gets the prices of AR and SOL in [`Arweave::currency`] from [`Arweave::oracle`], as
+    /// BigUints with two decimals.
+    pub async fn get_fiat_prices(&self) -> Result<(BigUint, BigUint), Error> {
+        let prices = self.oracle.fiat_prices(self, self.currency).await?;
+        Ok((
+            BigUint::from((prices.ar * 100.0).floor() as u64),
+            BigUint::from((prices.sol * 100.0).floor() as u64),
+        ))
+    }
+
+    /// Gets base and incremental prices for a 256 KB block of data.
     pub async fn get_price_terms(&self, reward_mult: f32) -> Result<(u64, u64), Error> {
-        let (prices1, prices2) = try_join(
-            self.get_price(&(256 * 1024)),
-            self.get_price(&(256 * 1024 * 2)),
+        let (base, incremental) = self.get_network_price_terms().await?;
+        let price1 = apply_reward_mult(base as u128, reward_mult) as u64;
+        let price2 = apply_reward_mult(base as u128 + incremental as u128, reward_mult) as u64;
+        Ok((price1, price2.saturating_sub(price1)))
+    }
+
+    /// Gets base and incremental prices for a 256 KB block of data as quoted by the gateway,
+    /// without a reward multiplier.
+    pub async fn get_network_price_terms(&self) -> Result<(u64, u64), Error> {
+        let (price1, price2) = try_join(
+            self.gateway().get_price(self, 256 * 1024),
+            self.gateway().get_price(self, 256 * 1024 * 2),
         )
         .await?;
-        let base = (prices1.0.to_u64_digits()[0] as f32 * reward_mult) as u64;
-        let incremental = (prices2.0.to_u64_digits()[0] as f32 * reward_mult) as u64 - &base;
-        Ok((base, incremental))
+        Ok((price1, price2.saturating_sub(price1)))
+    }
+
+    /// Gets the reward in winstons for transferring AR to `target`, including the fee for
+    /// creating a new wallet if `target` doesn't exist yet.
+    pub async fn get_transfer_price(&self, target: &Base64) -> Result<u128, Error> {
+        let url = self.base_url.join(&format!("price/0/{}", target))?;
+        let winstons = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| Error::ArweaveGetPriceError(e))?
+            .json::<u128>()
+            .await?;
+        Ok(winstons)
+    }
+
+    /// Gets an anchor to use as `last_tx` for a new transaction from the gateway.
+    pub async fn get_tx_anchor(&self) -> Result<Base64, Error> {
+        self.gateway().get_tx_anchor(self).await
+    }
+
+    /// Returns [`Arweave::simulation`] if set, or else [`Arweave::transport`].
+    fn gateway(&self) -> &dyn ArweaveTransport {
+        match &self.simulation {
+            Some(simulation) => simulation.as_ref(),
+            None => self.transport.as_ref(),
+        }
+    }
+
+    /// Keeps a fresh anchor in [`Arweave::anchor_cache`] by fetching one every `interval_blocks`
+    /// blocks in the background, until the returned handle is dropped, so that anchors don't go
+    /// stale on long runs. Intervals of up to two blocks keep uploads from fetching anchors
+    /// themselves with the default [`cache::ANCHOR_TTL_SECS`]. Returns `None` without an anchor
+    /// cache or under simulation, and [`Error::InvalidAnchorRefreshInterval`] if `interval_blocks`
+    /// is 0.
+    pub fn spawn_anchor_refresher(
+        &self,
+        interval_blocks: u64,
+    ) -> Result<Option<cache::AnchorRefresher>, Error> {
+        if interval_blocks == 0 {
+            return Err(Error::InvalidAnchorRefreshInterval);
+        }
+        match (&self.anchor_cache, &self.simulation) {
+            (Some(anchor_cache), None) => Ok(Some(anchor_cache.spawn_refresher(
+                self.client.clone(),
+                self.base_url.join("tx_anchor")?,
+                Duration::from_secs(interval_blocks * BLOCK_TIME_SECS),
+            ))),
+            _ => Ok(None),
+        }
     }
 
     /// Gets transaction from the network.
     pub async fn get_transaction(&self, id: &Base64) -> Result<Transaction, Error> {
-        let url = self.base_url.join("tx/")?.join(&id.to_string())?;
-        let resp = reqwest::get(url).await?.json::<Transaction>().await?;
+        let (_, body) = self.gateway().get_transaction(self, id).await?;
+        Ok(serde_json::from_str::<Transaction>(&body)?)
+    }
+
+    /// Gets the data of a transaction, decompressing it if the transaction has a
+    /// `Content-Encoding` tag.
+    pub async fn get_transaction_data(&self, id: &Base64) -> Result<Vec<u8>, Error> {
+        let (transaction, data) = try_join(
+            self.get_transaction(id),
+            self.gateway().get_transaction_data(self, id),
+        )
+        .await?;
+        self.decode_transaction_data(&transaction, &data)
+    }
+
+    /// Gets the data of a transaction like [`Arweave::get_transaction_data`], checking the id and
+    /// signature of the transaction with [`Arweave::verify_transaction`] and its data against its
+    /// `data_root` and `data_size` first, returning [`Error::DataMismatch`] if they differ. Data items in bundles have no transaction of their
+    /// own to check against, so their data is returned as is with [`VerifyOutcome::Unverified`].
+    pub async fn get_verified_transaction_data(
+        &self,
+        id: &Base64,
+    ) -> Result<(Vec<u8>, VerifyOutcome), Error> {
+        let gateway = self.gateway();
+        let ((resp_status, body), data) = try_join(
+            gateway.get_transaction(self, id),
+            gateway.get_transaction_data(self, id),
+        )
+        .await?;
+
+        match resp_status {
+            ResponseStatusCode::OK => {
+                let transaction: Transaction = serde_json::from_str(&body)?;
+                if &transaction.id != id {
+                    return Err(Error::InvalidTransactionId(id.to_string()));
+                }
+                self.verify_transaction_id(&transaction)?;
+                self.verify_transaction(&transaction)?;
+                let local_transaction = self.merklize(data.clone())?;
+                if local_transaction.data_root != transaction.data_root
+                    || local_transaction.data_size != transaction.data_size
+                {
+                    return Err(Error::DataMismatch(id.to_string()));
+                }
+                Ok((
+                    self.decode_transaction_data(&transaction, &data)?,
+                    VerifyOutcome::Verified,
+                ))
+            }
+            ResponseStatusCode::ACCEPTED => Ok((data, VerifyOutcome::Pending)),
+            _ => Ok((data, VerifyOutcome::Unverified)),
+        }
+    }
+
+    /// Downloads the data of transaction `id` to `file_path`, creating parent directories as
+    /// needed. Nothing is written if the data doesn't match the transaction.
+    pub async fn download_transaction(
+        &self,
+        id: Base64,
+        file_path: PathBuf,
+    ) -> Result<Verification, Error> {
+        let (data, outcome) = self.get_verified_transaction_data(&id).await?;
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&file_path, data).await?;
+        Ok(Verification {
+            id,
+            file_path,
+            outcome,
+        })
+    }
+
+    /// Gets the position of the data of transaction `id` in the weave.
+    pub async fn get_tx_offset(&self, id: &Base64) -> Result<TxOffset, Error> {
+        self.gateway().get_tx_offset(self, id).await
+    }
+
+    /// Downloads `length` bytes from `offset` of the data of transaction `id`, fetching only the
+    /// chunks that cover them and validating each against the data root of the transaction, e.g.
+    /// to preview the header of a large archive. The range is of the data as stored, before any
+    /// `Content-Encoding` is decoded.
+    pub async fn download_range(
+        &self,
+        id: &Base64,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>, Error> {
+        let (transaction, tx_offset) =
+            try_join(self.get_transaction(id), self.get_tx_offset(id)).await?;
+        let end = offset
+            .checked_add(length)
+            .filter(|end| *end <= transaction.data_size)
+            .ok_or(Error::InvalidByteRange(offset, length))?;
+        let root_id: [u8; 32] = transaction
+            .data_root
+            .0
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidDataRoot(id.to_string()))?;
+        let data_start = tx_offset
+            .offset
+            .checked_add(1)
+            .and_then(|data_end| data_end.checked_sub(tx_offset.size))
+            .ok_or_else(|| Error::InvalidTxOffset(id.to_string()))?;
+
+        let mut data = Vec::with_capacity(length as usize);
+        let mut position = offset as usize;
+        while position < end as usize {
+            let downloaded = self
+                .gateway()
+                .get_chunk(self, data_start + position as u64)
+                .await?;
+            let range = validate_downloaded_chunk(
+                root_id,
+                &downloaded.chunk.0,
+                &downloaded.data_path.0,
+                &self.crypto,
+            )?;
+            if !range.contains(&position) {
+                return Err(Error::InvalidProof);
+            }
+            let take = range.end.min(end as usize);
+            data.extend_from_slice(&downloaded.chunk.0[position - range.start..take - range.start]);
+            position = range.end;
+        }
+        Ok(data)
+    }
+
+    fn decode_transaction_data(
+        &self,
+        transaction: &Transaction,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let content_encoding = transaction
+            .tags
+            .iter()
+            .find(|t| t.name.0 == b"Content-Encoding")
+            .map(|t| t.value.to_utf8_string())
+            .transpose()?;
+
+        match content_encoding {
+            Some(content_encoding) => {
+                Compression::from_content_encoding(&content_encoding)?.decompress(data)
+            }
+            None => Ok(data.to_vec()),
+        }
+    }
+
+    /// Gets a pending transaction from the mempool of the node.
+    pub async fn get_unconfirmed_transaction(&self, id: &Base64) -> Result<Transaction, Error> {
+        let url = self
+            .base_url
+            .join("unconfirmed_tx/")?
+            .join(&id.to_string())?;
+        let resp = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Transaction>()
+            .await?;
         Ok(resp)
     }
 
@@ -451,10 +1689,109 @@ impl Arweave {
         let url = self
             .base_url
             .join(&format!("wallet/{}/balance", &wallet_address))?;
-        let winstons = reqwest::get(url).await?.json::<u64>().await?;
+        let winstons = self.client.get(url).send().await?.json::<u128>().await?;
         Ok(BigUint::from(winstons))
     }
 
+    /// Gets a page of up to [`graphql::GRAPHQL_PAGE_SIZE`] transactions owned by
+    /// `wallet_address`, or the wallet if `None`, newest first, from the GraphQL endpoint of
+    /// [`Arweave::base_url`]. Pass [`TransactionPage::next_cursor`] as `after_cursor` to get the
+    /// next page.
+    pub async fn get_wallet_transactions(
+        &self,
+        wallet_address: Option<String>,
+        after_cursor: Option<String>,
+    ) -> Result<TransactionPage, Error> {
+        self.get_wallet_transactions_with_tags(wallet_address, &[], after_cursor)
+            .await
+    }
+
+    /// Same as [`Arweave::get_wallet_transactions`], only including transactions that have all
+    /// of `tags`.
+    pub async fn get_wallet_transactions_with_tags(
+        &self,
+        wallet_address: Option<String>,
+        tags: &[Tag<String>],
+        after_cursor: Option<String>,
+    ) -> Result<TransactionPage, Error> {
+        let wallet_address = if let Some(wallet_address) = wallet_address {
+            wallet_address
+        } else {
+            self.crypto.wallet_address()?.to_string()
+        };
+        let query =
+            wallet_transactions_with_tags_query(&wallet_address, tags, after_cursor.as_deref());
+        let body = self.gateway().query_graphql(self, &query).await?;
+        parse_transaction_page(&body)
+    }
+
+    /// Gets the latest transaction tagged with `content_hash` as its [`CONTENT_HASH_TAG`] of any
+    /// of the [`Arweave::signing_wallet_addresses`], if there is one.
+    pub async fn find_transaction_by_content_hash(
+        &self,
+        content_hash: &str,
+    ) -> Result<Option<WalletTransaction>, Error> {
+        let wallet_addresses: Vec<String> = self
+            .signing_wallet_addresses()?
+            .iter()
+            .map(Base64::to_string)
+            .collect();
+        let query = content_hash_query(&wallet_addresses, content_hash);
+        let body = self.gateway().query_graphql(self, &query).await?;
+        Ok(parse_transaction_page(&body)?
+            .transactions
+            .into_iter()
+            .next())
+    }
+
+    /// Returns the addresses of the wallets uploads are signed with, those of
+    /// [`Arweave::key_ring`] if set.
+    pub fn signing_wallet_addresses(&self) -> Result<Vec<Base64>, Error> {
+        match &self.key_ring {
+            Some(key_ring) => key_ring.wallet_addresses(),
+            None => Ok(vec![self.crypto.wallet_address()?]),
+        }
+    }
+
+    /// Sums the rewards of transactions with the given data sizes and compares the total with
+    /// the balance of the wallet, returning [`Error::InsufficientBalance`] if it is short, so
+    /// that an upload can be stopped before anything is posted. With [`Arweave::key_ring`] set,
+    /// the transactions are split between its keypairs in turn and each is checked for its
+    /// share. Returns the required amount.
+    pub async fn check_balance<I>(
+        &self,
+        data_sizes: I,
+        price_terms: (u64, u64),
+    ) -> Result<u128, Error>
+    where
+        I: Iterator<Item = u64>,
+    {
+        let addresses = self.signing_wallet_addresses()?;
+        let mut shares = vec![0u128; addresses.len()];
+        for (i, data_size) in data_sizes.enumerate() {
+            shares[i % addresses.len()] += price_for_data_size(data_size, price_terms);
+        }
+        let required = shares.iter().sum::<u128>();
+        if self.simulation.is_some() {
+            return Ok(required);
+        }
+        let balances = try_join_all(
+            addresses
+                .iter()
+                .map(|address| self.get_wallet_balance(Some(address.to_string()))),
+        )
+        .await?;
+        for (available, share) in balances.into_iter().zip(shares) {
+            if available < BigUint::from(share) {
+                return Err(Error::InsufficientBalance {
+                    required: BigUint::from(share),
+                    available,
+                });
+            }
+        }
+        Ok(required)
+    }
+
     //-------------------------
     // Bundle
     //-------------------------
@@ -492,14 +1829,42 @@ impl Arweave {
         &self,
         data_items: Vec<(DataItem, Status)>,
     ) -> Result<(Vec<u8>, Value), Error> {
-        let data_items_len = (data_items.len()) as u64;
-        let ((headers, binaries), statuses): ((Vec<Vec<u8>>, Vec<Vec<u8>>), Vec<Status>) =
-            data_items
-                .into_iter()
-                .map(|(d, s)| (d.to_bundle_item().unwrap(), s))
-                .unzip();
+        let (data_items, statuses): (Vec<DataItem>, Vec<Status>) = data_items.into_iter().unzip();
+        let data_roots: Vec<(String, Option<Base64>, Option<u64>, Vec<Tag<String>>)> = statuses
+            .iter()
+            .filter_map(|s| {
+                let file_path = s.file_path.as_ref()?.to_string_lossy().into_owned();
+                Some((file_path, s.data_root.clone(), s.data_size, s.tags.clone()))
+            })
+            .collect();
+        let mut manifest = self.create_manifest(statuses)?;
+        // Kept in bundle statuses for the statuses of the files, and left out of the manifests
+        // created from them.
+        for (file_path, data_root, data_size, tags) in data_roots {
+            if let Some(data_root) = data_root {
+                manifest["paths"][&file_path]["data_root"] = json!(data_root.to_string());
+            }
+            if let Some(data_size) = data_size {
+                manifest["paths"][&file_path]["data_size"] = json!(data_size);
+            }
+            if !tags.is_empty() {
+                manifest["paths"][&file_path]["tags"] = json!(tags);
+            }
+        }
+        let binary = self.create_bundle(data_items)?;
 
-        let manifest = self.create_manifest(statuses)?;
+        Ok((binary, manifest))
+    }
+
+    /// Serializes `data_items` into the binary format of a bundle.
+    pub fn create_bundle(&self, data_items: Vec<DataItem>) -> Result<Vec<u8>, Error> {
+        let data_items_len = (data_items.len()) as u64;
+        let (headers, binaries): (Vec<Vec<u8>>, Vec<Vec<u8>>) = data_items
+            .iter()
+            .map(|d| d.to_bundle_item())
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .unzip();
 
         let binary: Vec<_> = data_items_len
             .to_le_bytes()
@@ -509,7 +1874,58 @@ impl Arweave {
             .chain(binaries.into_par_iter().flatten())
             .collect();
 
-        Ok((binary, manifest))
+        Ok(binary)
+    }
+
+    /// Packs `data_items` into a signed [`DataItem`] tagged as a bundle, so that it can be
+    /// included in another bundle. The nested data items keep their ids and remain individually
+    /// addressable.
+    pub fn create_nested_bundle_data_item(
+        &self,
+        data_items: Vec<DataItem>,
+    ) -> Result<DataItem, Error> {
+        let bundle = self.create_bundle(data_items)?;
+        let tags = vec![
+            Tag::<String>::from_utf8_strs("Bundle-Format", "binary")?,
+            Tag::<String>::from_utf8_strs("Bundle-Version", "2.0.0")?,
+        ];
+        let data_item = self.create_data_item(bundle, tags, false)?;
+        self.sign_data_item(data_item)
+    }
+
+    /// Creates a bundle transaction with a nested bundle for each of `paths_groups`, so that
+    /// extremely large collections can be packed hierarchically. The manifest returned includes
+    /// the files of every nested bundle.
+    pub async fn create_nested_bundle_transaction_from_file_paths(
+        &self,
+        paths_groups: Vec<Vec<PathBuf>>,
+        tags: Vec<Tag<String>>,
+        price_terms: (u64, u64),
+    ) -> Result<(Transaction, Value), Error> {
+        let mut nested_data_items = Vec::with_capacity(paths_groups.len());
+        let mut statuses = Vec::new();
+        for paths in paths_groups {
+            let (data_items, group_statuses): (Vec<DataItem>, Vec<Status>) = self
+                .create_data_items_from_file_paths(paths, tags.clone())
+                .await?
+                .into_iter()
+                .unzip();
+            nested_data_items.push(self.create_nested_bundle_data_item(data_items)?);
+            statuses.extend(group_statuses);
+        }
+
+        let bundle = self.create_bundle(nested_data_items)?;
+        let manifest = self.create_manifest(statuses)?;
+        let other_tags = Some(vec![
+            Tag::<Base64>::from_utf8_strs("Bundle-Format", "binary")?,
+            Tag::<Base64>::from_utf8_strs("Bundle-Version", "2.0.0")?,
+        ]);
+
+        let transaction = self
+            .create_transaction(bundle, other_tags, None, price_terms, true)
+            .await?;
+
+        Ok((transaction, manifest))
     }
 
     pub async fn create_bundle_transaction_from_file_paths(
@@ -542,10 +1958,12 @@ impl Arweave {
         mut tags: Vec<Tag<String>>,
         auto_content_tag: bool,
     ) -> Result<DataItem, Error> {
+        let app_tags = self.app_tags_except(&tags)?;
         tags.push(Tag::<String>::from_utf8_strs(
             "User-Agent",
             &format!("arloader/{}", VERSION),
         )?);
+        tags.extend(app_tags);
 
         // Get content type from [magic numbers](https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types)
         // and include additional tags if any.
@@ -558,6 +1976,7 @@ impl Arweave {
 
             tags.push(Tag::<String>::from_utf8_strs("Content-Type", content_type)?)
         }
+        validate_tags(&tags, MAX_DATA_ITEM_TAGS_BYTES)?;
 
         // let mut anchor = Base64(Vec::with_capacity(32));
         // self.crypto.fill_rand(&mut anchor.0)?;
@@ -585,8 +2004,28 @@ impl Arweave {
                 Tag::from_utf8_strs("Content-Type", &content_type.to_string())?;
             tags.push(content_tag);
         }
+        if let Some(tag_pattern) = &self.tag_pattern {
+            tags.extend(tag_pattern.tags_for_path(&file_path)?);
+        }
+        if let Some(content_disposition) = &self.content_disposition {
+            tags.extend(content_disposition.tags_for_path(&file_path)?);
+        }
 
         let data = fs::read(&file_path).await?;
+        if self.chain_dedup {
+            tags.push(Tag::from_utf8_strs(
+                CONTENT_HASH_TAG,
+                &blake3::hash(&data).to_string(),
+            )?);
+        }
+        let (data, auto_content_tag) = self.compress_data(data, &mut tags, auto_content_tag)?;
+        self.tag_ipfs_cid(&data, &mut tags)?;
+        let data_size = data.len() as u64;
+        let leaves = chunk_ranges(data.len())
+            .into_iter()
+            .map(|range| generate_leaf(&data[range.clone()], range.start, &self.crypto))
+            .collect::<Result<Vec<Node>, Error>>()?;
+        let data_root = Base64(generate_data_root(leaves, &self.crypto)?.id.to_vec());
         let data_item = self.create_data_item(data, tags, auto_content_tag)?;
         let data_item = self.sign_data_item(data_item)?;
 
@@ -594,6 +2033,9 @@ impl Arweave {
             id: data_item.id.clone(),
             file_path: Some(file_path),
             content_type: status_content_type,
+            tags: data_item.tags.clone(),
+            data_root: Some(data_root),
+            data_size: Some(data_size),
             ..Status::default()
         };
 
@@ -647,13 +2089,17 @@ impl Arweave {
                 (0..bytes_len).for_each(|_| bytes_vec.push(bundle_iter.next().unwrap()));
                 let mut data_item = DataItem::deserialize(bytes_vec)?;
 
-                let deep_hash = self
-                    .crypto
-                    .deep_hash(data_item.to_deep_hash_item()?)
-                    .unwrap();
-                self.crypto
-                    .verify(&data_item.signature.0, &deep_hash)
-                    .unwrap();
+                let deep_hash = self.crypto.deep_hash(data_item.to_deep_hash_item()?)?;
+                match data_item.signature_type {
+                    #[cfg(feature = "ethereum")]
+                    bundle::ETHEREUM_SIGNATURE_TYPE => {
+                        ethereum::verify(&data_item.owner.0, &deep_hash, &data_item.signature.0)?
+                    }
+                    _ => self
+                        .crypto
+                        .verify(&data_item.signature.0, &deep_hash)
+                        .map_err(|_| Error::InvalidDataItem)?,
+                }
 
                 data_item.id.0 = ids_iter.next().unwrap();
 
@@ -664,6 +2110,20 @@ impl Arweave {
         data_items
     }
 
+    /// Deserializes `bundle` like [`Arweave::deserialize_bundle`], replacing data items that are
+    /// bundles themselves with the data items nested in them.
+    pub fn deserialize_nested_bundle(&self, bundle: Vec<u8>) -> Result<Vec<DataItem>, Error> {
+        let mut data_items = Vec::new();
+        for data_item in self.deserialize_bundle(bundle)? {
+            if data_item.is_bundle() {
+                data_items.extend(self.deserialize_nested_bundle(data_item.data.0)?);
+            } else {
+                data_items.push(data_item);
+            }
+        }
+        Ok(data_items)
+    }
+
     pub async fn post_bundle_transaction_from_file_paths(
         &self,
         paths_chunk: PathsChunk,
@@ -671,6 +2131,31 @@ impl Arweave {
         price_terms: (u64, u64),
         buffer: usize,
     ) -> Result<BundleStatus, Error> {
+        self.post_logged_bundle_from_file_paths(paths_chunk, tags, price_terms, buffer, None)
+            .await
+    }
+
+    /// Posts a bundle of `paths_chunk` like [`Arweave::post_bundle_transaction_from_file_paths`],
+    /// writing its status with [`Arweave::write_bundle_status`] to `log_dir`, if set, as
+    /// [`StatusCode::Signed`] before it is posted and again once it has been.
+    async fn post_logged_bundle_from_file_paths(
+        &self,
+        paths_chunk: PathsChunk,
+        tags: Vec<Tag<String>>,
+        price_terms: (u64, u64),
+        buffer: usize,
+        log_dir: Option<&Path>,
+    ) -> Result<BundleStatus, Error> {
+        let (paths_chunk, blocked) = self.scan_paths_chunk(paths_chunk).await?;
+        if paths_chunk.0.is_empty() {
+            return Ok(BundleStatus {
+                status: StatusCode::Blocked,
+                blocked,
+                ..Default::default()
+            });
+        }
+
+        let created_at = Utc::now();
         let number_of_files = paths_chunk.0.len() as u64;
         let data_items = self
             .create_data_items_from_file_paths(paths_chunk.0, tags)
@@ -686,27 +2171,204 @@ impl Arweave {
             .create_transaction(bundle, other_tags, None, price_terms, true)
             .await?;
 
-        let signed_transaction = self.sign_transaction(transaction)?;
-
-        let (id, reward) = if paths_chunk.1 > MAX_TX_DATA {
-            self.post_transaction_chunks(signed_transaction, buffer)
-                .await?
-        } else {
-            self.post_transaction(&signed_transaction).await?
-        };
+        let signed_transaction = self.sign_upload_transaction(transaction)?;
+        let signed_at = Utc::now();
+        let persisted_path = self.persist_signed_transaction(&signed_transaction).await?;
 
-        let status = BundleStatus {
-            id,
-            reward,
+        let mut status = BundleStatus {
+            id: signed_transaction.id.clone(),
+            status: StatusCode::Signed,
+            reward: signed_transaction.reward,
             number_of_files,
             data_size: paths_chunk.1,
             file_paths: manifest["paths"].clone(),
+            blocked,
+            created_at,
+            signed_at: Some(signed_at),
             ..Default::default()
         };
+        if let Some(log_dir) = log_dir {
+            self.write_bundle_status(&status, log_dir).await?;
+        }
+        status.first_submitted = Some(Utc::now());
+        let posted = self
+            .post_bundle_transaction(
+                &signed_transaction,
+                number_of_files,
+                paths_chunk.1 > MAX_TX_DATA,
+                buffer,
+            )
+            .await;
+        match posted {
+            Ok(_) => {
+                status.status = StatusCode::Submitted;
+                status.record_post(Utc::now(), signed_transaction.data_size);
+                if let Some(persisted_path) = persisted_path {
+                    fs::remove_file(persisted_path).await?;
+                }
+                status.seeded_peers = self.seed_chunks(&signed_transaction).await;
+                #[cfg(feature = "metrics")]
+                metrics::FILES_UPLOADED.inc_by(number_of_files);
+            }
+            Err(e) => {
+                warn!(id = %status.id, "failed to post bundle: {}", e);
+                status.status = StatusCode::Failed {
+                    reason: e.to_string(),
+                };
+                status.first_submitted = None;
+            }
+        }
+        if let Some(log_dir) = log_dir {
+            self.write_bundle_status(&status, log_dir).await?;
+        }
 
         Ok(status)
     }
 
+    /// Writes `status` to a file in `log_dir` named by its id, whichever
+    /// [`Arweave::status_store`] is set, and the [`Status`] of each of its files to the store.
+    pub async fn write_bundle_status(
+        &self,
+        status: &BundleStatus,
+        log_dir: &Path,
+    ) -> Result<(), Error> {
+        fs::write(
+            log_dir.join(status.id.to_string()).with_extension("json"),
+            serde_json::to_string(status)?,
+        )
+        .await?;
+        for file_status in self.file_statuses_from_bundle_status(status)? {
+            self.write_status(file_status, log_dir.to_path_buf(), None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Uploads `paths` as data items in bundles of up to the `bundle_size` of
+    /// [`Arweave::bundle_policy`], or of [`MAX_TX_DATA`] without one. Returns a [`Status`] for
+    /// each file, with the id of its data item and the id of the bundle it was posted in, written
+    /// to `log_dir` if set. The reward of each bundle is split between its files by size. With
+    /// [`Arweave::chain_dedup`], files already uploaded are left out and their statuses returned
+    /// instead.
+    pub async fn upload_files_in_bundles(
+        &self,
+        paths: Vec<PathBuf>,
+        log_dir: Option<PathBuf>,
+        tags: Option<Vec<Tag<Base64>>>,
+        price_terms: (u64, u64),
+        buffer: usize,
+    ) -> Result<Vec<Status>, Error> {
+        let (paths, uploaded) = match self.chain_dedup {
+            true => self.filter_uploaded_paths(paths, log_dir.clone()).await?,
+            false => (paths, Vec::new()),
+        };
+        let bundle_size = self
+            .bundle_policy
+            .as_ref()
+            .map(|p| p.bundle_size)
+            .unwrap_or(MAX_TX_DATA);
+        let paths_chunks: Vec<PathsChunk> = self
+            .chunk_file_paths(paths.into_iter(), bundle_size)?
+            .into_iter()
+            .filter(|c| !c.0.is_empty())
+            .collect();
+        if paths_chunks.is_empty() {
+            return Ok(uploaded);
+        }
+        let tags = tags
+            .unwrap_or_default()
+            .iter()
+            .map(|t| {
+                Tag::<String>::from_utf8_strs(&t.name.to_utf8_string()?, &t.value.to_utf8_string()?)
+            })
+            .collect::<Result<Vec<Tag<String>>, Error>>()?;
+
+        let bundle_statuses: Vec<BundleStatus> = upload_bundles_stream(
+            self,
+            paths_chunks,
+            tags,
+            log_dir.clone(),
+            price_terms,
+            buffer,
+        )
+        .try_collect()
+        .await?;
+        let mut statuses = Vec::new();
+        for bundle_status in bundle_statuses {
+            // Statuses of bundled files are written as their bundles are posted.
+            if let Some(log_dir) = &log_dir {
+                for status in &bundle_status.blocked {
+                    self.write_status(status.clone(), log_dir.clone(), None)
+                        .await?;
+                }
+            }
+            statuses.extend(bundle_status.blocked.iter().cloned());
+            statuses.extend(self.file_statuses_from_bundle_status(&bundle_status)?);
+        }
+        statuses.extend(uploaded);
+        Ok(statuses)
+    }
+
+    /// Returns a [`Status`] for each file in the manifest of `bundle_status`, sharing its status
+    /// code and its reward split by the data sizes of the data items, or evenly if they weren't
+    /// recorded.
+    fn file_statuses_from_bundle_status(
+        &self,
+        bundle_status: &BundleStatus,
+    ) -> Result<Vec<Status>, Error> {
+        let files = match bundle_status.file_paths.as_object() {
+            Some(files) => files,
+            None => return Ok(Vec::new()),
+        };
+        let mut sizes: Vec<u64> = files
+            .values()
+            .map(|entry| entry["data_size"].as_u64().unwrap_or(0))
+            .collect();
+        if sizes.iter().all(|size| *size == 0) {
+            sizes.iter_mut().for_each(|size| *size = 1);
+        }
+        let total_size = sizes.iter().sum::<u64>().max(1) as u128;
+
+        files
+            .iter()
+            .zip(sizes)
+            .map(|((file_path, entry), size)| {
+                Ok(Status {
+                    id: Base64::from_str(entry["id"].as_str().ok_or(Error::ManifestNotFound)?)?,
+                    status: bundle_status.status.clone(),
+                    file_path: Some(PathBuf::from(file_path)),
+                    content_type: entry["content_type"]
+                        .as_str()
+                        .unwrap_or(mime_guess::mime::OCTET_STREAM.as_ref())
+                        .to_string(),
+                    created_at: bundle_status.created_at,
+                    first_submitted: bundle_status.first_submitted,
+                    signed_at: bundle_status.signed_at,
+                    posted_at: bundle_status.posted_at,
+                    bytes_per_sec: bundle_status.bytes_per_sec,
+                    reward: bundle_status.reward * size as u128 / total_size,
+                    bundle_id: Some(bundle_status.id.clone()),
+                    data_root: entry["data_root"]
+                        .as_str()
+                        .map(Base64::from_str)
+                        .transpose()?,
+                    data_size: entry["data_size"].as_u64(),
+                    tags: match entry.get("tags") {
+                        Some(tags) => serde_json::from_value(tags.clone())?,
+                        // bundle statuses written before the tags were kept
+                        None => entry["content_encoding"]
+                            .as_str()
+                            .map(|encoding| Tag::from_utf8_strs("Content-Encoding", encoding))
+                            .transpose()?
+                            .into_iter()
+                            .collect(),
+                    },
+                    ..Status::default()
+                })
+            })
+            .collect()
+    }
+
     pub async fn post_bundle_transaction_from_file_paths_with_sol(
         &self,
         paths_chunk: PathsChunk,
@@ -717,6 +2379,42 @@ impl Arweave {
         sol_ar_url: Url,
         from_keypair: &Keypair,
     ) -> Result<BundleStatus, Error> {
+        self.post_logged_bundle_from_file_paths_with_sol(
+            paths_chunk,
+            tags,
+            price_terms,
+            chunks_buffer,
+            solana_url,
+            sol_ar_url,
+            from_keypair,
+            None,
+        )
+        .await
+    }
+
+    /// Posts a bundle of `paths_chunk` paying with SOL, writing its status to `log_dir` like
+    /// [`Arweave::post_logged_bundle_from_file_paths`].
+    async fn post_logged_bundle_from_file_paths_with_sol(
+        &self,
+        paths_chunk: PathsChunk,
+        tags: Vec<Tag<String>>,
+        price_terms: (u64, u64),
+        chunks_buffer: usize,
+        solana_url: Url,
+        sol_ar_url: Url,
+        from_keypair: &Keypair,
+        log_dir: Option<&Path>,
+    ) -> Result<BundleStatus, Error> {
+        let (paths_chunk, blocked) = self.scan_paths_chunk(paths_chunk).await?;
+        if paths_chunk.0.is_empty() {
+            return Ok(BundleStatus {
+                status: StatusCode::Blocked,
+                blocked,
+                ..Default::default()
+            });
+        }
+
+        let created_at = Utc::now();
         let number_of_files = paths_chunk.0.len() as u64;
         let data_items = self
             .create_data_items_from_file_paths(paths_chunk.0, tags)
@@ -736,26 +2434,81 @@ impl Arweave {
             .sign_transaction_with_sol(transaction, solana_url, sol_ar_url, from_keypair)
             .await?;
 
-        let (id, reward) = if paths_chunk.1 > MAX_TX_DATA {
-            self.post_transaction_chunks(signed_transaction, chunks_buffer)
-                .await?
-        } else {
-            self.post_transaction(&signed_transaction).await?
-        };
-
-        let status = BundleStatus {
-            id,
-            reward,
+        let mut status = BundleStatus {
+            id: signed_transaction.id.clone(),
+            status: StatusCode::Signed,
+            reward: signed_transaction.reward,
             number_of_files,
             data_size: paths_chunk.1,
             file_paths: manifest["paths"].clone(),
             sol_sig: Some(sig_response),
+            blocked,
+            created_at,
+            signed_at: Some(Utc::now()),
             ..Default::default()
         };
+        if let Some(log_dir) = log_dir {
+            self.write_bundle_status(&status, log_dir).await?;
+        }
+        status.first_submitted = Some(Utc::now());
+        let posted = self
+            .post_bundle_transaction(
+                &signed_transaction,
+                number_of_files,
+                paths_chunk.1 > MAX_TX_DATA,
+                chunks_buffer,
+            )
+            .await;
+        match posted {
+            Ok(_) => {
+                status.status = StatusCode::Submitted;
+                status.record_post(Utc::now(), signed_transaction.data_size);
+                status.seeded_peers = self.seed_chunks(&signed_transaction).await;
+                #[cfg(feature = "metrics")]
+                metrics::FILES_UPLOADED.inc_by(number_of_files);
+            }
+            Err(e) => {
+                warn!(id = %status.id, "failed to post bundle: {}", e);
+                status.status = StatusCode::Failed {
+                    reason: e.to_string(),
+                };
+                status.first_submitted = None;
+            }
+        }
+        if let Some(log_dir) = log_dir {
+            self.write_bundle_status(&status, log_dir).await?;
+        }
 
         Ok(status)
     }
 
+    /// Posts a signed bundle transaction of `number_of_files` files, its data in chunks if
+    /// `in_chunks`, sending its [`UploadProgress`].
+    async fn post_bundle_transaction(
+        &self,
+        signed_transaction: &Transaction,
+        number_of_files: u64,
+        in_chunks: bool,
+        chunks_buffer: usize,
+    ) -> Result<(Base64, u128), Error> {
+        self.send_progress(UploadProgress::Started {
+            id: signed_transaction.id.clone(),
+            files: number_of_files,
+            data_size: signed_transaction.data_size,
+        });
+        let posted = if in_chunks {
+            self.post_transaction_chunks(signed_transaction, chunks_buffer)
+                .await
+        } else {
+            self.post_transaction(signed_transaction).await
+        };
+        self.send_progress(UploadProgress::Finished {
+            id: signed_transaction.id.clone(),
+            posted: posted.is_ok(),
+        });
+        posted
+    }
+
     pub fn sign_data_item(&self, mut data_item: DataItem) -> Result<DataItem, Error> {
         data_item.owner = self.crypto.keypair_modulus()?;
         let deep_hash_item = data_item.to_deep_hash_item()?;
@@ -768,6 +2521,162 @@ impl Arweave {
         Ok(data_item)
     }
 
+    /// Signs `data_item` with an Ethereum key instead of the wallet of `self`, for Bundlr nodes
+    /// to charge the Ethereum wallet for it.
+    #[cfg(feature = "ethereum")]
+    pub fn sign_data_item_with_ethereum(
+        &self,
+        signer: &ethereum::EthereumSigner,
+        data_item: DataItem,
+    ) -> Result<DataItem, Error> {
+        signer.sign_data_item(&self.crypto, data_item)
+    }
+
+    //-------------------------
+    // Bundlr
+    //-------------------------
+
+    /// Posts a signed [`DataItem`] to a Bundlr node and verifies the receipt returned by the node.
+    pub async fn post_data_item_to_bundlr(
+        &self,
+        data_item: &DataItem,
+        bundlr_url: Url,
+    ) -> Result<BundlrReceipt, Error> {
+        let url = bundlr_url.join("tx/arweave")?;
+        let resp = self
+            .client
+            .post(url)
+            .body(data_item.serialize()?)
+            .header(&ACCEPT, "application/json")
+            .header(&CONTENT_TYPE, "application/octet-stream")
+            .send()
+            .await
+            .map_err(|e| Error::ArweavePostError(e))?
+            .error_for_status()?;
+        debug!("post_data_item_to_bundlr {:?}", &resp);
+
+        let receipt = resp.json::<BundlrReceipt>().await?;
+        receipt.verify(data_item, &self.crypto)?;
+        Ok(receipt)
+    }
+
+    /// Uploads a file to a Bundlr node as a single [`DataItem`], recording the receipt
+    /// in the returned [`Status`] and writing it to `log_dir` if provided.
+    pub async fn upload_file_to_bundlr(
+        &self,
+        file_path: PathBuf,
+        log_dir: Option<PathBuf>,
+        tags: Vec<Tag<String>>,
+        bundlr_url: Url,
+    ) -> Result<Status, Error> {
+        if let Some(status) = self.block_if_denied(&file_path, &log_dir).await? {
+            return Ok(status);
+        }
+        let (data_item, mut status) = self
+            .create_data_item_from_file_path(file_path, tags)
+            .await?;
+        let receipt = self
+            .post_data_item_to_bundlr(&data_item, bundlr_url)
+            .await?;
+        status.bundlr_receipt = Some(receipt);
+        status.first_submitted = Some(Utc::now());
+
+        #[cfg(feature = "metrics")]
+        metrics::FILES_UPLOADED.inc();
+
+        if let Some(log_dir) = log_dir {
+            self.write_status(status.clone(), log_dir, None).await?;
+        }
+        Ok(status)
+    }
+
+    /// Gets the address of the Bundlr node at `bundlr_url` that funding transactions are sent to.
+    pub async fn get_bundlr_address(&self, bundlr_url: Url) -> Result<Base64, Error> {
+        let info = self
+            .client
+            .get(bundlr_url.join("info")?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<BundlrInfo>()
+            .await?;
+        let address = info
+            .addresses
+            .get("arweave")
+            .ok_or_else(|| Error::BundlrAddressNotFound(bundlr_url.to_string()))?;
+        Ok(Base64::from_str(address)?)
+    }
+
+    /// Returns the balance that uploads to the Bundlr node at `bundlr_url` are paid from, for
+    /// `wallet_address` or the wallet if `None`.
+    pub async fn get_bundlr_balance(
+        &self,
+        wallet_address: Option<String>,
+        bundlr_url: Url,
+    ) -> Result<BigUint, Error> {
+        let wallet_address = if let Some(wallet_address) = wallet_address {
+            wallet_address
+        } else {
+            self.crypto.wallet_address()?.to_string()
+        };
+        let mut url = bundlr_url.join("account/balance/arweave")?;
+        url.query_pairs_mut()
+            .append_pair("address", &wallet_address);
+        let balance = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<BundlrBalance>()
+            .await?;
+        Ok(BigUint::from(balance.winstons()?))
+    }
+
+    /// Transfers `amount` winstons to the Bundlr node at `bundlr_url` and waits, polling every
+    /// `poll_secs` seconds, until the transaction is confirmed and the node has credited it to
+    /// the balance of the wallet. Returns the id of the funding transaction, or
+    /// [`Error::BundlrFundingNotCredited`] if the balance hasn't gone up by `amount` after
+    /// [`BUNDLR_FUNDING_RETRIES`] polls, e.g. because uploads were paid from it in the meantime.
+    /// Returns [`Error::InvalidPollInterval`] before transferring anything if `poll_secs` is 0.
+    pub async fn fund_bundlr(
+        &self,
+        amount: u128,
+        bundlr_url: Url,
+        poll_secs: u64,
+    ) -> Result<Base64, Error> {
+        if poll_secs == 0 {
+            return Err(Error::InvalidPollInterval);
+        }
+        let (address, balance) = try_join(
+            self.get_bundlr_address(bundlr_url.clone()),
+            self.get_bundlr_balance(None, bundlr_url.clone()),
+        )
+        .await?;
+        let transaction = self.create_transfer_transaction(address, amount).await?;
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.wait_for_confirmation(&id, poll_secs).await?;
+
+        // The node finds funding transactions on its own, submitting the id only speeds it up.
+        let submitted = self
+            .client
+            .post(bundlr_url.join("account/balance/arweave")?)
+            .json(&json!({ "tx_id": id.to_string() }))
+            .send()
+            .await;
+        debug!("fund_bundlr submitted {:?}", submitted);
+
+        let credited = balance + BigUint::from(amount);
+        for _ in 0..BUNDLR_FUNDING_RETRIES {
+            if self.get_bundlr_balance(None, bundlr_url.clone()).await? >= credited {
+                return Ok(id);
+            }
+            sleep(Duration::from_secs(poll_secs)).await;
+        }
+        Err(Error::BundlrFundingNotCredited(id.to_string()))
+    }
+
     //-------------------------
     // Transaction
     //-------------------------
@@ -780,66 +2689,272 @@ impl Arweave {
         price_terms: (u64, u64),
         auto_content_tag: bool,
     ) -> Result<Transaction, Error> {
-        let mut transaction = self.merklize(data)?;
+        let content_type = auto_content_tag.then(|| infer_content_type(&data));
+        let transaction = self.merklize(data)?;
+        self.complete_transaction(transaction, content_type, other_tags, last_tx, price_terms)
+            .await
+    }
+
+    /// Creates a transaction for the file at `file_path` without its data, reading and hashing
+    /// the file a chunk at a time within [`Arweave::chunk_budget`]. Its chunks are posted from
+    /// the file with [`Arweave::post_transaction_from_file`].
+    pub async fn create_transaction_from_file_chunks(
+        &self,
+        file_path: &Path,
+        other_tags: Option<Vec<Tag<Base64>>>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+        auto_content_tag: bool,
+    ) -> Result<Transaction, Error> {
+        let data_size = file_path.metadata()?.len();
+        let mut content_type = None;
+        let mut leaves = Vec::new();
+        let mut cid_builder = self.ipfs_cid.then(|| ipfs::CidBuilder::new(&self.crypto));
+        for range in chunk_ranges(data_size as usize) {
+            let _permit = self.acquire_chunk_permit().await;
+            let chunk = rt::read_range(file_path, range.start as u64, range.len()).await?;
+            if auto_content_tag && content_type.is_none() {
+                content_type = Some(infer_content_type(&chunk));
+            }
+            if let Some(cid_builder) = &mut cid_builder {
+                cid_builder.update(&chunk)?;
+            }
+            leaves.push(generate_leaf(&chunk, range.start, &self.crypto)?);
+        }
+        let other_tags = match cid_builder {
+            Some(cid_builder) => {
+                let mut tags = other_tags.unwrap_or_default();
+                tags.push(Tag::from_utf8_strs(
+                    ipfs::IPFS_ADD_TAG,
+                    &cid_builder.finish()?,
+                )?);
+                Some(tags)
+            }
+            None => other_tags,
+        };
+        let transaction = self.transaction_from_leaves(leaves, data_size)?;
+        self.complete_transaction(transaction, content_type, other_tags, last_tx, price_terms)
+            .await
+    }
+
+    /// Sets the owner, tags, anchor and reward of a merklized `transaction`, tagging it with
+    /// `content_type` if given.
+    async fn complete_transaction(
+        &self,
+        mut transaction: Transaction,
+        content_type: Option<&str>,
+        other_tags: Option<Vec<Tag<Base64>>>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+    ) -> Result<Transaction, Error> {
         transaction.owner = self.crypto.keypair_modulus()?;
+        let other_tags = other_tags.unwrap_or_default();
 
         let mut tags = vec![Tag::<Base64>::from_utf8_strs(
             "User-Agent",
             &format!("arloader/{}", VERSION),
         )?];
+        tags.extend(self.app_tags_except(&other_tags)?);
 
-        // Get content type from [magic numbers](https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types)
-        // and include additional tags if any.
-        if auto_content_tag {
-            let content_type = if let Some(kind) = infer::get(&transaction.data.0) {
-                kind.mime_type()
-            } else {
-                "application/octet-stream"
-            };
-
+        if let Some(content_type) = content_type {
             tags.push(Tag::<Base64>::from_utf8_strs("Content-Type", content_type)?)
         }
 
         // Add other tags if provided.
-        if let Some(other_tags) = other_tags {
-            tags.extend(other_tags);
-        }
+        tags.extend(other_tags);
+        validate_tags(&tags, MAX_TX_TAGS_BYTES)?;
         transaction.tags = tags;
 
         // Fetch and set last_tx if not provided (primarily for testing).
         let last_tx = if let Some(last_tx) = last_tx {
             last_tx
         } else {
-            let resp = reqwest::get(self.base_url.join("tx_anchor")?).await?;
-            debug!("last_tx: {}", resp.status());
-            let last_tx_str = resp.text().await?;
-            Base64::from_str(&last_tx_str)?
+            self.get_tx_anchor().await?
         };
         transaction.last_tx = last_tx;
 
-        let blocks_len =
-            transaction.data_size / BLOCK_SIZE + (transaction.data_size % BLOCK_SIZE != 0) as u64;
-        let reward = price_terms.0 + price_terms.1 * (blocks_len - 1);
-        transaction.reward = reward;
+        transaction.reward = price_for_data_size(transaction.data_size, price_terms);
 
         Ok(transaction)
     }
 
+    /// Creates a transaction from the contents of `file_path`. If `content_type` is set, it
+    /// replaces any `Content-Type` tag in `other_tags` and the content type isn't inferred from
+    /// the data.
+    #[instrument(
+        skip(self, other_tags, last_tx, price_terms, auto_content_tag, content_type),
+        fields(file_path = %file_path.display(), bytes)
+    )]
     pub async fn create_transaction_from_file_path(
         &self,
         file_path: PathBuf,
         other_tags: Option<Vec<Tag<Base64>>>,
         last_tx: Option<Base64>,
         price_terms: (u64, u64),
-        auto_content_tag: bool,
+        mut auto_content_tag: bool,
+        content_type: Option<&str>,
     ) -> Result<Transaction, Error> {
         let data = fs::read(file_path).await?;
-        self.create_transaction(data, other_tags, last_tx, price_terms, auto_content_tag)
+        Span::current().record("bytes", &(data.len() as u64));
+        let mut tags = other_tags.unwrap_or_default();
+        if let Some(content_type) = content_type {
+            override_content_type(&mut tags, content_type)?;
+            auto_content_tag = false;
+        }
+        let (data, auto_content_tag) = self.compress_data(data, &mut tags, auto_content_tag)?;
+        self.tag_ipfs_cid(&data, &mut tags)?;
+        self.create_transaction(data, Some(tags), last_tx, price_terms, auto_content_tag)
             .await
     }
 
+    /// Tags `data` with its IPFS CID if [`Arweave::ipfs_cid`] is set.
+    fn tag_ipfs_cid<T>(&self, data: &[u8], tags: &mut Vec<T>) -> Result<(), Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        if self.ipfs_cid {
+            tags.push(T::from_utf8_strs(
+                ipfs::IPFS_ADD_TAG,
+                &ipfs::cid(data, &self.crypto)?,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Compresses `data` if [`Arweave::compression`] is set, adding a `Content-Encoding` tag.
+    /// The content type is inferred from the uncompressed data if `auto_content_tag` is true, so
+    /// the returned flag tells whether it still needs to be inferred.
+    fn compress_data<T>(
+        &self,
+        data: Vec<u8>,
+        tags: &mut Vec<T>,
+        auto_content_tag: bool,
+    ) -> Result<(Vec<u8>, bool), Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        let compression = match self.compression {
+            Some(compression) => compression,
+            None => return Ok((data, auto_content_tag)),
+        };
+        if auto_content_tag {
+            tags.push(T::from_utf8_strs(
+                "Content-Type",
+                infer_content_type(&data),
+            )?);
+        }
+        tags.push(T::from_utf8_strs(
+            "Content-Encoding",
+            compression.content_encoding(),
+        )?);
+        Ok((compression.compress(&data)?, false))
+    }
+
+    /// Creates an unsigned transaction without data that transfers `quantity` winstons to the
+    /// wallet with address `target`.
+    pub async fn create_transfer_transaction(
+        &self,
+        target: Base64,
+        quantity: u128,
+    ) -> Result<Transaction, Error> {
+        let (reward, last_tx) =
+            try_join(self.get_transfer_price(&target), self.get_tx_anchor()).await?;
+        let mut tags = vec![Tag::<Base64>::from_utf8_strs(
+            "User-Agent",
+            &format!("arloader/{}", VERSION),
+        )?];
+        tags.extend(self.app_tags()?);
+        Ok(Transaction {
+            format: 2,
+            owner: self.crypto.keypair_modulus()?,
+            tags,
+            target,
+            quantity,
+            last_tx,
+            reward,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the tags of [`Arweave::app_tags`] for a transaction created now, empty if unset.
+    fn app_tags<T>(&self) -> Result<Vec<T>, Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        match &self.app_tags {
+            Some(app_tags) => app_tags.tags(Utc::now().timestamp()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the tags of [`Arweave::app_tags`] not named by any of `tags`, so that the
+    /// caller's own `App-Name` or `App-Version`, e.g. of a SmartWeave contract, isn't duplicated.
+    fn app_tags_except<T>(&self, tags: &[Tag<T>]) -> Result<Vec<Tag<T>>, Error>
+    where
+        T: PartialEq,
+        Tag<T>: FromUtf8Strs<Tag<T>>,
+    {
+        let mut app_tags: Vec<Tag<T>> = self.app_tags()?;
+        app_tags.retain(|app_tag| !tags.iter().any(|tag| tag.name == app_tag.name));
+        Ok(app_tags)
+    }
+
+    /// Projected reward in winstons for uploading the file at `file_path`, zero if it can't be
+    /// read.
+    fn projected_reward(&self, file_path: &Path, price_terms: (u64, u64)) -> u128 {
+        file_path.metadata().map_or(0, |metadata| {
+            price_for_data_size(metadata.len(), price_terms)
+        })
+    }
+
+    /// Checks the balances of the [`Arweave::signing_wallet_addresses`] with
+    /// [`Arweave::balance_guard`], if set, pausing or returning [`Error::InsufficientBalance`] if
+    /// together they are short of the cost of the files left to upload. Simulated uploads are
+    /// never checked.
+    async fn check_balance_guard(&self) -> Result<(), Error> {
+        match (&self.balance_guard, &self.simulation) {
+            (Some(guard), None) => guard.check(|| self.get_signing_wallets_balance()).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the projected `cost` of an upload that finished, failed or was aborted from
+    /// [`Arweave::balance_guard`], if set.
+    fn release_balance_guard(&self, cost: u128) {
+        if let Some(guard) = &self.balance_guard {
+            guard.record_uploaded(cost);
+        }
+    }
+
+    /// Sums the balances of the [`Arweave::signing_wallet_addresses`].
+    async fn get_signing_wallets_balance(&self) -> Result<BigUint, Error> {
+        let balances = try_join_all(
+            self.signing_wallet_addresses()?
+                .iter()
+                .map(|address| self.get_wallet_balance(Some(address.to_string()))),
+        )
+        .await?;
+        Ok(balances.into_iter().sum())
+    }
+
     pub fn merklize(&self, data: Vec<u8>) -> Result<Transaction, Error> {
-        let mut chunks = generate_leaves(data.clone(), &self.crypto)?;
+        let leaves = chunk_ranges(data.len())
+            .into_iter()
+            .map(|range| generate_leaf(&data[range.clone()], range.start, &self.crypto))
+            .collect::<Result<Vec<Node>, Error>>()?;
+        let transaction = self.transaction_from_leaves(leaves, data.len() as u64)?;
+        Ok(Transaction {
+            data: Base64(data),
+            ..transaction
+        })
+    }
+
+    /// Creates a transaction without data from the leaves of data of `data_size` bytes.
+    fn transaction_from_leaves(
+        &self,
+        mut chunks: Vec<Node>,
+        data_size: u64,
+    ) -> Result<Transaction, Error> {
         let root = generate_data_root(chunks.clone(), &self.crypto)?;
         let data_root = Base64(root.id.clone().into_iter().collect());
         let mut proofs = resolve_proofs(root, None)?;
@@ -853,8 +2968,7 @@ impl Arweave {
 
         Ok(Transaction {
             format: 2,
-            data_size: data.len() as u64,
-            data: Base64(data),
+            data_size,
             data_root,
             chunks,
             proofs,
@@ -862,93 +2976,717 @@ impl Arweave {
         })
     }
 
-    pub async fn post_chunk(&self, chunk: &Chunk) -> Result<usize, Error> {
-        let url = self.base_url.join("chunk/")?;
-        let client = reqwest::Client::new();
-
-        client
-            .post(url)
-            .json(&chunk)
-            .header(&ACCEPT, "application/json")
-            .header(&CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .map_err(|e| Error::ArweavePostError(e))?;
-
-        Ok(chunk.offset)
+    /// Creates a [`LeafIndex`] from the data of the file at `file_path`, as read from disk, so
+    /// it does not apply to uploads compressed with [`Arweave::compression`].
+    pub async fn create_leaf_index(&self, file_path: PathBuf) -> Result<LeafIndex, Error> {
+        let data = fs::read(file_path).await?;
+        let data_size = data.len() as u64;
+        let leaves = generate_leaves(data, &self.crypto)?;
+        let root = generate_data_root(leaves.clone(), &self.crypto)?;
+        Ok(LeafIndex::from_leaves(
+            &leaves,
+            Base64(root.id.to_vec()),
+            data_size,
+        ))
     }
 
-    pub async fn post_chunk_with_retries(&self, chunk: Chunk) -> Result<usize, Error> {
-        let mut retries = 0;
-        let mut resp = self.post_chunk(&chunk).await;
+    /// Writes `leaf_index` for `file_path` to the [`LEAVES_DIR_NAME`] directory of `log_dir`,
+    /// named in the same way as statuses.
+    pub async fn write_leaf_index(
+        &self,
+        leaf_index: &LeafIndex,
+        file_path: &Path,
+        log_dir: PathBuf,
+    ) -> Result<PathBuf, Error> {
+        let leaves_dir = log_dir.join(LEAVES_DIR_NAME);
+        fs::create_dir_all(&leaves_dir).await?;
+        let index_path = leaves_dir
+            .join(hash_file_path(file_path))
+            .with_extension("json");
+        fs::write(&index_path, serde_json::to_string(leaf_index)?).await?;
+        Ok(index_path)
+    }
 
-        while retries < CHUNKS_RETRIES {
-            match resp {
-                Ok(offset) => return Ok(offset),
-                Err(_) => {
-                    sleep(Duration::from_secs(CHUNKS_RETRY_SLEEP)).await;
-                    retries += 1;
-                    resp = self.post_chunk(&chunk).await;
-                }
+    pub async fn read_leaf_index(
+        &self,
+        file_path: &Path,
+        log_dir: PathBuf,
+    ) -> Result<LeafIndex, Error> {
+        let leaves_dir = log_dir.join(LEAVES_DIR_NAME);
+        let mut index_path = leaves_dir
+            .join(hash_file_path(file_path))
+            .with_extension("json");
+        if let Some(legacy_hash) = legacy_hash_file_path(file_path) {
+            if !index_path.exists() {
+                index_path = leaves_dir.join(legacy_hash).with_extension("json");
             }
         }
-        resp
+        let data = fs::read_to_string(index_path).await?;
+        Ok(serde_json::from_str(&data)?)
     }
 
-    pub async fn post_transaction(
+    /// Regenerates the [`Chunk`] at `index` of the file at `file_path`, reading only that
+    /// chunk's bytes and hashing only the leaf ids of `leaf_index`. Returns
+    /// [`Error::InvalidProof`] if the bytes no longer match the hash recorded for the chunk.
+    pub async fn regenerate_chunk(
         &self,
-        signed_transaction: &Transaction,
-    ) -> Result<(Base64, u64), Error> {
-        if signed_transaction.id.0.is_empty() {
-            return Err(error::Error::UnsignedTransaction.into());
+        file_path: PathBuf,
+        leaf_index: &LeafIndex,
+        index: usize,
+    ) -> Result<Chunk, Error> {
+        let proof = leaf_index.proof(index, &self.crypto)?;
+        let leaf = &leaf_index.leaves[index];
+
+        let data = rt::read_range(
+            file_path,
+            leaf.min_byte_range as u64,
+            leaf.max_byte_range - leaf.min_byte_range,
+        )
+        .await?;
+        if self.crypto.hash_sha256(&data)?.as_ref() != leaf.data_hash.0.as_slice() {
+            return Err(Error::InvalidProof);
         }
 
-        let url = self.base_url.join("tx/")?;
-        let client = reqwest::Client::new();
-        let resp = client
-            .post(url)
-            .json(&signed_transaction)
-            .header(&ACCEPT, "application/json")
-            .header(&CONTENT_TYPE, "application/json")
-            .send()
-            .await?;
-        debug!("post_transaction {:?}", &resp);
-        assert_eq!(resp.status().as_u16(), 200);
-
-        Ok((signed_transaction.id.clone(), signed_transaction.reward))
+        Ok(Chunk::new(
+            leaf_index.data_root.clone(),
+            leaf_index.data_size,
+            proof,
+            data,
+        ))
     }
 
-    pub async fn post_transaction_chunks(
+    /// Writes the chunk payloads and data paths of the transaction of `status` to
+    /// `export_dir/<id>/<offset>.json`, each in the format of the `chunk/` endpoint, so that node
+    /// operators can seed their chunk storage from local data. The chunks are rebuilt from the
+    /// file at `status.file_path`, compressed with [`Arweave::compression`] as for uploads, so
+    /// they only match the transaction if the file and compression are unchanged, which is
+    /// checked against the data root of the status, or of the transaction if the status has none,
+    /// returning [`Error::InvalidDataRoot`] if they don't. Returns the directory the chunks were
+    /// written to.
+    pub async fn export_chunks(
         &self,
-        signed_transaction: Transaction,
-        chunks_buffer: usize,
-    ) -> Result<(Base64, u64), Error> {
-        if signed_transaction.id.0.is_empty() {
-            return Err(error::Error::UnsignedTransaction.into());
+        status: &Status,
+        export_dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let file_path = status.file_path.as_ref().ok_or(Error::MissingFilePath)?;
+        let data = fs::read(file_path).await?;
+        let (data, _) = self.compress_data(data, &mut Vec::<Tag<Base64>>::new(), false)?;
+        let transaction = self.merklize(data)?;
+        let data_root = match &status.data_root {
+            Some(data_root) => data_root.clone(),
+            None => self.get_transaction(&status.id).await?.data_root,
+        };
+        if transaction.data_root != data_root {
+            return Err(Error::InvalidDataRoot(data_root.to_string()));
         }
 
-        let transaction_with_no_data = signed_transaction.clone_with_no_data()?;
-        let (id, reward) = self.post_transaction(&transaction_with_no_data).await?;
-
-        let results: Vec<Result<usize, Error>> =
-            upload_transaction_chunks_stream(&self, signed_transaction, chunks_buffer)
-                .collect()
-                .await;
+        let chunks_dir = export_dir.join(status.id.to_string());
+        fs::create_dir_all(&chunks_dir).await?;
+        for i in 0..transaction.chunks.len() {
+            let chunk = transaction.get_chunk(i)?;
+            fs::write(
+                chunks_dir
+                    .join(chunk.offset.to_string())
+                    .with_extension("json"),
+                serde_json::to_string(&chunk)?,
+            )
+            .await?;
+        }
+        Ok(chunks_dir)
+    }
 
-        results.into_iter().collect::<Result<Vec<usize>, Error>>()?;
+    /// Exports the chunks of every confirmed [`Status`] in `log_dir` with
+    /// [`Arweave::export_chunks`].
+    pub async fn export_confirmed_chunks(
+        &self,
+        log_dir: PathBuf,
+        export_dir: &Path,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let confirmed = self
+            .filter_all_statuses(log_dir, Some(vec![StatusCode::Confirmed]), None)
+            .await?;
+        let mut chunks_dirs = Vec::with_capacity(confirmed.len());
+        for status in confirmed.iter().filter(|s| s.file_path.is_some()) {
+            chunks_dirs.push(self.export_chunks(status, export_dir).await?);
+        }
+        Ok(chunks_dirs)
+    }
 
-        Ok((id, reward))
+    pub async fn post_chunk(&self, chunk: &Chunk) -> Result<usize, Error> {
+        self.gateway().post_chunk(self, chunk).await?;
+        Ok(chunk.offset)
     }
 
-    /// Gets deep hash, signs and sets signature and id.
-    pub fn sign_transaction(&self, mut transaction: Transaction) -> Result<Transaction, Error> {
-        let deep_hash_item = transaction.to_deep_hash_item()?;
-        let deep_hash = self.crypto.deep_hash(deep_hash_item)?;
-        let signature = self.crypto.sign(&deep_hash)?;
-        let id = self.crypto.hash_sha256(&signature)?;
-        transaction.signature = Base64(signature);
-        transaction.id = Base64(id.to_vec());
-        Ok(transaction)
+    /// Sends a post, waiting out gateway maintenance with [`Arweave::maintenance`] if set, which
+    /// holds every post until the gateway is back. Without it, maintenance responses return
+    /// [`Error::GatewayUnavailable`].
+    pub(crate) async fn send_post(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        if let Some(maintenance) = &self.maintenance {
+            maintenance.wait_until_resumed().await;
+        }
+        loop {
+            let probe = request
+                .try_clone()
+                .expect("posts have json bodies, which can be cloned");
+            let resp = match probe.send().await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    let e = Error::ArweavePostError(e);
+                    if let Some(concurrency) = &self.adaptive_concurrency {
+                        if e.is_retryable() {
+                            concurrency.record(false);
+                        }
+                    }
+                    return Err(e);
+                }
+            };
+            // Maintenance is waited out with `maintenance` rather than counted as an error.
+            if let Some(concurrency) = &self.adaptive_concurrency {
+                if resp.status() != ResponseStatusCode::SERVICE_UNAVAILABLE {
+                    concurrency.record(!is_retryable_status(resp.status().as_u16()));
+                }
+            }
+            if resp.status() != ResponseStatusCode::SERVICE_UNAVAILABLE {
+                if let Some(maintenance) = &self.maintenance {
+                    maintenance.resume();
+                }
+                return Ok(resp);
+            }
+
+            let retry_after = client::retry_after(resp.headers());
+            match &self.maintenance {
+                Some(maintenance) => sleep(maintenance.pause(retry_after)).await,
+                None => return Err(Error::GatewayUnavailable(retry_after)),
+            }
+        }
+    }
+
+    /// Waits for a permit from [`Arweave::host_limiter`] to post to the host of `url`.
+    pub(crate) async fn acquire_host_permit(&self, url: &Url) -> Option<OwnedSemaphorePermit> {
+        match &self.host_limiter {
+            Some(host_limiter) => Some(host_limiter.acquire(url).await),
+            None => None,
+        }
+    }
+
+    /// Returns how many uploads are started at once in place of `buffer`, the `max` of
+    /// [`Arweave::adaptive_concurrency`] if set, within which its limit applies.
+    pub(crate) fn upload_buffer(&self, buffer: usize) -> usize {
+        self.adaptive_concurrency
+            .as_ref()
+            .map_or(buffer, |concurrency| concurrency.max)
+    }
+
+    /// Waits for a permit from [`Arweave::adaptive_concurrency`] to start another upload.
+    pub(crate) async fn acquire_upload_permit(&self) -> Option<client::AdaptivePermit> {
+        match &self.adaptive_concurrency {
+            Some(concurrency) => Some(concurrency.acquire().await),
+            None => None,
+        }
+    }
+
+    /// Waits for a permit from [`Arweave::chunk_budget`] to hold another chunk in memory.
+    pub(crate) async fn acquire_chunk_permit(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.chunk_budget {
+            Some(chunk_budget) => Some(chunk_budget.acquire().await),
+            None => None,
+        }
+    }
+
+    /// Sends `event` to [`Arweave::progress`] if set.
+    pub(crate) fn send_progress(&self, event: UploadProgress) {
+        if let Some(progress) = &self.progress {
+            // Nobody watching progress is not a reason to fail uploads.
+            let _ = progress.send(event);
+        }
+    }
+
+    /// Whether [`Arweave::upload_file_from_path`] reads files a chunk at a time. Needs a
+    /// [`Arweave::chunk_budget`], and compression, persisting signed transactions and seeding
+    /// peers need the whole data, so files are read whole with any of them set.
+    fn streams_file_data(&self) -> bool {
+        self.chunk_budget.is_some()
+            && self.compression.is_none()
+            && self.signed_tx_dir.is_none()
+            && self.seed_peers.is_empty()
+    }
+
+    pub async fn post_chunk_with_retries(&self, chunk: Chunk) -> Result<usize, Error> {
+        let mut retries = 0;
+        let mut resp = self.post_chunk(&chunk).await;
+
+        while retries < CHUNKS_RETRIES {
+            match resp {
+                Ok(offset) => return Ok(offset),
+                Err(e) if !e.is_retryable() => return Err(e),
+                Err(_) => {
+                    sleep(Duration::from_secs(CHUNKS_RETRY_SLEEP)).await;
+                    retries += 1;
+                    #[cfg(feature = "metrics")]
+                    metrics::CHUNK_RETRIES.inc();
+                    resp = self.post_chunk(&chunk).await;
+                }
+            }
+        }
+        resp
+    }
+
+    #[instrument(
+        skip_all,
+        fields(id = %signed_transaction.id, bytes = signed_transaction.data.0.len() as u64)
+    )]
+    pub async fn post_transaction(
+        &self,
+        signed_transaction: &Transaction,
+    ) -> Result<(Base64, u128), Error> {
+        if signed_transaction.id.0.is_empty() {
+            return Err(error::Error::UnsignedTransaction.into());
+        }
+
+        #[cfg(feature = "metrics")]
+        let timer = metrics::POST_LATENCY.start_timer();
+        self.gateway()
+            .post_transaction(self, signed_transaction)
+            .await?;
+
+        #[cfg(feature = "metrics")]
+        {
+            timer.observe_duration();
+            metrics::BYTES_POSTED.inc_by(signed_transaction.data.0.len() as u64);
+        }
+
+        Ok((signed_transaction.id.clone(), signed_transaction.reward))
+    }
+
+    #[instrument(
+        skip_all,
+        fields(id = %signed_transaction.id, bytes = signed_transaction.data_size)
+    )]
+    pub async fn post_transaction_chunks(
+        &self,
+        signed_transaction: &Transaction,
+        chunks_buffer: usize,
+    ) -> Result<(Base64, u128), Error> {
+        if signed_transaction.id.0.is_empty() {
+            return Err(error::Error::UnsignedTransaction.into());
+        }
+
+        #[cfg(feature = "metrics")]
+        let signed_transaction_data_size = signed_transaction.data_size;
+        let transaction_with_no_data = signed_transaction.clone_with_no_data()?;
+        let (id, reward) = self.post_transaction(&transaction_with_no_data).await?;
+
+        let results: Vec<Result<usize, Error>> =
+            upload_transaction_chunks_stream(&self, signed_transaction, chunks_buffer)
+                .collect()
+                .await;
+
+        results.into_iter().collect::<Result<Vec<usize>, Error>>()?;
+
+        #[cfg(feature = "metrics")]
+        metrics::BYTES_POSTED.inc_by(signed_transaction_data_size);
+
+        Ok((id, reward))
+    }
+
+    /// Posts `signed_transaction`, created with [`Arweave::create_transaction_from_file_chunks`],
+    /// and then its chunks, each read from `file_path` just before it is posted so that no more
+    /// than [`Arweave::chunk_budget`] chunks are in memory.
+    pub async fn post_transaction_from_file(
+        &self,
+        signed_transaction: &Transaction,
+        file_path: &Path,
+        chunks_buffer: usize,
+    ) -> Result<(Base64, u128), Error> {
+        let (id, reward) = self.post_transaction(signed_transaction).await?;
+
+        stream::iter(0..signed_transaction.chunks.len())
+            .map(|i| async move {
+                let _permit = self.acquire_chunk_permit().await;
+                let leaf = &signed_transaction.chunks[i];
+                let data = rt::read_range(
+                    file_path,
+                    leaf.min_byte_range as u64,
+                    leaf.max_byte_range - leaf.min_byte_range,
+                )
+                .await?;
+                let bytes = data.len() as u64;
+                let posted = self
+                    .post_chunk_with_retries(Chunk::new(
+                        signed_transaction.data_root.clone(),
+                        signed_transaction.data_size,
+                        signed_transaction.proofs[i].clone(),
+                        data,
+                    ))
+                    .await?;
+                self.send_progress(UploadProgress::ChunkPosted {
+                    id: signed_transaction.id.clone(),
+                    bytes,
+                });
+                Ok::<usize, Error>(posted)
+            })
+            .buffer_unordered(chunks_buffer)
+            .try_collect::<Vec<usize>>()
+            .await?;
+
+        #[cfg(feature = "metrics")]
+        metrics::BYTES_POSTED.inc_by(signed_transaction.data_size);
+
+        Ok((id, reward))
+    }
+
+    /// Returns the urls of the peers of the node at [`Arweave::base_url`], as listed by its
+    /// `peers` endpoint.
+    pub async fn get_peers(&self) -> Result<Vec<Url>, Error> {
+        let url = self.base_url.join("peers")?;
+        let peers: Vec<String> = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(peers
+            .iter()
+            .filter_map(|peer| Url::from_str(&format!("http://{}/", peer)).ok())
+            .collect())
+    }
+
+    /// Returns up to `max_peers` of the peers of [`Arweave::base_url`] that answer their `info`
+    /// endpoint within [`PEER_PROBE_TIMEOUT_MILLIS`], fastest first, e.g. to use as
+    /// [`Arweave::seed_peers`].
+    pub async fn discover_peers(&self, max_peers: usize) -> Result<Vec<Url>, Error> {
+        let peers = self.get_peers().await?;
+        let healthy = stream::iter(peers)
+            .map(|peer| async move {
+                let url = peer.join("info").ok()?;
+                self.client
+                    .get(url)
+                    .timeout(Duration::from_millis(PEER_PROBE_TIMEOUT_MILLIS))
+                    .send()
+                    .await
+                    .and_then(|resp| resp.error_for_status())
+                    .ok()
+                    .map(|_| peer)
+            })
+            .buffer_unordered(PEER_PROBE_BUFFER)
+            .filter_map(|peer| async move { peer })
+            .take(max_peers)
+            .collect()
+            .await;
+        Ok(healthy)
+    }
+
+    /// Pushes `signed_transaction` and its chunks to each of [`Arweave::seed_peers`]
+    /// concurrently, returning the peers that accepted all of them. Failures are only logged,
+    /// since the transaction has already been posted to the gateway.
+    pub async fn seed_chunks(&self, signed_transaction: &Transaction) -> Vec<String> {
+        if self.seed_peers.is_empty() {
+            return Vec::new();
+        }
+        let transaction_with_no_data = match signed_transaction.clone_with_no_data() {
+            Ok(transaction) => transaction,
+            Err(e) => {
+                warn!(id = %signed_transaction.id, "failed to seed transaction: {}", e);
+                return Vec::new();
+            }
+        };
+        let results = join_all(
+            self.seed_peers
+                .iter()
+                .map(|peer| self.seed_peer(peer, &transaction_with_no_data, signed_transaction)),
+        )
+        .await;
+
+        self.seed_peers
+            .iter()
+            .zip(results)
+            .filter_map(|(peer, result)| match result {
+                Ok(()) => Some(peer.to_string()),
+                Err(e) => {
+                    warn!(id = %signed_transaction.id, %peer, "failed to seed transaction: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    async fn seed_peer(
+        &self,
+        peer: &Url,
+        transaction_with_no_data: &Transaction,
+        signed_transaction: &Transaction,
+    ) -> Result<(), Error> {
+        let post = |url: Url| {
+            self.client
+                .post(url)
+                .header(&ACCEPT, "application/json")
+                .header(&CONTENT_TYPE, "application/json")
+        };
+        let url = peer.join("tx/")?;
+        let _permit = self.acquire_host_permit(&url).await;
+        post(url)
+            .json(transaction_with_no_data)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(Error::ArweavePostError)?;
+
+        let url = peer.join("chunk/")?;
+        for i in 0..signed_transaction.chunks.len() {
+            let chunk = signed_transaction.get_chunk(i)?;
+            post(url.clone())
+                .json(&chunk)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status())
+                .map_err(Error::ArweavePostError)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `signed_transaction` with its chunks to `dir` as `<id>.json`, returning the path
+    /// of the file.
+    pub async fn write_signed_transaction(
+        &self,
+        signed_transaction: &Transaction,
+        dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let signed = SignedTransaction::from_transaction(signed_transaction)?;
+        let path = dir
+            .join(signed.transaction.id.to_string())
+            .with_extension("json");
+        fs::write(&path, serde_json::to_string(&signed)?).await?;
+        Ok(path)
+    }
+
+    pub async fn read_signed_transaction(&self, path: &Path) -> Result<SignedTransaction, Error> {
+        let data = fs::read_to_string(path).await?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// Posts the header of a [`SignedTransaction`] and then its chunks, once they pass
+    /// [`Arweave::verify_signed_transaction`].
+    pub async fn post_signed_transaction(
+        &self,
+        signed: SignedTransaction,
+        chunks_buffer: usize,
+    ) -> Result<(Base64, u128), Error> {
+        self.verify_signed_transaction(&signed)?;
+        let (id, reward) = self.post_transaction(&signed.transaction).await?;
+        stream::iter(signed.chunks)
+            .map(|chunk| self.post_chunk_with_retries(chunk))
+            .buffer_unordered(chunks_buffer)
+            .try_collect::<Vec<usize>>()
+            .await?;
+        Ok((id, reward))
+    }
+
+    /// Posts the signed transaction written to `path` by [`Arweave::write_signed_transaction`],
+    /// removing the file once the transaction and all of its chunks have been posted.
+    pub async fn post_signed_transaction_file(
+        &self,
+        path: &Path,
+        chunks_buffer: usize,
+    ) -> Result<(Base64, u128), Error> {
+        let signed = self.read_signed_transaction(path).await?;
+        let posted = self.post_signed_transaction(signed, chunks_buffer).await?;
+        fs::remove_file(path).await?;
+        Ok(posted)
+    }
+
+    /// Writes `signed_transaction` to [`Arweave::signed_tx_dir`] if set, before it is posted.
+    async fn persist_signed_transaction(
+        &self,
+        signed_transaction: &Transaction,
+    ) -> Result<Option<PathBuf>, Error> {
+        match &self.signed_tx_dir {
+            Some(dir) => {
+                fs::create_dir_all(dir).await?;
+                Ok(Some(
+                    self.write_signed_transaction(signed_transaction, dir)
+                        .await?,
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Adds the `Content-Type` tag guessed from the extension of `file_path` to `additional_tags`,
+    /// along with any from [`Arweave::tag_pattern`] and [`Arweave::content_disposition`]. Also
+    /// returns whether the content type still needs to be inferred from the data and the content
+    /// type to record in the status.
+    fn file_tags(
+        &self,
+        file_path: &Path,
+        mut additional_tags: Option<Vec<Tag<Base64>>>,
+    ) -> Result<(Option<Vec<Tag<Base64>>>, bool, String), Error> {
+        let mut auto_content_tag = true;
+        let mut status_content_type = mime_guess::mime::OCTET_STREAM.to_string();
+
+        if let Some(content_type) = mime_guess::from_path(file_path).first() {
+            status_content_type = content_type.to_string();
+            auto_content_tag = false;
+            let content_tag: Tag<Base64> =
+                Tag::from_utf8_strs("Content-Type", &content_type.to_string())?;
+            additional_tags
+                .get_or_insert_with(Vec::new)
+                .push(content_tag);
+        }
+        if let Some(tag_pattern) = &self.tag_pattern {
+            let pattern_tags = tag_pattern.tags_for_path(file_path)?;
+            additional_tags
+                .get_or_insert_with(Vec::new)
+                .extend(pattern_tags);
+        }
+        if let Some(content_disposition) = &self.content_disposition {
+            let disposition_tags = content_disposition.tags_for_path(file_path)?;
+            additional_tags
+                .get_or_insert_with(Vec::new)
+                .extend(disposition_tags);
+        }
+        Ok((additional_tags, auto_content_tag, status_content_type))
+    }
+
+    /// Creates a transaction for the file at `file_path`, tagged as uploads are, and writes it
+    /// unsigned with its chunks to `dir`, named by the hash of `file_path`. Fetching the anchor
+    /// needs network access but not the keypair, so that the transaction can be signed on
+    /// another machine with [`Arweave::sign_transaction_file`]. Anchors expire after 50 blocks,
+    /// so the signed transaction has to be posted within roughly 100 minutes of its creation.
+    pub async fn write_unsigned_transaction(
+        &self,
+        file_path: PathBuf,
+        dir: &Path,
+        additional_tags: Option<Vec<Tag<Base64>>>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+    ) -> Result<PathBuf, Error> {
+        let (additional_tags, auto_content_tag, _) = self.file_tags(&file_path, additional_tags)?;
+        let transaction = self
+            .create_transaction_from_file_path(
+                file_path.clone(),
+                additional_tags,
+                last_tx,
+                price_terms,
+                auto_content_tag,
+                None,
+            )
+            .await?;
+        let unsigned = UnsignedTransaction::from_transaction(&transaction)?;
+        let path = dir.join(hash_file_path(&file_path)).with_extension("json");
+        fs::write(&path, serde_json::to_string(&unsigned)?).await?;
+        Ok(path)
+    }
+
+    pub async fn read_unsigned_transaction(
+        &self,
+        path: &Path,
+    ) -> Result<UnsignedTransaction, Error> {
+        let data = fs::read_to_string(path).await?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// Sets the owner of an [`UnsignedTransaction`] to the modulus of the keypair and signs it.
+    /// Only the header is signed, so this doesn't need the data or network access.
+    pub fn sign_unsigned_transaction(
+        &self,
+        unsigned: UnsignedTransaction,
+    ) -> Result<SignedTransaction, Error> {
+        let mut transaction = unsigned.transaction;
+        transaction.owner = self.crypto.keypair_modulus()?;
+        Ok(SignedTransaction {
+            transaction: self.sign_transaction(transaction)?,
+            chunks: unsigned.chunks,
+        })
+    }
+
+    /// Signs the unsigned transaction written to `path` by
+    /// [`Arweave::write_unsigned_transaction`] and writes it to `signed_dir` as `<id>.json`, ready
+    /// to be posted with [`Arweave::post_signed_transaction_file`]. Returns the path of the
+    /// signed transaction.
+    pub async fn sign_transaction_file(
+        &self,
+        path: &Path,
+        signed_dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let unsigned = self.read_unsigned_transaction(path).await?;
+        let signed = self.sign_unsigned_transaction(unsigned)?;
+        let signed_path = signed_dir
+            .join(signed.transaction.id.to_string())
+            .with_extension("json");
+        fs::write(&signed_path, serde_json::to_string(&signed)?).await?;
+        Ok(signed_path)
+    }
+
+    /// Gets deep hash, signs and sets signature and id.
+    pub fn sign_transaction(&self, transaction: Transaction) -> Result<Transaction, Error> {
+        sign_transaction_with(&self.crypto, transaction)
+    }
+
+    /// Signs an upload with the next keypair of [`Arweave::key_ring`], setting it as the owner,
+    /// or with [`Arweave::crypto`] if there is no key ring.
+    pub fn sign_upload_transaction(
+        &self,
+        mut transaction: Transaction,
+    ) -> Result<Transaction, Error> {
+        match &self.key_ring {
+            Some(key_ring) => {
+                let crypto = key_ring.next();
+                transaction.owner = crypto.keypair_modulus()?;
+                sign_transaction_with(crypto, transaction)
+            }
+            None => self.sign_transaction(transaction),
+        }
+    }
+
+    /// Recomputes the deep hash of `transaction` and verifies its signature against the public
+    /// key in its `owner` field, e.g. to check a transaction signed elsewhere before posting it.
+    pub fn verify_transaction(&self, transaction: &Transaction) -> Result<(), Error> {
+        if transaction.signature.0.is_empty() || transaction.owner.0.is_empty() {
+            return Err(Error::UnsignedTransaction);
+        }
+        let deep_hash = self.crypto.deep_hash(transaction.to_deep_hash_item()?)?;
+        self.crypto
+            .verify_with_modulus(&transaction.owner.0, &transaction.signature.0, &deep_hash)
+            .map_err(|_| Error::InvalidSignature)
+    }
+
+    /// Checks that the id of `transaction` is the SHA-256 hash of its signature.
+    pub fn verify_transaction_id(&self, transaction: &Transaction) -> Result<(), Error> {
+        let hash = self.crypto.hash_sha256(&transaction.signature.0)?;
+        if transaction.id.0.as_slice() != hash.as_slice() {
+            return Err(Error::InvalidTransactionId(transaction.id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Checks that the data root and data size of `transaction` match those regenerated from
+    /// `data`.
+    pub fn verify_data_root(&self, transaction: &Transaction, data: Vec<u8>) -> Result<(), Error> {
+        let merklized = self.merklize(data)?;
+        if merklized.data_root != transaction.data_root
+            || merklized.data_size != transaction.data_size
+        {
+            return Err(Error::InvalidDataRoot(transaction.data_root.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Checks the id of a [`SignedTransaction`] against its signature and its data root against
+    /// that regenerated from its chunks, so that corrupted transaction files aren't posted.
+    pub fn verify_signed_transaction(&self, signed: &SignedTransaction) -> Result<(), Error> {
+        self.verify_transaction_id(&signed.transaction)?;
+        let mut chunks: Vec<&Chunk> = signed.chunks.iter().collect();
+        chunks.sort_by_key(|c| c.offset);
+        let data = chunks.iter().fold(Vec::new(), |mut data, c| {
+            data.extend_from_slice(&c.chunk.0);
+            data
+        });
+        self.verify_data_root(&signed.transaction, data)
     }
 
     /// Signs transaction with sol_ar service.
@@ -959,10 +3697,13 @@ impl Arweave {
         sol_ar_url: Url,
         from_keypair: &Keypair,
     ) -> Result<(Transaction, SigResponse), Error> {
-        let lamports = std::cmp::max(&transaction.reward * 0, FLOOR);
+        let lamports = std::cmp::max((transaction.reward * 0) as u64, FLOOR);
 
-        let mut sol_tx = create_sol_transaction(solana_url.clone(), from_keypair, lamports).await?;
-        let mut resp = get_sol_ar_signature(
+        let mut sol_tx = self
+            .create_sol_payment(solana_url.clone(), from_keypair, lamports)
+            .await?;
+        let mut resp = get_sol_ar_signature_with_client(
+            &self.client,
             sol_ar_url.clone(),
             transaction.to_deep_hash_item()?,
             sol_tx.clone(),
@@ -983,9 +3724,11 @@ impl Arweave {
                     );
                     retries += 1;
                     sleep(Duration::from_millis(300)).await;
-                    sol_tx =
-                        create_sol_transaction(solana_url.clone(), from_keypair, lamports).await?;
-                    resp = get_sol_ar_signature(
+                    sol_tx = self
+                        .create_sol_payment(solana_url.clone(), from_keypair, lamports)
+                        .await?;
+                    resp = get_sol_ar_signature_with_client(
+                        &self.client,
                         sol_ar_url.clone(),
                         transaction.to_deep_hash_item()?,
                         sol_tx.clone(),
@@ -1008,51 +3751,220 @@ impl Arweave {
         }
     }
 
+    /// Creates the Solana transaction paying `lamports` for a transaction signed with SOL, in
+    /// [`Arweave::sol_payment_token`] if set.
+    async fn create_sol_payment(
+        &self,
+        solana_url: Url,
+        from_keypair: &Keypair,
+        lamports: u64,
+    ) -> Result<String, Error> {
+        match &self.sol_payment_token {
+            Some(token) => {
+                let (_, fiat_per_sol) = self.get_fiat_prices().await?;
+                let cents_per_sol = fiat_per_sol.to_u64_digits().first().copied().unwrap_or(0);
+                let amount = token.base_units_for_lamports(lamports, cents_per_sol);
+                create_spl_token_transaction_with_client(
+                    &self.client,
+                    solana_url,
+                    from_keypair,
+                    token,
+                    amount,
+                )
+                .await
+            }
+            None => {
+                create_sol_transaction_with_client(&self.client, solana_url, from_keypair, lamports)
+                    .await
+            }
+        }
+    }
+
+    /// Uploads the file at `file_path`, tagged with the content type guessed from its extension,
+    /// or inferred from its data if the extension is unknown, unless `content_type` is set.
     pub async fn upload_file_from_path(
         &self,
         file_path: PathBuf,
         log_dir: Option<PathBuf>,
-        mut additional_tags: Option<Vec<Tag<Base64>>>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
         last_tx: Option<Base64>,
         price_terms: (u64, u64),
+        content_type: Option<&str>,
     ) -> Result<Status, Error> {
-        let mut auto_content_tag = true;
-        let mut status_content_type = mime_guess::mime::OCTET_STREAM.to_string();
-
-        if let Some(content_type) = mime_guess::from_path(file_path.clone()).first() {
-            status_content_type = content_type.to_string();
+        let created_at = Utc::now();
+        if let Some(status) = self.block_if_denied(&file_path, &log_dir).await? {
+            return Ok(status);
+        }
+        let (mut additional_tags, mut auto_content_tag, mut status_content_type) =
+            self.file_tags(&file_path, additional_tags)?;
+        if let Some(content_type) = content_type {
+            override_content_type(additional_tags.get_or_insert_with(Vec::new), content_type)?;
             auto_content_tag = false;
-            let content_tag: Tag<Base64> =
-                Tag::from_utf8_strs("Content-Type", &content_type.to_string())?;
-            if let Some(mut tags) = additional_tags {
-                tags.push(content_tag);
-                additional_tags = Some(tags);
-            } else {
-                additional_tags = Some(vec![content_tag]);
-            }
+            status_content_type = content_type.to_string();
+        }
+        if let Some(status) = self
+            .tag_content_hash(&file_path, &log_dir, &mut additional_tags)
+            .await?
+        {
+            return Ok(status);
         }
 
-        let transaction = self
-            .create_transaction_from_file_path(
+        let streams_file_data = self.streams_file_data();
+        let transaction = if streams_file_data {
+            self.create_transaction_from_file_chunks(
+                &file_path,
+                additional_tags,
+                last_tx,
+                price_terms,
+                auto_content_tag,
+            )
+            .await?
+        } else {
+            self.create_transaction_from_file_path(
                 file_path.clone(),
                 additional_tags,
                 last_tx,
                 price_terms,
                 auto_content_tag,
+                None,
             )
-            .await?;
-        let signed_transaction = self.sign_transaction(transaction)?;
-        let (id, reward) = self.post_transaction(&signed_transaction).await?;
-
-        let status = Status {
-            id,
-            reward,
-            file_path: Some(file_path),
-            content_type: status_content_type,
-            ..Default::default()
+            .await?
         };
+        let chunks_file = streams_file_data.then(|| file_path.clone());
+        self.post_upload_transaction(
+            transaction,
+            file_path,
+            status_content_type,
+            log_dir,
+            created_at,
+            chunks_file,
+        )
+        .await
+    }
 
-        if let Some(log_dir) = log_dir {
+    /// Uploads the object at `url`, e.g. from [`source::UploadSource::urls`]. The object is
+    /// streamed to a temporary file and uploaded from it like a local file, so that with
+    /// [`Arweave::chunk_budget`] set it is never held in memory as a whole. The status is logged
+    /// with the url as its `file_path`, which the content type and tags are also derived from.
+    pub async fn upload_file_from_url(
+        &self,
+        url: Url,
+        log_dir: Option<PathBuf>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
+        last_tx: Option<Base64>,
+        price_terms: (u64, u64),
+    ) -> Result<Status, Error> {
+        let created_at = Utc::now();
+        let file_path = PathBuf::from(url.as_str());
+        let (additional_tags, auto_content_tag, status_content_type) =
+            self.file_tags(Path::new(url.path()), additional_tags)?;
+
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        // Removed with the object when dropped, after the upload.
+        let temp_dir = TempDir::from_str(&format!("{}/", std::env::temp_dir().display())).await?;
+        let object_path = temp_dir.0.join("object");
+        rt::download_to_file(response, &object_path).await?;
+
+        let streams_file_data = self.streams_file_data();
+        let transaction = if streams_file_data {
+            self.create_transaction_from_file_chunks(
+                &object_path,
+                additional_tags,
+                last_tx,
+                price_terms,
+                auto_content_tag,
+            )
+            .await?
+        } else {
+            let data = fs::read(&object_path).await?;
+            let mut tags = additional_tags.unwrap_or_default();
+            let (data, auto_content_tag) = self.compress_data(data, &mut tags, auto_content_tag)?;
+            self.tag_ipfs_cid(&data, &mut tags)?;
+            self.create_transaction(data, Some(tags), last_tx, price_terms, auto_content_tag)
+                .await?
+        };
+        self.post_upload_transaction(
+            transaction,
+            file_path,
+            status_content_type,
+            log_dir,
+            created_at,
+            streams_file_data.then_some(object_path),
+        )
+        .await
+    }
+
+    /// Signs and posts `transaction` for the upload of `file_path`, writing its status to
+    /// `log_dir` if set. Failed posts are returned as [`StatusCode::Failed`] statuses. With
+    /// `chunks_file`, the transaction has no data and its chunks are read from that file.
+    async fn post_upload_transaction(
+        &self,
+        transaction: Transaction,
+        file_path: PathBuf,
+        status_content_type: String,
+        log_dir: Option<PathBuf>,
+        created_at: DateTime<Utc>,
+        chunks_file: Option<PathBuf>,
+    ) -> Result<Status, Error> {
+        let signed_transaction = self.sign_upload_transaction(transaction)?;
+        let signed_at = Utc::now();
+        let persisted_path = self.persist_signed_transaction(&signed_transaction).await?;
+        let mut status = Status {
+            id: signed_transaction.id.clone(),
+            status: StatusCode::Signed,
+            reward: signed_transaction.reward,
+            file_path: Some(file_path),
+            content_type: status_content_type,
+            created_at,
+            signed_at: Some(signed_at),
+            tags: signed_transaction.tags.iter().map(Tag::from).collect(),
+            data_root: Some(signed_transaction.data_root.clone()),
+            data_size: Some(signed_transaction.data_size),
+            ..Default::default()
+        };
+        if let Some(log_dir) = &log_dir {
+            self.write_status(status.clone(), log_dir.clone(), None)
+                .await?;
+        }
+        status.first_submitted = Some(Utc::now());
+        self.send_progress(UploadProgress::Started {
+            id: signed_transaction.id.clone(),
+            files: 1,
+            data_size: signed_transaction.data_size,
+        });
+        let posted = match &chunks_file {
+            Some(chunks_file) => {
+                let chunks_buffer = self.chunk_budget.as_ref().map_or(1, |b| b.max_chunks);
+                self.post_transaction_from_file(&signed_transaction, chunks_file, chunks_buffer)
+                    .await
+            }
+            None => self.post_transaction(&signed_transaction).await,
+        };
+        self.send_progress(UploadProgress::Finished {
+            id: signed_transaction.id.clone(),
+            posted: posted.is_ok(),
+        });
+        match posted {
+            Ok(_) => {
+                status.status = StatusCode::Submitted;
+                status.record_post(Utc::now(), signed_transaction.data_size);
+                if let Some(persisted_path) = persisted_path {
+                    fs::remove_file(persisted_path).await?;
+                }
+                status.seeded_peers = self.seed_chunks(&signed_transaction).await;
+                #[cfg(feature = "metrics")]
+                metrics::FILES_UPLOADED.inc();
+            }
+            Err(e) => {
+                warn!(id = %status.id, "failed to post transaction: {}", e);
+                status.status = StatusCode::Failed {
+                    reason: e.to_string(),
+                };
+                status.first_submitted = None;
+            }
+        }
+
+        if let Some(log_dir) = log_dir {
             self.write_status(status.clone(), log_dir, None).await?;
         }
         Ok(status)
@@ -1062,27 +3974,24 @@ impl Arweave {
         &self,
         file_path: PathBuf,
         log_dir: Option<PathBuf>,
-        mut additional_tags: Option<Vec<Tag<Base64>>>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
         last_tx: Option<Base64>,
         price_terms: (u64, u64),
         solana_url: Url,
         sol_ar_url: Url,
         from_keypair: &Keypair,
     ) -> Result<Status, Error> {
-        let mut auto_content_tag = true;
-        let mut status_content_type = mime_guess::mime::OCTET_STREAM.to_string();
-
-        if let Some(content_type) = mime_guess::from_path(file_path.clone()).first() {
-            status_content_type = content_type.to_string();
-            auto_content_tag = false;
-            let content_tag: Tag<Base64> =
-                Tag::from_utf8_strs("Content-Type", &content_type.to_string())?;
-            if let Some(mut tags) = additional_tags {
-                tags.push(content_tag);
-                additional_tags = Some(tags);
-            } else {
-                additional_tags = Some(vec![content_tag]);
-            }
+        let created_at = Utc::now();
+        if let Some(status) = self.block_if_denied(&file_path, &log_dir).await? {
+            return Ok(status);
+        }
+        let (mut additional_tags, auto_content_tag, status_content_type) =
+            self.file_tags(&file_path, additional_tags)?;
+        if let Some(status) = self
+            .tag_content_hash(&file_path, &log_dir, &mut additional_tags)
+            .await?
+        {
+            return Ok(status);
         }
 
         let transaction = self
@@ -1092,6 +4001,7 @@ impl Arweave {
                 last_tx,
                 price_terms,
                 auto_content_tag,
+                None,
             )
             .await?;
 
@@ -1099,15 +4009,51 @@ impl Arweave {
             .sign_transaction_with_sol(transaction, solana_url, sol_ar_url, from_keypair)
             .await?;
 
-        let (id, reward) = self.post_transaction(&signed_transaction).await?;
-
         let mut status = Status {
             file_path: Some(file_path),
             content_type: status_content_type,
-            id,
-            reward,
+            id: signed_transaction.id.clone(),
+            status: StatusCode::Signed,
+            reward: signed_transaction.reward,
+            created_at,
+            signed_at: Some(Utc::now()),
+            tags: signed_transaction.tags.iter().map(Tag::from).collect(),
+            data_root: Some(signed_transaction.data_root.clone()),
+            data_size: Some(signed_transaction.data_size),
             ..Default::default()
         };
+        if let Some(log_dir) = &log_dir {
+            status.sol_sig = Some(sig_response.clone());
+            self.write_status(status.clone(), log_dir.clone(), None)
+                .await?;
+        }
+        status.first_submitted = Some(Utc::now());
+        self.send_progress(UploadProgress::Started {
+            id: signed_transaction.id.clone(),
+            files: 1,
+            data_size: signed_transaction.data_size,
+        });
+        let posted = self.post_transaction(&signed_transaction).await;
+        self.send_progress(UploadProgress::Finished {
+            id: signed_transaction.id.clone(),
+            posted: posted.is_ok(),
+        });
+        match posted {
+            Ok(_) => {
+                status.status = StatusCode::Submitted;
+                status.record_post(Utc::now(), signed_transaction.data_size);
+                status.seeded_peers = self.seed_chunks(&signed_transaction).await;
+                #[cfg(feature = "metrics")]
+                metrics::FILES_UPLOADED.inc();
+            }
+            Err(e) => {
+                warn!(id = %status.id, "failed to post transaction: {}", e);
+                status.status = StatusCode::Failed {
+                    reason: e.to_string(),
+                };
+                status.first_submitted = None;
+            }
+        }
 
         if let Some(log_dir) = log_dir {
             status.sol_sig = Some(sig_response);
@@ -1116,6 +4062,170 @@ impl Arweave {
         Ok(status)
     }
 
+    /// Runs the [`FileScanner`], if there is one, on `file_path`.
+    pub async fn scan_file(&self, file_path: &Path) -> Result<ScanVerdict, Error> {
+        match &self.scanner {
+            Some(scanner) => scanner.scan(file_path).await,
+            None => Ok(ScanVerdict::Allow),
+        }
+    }
+
+    /// Returns a [`StatusCode::Blocked`] status, written to `log_dir` if provided, if the
+    /// [`FileScanner`] denies `file_path`.
+    async fn block_if_denied(
+        &self,
+        file_path: &PathBuf,
+        log_dir: &Option<PathBuf>,
+    ) -> Result<Option<Status>, Error> {
+        let reason = match self.scan_file(file_path).await? {
+            ScanVerdict::Allow => return Ok(None),
+            ScanVerdict::Deny(reason) => reason,
+        };
+        debug!(file_path = %file_path.display(), %reason, "upload blocked");
+        self.send_progress(UploadProgress::Skipped { files: 1 });
+        let status = Status {
+            status: StatusCode::Blocked,
+            file_path: Some(file_path.clone()),
+            blocked_reason: Some(reason),
+            ..Default::default()
+        };
+        if let Some(log_dir) = log_dir {
+            self.write_status(status.clone(), log_dir.clone(), None)
+                .await?;
+        }
+        Ok(Some(status))
+    }
+
+    /// With [`Arweave::chain_dedup`], returns the status of an earlier upload of the contents of
+    /// `file_path` if [`Arweave::find_uploaded`] finds one, and otherwise adds their hash to
+    /// `tags` as the [`CONTENT_HASH_TAG`].
+    async fn tag_content_hash(
+        &self,
+        file_path: &PathBuf,
+        log_dir: &Option<PathBuf>,
+        tags: &mut Option<Vec<Tag<Base64>>>,
+    ) -> Result<Option<Status>, Error> {
+        if !self.chain_dedup {
+            return Ok(None);
+        }
+        let content_hash = rt::hash_file(file_path).await?.to_string();
+        if let Some(status) = self
+            .find_uploaded(file_path, &content_hash, log_dir)
+            .await?
+        {
+            self.send_progress(UploadProgress::Skipped { files: 1 });
+            return Ok(Some(status));
+        }
+        tags.get_or_insert_with(Vec::new)
+            .push(Tag::from_utf8_strs(CONTENT_HASH_TAG, &content_hash)?);
+        Ok(None)
+    }
+
+    /// Splits `paths` into those whose contents haven't been uploaded and the statuses of those
+    /// that have, as found by [`Arweave::find_uploaded`], for files uploaded in bundles with
+    /// [`Arweave::chain_dedup`]. Bundled data items are tagged with the hashes of their contents
+    /// when they are created.
+    pub async fn filter_uploaded_paths(
+        &self,
+        paths: Vec<PathBuf>,
+        log_dir: Option<PathBuf>,
+    ) -> Result<(Vec<PathBuf>, Vec<Status>), Error> {
+        let mut not_uploaded = Vec::new();
+        let mut uploaded = Vec::new();
+        for path in paths {
+            let content_hash = rt::hash_file(&path).await?.to_string();
+            match self.find_uploaded(&path, &content_hash, &log_dir).await? {
+                Some(status) => uploaded.push(status),
+                None => not_uploaded.push(path),
+            }
+        }
+        if !uploaded.is_empty() {
+            self.send_progress(UploadProgress::Skipped {
+                files: uploaded.len() as u64,
+            });
+        }
+        Ok((not_uploaded, uploaded))
+    }
+
+    /// Returns a status for an upload of the contents of `file_path`, whose hash is
+    /// `content_hash`, if there is one. The status in `log_dir` is used if it was tagged with the
+    /// same hash and doesn't [`StatusCode::needs_reupload`], since GraphQL only finds
+    /// transactions some time after they are posted. Otherwise the latest transaction tagged
+    /// with the hash is looked up with [`Arweave::find_transaction_by_content_hash`] and its
+    /// status written to `log_dir` if provided.
+    pub async fn find_uploaded(
+        &self,
+        file_path: &PathBuf,
+        content_hash: &str,
+        log_dir: &Option<PathBuf>,
+    ) -> Result<Option<Status>, Error> {
+        if let Some(log_dir) = log_dir {
+            if let Ok(status) = self.read_status(file_path.clone(), log_dir.clone()).await {
+                let same_contents = status
+                    .tags
+                    .iter()
+                    .any(|t| t.name == CONTENT_HASH_TAG && t.value == content_hash);
+                if same_contents && !status.status.needs_reupload() {
+                    debug!(file_path = %file_path.display(), id = %status.id, "already uploaded");
+                    return Ok(Some(status));
+                }
+            }
+        }
+
+        let transaction = match self.find_transaction_by_content_hash(content_hash).await? {
+            Some(transaction) => transaction,
+            None => return Ok(None),
+        };
+        debug!(file_path = %file_path.display(), id = %transaction.id, "already uploaded");
+        let content_type = transaction
+            .tags
+            .iter()
+            .find(|t| t.name == "Content-Type")
+            .map(|t| t.value.clone())
+            .unwrap_or_else(|| mime_guess::mime::OCTET_STREAM.to_string());
+        let status = Status {
+            id: transaction.id,
+            status: if transaction.block.is_some() {
+                StatusCode::Confirmed
+            } else {
+                StatusCode::Pending
+            },
+            file_path: Some(file_path.clone()),
+            content_type,
+            tags: transaction.tags,
+            data_size: Some(transaction.data_size),
+            ..Default::default()
+        };
+        if let Some(log_dir) = log_dir {
+            self.write_status(status.clone(), log_dir.clone(), None)
+                .await?;
+        }
+        Ok(Some(status))
+    }
+
+    /// Splits `paths_chunk` into the paths allowed by the [`FileScanner`], with their combined
+    /// size, and [`StatusCode::Blocked`] statuses for the rest.
+    pub async fn scan_paths_chunk(
+        &self,
+        paths_chunk: PathsChunk,
+    ) -> Result<(PathsChunk, Vec<Status>), Error> {
+        if self.scanner.is_none() {
+            return Ok((paths_chunk, Vec::new()));
+        }
+        let mut allowed = PathsChunk(Vec::new(), 0);
+        let mut blocked = Vec::new();
+        for path in paths_chunk.0 {
+            match self.block_if_denied(&path, &None).await? {
+                Some(status) => blocked.push(status),
+                None => {
+                    allowed.1 += path.metadata()?.len();
+                    allowed.0.push(path);
+                }
+            }
+        }
+        Ok((allowed, blocked))
+    }
+
     /// Uploads files from an iterator of paths.
     ///
     /// Optionally logs Status objects to `log_dir`, if provided and optionally adds tags to each
@@ -1134,17 +4244,248 @@ impl Arweave {
     {
         let statuses = if let Some(tags_iter) = tags_iter {
             try_join_all(paths_iter.zip(tags_iter).map(|(p, t)| {
-                self.upload_file_from_path(p, log_dir.clone(), t, last_tx.clone(), price_terms)
+                self.upload_file_from_path(
+                    p,
+                    log_dir.clone(),
+                    t,
+                    last_tx.clone(),
+                    price_terms,
+                    None,
+                )
             }))
         } else {
             try_join_all(paths_iter.map(|p| {
-                self.upload_file_from_path(p, log_dir.clone(), None, last_tx.clone(), price_terms)
+                self.upload_file_from_path(
+                    p,
+                    log_dir.clone(),
+                    None,
+                    last_tx.clone(),
+                    price_terms,
+                    None,
+                )
             }))
         }
         .await?;
         Ok(statuses)
     }
 
+    /// Watches `dir` and uploads each new or modified file once it hasn't been written to for
+    /// `debounce`, or after [`WATCH_MAX_DEBOUNCES`] periods of it if it keeps being written to,
+    /// writing statuses to `log_dir` and passing them to `on_status`. Files that already have a
+    /// status in `log_dir` are only uploaded again if they were modified after the status was
+    /// created, and the statuses of uploaded files are updated
+    /// every [`WATCH_STATUS_POLL_SECS`] until they are confirmed. Errors are logged and retried,
+    /// so this runs until the watcher is dropped.
+    pub async fn watch_and_upload<F>(
+        &self,
+        dir: PathBuf,
+        log_dir: PathBuf,
+        tags: Option<Vec<Tag<Base64>>>,
+        reward_mult: f32,
+        debounce: Duration,
+        mut on_status: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&Status),
+    {
+        // Events carry paths under the watched directory as given, so both are normalized for
+        // status writes to be told apart from files to upload.
+        let dir = dir.canonicalize()?;
+        fs::create_dir_all(&log_dir).await?;
+        let log_dir = log_dir.canonicalize()?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<notify::Event>| {
+                let _ = tx.send(event);
+            },
+            notify::Config::default(),
+        )?;
+        watcher.watch(&dir, RecursiveMode::Recursive)?;
+
+        // Keyed by path, the times a file was first and last written to since it was uploaded.
+        let mut pending: HashMap<PathBuf, (std::time::Instant, std::time::Instant)> =
+            HashMap::new();
+        let mut unconfirmed: Vec<PathBuf> = Vec::new();
+        let mut last_poll = std::time::Instant::now();
+        let max_wait = debounce * WATCH_MAX_DEBOUNCES;
+        // An interval rather than a sleep per loop, so that a steady stream of events can't keep
+        // pending files from being checked.
+        let mut ticks = tokio::time::interval((debounce / 2).max(Duration::from_millis(1)));
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Some(Ok(event)) => {
+                        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                            for path in event.paths {
+                                // Status writes would otherwise trigger uploads of their own.
+                                if path.is_file() && !path.starts_with(&log_dir) {
+                                    let now = std::time::Instant::now();
+                                    pending.entry(path).or_insert((now, now)).1 = now;
+                                }
+                            }
+                        }
+                        continue;
+                    }
+                    Some(Err(e)) => {
+                        warn!("failed to watch {}: {}", dir.display(), e);
+                        continue;
+                    }
+                    None => return Ok(()),
+                },
+                _ = ticks.tick() => {}
+            }
+
+            let ready: Vec<PathBuf> = pending
+                .iter()
+                .filter(|(_, (first_written_at, written_at))| {
+                    written_at.elapsed() >= debounce || first_written_at.elapsed() >= max_wait
+                })
+                .map(|(path, _)| path.clone())
+                .collect();
+            if !ready.is_empty() {
+                let price_terms = match self.get_price_terms(reward_mult).await {
+                    Ok(price_terms) => price_terms,
+                    Err(e) => {
+                        warn!("failed to get price terms: {}", e);
+                        continue;
+                    }
+                };
+                for path in ready {
+                    pending.remove(&path);
+                    if let Ok(status) = self.read_status(path.clone(), log_dir.clone()).await {
+                        let modified = path.metadata().and_then(|m| m.modified());
+                        if !matches!(modified, Ok(modified) if DateTime::<Utc>::from(modified) > status.created_at)
+                        {
+                            continue;
+                        }
+                    }
+                    match self
+                        .upload_file_from_path(
+                            path.clone(),
+                            Some(log_dir.clone()),
+                            tags.clone(),
+                            None,
+                            price_terms,
+                            None,
+                        )
+                        .await
+                    {
+                        Ok(status) => {
+                            on_status(&status);
+                            if !unconfirmed.contains(&path) {
+                                unconfirmed.push(path);
+                            }
+                        }
+                        Err(e) => warn!(file_path = %path.display(), "failed to upload: {}", e),
+                    }
+                }
+            }
+
+            if last_poll.elapsed() >= Duration::from_secs(WATCH_STATUS_POLL_SECS) {
+                last_poll = std::time::Instant::now();
+                let mut still_unconfirmed = Vec::new();
+                for path in unconfirmed {
+                    match self.update_status(path.clone(), log_dir.clone()).await {
+                        Ok(status) if status.status == StatusCode::Confirmed => on_status(&status),
+                        Ok(status) => {
+                            on_status(&status);
+                            still_unconfirmed.push(path);
+                        }
+                        Err(e) => {
+                            warn!(file_path = %path.display(), "failed to update status: {}", e);
+                            still_unconfirmed.push(path);
+                        }
+                    }
+                }
+                unconfirmed = still_unconfirmed;
+            }
+        }
+    }
+
+    /// Groups file paths by the BLAKE3 hash of their contents, preserving the order in which
+    /// each hash is first seen.
+    pub fn group_file_paths_by_hash<IP>(&self, paths_iter: IP) -> Result<Vec<PathsGroup>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        let hashed_paths = paths_iter
+            .collect::<Vec<PathBuf>>()
+            .into_par_iter()
+            .map(|p| Ok((hash_file_contents(&p)?, p)))
+            .collect::<Result<Vec<(String, PathBuf)>, std::io::Error>>()?;
+
+        let mut positions = HashMap::<String, usize>::new();
+        let mut paths_groups = Vec::<PathsGroup>::new();
+        for (hash, path) in hashed_paths {
+            if let Some(&position) = positions.get(&hash) {
+                paths_groups[position].1.push(path);
+            } else {
+                positions.insert(hash.clone(), paths_groups.len());
+                paths_groups.push(PathsGroup(hash, vec![path]));
+            }
+        }
+        Ok(paths_groups)
+    }
+
+    /// Uploads the first file of a [`PathsGroup`] and records the resulting transaction id in
+    /// statuses for the rest of the paths in the group.
+    ///
+    /// If `log_dir` is provided, the status of the uploaded file is also written to a dedup index
+    /// in `log_dir/dedup/`, keyed by the hash of the file contents. When the index already has an
+    /// entry for the hash, nothing is uploaded and the existing transaction id is recorded for
+    /// all paths in the group.
+    pub async fn upload_paths_group(
+        &self,
+        paths_group: PathsGroup,
+        log_dir: Option<PathBuf>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
+        price_terms: (u64, u64),
+    ) -> Result<Vec<Status>, Error> {
+        let PathsGroup(hash, mut paths) = paths_group;
+        let index_path = log_dir
+            .as_ref()
+            .map(|d| d.join("dedup").join(&hash).with_extension("json"));
+
+        let (original, mut statuses) = match &index_path {
+            Some(index_path) if index_path.exists() => {
+                let data = fs::read_to_string(index_path).await?;
+                (serde_json::from_str::<Status>(&data)?, Vec::new())
+            }
+            _ => {
+                let status = self
+                    .upload_file_from_path(
+                        paths.remove(0),
+                        log_dir.clone(),
+                        additional_tags,
+                        None,
+                        price_terms,
+                        None,
+                    )
+                    .await?;
+                if let Some(index_path) = &index_path {
+                    fs::create_dir_all(index_path.parent().unwrap()).await?;
+                    fs::write(index_path, serde_json::to_string(&status)?).await?;
+                }
+                (status.clone(), vec![status])
+            }
+        };
+
+        for path in paths {
+            let status = Status {
+                file_path: Some(path),
+                reward: 0,
+                last_modified: Utc::now(),
+                ..original.clone()
+            };
+            if let Some(log_dir) = &log_dir {
+                self.write_status(status.clone(), log_dir.clone(), None)
+                    .await?;
+            }
+            statuses.push(status);
+        }
+        Ok(statuses)
+    }
+
     //-------------------------
     // Status
     //-------------------------
@@ -1158,6 +4499,79 @@ impl Arweave {
         Ok(log_dir)
     }
 
+    /// Returns ids of pending transactions owned by the
+    /// [`Arweave::signing_wallet_addresses`] that were not created from `log_dir`, found through
+    /// GraphQL so that the whole mempool doesn't have to be fetched. Transactions posted to the
+    /// network by other tooling sharing a wallet can interfere with anchors, so this can be used
+    /// as a pre-flight check before uploading.
+    pub async fn get_unknown_pending_ids(
+        &self,
+        log_dir: Option<PathBuf>,
+    ) -> Result<Vec<Base64>, Error> {
+        let known_ids = if let Some(log_dir) = log_dir {
+            self.read_log_dir_ids(log_dir).await?
+        } else {
+            Vec::new()
+        };
+        let wallet_addresses: Vec<String> = self
+            .signing_wallet_addresses()?
+            .iter()
+            .map(Base64::to_string)
+            .collect();
+
+        // Pending transactions come first, so pages are only read until a mined one shows up.
+        let mut unknown_ids = Vec::new();
+        let mut after_cursor = None;
+        loop {
+            let query = pending_transactions_query(&wallet_addresses, after_cursor.as_deref());
+            let body = self.gateway().query_graphql(self, &query).await?;
+            let page = parse_transaction_page(&body)?;
+            let has_mined = page.transactions.iter().any(|t| t.block.is_some());
+            after_cursor = page.next_cursor();
+            unknown_ids.extend(
+                page.transactions
+                    .into_iter()
+                    .filter(|t| t.block.is_none() && !known_ids.contains(&t.id))
+                    .map(|t| t.id),
+            );
+            if has_mined || after_cursor.is_none() {
+                break;
+            }
+        }
+        Ok(unknown_ids)
+    }
+
+    /// Returns whether any of the latest transactions of the wallet are pending, found through
+    /// GraphQL so that the whole mempool doesn't have to be fetched.
+    #[cfg(feature = "session-wallet")]
+    async fn has_pending_transactions(&self) -> Result<bool, Error> {
+        let page = self.get_wallet_transactions(None, None).await?;
+        Ok(page.transactions.iter().any(|t| t.block.is_none()))
+    }
+
+    /// Polls the status of a transaction every `poll_secs` seconds until it is confirmed. Returns
+    /// [`Error::StatusNotFound`] if the transaction can't be found after
+    /// [`CONFIRMATION_NOT_FOUND_RETRIES`] polls.
+    pub async fn wait_for_confirmation(
+        &self,
+        id: &Base64,
+        poll_secs: u64,
+    ) -> Result<Status, Error> {
+        let mut not_found = 0;
+        loop {
+            let status = self.get_status(id).await?;
+            match status.status {
+                StatusCode::Confirmed => return Ok(status),
+                StatusCode::NotFound if not_found >= CONFIRMATION_NOT_FOUND_RETRIES => {
+                    return Err(Error::StatusNotFound)
+                }
+                StatusCode::NotFound => not_found += 1,
+                _ => {}
+            }
+            sleep(Duration::from_secs(poll_secs)).await;
+        }
+    }
+
     /// Filters saved Status objects by status and/or number of confirmations. Return
     /// all statuses if no status codes or maximum confirmations are provided.
     ///
@@ -1171,6 +4585,21 @@ impl Arweave {
         statuses: Option<Vec<StatusCode>>,
         max_confirms: Option<u64>,
     ) -> Result<Vec<S>, Error>
+    where
+        S: Filterable,
+    {
+        self.filter_statuses_with(all_statuses, statuses, max_confirms, None)
+    }
+
+    /// Filters statuses like [`Arweave::filter_statuses`], and with a [`StatusFilter`] also by
+    /// their tags and file paths.
+    pub fn filter_statuses_with<S>(
+        &self,
+        all_statuses: Vec<S>,
+        statuses: Option<Vec<StatusCode>>,
+        max_confirms: Option<u64>,
+        filter: Option<&StatusFilter>,
+    ) -> Result<Vec<S>, Error>
     where
         S: Filterable,
     {
@@ -1187,7 +4616,8 @@ impl Arweave {
                         } else {
                             0
                         };
-                        (&statuses.iter().any(|c| c == s.status)) & (confirms <= max_confirms)
+                        (&statuses.iter().any(|c| c.is_kind_of(s.status)))
+                            & (confirms <= max_confirms)
                     })
                     .collect()
             } else {
@@ -1195,7 +4625,7 @@ impl Arweave {
                     .into_iter()
                     .filter(|s| {
                         let s = s.get_filter_elements();
-                        statuses.iter().any(|c| c == s.status)
+                        statuses.iter().any(|c| c.is_kind_of(s.status))
                     })
                     .collect()
             }
@@ -1218,37 +4648,30 @@ impl Arweave {
             }
         };
 
+        let filtered = match filter {
+            Some(filter) => filtered
+                .into_iter()
+                .filter(|s| filter.matches(&s.get_filter_elements()))
+                .collect(),
+            None => filtered,
+        };
         Ok(filtered)
     }
 
     /// Gets status from network.
     pub async fn get_status(&self, id: &Base64) -> Result<Status, Error> {
-        let url = self.base_url.join(&format!("tx/{}/status", id))?;
-        let resp = reqwest::get(url).await?;
-        let mut status = Status {
+        let tx_status = self.get_tx_status(id).await?;
+        Ok(Status {
             id: id.clone(),
+            status: tx_status.status_code(),
+            raw_status: tx_status.raw_status(),
             ..Status::default()
-        };
+        })
+    }
 
-        match resp.status() {
-            ResponseStatusCode::OK => {
-                let resp_string = resp.text().await?;
-                if &resp_string == &String::from("Pending") {
-                    status.status = StatusCode::Pending;
-                } else {
-                    status.raw_status = Some(serde_json::from_str(&resp_string)?);
-                    status.status = StatusCode::Confirmed;
-                }
-            }
-            ResponseStatusCode::ACCEPTED => {
-                status.status = StatusCode::Pending;
-            }
-            ResponseStatusCode::NOT_FOUND => {
-                status.status = StatusCode::NotFound;
-            }
-            _ => unreachable!(),
-        }
-        Ok(status)
+    /// Gets the [`TxStatusResponse`] of the transaction with `id` from the network.
+    pub async fn get_tx_status(&self, id: &Base64) -> Result<TxStatusResponse, Error> {
+        self.gateway().get_tx_status(self, id).await
     }
 
     pub async fn read_bundle_status(&self, file_path: PathBuf) -> Result<BundleStatus, Error> {
@@ -1257,7 +4680,8 @@ impl Arweave {
         Ok(status)
     }
 
-    // Reads statuses from a list of paths.
+    /// Reads the bundle statuses that [`Arweave::write_bundle_status`] wrote to files in
+    /// `log_dir`.
     pub async fn read_bundle_statuses(&self, log_dir: &str) -> Result<Vec<BundleStatus>, Error> {
         let paths_iter = glob(&format!("{}*.json", log_dir))?
             .filter_map(Result::ok)
@@ -1274,11 +4698,102 @@ impl Arweave {
         IP: Iterator<Item = PathBuf> + Send,
     {
         let statuses = self.read_statuses(paths_iter, log_dir).await?;
-        let status_counts: HashMap<StatusCode, u32> =
+        self.summarize_statuses(statuses)
+    }
+
+    /// Returns the same summary as [`Arweave::status_summary`] for every [`Status`] in `log_dir`.
+    pub async fn status_summary_all(&self, log_dir: PathBuf) -> Result<String, Error> {
+        let statuses = self.read_all_statuses(log_dir).await?;
+        self.summarize_statuses(statuses)
+    }
+
+    /// Returns a [`StatusReport`] of the statuses of `paths_iter` in `log_dir`, or of every
+    /// status in `log_dir` if no paths are given, linking to each file on `gateway_url`.
+    pub async fn status_report<IP>(
+        &self,
+        paths_iter: Option<IP>,
+        log_dir: PathBuf,
+        gateway_url: Url,
+    ) -> Result<StatusReport, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        let statuses = match paths_iter {
+            Some(paths_iter) => self.read_statuses(paths_iter, log_dir).await?,
+            None => self.read_all_statuses(log_dir).await?,
+        };
+        StatusReport::from_statuses(&statuses, gateway_url)
+    }
+
+    /// Projects daily and weekly spend from the uploads recorded in `log_dir`, at the rate bytes
+    /// were uploaded over the last `window_days` and the rewards paid for the most recent uploads.
+    /// Returns `None` if no paid uploads have been recorded.
+    pub async fn forecast_spend(
+        &self,
+        log_dir: PathBuf,
+        window_days: u32,
+    ) -> Result<Option<SpendForecast>, Error> {
+        let mut samples: Vec<SpendSample> = self
+            .read_all_statuses(log_dir.clone())
+            .await?
+            .iter()
+            .filter_map(SpendSample::from_status)
+            .collect();
+        samples.extend(
+            self.read_bundle_statuses(&log_dir.join("").to_string_lossy())
+                .await?
+                .iter()
+                .map(SpendSample::from),
+        );
+
+        let forecast = SpendForecast::from_samples(&samples, Utc::now(), window_days);
+        #[cfg(feature = "metrics")]
+        if let Some(forecast) = &forecast {
+            metrics::record_forecast(forecast);
+        }
+        Ok(forecast)
+    }
+
+    /// Returns a [`BatchDigest`] of the statuses and bundle statuses in `log_dir`.
+    pub async fn batch_digest(&self, log_dir: PathBuf) -> Result<BatchDigest, Error> {
+        let statuses = self.read_all_statuses(log_dir.clone()).await?;
+        let bundle_statuses = self
+            .read_bundle_statuses(&log_dir.join("").to_string_lossy())
+            .await?;
+        Ok(BatchDigest::from_statuses(
+            log_dir,
+            &statuses,
+            &bundle_statuses,
+        ))
+    }
+
+    fn summarize_statuses(&self, statuses: Vec<Status>) -> Result<String, Error> {
+        let latencies: Vec<chrono::Duration> = statuses
+            .iter()
+            .filter_map(|s| s.confirmation_latency())
+            .collect();
+        let mean_secs = |durations: Vec<chrono::Duration>| {
+            (!durations.is_empty()).then(|| {
+                durations
+                    .iter()
+                    .map(|d| d.num_milliseconds() as f64 / 1000.0)
+                    .sum::<f64>()
+                    / durations.len() as f64
+            })
+        };
+        let mean_preparation = mean_secs(
+            statuses
+                .iter()
+                .filter_map(|s| s.preparation_time())
+                .collect(),
+        );
+        let mean_posting = mean_secs(statuses.iter().filter_map(|s| s.posting_time()).collect());
+        let throughputs: Vec<f64> = statuses.iter().filter_map(|s| s.bytes_per_sec).collect();
+        let status_counts: HashMap<String, u32> =
             statuses
                 .into_iter()
                 .fold(HashMap::new(), |mut map, status| {
-                    *map.entry(status.status).or_insert(0) += 1;
+                    *map.entry(status.status.to_string()).or_insert(0) += 1;
                     map
                 });
 
@@ -1291,8 +4806,14 @@ impl Arweave {
             StatusCode::Pending,
             StatusCode::NotFound,
             StatusCode::Confirmed,
+            StatusCode::Blocked,
+            StatusCode::Failed {
+                reason: String::new(),
+            },
+            StatusCode::Dropped,
+            StatusCode::Signed,
         ] {
-            let v = status_counts.get(&k).unwrap_or(&0);
+            let v = status_counts.get(&k.to_string()).unwrap_or(&0);
             writeln!(output, " {:<16} {:>10}", &k.to_string(), v)?;
             total += v;
         }
@@ -1300,24 +4821,326 @@ impl Arweave {
         writeln!(output, "{:-<29}", "")?;
         writeln!(output, " {:<15}  {:>10}", "Total", total)?;
 
+        if !latencies.is_empty() {
+            let mean =
+                latencies.iter().map(|l| l.num_seconds()).sum::<i64>() / latencies.len() as i64;
+            let max = latencies.iter().map(|l| l.num_seconds()).max().unwrap();
+            writeln!(output, "{:-<29}", "")?;
+            writeln!(output, " {:<15}  {:>9}s", "Avg to confirm", mean)?;
+            writeln!(output, " {:<15}  {:>9}s", "Max to confirm", max)?;
+        }
+
+        if let (Some(preparation), Some(posting)) = (mean_preparation, mean_posting) {
+            writeln!(output, "{:-<29}", "")?;
+            writeln!(output, " {:<15}  {:>9.1}s", "Avg to prepare", preparation)?;
+            writeln!(output, " {:<15}  {:>9.1}s", "Avg to post", posting)?;
+            writeln!(
+                output,
+                " {:<15}  {:>10.0}",
+                "Avg bytes/sec",
+                throughputs.iter().sum::<f64>() / throughputs.len().max(1) as f64
+            )?;
+        }
+
         Ok(output)
     }
 
-    // Reads a status from file.
-    pub async fn read_status(&self, file_path: PathBuf, log_dir: PathBuf) -> Result<Status, Error> {
-        let file_path_hash = blake3::hash(file_path.to_str().unwrap().as_bytes());
-
-        let status_path = log_dir
-            .join(file_path_hash.to_string())
-            .with_extension("json");
+    /// Checks the `data_root` and `data_size` of the transaction recorded in the status of
+    /// `file_path` against those calculated from the contents of the file. If the status
+    /// records them, they are checked without fetching the transaction header, and only the
+    /// status of the transaction, or of its bundle for files uploaded in bundles, is requested.
+    /// Files uploaded with compression are compressed the same way before they are checked.
+    pub async fn verify_upload(
+        &self,
+        file_path: PathBuf,
+        log_dir: PathBuf,
+    ) -> Result<Verification, Error> {
+        let status = self.read_status(file_path.clone(), log_dir).await?;
+        if let (Some(data_root), Some(data_size)) = (&status.data_root, status.data_size) {
+            let data = read_uploaded_data(&file_path, &status.tags).await?;
+            let local_transaction = self.merklize(data)?;
+            let outcome = if local_transaction.data_root != *data_root
+                || local_transaction.data_size != data_size
+            {
+                VerifyOutcome::Mismatch
+            } else {
+                // Data items of bundles are confirmed with their bundle.
+                let id = status.bundle_id.as_ref().unwrap_or(&status.id);
+                match self.get_tx_status(id).await?.status_code() {
+                    StatusCode::Confirmed => VerifyOutcome::Verified,
+                    StatusCode::NotFound => VerifyOutcome::NotFound,
+                    _ => VerifyOutcome::Pending,
+                }
+            };
+            return Ok(Verification {
+                id: status.id,
+                file_path,
+                outcome,
+            });
+        }
+
+        let (resp_status, body) = self.gateway().get_transaction(self, &status.id).await?;
+
+        let outcome = match resp_status {
+            ResponseStatusCode::OK => {
+                let network_transaction: Transaction = serde_json::from_str(&body)?;
+                let data = read_uploaded_data(&file_path, &status.tags).await?;
+                let local_transaction = self.merklize(data)?;
+                if network_transaction.data_root == local_transaction.data_root
+                    && network_transaction.data_size == local_transaction.data_size
+                {
+                    VerifyOutcome::Verified
+                } else {
+                    VerifyOutcome::Mismatch
+                }
+            }
+            ResponseStatusCode::ACCEPTED => VerifyOutcome::Pending,
+            _ => VerifyOutcome::NotFound,
+        };
+
+        Ok(Verification {
+            id: status.id,
+            file_path,
+            outcome,
+        })
+    }
+
+    /// Downloads the data of the transaction recorded in the status of `file_path` and compares
+    /// it with the contents of the file, after checking it against the data root of the
+    /// transaction. Files uploaded in bundles are downloaded by the id of their data item once
+    /// their bundle is confirmed, and checked against the data root recorded in their status.
+    /// Compressed data is decompressed before it is compared. Slower than
+    /// [`Arweave::verify_upload`], but checks that the data can still be retrieved from the
+    /// gateway.
+    pub async fn verify_download(
+        &self,
+        file_path: PathBuf,
+        log_dir: PathBuf,
+    ) -> Result<Verification, Error> {
+        let status = self.read_status(file_path.clone(), log_dir).await?;
+        let downloaded = match &status.bundle_id {
+            Some(bundle_id) => self.get_data_item_download(&status, bundle_id).await,
+            None => self.get_verified_transaction_data(&status.id).await,
+        };
+        let outcome = match downloaded {
+            Ok((data, VerifyOutcome::Verified)) => {
+                if data == fs::read(&file_path).await? {
+                    VerifyOutcome::Verified
+                } else {
+                    VerifyOutcome::Mismatch
+                }
+            }
+            Ok((_, outcome)) => outcome,
+            Err(Error::DataMismatch(_)) => VerifyOutcome::Mismatch,
+            Err(Error::Reqwest(e)) if e.status() == Some(ResponseStatusCode::NOT_FOUND) => {
+                VerifyOutcome::NotFound
+            }
+            Err(Error::UnexpectedStatusResponse(404, _)) => VerifyOutcome::NotFound,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Verification {
+            id: status.id,
+            file_path,
+            outcome,
+        })
+    }
+
+    /// Gets the decoded data of the data item of `status`, uploaded in bundle `bundle_id`, with
+    /// [`VerifyOutcome::Verified`] if the bundle is confirmed and the data matches the data root
+    /// and size recorded in `status`, returning [`Error::DataMismatch`] if it doesn't.
+    async fn get_data_item_download(
+        &self,
+        status: &Status,
+        bundle_id: &Base64,
+    ) -> Result<(Vec<u8>, VerifyOutcome), Error> {
+        match self.get_tx_status(bundle_id).await?.status_code() {
+            StatusCode::Confirmed => {}
+            StatusCode::NotFound => return Ok((Vec::new(), VerifyOutcome::NotFound)),
+            _ => return Ok((Vec::new(), VerifyOutcome::Pending)),
+        }
+        let data = self
+            .gateway()
+            .get_transaction_data(self, &status.id)
+            .await?;
+        if let (Some(data_root), Some(data_size)) = (&status.data_root, status.data_size) {
+            let local_transaction = self.merklize(data.clone())?;
+            if local_transaction.data_root != *data_root || local_transaction.data_size != data_size
+            {
+                return Err(Error::DataMismatch(status.id.to_string()));
+            }
+        }
+        let data = match content_encoding(&status.tags) {
+            Some(content_encoding) => {
+                Compression::from_content_encoding(content_encoding)?.decompress(&data)?
+            }
+            None => data,
+        };
+        Ok((data, VerifyOutcome::Verified))
+    }
+
+    /// Returns a summary table of counts of [`VerifyOutcome`]s.
+    pub fn verification_summary(&self, verifications: &[Verification]) -> Result<String, Error> {
+        let outcome_counts: HashMap<&VerifyOutcome, u32> =
+            verifications.iter().fold(HashMap::new(), |mut map, v| {
+                *map.entry(&v.outcome).or_insert(0) += 1;
+                map
+            });
+
+        let mut total = 0;
+        let mut output = String::new();
+        writeln!(output, " {:<15}  {:>10}", "outcome", "count")?;
+        writeln!(output, "{:-<29}", "")?;
+        for k in vec![
+            VerifyOutcome::Verified,
+            VerifyOutcome::Mismatch,
+            VerifyOutcome::Pending,
+            VerifyOutcome::NotFound,
+            VerifyOutcome::Unverified,
+        ] {
+            let v = outcome_counts.get(&k).unwrap_or(&0);
+            writeln!(output, " {:<16} {:>10}", &k.to_string(), v)?;
+            total += v;
+        }
+
+        writeln!(output, "{:-<29}", "")?;
+        writeln!(output, " {:<15}  {:>10}", "Total", total)?;
+
+        Ok(output)
+    }
+
+    // Reads the transaction ids of all statuses and bundle statuses written to a log directory,
+    // each id once.
+    pub async fn read_log_dir_ids(&self, log_dir: PathBuf) -> Result<Vec<Base64>, Error> {
+        let mut ids: Vec<Base64> = self
+            .read_bundle_statuses(&log_dir.join("").to_string_lossy())
+            .await?
+            .into_iter()
+            .map(|status| status.id)
+            .collect();
+        ids.extend(
+            self.read_all_statuses(log_dir)
+                .await?
+                .into_iter()
+                .map(|status| status.id),
+        );
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(id.0.clone()));
+        Ok(ids)
+    }
 
-        if status_path.exists() {
-            let data = fs::read_to_string(status_path).await?;
-            let status: Status = serde_json::from_str(&data)?;
-            Ok(status)
+    /// Adds the confirmed statuses in `statuses` to the `assets.json` file in `log_dir`, which
+    /// maps file paths to their transaction ids and gateway urls, so that other tools can consume
+    /// a single file instead of the hashed status files. The file is replaced atomically.
+    pub async fn update_assets_file(
+        &self,
+        statuses: &[Status],
+        log_dir: PathBuf,
+    ) -> Result<PathBuf, Error> {
+        let assets_path = log_dir.join(ASSETS_FILE_NAME);
+        let mut assets: serde_json::Map<String, Value> = if assets_path.exists() {
+            serde_json::from_str(&fs::read_to_string(&assets_path).await?)?
         } else {
-            Err(Error::StatusNotFound)
+            serde_json::Map::new()
+        };
+
+        for status in statuses
+            .iter()
+            .filter(|s| s.status == StatusCode::Confirmed)
+        {
+            if let Some(file_path) = &status.file_path {
+                assets.insert(
+                    file_path.display().to_string(),
+                    json!({
+                        "id": status.id.to_string(),
+                        "url": self.base_url.join(&status.id.to_string())?.to_string(),
+                    }),
+                );
+            }
+        }
+
+        let temp_path = assets_path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(&assets)?).await?;
+        fs::rename(&temp_path, &assets_path).await?;
+        Ok(assets_path)
+    }
+
+    /// Returns false if a status in `log_dir` shows that the file has already been uploaded,
+    /// i.e. it doesn't [`StatusCode::needs_reupload`]. Uploads left [`StatusCode::Signed`] are
+    /// resumed with [`Arweave::resume_signed`] first. Always returns true if `log_dir` is `None`.
+    pub async fn is_not_uploaded(&self, file_path: PathBuf, log_dir: Option<PathBuf>) -> bool {
+        match log_dir {
+            Some(log_dir) => match self.read_status(file_path, log_dir.clone()).await {
+                Ok(status) if status.status == StatusCode::Signed => {
+                    match self.resume_signed(status, log_dir).await {
+                        Ok(status) => status.status.needs_reupload(),
+                        Err(e) => {
+                            warn!("failed to resume signed upload: {}", e);
+                            true
+                        }
+                    }
+                }
+                Ok(status) => status.status.needs_reupload(),
+                Err(_) => true,
+            },
+            None => true,
+        }
+    }
+
+    /// [`Arweave::is_not_uploaded`], sending [`UploadProgress::Skipped`] for files that have
+    /// been uploaded.
+    async fn skip_if_uploaded(&self, file_path: PathBuf, log_dir: Option<PathBuf>) -> bool {
+        let not_uploaded = self.is_not_uploaded(file_path, log_dir).await;
+        if !not_uploaded {
+            self.send_progress(UploadProgress::Skipped { files: 1 });
+        }
+        not_uploaded
+    }
+
+    /// Resumes an upload that was interrupted after `status` was logged as
+    /// [`StatusCode::Signed`]. The transaction, or the bundle of a bundled file, is posted again
+    /// if it was persisted to [`Arweave::signed_tx_dir`], and otherwise the status is updated
+    /// from the network, leaving it [`StatusCode::NotFound`] to be uploaded again if it never got
+    /// there. The resumed status is written to `log_dir`.
+    pub async fn resume_signed(
+        &self,
+        mut status: Status,
+        log_dir: PathBuf,
+    ) -> Result<Status, Error> {
+        let id = status.bundle_id.as_ref().unwrap_or(&status.id);
+        let persisted_path = self
+            .signed_tx_dir
+            .as_ref()
+            .map(|dir| dir.join(id.to_string()).with_extension("json"))
+            .filter(|path| path.exists());
+        match persisted_path {
+            Some(path) => {
+                status.first_submitted = Some(Utc::now());
+                self.post_signed_transaction_file(&path, 1).await?;
+                status.status = StatusCode::Submitted;
+                status.record_post(Utc::now(), status.data_size.unwrap_or_default());
+                self.write_status(status.clone(), log_dir, None).await?;
+                Ok(status)
+            }
+            None => self.refresh_status(status, log_dir).await,
+        }
+    }
+
+    // Reads a status from [`Arweave::status_store`], by default from file or from the status
+    // archive if `log_dir` has been compacted.
+    pub async fn read_status(&self, file_path: PathBuf, log_dir: PathBuf) -> Result<Status, Error> {
+        if let Some(status) = self
+            .status_store
+            .read_status(&log_dir, &hash_file_path(&file_path))
+            .await?
+        {
+            return Ok(status);
+        }
+        match legacy_hash_file_path(&file_path) {
+            Some(key) => self.status_store.read_status(&log_dir, &key).await?,
+            None => None,
         }
+        .ok_or(Error::StatusNotFound)
     }
 
     // Reads statuses from a list of paths.
@@ -1332,31 +5155,170 @@ impl Arweave {
         try_join_all(paths_iter.map(|p| self.read_status(p, log_dir.clone()))).await
     }
 
+    /// Returns a stream of every [`Status`] written to `log_dir`, read from
+    /// [`Arweave::status_store`] so that the paths of the uploaded files aren't needed. With the
+    /// default [`FileStatusStore`], archived statuses follow those in status files, leaving out
+    /// any that have been written again since.
+    pub fn list_all_statuses(
+        &self,
+        log_dir: PathBuf,
+    ) -> Result<impl Stream<Item = Result<Status, Error>> + '_, Error> {
+        self.status_store.list_statuses(log_dir)
+    }
+
+    /// Moves every status file in `log_dir` into its status archive, a single file that
+    /// [`Arweave::read_status`] and [`Arweave::list_all_statuses`] fall back to, so that log
+    /// directories of large collections don't hold thousands of files. Statuses written after
+    /// compaction go to status files again and take precedence over archived ones until the next
+    /// compaction. Returns the number of archived statuses.
+    pub async fn compact_statuses(&self, log_dir: PathBuf) -> Result<usize, Error> {
+        self.status_store.compact_statuses(&log_dir).await
+    }
+
+    /// Removes the statuses in `log_dir`, archived or not, that were last modified before
+    /// `cutoff`. Returns the number of statuses removed.
+    pub async fn prune_statuses(
+        &self,
+        log_dir: PathBuf,
+        cutoff: DateTime<Utc>,
+    ) -> Result<usize, Error> {
+        self.status_store
+            .retain_statuses(&log_dir, &|status| status.last_modified >= cutoff)
+            .await
+    }
+
+    /// Removes the statuses in `log_dir`, archived or not, of files that no longer exist.
+    /// Relative file paths are resolved against `base_dir`, the directory the files were
+    /// uploaded from, and their statuses are kept if it is `None`. Statuses of uploads from urls
+    /// and of transactions without a file are kept. Returns the number of statuses removed.
+    pub async fn prune_missing_statuses(
+        &self,
+        log_dir: PathBuf,
+        base_dir: Option<PathBuf>,
+    ) -> Result<usize, Error> {
+        let keep = |status: &Status| match &status.file_path {
+            Some(file_path) if file_path.to_string_lossy().contains("://") => true,
+            Some(file_path) if file_path.is_absolute() => file_path.exists(),
+            Some(file_path) => match &base_dir {
+                Some(base_dir) => base_dir.join(file_path).exists(),
+                None => true,
+            },
+            None => true,
+        };
+        self.status_store.retain_statuses(&log_dir, &keep).await
+    }
+
+    /// Reads every [`Status`] written to `log_dir`.
+    pub async fn read_all_statuses(&self, log_dir: PathBuf) -> Result<Vec<Status>, Error> {
+        self.list_all_statuses(log_dir)?.try_collect().await
+    }
+
+    /// Reads every [`Status`] written to `log_dir` and filters them as in
+    /// [`Arweave::filter_statuses`].
+    pub async fn filter_all_statuses(
+        &self,
+        log_dir: PathBuf,
+        statuses: Option<Vec<StatusCode>>,
+        max_confirms: Option<u64>,
+    ) -> Result<Vec<Status>, Error> {
+        let all_statuses = self.read_all_statuses(log_dir).await?;
+        self.filter_statuses(all_statuses, statuses, max_confirms)
+    }
+
     pub async fn update_bundle_status(&self, file_path: PathBuf) -> Result<BundleStatus, Error> {
         let data = fs::read_to_string(&file_path).await?;
         let mut status: BundleStatus = serde_json::from_str(&data)?;
         let trans_status = self.get_status(&status.id).await?;
-        status.last_modified = Utc::now();
-        status.status = trans_status.status;
+        status.status = status.status.updated(trans_status.status);
         status.raw_status = trans_status.raw_status;
+        status.record_check(Utc::now());
+        #[cfg(feature = "metrics")]
+        metrics::record_status(&status.status);
         fs::write(&file_path, serde_json::to_string(&status)?).await?;
         Ok(status)
     }
 
+    #[instrument(skip(self, log_dir), fields(file_path = %file_path.display(), id))]
     pub async fn update_status(
         &self,
         file_path: PathBuf,
         log_dir: PathBuf,
     ) -> Result<Status, Error> {
-        let mut status = self.read_status(file_path, log_dir.clone()).await?;
-        let trans_status = self.get_status(&status.id).await?;
-        status.last_modified = Utc::now();
-        status.status = trans_status.status;
+        let status = self.read_status(file_path, log_dir.clone()).await?;
+        self.refresh_status(status, log_dir).await
+    }
+
+    /// Updates `status` from the network and writes it to `log_dir`, keyed by its file path or,
+    /// without one, by its id.
+    async fn refresh_status(&self, mut status: Status, log_dir: PathBuf) -> Result<Status, Error> {
+        if status.status == StatusCode::Blocked {
+            return Ok(status);
+        }
+        Span::current().record("id", &display(&status.id));
+        let trans_status = self
+            .get_status(status.bundle_id.as_ref().unwrap_or(&status.id))
+            .await?;
+        let updated_status = status.status.updated(trans_status.status);
+        let previous_status = std::mem::replace(&mut status.status, updated_status);
         status.raw_status = trans_status.raw_status;
+        status.record_check(Utc::now());
+        #[cfg(feature = "metrics")]
+        metrics::record_status(&status.status);
+        debug!(status = %status.status, "updated status");
         self.write_status(status.clone(), log_dir, None).await?;
+
+        if status.status != previous_status
+            && matches!(
+                status.status,
+                StatusCode::Confirmed | StatusCode::NotFound | StatusCode::Dropped
+            )
+        {
+            let notification = StatusNotification {
+                file_path: status.file_path.clone(),
+                id: status.id.clone(),
+                status: status.status.clone(),
+                previous_status,
+            };
+            // The status is already updated, so a failed notification doesn't fail the update.
+            if let Err(e) = self.notify_webhook(&notification).await {
+                warn!("failed to post status notification: {}", e);
+            }
+        }
         Ok(status)
     }
 
+    /// Posts `notification` as json to [`Arweave::webhook_url`], if set.
+    pub async fn notify_webhook(&self, notification: &StatusNotification) -> Result<(), Error> {
+        self.post_to_webhook(notification).await
+    }
+
+    /// Posts `digest` as json to [`Arweave::webhook_url`] and, with the `email` feature, emails
+    /// it with [`Arweave::smtp_digest`], if set.
+    pub async fn send_digest(&self, digest: &BatchDigest) -> Result<(), Error> {
+        self.post_to_webhook(digest).await?;
+        #[cfg(feature = "email")]
+        if let Some(smtp_digest) = &self.smtp_digest {
+            smtp_digest.send(digest).await?;
+        }
+        Ok(())
+    }
+
+    async fn post_to_webhook<T: serde::Serialize>(&self, body: &T) -> Result<(), Error> {
+        if let Some(webhook_url) = &self.webhook_url {
+            self.client
+                .post(webhook_url.clone())
+                .json(body)
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        Ok(())
+    }
+
+    /// Updates the statuses of `paths_iter` in `log_dir` as limited by [`Arweave::status_updates`],
+    /// returning them in order. A status that can't be updated is logged and returned as last
+    /// written, so that one failed request doesn't abort the run, and files without statuses are
+    /// left out.
     pub async fn update_statuses<IP>(
         &self,
         paths_iter: IP,
@@ -1365,10 +5327,82 @@ impl Arweave {
     where
         IP: Iterator<Item = PathBuf> + Send,
     {
-        try_join_all(paths_iter.map(|p| self.update_status(p, log_dir.clone()))).await
+        let log_dir = &log_dir;
+        let results: Vec<(PathBuf, Result<Status, Error>)> =
+            paced(paths_iter, self.status_updates.delay)
+                .map(|p| async move {
+                    let result = self.update_status(p.clone(), log_dir.clone()).await;
+                    (p, result)
+                })
+                .buffered(self.status_updates.buffer.max(1))
+                .collect()
+                .await;
+
+        let mut statuses = Vec::new();
+        for (file_path, result) in results {
+            match result {
+                Ok(status) => statuses.push(status),
+                Err(e) => {
+                    warn!("failed to update status of {}: {}", file_path.display(), e);
+                    if let Ok(status) = self.read_status(file_path, log_dir.clone()).await {
+                        statuses.push(status);
+                    }
+                }
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Re-uploads the files in `paths_iter` whose statuses in `log_dir` have been
+    /// [`StatusCode::NotFound`], [`StatusCode::Failed`] or [`StatusCode::Dropped`] for at least
+    /// `min_age_blocks` blocks since they were last submitted, as estimated with
+    /// [`BLOCK_TIME_SECS`]. Transactions are rebuilt with a fresh anchor, `tags` and
+    /// `price_terms`, up to `buffer` at a time, and their statuses in `log_dir` are replaced.
+    /// Files without statuses are left alone.
+    pub async fn reupload_not_found<IP>(
+        &self,
+        paths_iter: IP,
+        log_dir: PathBuf,
+        min_age_blocks: u64,
+        tags: Option<Vec<Tag<Base64>>>,
+        price_terms: (u64, u64),
+        buffer: usize,
+    ) -> Result<Vec<Status>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        let min_age = chrono::Duration::seconds((min_age_blocks * BLOCK_TIME_SECS) as i64);
+        let now = Utc::now();
+        let mut stale_paths = Vec::new();
+        for file_path in paths_iter {
+            let status = match self.read_status(file_path.clone(), log_dir.clone()).await {
+                Ok(status) => status,
+                Err(_) => continue,
+            };
+            let submitted_at = status.first_submitted.unwrap_or(status.created_at);
+            if status.status.needs_reupload() && now - submitted_at >= min_age {
+                stale_paths.push(file_path);
+            }
+        }
+
+        stream::iter(stale_paths)
+            .map(|p| {
+                self.upload_file_from_path(
+                    p,
+                    Some(log_dir.clone()),
+                    tags.clone(),
+                    None,
+                    price_terms,
+                    None,
+                )
+            })
+            .buffer_unordered(buffer)
+            .try_collect()
+            .await
     }
 
-    /// Writes Status Json to `log_dir` with file name based on BLAKE3 hash of `status.file_path`.
+    /// Writes Status to [`Arweave::status_store`], by default as Json to `log_dir` with file name
+    /// based on BLAKE3 hash of `status.file_path`.
     ///
     /// This is done to facilitate checking the status of uploaded file and also means that only
     /// one status object can exist for a given `file_path`. If for some reason you wanted to record
@@ -1384,20 +5418,98 @@ impl Arweave {
             stem
         } else {
             if let Some(file_path) = &status.file_path {
-                if status.id.0.is_empty() {
+                if status.id.0.is_empty() && status.status != StatusCode::Blocked {
                     return Err(error::Error::UnsignedTransaction.into());
                 }
-                blake3::hash(file_path.to_str().unwrap().as_bytes()).to_string()
+                hash_file_path(&file_path)
             } else {
                 format!("txid_{}", status.id)
             }
         };
 
+        self.status_store
+            .write_status(&log_dir, &file_stem, &status)
+            .await
+    }
+
+    //-------------------------
+    // Session Wallet
+    //-------------------------
+
+    /// Runs `job` with a temporary session wallet funded with `budget` winstons from this
+    /// wallet, then sweeps whatever is left back.
+    ///
+    /// The session keypair is written to `log_dir` before it is funded so that the remaining
+    /// balance can be recovered if the job is interrupted. Funding and sweeping both wait for
+    /// confirmation, so this takes at least two blocks on top of the job itself. The job's
+    /// error, if any, takes precedence over an error sweeping the remaining balance.
+    #[cfg(feature = "session-wallet")]
+    pub async fn run_with_session_wallet<F, Fut, T>(
+        &self,
+        budget: u128,
+        log_dir: PathBuf,
+        job: F,
+    ) -> Result<T, Error>
+    where
+        F: FnOnce(Arc<Arweave>) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let (crypto, jwk) = crypto::Provider::generate()?;
+        let session = Arc::new(Arweave {
+            base_url: self.base_url.clone(),
+            client: self.client.clone(),
+            crypto,
+            price_cache: self.price_cache.clone(),
+            oracle: self.oracle.clone(),
+            currency: self.currency,
+            ..Default::default()
+        });
+        let session_address = session.crypto.wallet_address()?;
         fs::write(
-            log_dir.join(file_stem).with_extension("json"),
-            serde_json::to_string(&status)?,
+            log_dir
+                .join(format!("session_{}", session_address))
+                .with_extension("json"),
+            serde_json::to_string(&jwk)?,
         )
         .await?;
+
+        let transaction = self
+            .create_transfer_transaction(session_address, budget)
+            .await?;
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.wait_for_confirmation(&id, SESSION_POLL_SECS).await?;
+
+        let result = job(session.clone()).await;
+        let swept = session.sweep_to(self.crypto.wallet_address()?).await;
+        match (result, swept) {
+            (Ok(_), Err(e)) => Err(e),
+            (result, _) => result,
+        }
+    }
+
+    /// Transfers the remaining balance of the wallet to `target` once none of its transactions
+    /// are pending.
+    #[cfg(feature = "session-wallet")]
+    async fn sweep_to(&self, target: Base64) -> Result<(), Error> {
+        while self.has_pending_transactions().await? {
+            sleep(Duration::from_secs(SESSION_POLL_SECS)).await;
+        }
+
+        let balance = self
+            .get_wallet_balance(None)
+            .await?
+            .to_u128()
+            .unwrap_or_default();
+        let mut transaction = self.create_transfer_transaction(target, 0).await?;
+        if balance <= transaction.reward {
+            return Ok(());
+        }
+        transaction.quantity = balance - transaction.reward;
+
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.wait_for_confirmation(&id, SESSION_POLL_SECS).await?;
         Ok(())
     }
 
@@ -1427,7 +5539,7 @@ impl Arweave {
             .into_iter()
             .fold(serde_json::Map::new(), |mut m, s| {
                 m.insert(
-                    s.file_path.unwrap().to_str().unwrap().to_string(),
+                    s.file_path.unwrap().to_string_lossy().into_owned(),
                     json!({"id": s.id.to_string(), "content_type": s.content_type}),
                 );
                 m
@@ -1442,14 +5554,154 @@ impl Arweave {
         Ok(manifest)
     }
 
+    /// Creates a manifest from `statuses` with paths relative to `root`, so that the manifest
+    /// reproduces the folder hierarchy under `root`, e.g. `images/1.png`.
+    pub fn create_manifest_relative_to(
+        &self,
+        statuses: Vec<Status>,
+        root: &Path,
+    ) -> Result<Value, Error> {
+        let statuses = statuses
+            .into_iter()
+            .map(|mut s| {
+                let relative_path = s
+                    .file_path
+                    .as_ref()
+                    .ok_or(Error::MissingFilePath)?
+                    .strip_prefix(root)?
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                s.file_path = Some(PathBuf::from(relative_path));
+                Ok(s)
+            })
+            .collect::<Result<Vec<Status>, Error>>()?;
+        self.create_manifest(statuses)
+    }
+
+    /// Adds the index and fallback of [`Arweave::manifest_index`] to `manifest`, if set. The index
+    /// has to be one of the paths of the manifest. A fallback path is replaced with the id it maps
+    /// to, and as fallbacks were introduced in version 0.2.0 of the manifest format, the version is
+    /// raised to it.
+    pub fn with_manifest_index(&self, mut manifest: Value) -> Result<Value, Error> {
+        let manifest_index = match &self.manifest_index {
+            Some(manifest_index) => manifest_index,
+            None => return Ok(manifest),
+        };
+        let paths = manifest["paths"]
+            .as_object()
+            .ok_or(Error::ManifestNotFound)?
+            .clone();
+
+        if let Some(index) = &manifest_index.index {
+            if !paths.contains_key(index) {
+                return Err(Error::InvalidManifestPath(index.clone()));
+            }
+            manifest["index"] = json!({ "path": index });
+        }
+        if let Some(fallback) = &manifest_index.fallback {
+            let id = match paths.get(fallback) {
+                Some(entry) => entry["id"]
+                    .as_str()
+                    .ok_or_else(|| Error::InvalidManifestPath(fallback.clone()))?
+                    .to_string(),
+                None => match Base64::from_str(fallback) {
+                    Ok(id) if id.0.len() == 32 => id.to_string(),
+                    _ => return Err(Error::InvalidManifestPath(fallback.clone())),
+                },
+            };
+            manifest["fallback"] = json!({ "id": id });
+            manifest["version"] = json!("0.2.0");
+        }
+        Ok(manifest)
+    }
+
+    /// Returns the transaction id of every path in `manifest`, paired with where to download it to
+    /// under `output_dir`. Accepts both `arweave/paths` manifests and the manifest files written
+    /// by [`Arweave::write_manifest`]. Paths that would leave `output_dir` are rejected.
+    pub fn manifest_download_paths(
+        &self,
+        manifest: &Value,
+        output_dir: &Path,
+    ) -> Result<Vec<(Base64, PathBuf)>, Error> {
+        manifest
+            .get("paths")
+            .unwrap_or(manifest)
+            .as_object()
+            .ok_or(Error::ManifestNotFound)?
+            .iter()
+            .map(|(path, entry)| {
+                let id = entry["id"]
+                    .as_str()
+                    .ok_or_else(|| Error::InvalidManifestPath(path.clone()))?;
+                let relative_path = PathBuf::from(path);
+                if path.is_empty()
+                    || !relative_path
+                        .components()
+                        .all(|c| matches!(c, std::path::Component::Normal(_)))
+                {
+                    return Err(Error::InvalidManifestPath(path.clone()));
+                }
+                Ok((Base64::from_str(id)?, output_dir.join(relative_path)))
+            })
+            .collect()
+    }
+
+    /// Uploads every file in the directory tree under `root` as its own transaction, writing
+    /// statuses to `log_dir`, then uploads a manifest with paths relative to `root` and writes it
+    /// to `log_dir`. Returns the manifest transaction id and the statuses of the files.
+    pub async fn upload_dir(
+        &self,
+        root: PathBuf,
+        log_dir: PathBuf,
+        tags: Option<Vec<Tag<Base64>>>,
+        price_terms: (u64, u64),
+        buffer: usize,
+    ) -> Result<(Base64, Vec<Status>), Error> {
+        let paths_iter = glob(&root.join("**/*").display().to_string())?
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file());
+
+        let statuses: Vec<Status> = upload_files_stream(
+            self,
+            paths_iter,
+            tags,
+            Some(log_dir.clone()),
+            false,
+            None,
+            price_terms,
+            buffer,
+        )
+        .try_collect()
+        .await?;
+
+        let manifest = self.create_manifest_relative_to(statuses.clone(), &root)?;
+        let manifest = self.with_manifest_index(manifest)?;
+        let transaction = self
+            .create_transaction_from_manifest(manifest.clone(), price_terms)
+            .await?;
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.write_manifest(manifest, id.to_string(), log_dir)
+            .await?;
+
+        Ok((id, statuses))
+    }
+
     pub fn create_manifest_from_bundle_statuses(
         &self,
         statuses: Vec<BundleStatus>,
     ) -> Result<Value, Error> {
         let paths = statuses
             .into_iter()
-            .fold(serde_json::Map::new(), |mut m, mut s| {
-                m.append(s.file_paths.as_object_mut().unwrap());
+            .fold(serde_json::Map::new(), |mut m, s| {
+                for (file_path, entry) in s.file_paths.as_object().unwrap() {
+                    m.insert(
+                        file_path.clone(),
+                        json!({"id": entry["id"], "content_type": entry["content_type"]}),
+                    );
+                }
                 m
             });
 
@@ -1491,25 +5743,69 @@ impl Arweave {
         sol_ar_url: Url,
         from_keypair: Option<Keypair>,
     ) -> Result<String, Error> {
+        match self
+            .post_manifest_from_bundle_log_dir(
+                log_dir,
+                price_terms,
+                solana_url,
+                sol_ar_url,
+                from_keypair,
+            )
+            .await?
+        {
+            Some((id, num_files)) => Ok(format!("Uploaded manifest for {} files and wrote to {}manifest_{id}.json.\n\nRun `arloader get-status {id}` to confirm manifest transaction.",
+            num_files, log_dir, id=id.to_string())),
+            None => Ok(format!("No bundle statuses found in {}", log_dir)),
+        }
+    }
+
+    /// Creates a manifest from the bundle statuses in `log_dir`, posts it and writes it to
+    /// `log_dir`, returning the manifest transaction id and number of files in the manifest, or
+    /// `None` if there are no bundle statuses in `log_dir`.
+    pub async fn post_manifest_from_bundle_log_dir(
+        &self,
+        log_dir: &str,
+        price_terms: (u64, u64),
+        solana_url: Url,
+        sol_ar_url: Url,
+        from_keypair: Option<Keypair>,
+    ) -> Result<Option<(Base64, usize)>, Error> {
         let paths: Vec<PathBuf> = glob(&format!("{}*.json", log_dir.clone()))?
             .filter_map(Result::ok)
             .collect();
 
         let paths_len = paths.len();
         if paths_len == 0 {
-            return Ok(format!("No bundle statuses found in {}", log_dir));
+            return Ok(None);
         };
 
         let statuses = self.read_bundle_statuses(log_dir).await?;
 
         let manifest = self.create_manifest_from_bundle_statuses(statuses)?;
-        let num_files = manifest["paths"].as_object().unwrap().keys().len();
-        let transaction = self
-            .create_transaction_from_manifest(manifest.clone(), price_terms)
-            .await?;
+        let sol = from_keypair.map(|from_keypair| (solana_url, sol_ar_url, from_keypair));
+        self.post_manifest(manifest, price_terms, PathBuf::from(log_dir), sol)
+            .await
+            .map(Some)
+    }
 
-        let signed_transaction = if let Some(from_keypair) = from_keypair {
-            let (signed_transaction, _): (Transaction, SigResponse) = self
+    /// Posts `manifest` with the index and fallback of [`Arweave::manifest_index`], paid for
+    /// with SOL if `sol` has the Solana urls and keypair to pay with, and writes it to
+    /// `log_dir`. Returns the manifest transaction id and number of files in the manifest.
+    async fn post_manifest(
+        &self,
+        manifest: Value,
+        price_terms: (u64, u64),
+        log_dir: PathBuf,
+        sol: Option<(Url, Url, Keypair)>,
+    ) -> Result<(Base64, usize), Error> {
+        let manifest = self.with_manifest_index(manifest)?;
+        let num_files = manifest["paths"].as_object().unwrap().len();
+        let transaction = self
+            .create_transaction_from_manifest(manifest.clone(), price_terms)
+            .await?;
+
+        let signed_transaction = if let Some((solana_url, sol_ar_url, from_keypair)) = sol {
+            let (signed_transaction, _): (Transaction, SigResponse) = self
                 .sign_transaction_with_sol(transaction, solana_url, sol_ar_url, &from_keypair)
                 .await?;
             signed_transaction
@@ -1518,12 +5814,173 @@ impl Arweave {
         };
 
         let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.write_manifest(manifest, id.to_string(), log_dir)
+            .await?;
+
+        Ok((id, num_files))
+    }
+
+    /// Creates a manifest from the confirmed statuses in `log_dir`, with paths relative to
+    /// `root` if given and the index and fallback of [`Arweave::manifest_index`], posts it and
+    /// writes it to `log_dir`. Files uploaded in bundles are included once their bundle status
+    /// is confirmed. Returns the manifest transaction id and number of files in the manifest, or
+    /// `None` if no statuses in `log_dir` are confirmed.
+    pub async fn post_manifest_from_log_dir(
+        &self,
+        log_dir: PathBuf,
+        root: Option<&Path>,
+        price_terms: (u64, u64),
+    ) -> Result<Option<(Base64, usize)>, Error> {
+        let mut statuses: Vec<Status> = self
+            .filter_all_statuses(log_dir.clone(), Some(vec![StatusCode::Confirmed]), None)
+            .await?
+            .into_iter()
+            .filter(|s| s.file_path.is_some())
+            .collect();
+        // Only the bundle statuses of bundled files are updated, not their file statuses.
+        let bundle_log_dir = log_dir.join("").to_string_lossy().into_owned();
+        for bundle_status in self.read_bundle_statuses(&bundle_log_dir).await? {
+            if bundle_status.status == StatusCode::Confirmed {
+                statuses.extend(self.file_statuses_from_bundle_status(&bundle_status)?);
+            }
+        }
+        if statuses.is_empty() {
+            return Ok(None);
+        }
+
+        let manifest = match root {
+            Some(root) => self.create_manifest_relative_to(statuses, root)?,
+            None => self.create_manifest(statuses)?,
+        };
+        self.post_manifest(manifest, price_terms, log_dir, None)
+            .await
+            .map(Some)
+    }
+
+    /// Loads the manifest at `source`, either a manifest file, such as those written by
+    /// [`Arweave::write_manifest`], or the id of a manifest transaction. Returns it as an
+    /// `arweave/paths` manifest, with the id of its transaction if known.
+    pub async fn load_manifest(&self, source: &str) -> Result<(Value, Option<Base64>), Error> {
+        let path = Path::new(source);
+        let (manifest, id) = if path.is_file() {
+            let id = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.strip_prefix("manifest_"))
+                .and_then(|s| Base64::from_str(s).ok())
+                .filter(|id| id.0.len() == 32);
+            (serde_json::from_slice(&fs::read(path).await?)?, id)
+        } else {
+            let id = match Base64::from_str(source) {
+                Ok(id) if id.0.len() == 32 => id,
+                _ => return Err(Error::ManifestNotFound),
+            };
+            // Gateways resolve manifest ids to the files they point to, so the manifest itself
+            // is fetched raw.
+            let url = self.base_url.join(&format!("raw/{}", id))?;
+            let data = self
+                .client
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+            (serde_json::from_slice(&data)?, Some(id))
+        };
+        Ok((normalize_manifest(manifest)?, id))
+    }
 
-        self.write_manifest(manifest, id.to_string(), PathBuf::from(log_dir))
+    /// Merges the paths of `manifest` into the manifest loaded from `source` with
+    /// [`Arweave::load_manifest`], replacing entries of paths in both, and posts the result as a
+    /// new version. Posted manifests can't be changed, so the new version has an id of its own. It
+    /// is written to `log_dir` like other manifests and its [`ManifestVersion`] appended to
+    /// [`MANIFEST_LINEAGE_FILE_NAME`] in `log_dir`.
+    pub async fn append_to_manifest(
+        &self,
+        source: &str,
+        manifest: Value,
+        log_dir: PathBuf,
+        price_terms: (u64, u64),
+    ) -> Result<ManifestVersion, Error> {
+        let (mut merged, previous_id) = self.load_manifest(source).await?;
+        let new_paths = normalize_manifest(manifest)?["paths"]
+            .as_object()
+            .unwrap()
+            .clone();
+        let paths = new_paths.keys().cloned().collect();
+        merged["paths"].as_object_mut().unwrap().extend(new_paths);
+
+        let merged = self.with_manifest_index(merged)?;
+        let transaction = self
+            .create_transaction_from_manifest(merged.clone(), price_terms)
+            .await?;
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        self.write_manifest(merged, id.to_string(), log_dir.clone())
             .await?;
 
-        Ok(format!("Uploaded manifest for {} files and wrote to {}manifest_{id}.json.\n\nRun `arloader get-status {id}` to confirm manifest transaction.",
-        num_files, log_dir, id=id.to_string()))
+        let version = ManifestVersion {
+            id,
+            previous_id,
+            paths,
+            created_at: Utc::now(),
+        };
+        let lineage_path = log_dir.join(MANIFEST_LINEAGE_FILE_NAME);
+        let mut lineage = match fs::read(&lineage_path).await {
+            Ok(data) => serde_json::from_slice::<Vec<ManifestVersion>>(&data)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        lineage.push(version.clone());
+        fs::write(lineage_path, serde_json::to_string_pretty(&lineage)?).await?;
+        Ok(version)
+    }
+
+    /// Creates a SmartWeave interaction with the ArNS name token (ANT) contract
+    /// `ant_contract_id` that points the record for `sub_domain` at `manifest_id`. Use `@` as the
+    /// `sub_domain` to set the root of the name.
+    pub async fn create_arns_record_transaction(
+        &self,
+        ant_contract_id: &Base64,
+        sub_domain: &str,
+        manifest_id: &Base64,
+        price_terms: (u64, u64),
+    ) -> Result<Transaction, Error> {
+        let input = json!({
+            "function": "setRecord",
+            "subDomain": sub_domain,
+            "transactionId": manifest_id.to_string(),
+            "ttlSeconds": ARNS_TTL_SECONDS,
+        });
+        let tags = vec![
+            Tag::<Base64>::from_utf8_strs("App-Name", "SmartWeaveAction")?,
+            Tag::<Base64>::from_utf8_strs("App-Version", SMARTWEAVE_VERSION)?,
+            Tag::<Base64>::from_utf8_strs("Contract", &ant_contract_id.to_string())?,
+            Tag::<Base64>::from_utf8_strs("Input", &input.to_string())?,
+        ];
+
+        // Interactions carry no data of their own, but need some to be accepted by gateways.
+        let data = Utc::now().timestamp_millis().to_string().into_bytes();
+        self.create_transaction(data, Some(tags), None, price_terms, false)
+            .await
+    }
+
+    /// Signs and posts an interaction created with [`Arweave::create_arns_record_transaction`],
+    /// returning its id.
+    pub async fn set_arns_record(
+        &self,
+        ant_contract_id: &Base64,
+        sub_domain: &str,
+        manifest_id: &Base64,
+        price_terms: (u64, u64),
+    ) -> Result<Base64, Error> {
+        let transaction = self
+            .create_arns_record_transaction(ant_contract_id, sub_domain, manifest_id, price_terms)
+            .await?;
+        let signed_transaction = self.sign_transaction(transaction)?;
+        let (id, _) = self.post_transaction(&signed_transaction).await?;
+        Ok(id)
     }
 
     pub async fn write_manifest(
@@ -1557,6 +6014,59 @@ impl Arweave {
         Ok(())
     }
 
+    //-------------------------
+    // SmartWeave
+    //-------------------------
+
+    /// Uploads the javascript source of a SmartWeave contract from `source_path`. The id of the
+    /// returned status is the `Contract-Src` of contracts created from it.
+    pub async fn deploy_contract_source(
+        &self,
+        source_path: PathBuf,
+        log_dir: Option<PathBuf>,
+        price_terms: (u64, u64),
+    ) -> Result<Status, Error> {
+        let tags = vec![
+            Tag::<Base64>::from_utf8_strs("App-Name", "SmartWeaveContractSource")?,
+            Tag::<Base64>::from_utf8_strs("App-Version", SMARTWEAVE_VERSION)?,
+        ];
+        self.upload_file_from_path(source_path, log_dir, Some(tags), None, price_terms, None)
+            .await
+    }
+
+    /// Returns the tags that make a data transaction a SmartWeave contract of `contract_src`
+    /// with `init_state` as its initial state.
+    pub fn contract_tags(
+        &self,
+        contract_src: &Base64,
+        init_state: &Value,
+    ) -> Result<Vec<Tag<Base64>>, Error> {
+        Ok(vec![
+            Tag::<Base64>::from_utf8_strs("App-Name", "SmartWeaveContract")?,
+            Tag::<Base64>::from_utf8_strs("App-Version", SMARTWEAVE_VERSION)?,
+            Tag::<Base64>::from_utf8_strs("Contract-Src", &contract_src.to_string())?,
+            Tag::<Base64>::from_utf8_strs("Init-State", &init_state.to_string())?,
+        ])
+    }
+
+    /// Uploads `file_path` as a SmartWeave contract of `contract_src` initialized with
+    /// `init_state`, so that the data and the contract that owns it, e.g. an atomic NFT, are
+    /// created by a single transaction.
+    pub async fn upload_atomic_asset(
+        &self,
+        file_path: PathBuf,
+        log_dir: Option<PathBuf>,
+        additional_tags: Option<Vec<Tag<Base64>>>,
+        contract_src: &Base64,
+        init_state: &Value,
+        price_terms: (u64, u64),
+    ) -> Result<Status, Error> {
+        let mut tags = additional_tags.unwrap_or_default();
+        tags.extend(self.contract_tags(contract_src, init_state)?);
+        self.upload_file_from_path(file_path, log_dir, Some(tags), None, price_terms, None)
+            .await
+    }
+
     //-------------------------
     // Metadata
     //-------------------------
@@ -1586,6 +6096,77 @@ impl Arweave {
         Ok(())
     }
 
+    /// Rewrites the `image` and `properties.files` fields of the metadata file next to each
+    /// asset in `paths_iter` with the `https://arweave.net/{id}` uri from the asset's status in
+    /// `log_dir`. Returns [`Error::AssetsNotConfirmed`] without changing any files unless every
+    /// asset has a confirmed status.
+    pub async fn update_metadata_from_statuses<IP>(
+        &self,
+        paths_iter: IP,
+        log_dir: PathBuf,
+    ) -> Result<Vec<PathBuf>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        let statuses = self.read_statuses(paths_iter, log_dir).await?;
+        let unconfirmed = statuses
+            .iter()
+            .filter(|s| s.status != StatusCode::Confirmed)
+            .count();
+        if unconfirmed > 0 {
+            return Err(Error::AssetsNotConfirmed(unconfirmed));
+        }
+
+        try_join_all(statuses.into_iter().map(|s| async move {
+            let metadata_path = s.file_path.unwrap().with_extension("json");
+            let link = format!("https://arweave.net/{}", s.id);
+            let files_array = vec![json!({"uri": link, "type": s.content_type})];
+            self.update_metadata_file(metadata_path.clone(), files_array, link)
+                .await?;
+            Ok::<PathBuf, Error>(metadata_path)
+        }))
+        .await
+    }
+
+    /// Second pass of the NFT flow for assets uploaded as individual transactions. Updates the
+    /// statuses of the assets in `assets_log_dir` and, once all of them are confirmed, links the
+    /// metadata files to them with [`Arweave::update_metadata_from_statuses`] and uploads the
+    /// metadata files, writing their statuses to `metadata_log_dir`.
+    pub async fn upload_metadata_from_statuses<IP>(
+        &self,
+        paths_iter: IP,
+        assets_log_dir: PathBuf,
+        metadata_log_dir: PathBuf,
+        tags: Option<Vec<Tag<Base64>>>,
+        price_terms: (u64, u64),
+        buffer: usize,
+    ) -> Result<Vec<Status>, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        let paths: Vec<PathBuf> = paths_iter.collect();
+        self.update_statuses(paths.clone().into_iter(), assets_log_dir.clone())
+            .await?;
+        let metadata_paths = self
+            .update_metadata_from_statuses(paths.into_iter(), assets_log_dir)
+            .await?;
+
+        upload_files_stream(
+            self,
+            metadata_paths.into_iter(),
+            tags,
+            Some(metadata_log_dir),
+            false,
+            None,
+            price_terms,
+            buffer,
+        )
+        .collect::<Vec<Result<Status, Error>>>()
+        .await
+        .into_iter()
+        .collect()
+    }
+
     pub async fn update_metadata<IP>(
         &self,
         paths_iter: IP,
@@ -1685,107 +6266,2903 @@ impl Arweave {
                         m
                     });
 
-            let manifest_items_path = manifest_path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .join(format!("metaplex_items_{}", manifest_id))
-                .with_extension("json");
-            fs::write(&manifest_items_path, serde_json::to_string(&json!(items))?).await?;
-            Ok(manifest_items_path)
-        } else {
-            Err(Error::ManifestNotFound)
-        }
+            let manifest_items_path = manifest_path
+                .parent()
+                .unwrap()
+                .to_path_buf()
+                .join(format!("metaplex_items_{}", manifest_id))
+                .with_extension("json");
+            fs::write(&manifest_items_path, serde_json::to_string(&json!(items))?).await?;
+            Ok(manifest_items_path)
+        } else {
+            Err(Error::ManifestNotFound)
+        }
+    }
+
+    /// Writes `uris.json` alongside the manifest at `manifest_path`, mapping the index extracted
+    /// from each file name with `index_pattern` to the uri of the file, in index order, for use in
+    /// candy machine configuration. The first capture group of `index_pattern` is used as the
+    /// index if there is one, otherwise the whole match.
+    pub async fn write_metaplex_uris<IP>(
+        &self,
+        paths_iter: IP,
+        manifest_path: PathBuf,
+        link_file: bool,
+        index_pattern: &Regex,
+    ) -> Result<PathBuf, Error>
+    where
+        IP: Iterator<Item = PathBuf> + Send,
+    {
+        if !manifest_path.exists() {
+            return Err(Error::ManifestNotFound);
+        }
+        let manifest_id = manifest_path
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("manifest_", "");
+        let data = fs::read_to_string(manifest_path.clone()).await?;
+        let manifest: Value = serde_json::from_str(&data)?;
+
+        let mut uris = BTreeMap::new();
+        for p in paths_iter {
+            let file_path = p.display().to_string();
+            let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            let index = index_pattern
+                .captures(stem)
+                .and_then(|c| c.get(1).or_else(|| c.get(0)))
+                .and_then(|m| m.as_str().parse::<u64>().ok())
+                .ok_or_else(|| Error::InvalidFileIndex(file_path.clone()))?;
+            let id = manifest[&file_path]["id"]
+                .as_str()
+                .ok_or(Error::ManifestNotFound)?;
+            let uri = if link_file {
+                format!("https://arweave.net/{}/{}", manifest_id, file_path)
+            } else {
+                format!("https://arweave.net/{}", id)
+            };
+            if uris.insert(index, uri).is_some() {
+                return Err(Error::InvalidFileIndex(file_path));
+            }
+        }
+
+        let uris = uris
+            .into_iter()
+            .fold(serde_json::Map::new(), |mut m, (i, uri)| {
+                m.insert(i.to_string(), json!(uri));
+                m
+            });
+
+        let uris_path = manifest_path.parent().unwrap().join("uris.json");
+        fs::write(&uris_path, serde_json::to_string_pretty(&uris)?).await?;
+        Ok(uris_path)
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use crate::{
+        apply_reward_mult,
+        bundle::DataItem,
+        cache::ResponseCache,
+        client::{
+            AdaptiveConcurrency, BalanceGuard, LowBalanceAction, MaintenanceEvent, MaintenanceGate,
+        },
+        crypto::KeyRing,
+        error::Error,
+        graphql::CONTENT_HASH_TAG,
+        oracle::{FiatPrices, FixedRate},
+        simulation::{SimulatedGateway, SIMULATED_PRICE_TERMS},
+        status::{
+            BundleStatus, ManifestVersion, StatusCode, StatusFilter, StatusNotification,
+            VerifyOutcome,
+        },
+        store::status_file_paths,
+        transaction::{AppTags, Base64, FromUtf8Strs, Tag, Transaction},
+        upload_bundles_stream, upload_files_dedup_stream, upload_files_stream,
+        utils::{http_response, serve_http, TempDir},
+        Arweave, BundlePolicy, ChunkBudget, ManifestIndex, Status, StatusUpdatePolicy,
+        UploadProgress, BLOCK_SIZE, BLOCK_TIME_SECS, MANIFEST_LINEAGE_FILE_NAME,
+        SMARTWEAVE_VERSION,
+    };
+    use chrono::Utc;
+    use futures::{future::try_join_all, StreamExt, TryStreamExt};
+    use glob::glob;
+    use matches::assert_matches;
+    use num_bigint::BigUint;
+    use regex::Regex;
+    use serde_json::{json, Value};
+    use std::{
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+    use tokio::{fs, time::sleep};
+    use url::Url;
+
+    #[tokio::test]
+    async fn test_cannot_post_unsigned_transaction() -> Result<(), Error> {
+        let arweave = Arweave::from_keypair_path(
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            Url::from_str("http://url.com").unwrap(),
+        )
+        .await?;
+
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let last_tx = Base64::from_str("LCwsLCwsLA")?;
+        let other_tags = vec![Tag::<Base64>::from_utf8_strs("key2", "value2")?];
+        let transaction = arweave
+            .create_transaction_from_file_path(
+                file_path,
+                Some(other_tags),
+                Some(last_tx),
+                (0, 0),
+                true,
+                None,
+            )
+            .await?;
+
+        let error = arweave.post_transaction(&transaction).await.unwrap_err();
+        assert_matches!(error, Error::UnsignedTransaction);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_app_tags_with_contract_tags() -> Result<(), Error> {
+        let arweave = Arweave {
+            app_tags: Some(AppTags::default()),
+            ..Arweave::from_keypair_path(
+                PathBuf::from(
+                    "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+                ),
+                Url::from_str("http://url.com").unwrap(),
+            )
+            .await?
+        };
+        let init_state = json!({"owner": "owner"});
+        let tags = arweave.contract_tags(&Base64::from_str("LCwsLCwsLA")?, &init_state)?;
+        let transaction = arweave
+            .create_transaction(
+                b"{}".to_vec(),
+                Some(tags.clone()),
+                Some(Base64::from_str("LCwsLCwsLA")?),
+                (0, 0),
+                false,
+            )
+            .await?;
+        let tag_values = |name: &str| -> Vec<Vec<u8>> {
+            transaction
+                .tags
+                .iter()
+                .filter(|tag| tag.name.0 == name.as_bytes())
+                .map(|tag| tag.value.0.clone())
+                .collect()
+        };
+        assert_eq!(tag_values("App-Name"), vec![b"SmartWeaveContract".to_vec()]);
+        assert_eq!(
+            tag_values("App-Version"),
+            vec![SMARTWEAVE_VERSION.as_bytes().to_vec()]
+        );
+        assert_eq!(tag_values("Unix-Time").len(), 1);
+
+        let data_item = arweave.create_data_item(
+            b"{}".to_vec(),
+            tags.iter().map(Tag::<String>::from).collect(),
+            false,
+        )?;
+        let app_names: Vec<&Tag<String>> = data_item
+            .tags
+            .iter()
+            .filter(|tag| tag.name == "App-Name")
+            .collect();
+        assert_eq!(app_names.len(), 1);
+        assert_eq!(app_names[0].value, "SmartWeaveContract");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_write_read_status() -> Result<(), Error> {
+        let arweave = Arweave::from_keypair_path(
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            Url::from_str("http://url.com").unwrap(),
+        )
+        .await?;
+
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let last_tx = Base64::from_str("LCwsLCwsLA")?;
+        let other_tags = vec![Tag::<Base64>::from_utf8_strs("key2", "value2")?];
+        let transaction = arweave
+            .create_transaction_from_file_path(
+                file_path.clone(),
+                Some(other_tags),
+                Some(last_tx),
+                (0, 0),
+                true,
+                None,
+            )
+            .await?;
+
+        let signed_transaction = arweave.sign_transaction(transaction)?;
+
+        let status = Status {
+            id: signed_transaction.id.clone(),
+            reward: signed_transaction.reward,
+            file_path: Some(file_path.clone()),
+            ..Default::default()
+        };
+
+        let temp_log_dir = TempDir::from_str("./tests/").await?;
+        let log_dir = temp_log_dir.0.clone();
+
+        arweave
+            .write_status(status.clone(), log_dir.clone(), None)
+            .await?;
+
+        let read_status = arweave.read_status(file_path, log_dir).await?;
+
+        assert_eq!(status, read_status);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_file_path() {
+        assert_eq!(
+            crate::hash_file_path(&PathBuf::from("tests/fixtures/0.png")),
+            blake3::hash(b"tests/fixtures/0.png").to_string()
+        );
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let file_path = PathBuf::from(OsStr::from_bytes(b"tests/fixtures/\xff.png"));
+            let hash = crate::hash_file_path(&file_path);
+            assert!(crate::file_stem_is_status(&PathBuf::from(hash + ".json")));
+            assert!(!crate::file_stem_is_valid_txid(&file_path));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_file_path_logs() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let file_path = temp_dir.0.join(OsStr::from_bytes(b"\xff.txt"));
+            fs::write(&file_path, "tasty").await?;
+            let status = arweave
+                .upload_file_from_path(
+                    file_path.clone(),
+                    Some(temp_dir.0.clone()),
+                    None,
+                    Some(Base64(vec![0; 32])),
+                    (0, 0),
+                    None,
+                )
+                .await?;
+            let read = arweave
+                .read_status(file_path.clone(), temp_dir.0.clone())
+                .await?;
+            assert_eq!(read.file_path, Some(file_path.clone()));
+            assert_eq!(read.id, status.id);
+            let statuses = arweave.read_all_statuses(temp_dir.0.clone()).await?;
+            assert_eq!(statuses.len(), 1);
+            assert_eq!(statuses[0].file_path, Some(file_path));
+        }
+
+        // Statuses written under the hash of the path as a string are still found.
+        let file_path = PathBuf::from("tests\\fixtures\\0.png");
+        let legacy_hash = blake3::hash(file_path.to_str().unwrap().as_bytes()).to_string();
+        let status = Status {
+            id: Base64(vec![1; 32]),
+            file_path: Some(file_path.clone()),
+            ..Status::default()
+        };
+        fs::write(
+            temp_dir.0.join(&legacy_hash).with_extension("json"),
+            serde_json::to_string(&status)?,
+        )
+        .await?;
+        match crate::legacy_hash_file_path(&file_path) {
+            Some(hash) => {
+                assert_eq!(hash, legacy_hash);
+                let read = arweave.read_status(file_path, temp_dir.0.clone()).await?;
+                assert_eq!(read.id, status.id);
+            }
+            None => assert_eq!(crate::hash_file_path(&file_path), legacy_hash),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_is_not_uploaded() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_log_dir = TempDir::from_str("./tests/").await?;
+        let log_dir = temp_log_dir.0.clone();
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+
+        assert!(arweave.is_not_uploaded(file_path.clone(), None).await);
+        assert!(
+            arweave
+                .is_not_uploaded(file_path.clone(), Some(log_dir.clone()))
+                .await
+        );
+
+        let mut status = Status {
+            id: Base64(vec![7; 32]),
+            file_path: Some(file_path.clone()),
+            ..Default::default()
+        };
+        arweave
+            .write_status(status.clone(), log_dir.clone(), None)
+            .await?;
+        assert!(
+            !arweave
+                .is_not_uploaded(file_path.clone(), Some(log_dir.clone()))
+                .await
+        );
+
+        status.status = StatusCode::NotFound;
+        arweave.write_status(status, log_dir.clone(), None).await?;
+        assert!(arweave.is_not_uploaded(file_path, Some(log_dir)).await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dedup_paths_group() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let dir = temp_dir.0.clone();
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|f| dir.join(f))
+            .collect();
+        fs::write(&paths[0], "foo").await?;
+        fs::write(&paths[1], "bar").await?;
+        fs::write(&paths[2], "foo").await?;
+
+        let paths_groups = arweave.group_file_paths_by_hash(paths.clone().into_iter())?;
+        assert_eq!(paths_groups.len(), 2);
+        assert_eq!(paths_groups[0].1, vec![paths[0].clone(), paths[2].clone()]);
+        assert_eq!(paths_groups[1].1, vec![paths[1].clone()]);
+
+        // Already in dedup index, so nothing gets uploaded.
+        let original = Status {
+            id: Base64(vec![7; 32]),
+            reward: 1000,
+            ..Default::default()
+        };
+        fs::create_dir(dir.join("dedup")).await?;
+        fs::write(
+            dir.join("dedup")
+                .join(&paths_groups[0].0)
+                .with_extension("json"),
+            serde_json::to_string(&original)?,
+        )
+        .await?;
+
+        let statuses = arweave
+            .upload_paths_group(paths_groups[0].clone(), Some(dir.clone()), None, (0, 0))
+            .await?;
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses
+            .iter()
+            .all(|s| s.id == original.id && s.reward == 0));
+        let read_status = arweave.read_status(paths[2].clone(), dir).await?;
+        assert_eq!(read_status.id, original.id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_metadata_from_statuses() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let dir = temp_dir.0.clone();
+        let asset_path = dir.join("0.png");
+        fs::copy("tests/fixtures/0.png", &asset_path).await?;
+        fs::copy("tests/fixtures/0.json", dir.join("0.json")).await?;
+
+        let mut status = Status {
+            id: Base64(vec![7; 32]),
+            file_path: Some(asset_path.clone()),
+            content_type: "image/png".to_string(),
+            ..Default::default()
+        };
+        arweave
+            .write_status(status.clone(), dir.clone(), None)
+            .await?;
+        assert_matches!(
+            arweave
+                .update_metadata_from_statuses(vec![asset_path.clone()].into_iter(), dir.clone())
+                .await,
+            Err(Error::AssetsNotConfirmed(1))
+        );
+
+        status.status = StatusCode::Confirmed;
+        arweave
+            .write_status(status.clone(), dir.clone(), None)
+            .await?;
+        let metadata_paths = arweave
+            .update_metadata_from_statuses(vec![asset_path].into_iter(), dir.clone())
+            .await?;
+        assert_eq!(metadata_paths, vec![dir.join("0.json")]);
+
+        let link = format!("https://arweave.net/{}", status.id);
+        let metadata = arweave.read_metadata_file(dir.join("0.json")).await?;
+        assert_eq!(metadata["metadata"]["image"], link);
+        assert_eq!(
+            metadata["metadata"]["properties"]["files"],
+            serde_json::json!([{"uri": link, "type": "image/png"}])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_assets_file() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let confirmed = |path: &str, id: u8| Status {
+            id: Base64(vec![id; 32]),
+            file_path: Some(PathBuf::from(path)),
+            status: StatusCode::Confirmed,
+            ..Default::default()
+        };
+
+        let pending = Status {
+            status: StatusCode::Pending,
+            ..confirmed("images/2.png", 2)
+        };
+        arweave
+            .update_assets_file(&[confirmed("images/1.png", 1), pending], temp_dir.0.clone())
+            .await?;
+        let assets_path = arweave
+            .update_assets_file(&[confirmed("images/2.png", 2)], temp_dir.0.clone())
+            .await?;
+
+        let assets: Value = serde_json::from_str(&fs::read_to_string(assets_path).await?)?;
+        let assets = assets.as_object().unwrap();
+        assert_eq!(assets.len(), 2);
+        let id = Base64(vec![1; 32]).to_string();
+        assert_eq!(assets["images/1.png"]["id"], id);
+        assert_eq!(
+            assets["images/1.png"]["url"],
+            format!("https://arweave.net/{}", id)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_compact_and_prune_statuses() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let status = |path: &str, id: u8, days_ago: i64| Status {
+            id: Base64(vec![id; 32]),
+            file_path: Some(PathBuf::from(path)),
+            status: StatusCode::Confirmed,
+            last_modified: Utc::now() - chrono::Duration::days(days_ago),
+            ..Default::default()
+        };
+        for status in [
+            status("tests/fixtures/0.png", 0, 0),
+            status("tests/fixtures/1.png", 1, 30),
+            status("tests/fixtures/missing.png", 2, 0),
+        ] {
+            arweave
+                .write_status(status, temp_dir.0.clone(), None)
+                .await?;
+        }
+
+        assert_eq!(arweave.compact_statuses(temp_dir.0.clone()).await?, 3);
+        assert!(status_file_paths(&temp_dir.0)?.is_empty());
+        let read = arweave
+            .read_status(PathBuf::from("tests/fixtures/0.png"), temp_dir.0.clone())
+            .await?;
+        assert_eq!(read.id, Base64(vec![0; 32]));
+        assert_eq!(arweave.read_log_dir_ids(temp_dir.0.clone()).await?.len(), 3);
+
+        arweave
+            .write_status(
+                status("tests/fixtures/0.png", 3, 0),
+                temp_dir.0.clone(),
+                None,
+            )
+            .await?;
+        let statuses = arweave.read_all_statuses(temp_dir.0.clone()).await?;
+        assert_eq!(statuses.len(), 3);
+        assert_eq!(statuses[0].id, Base64(vec![3; 32]));
+
+        assert_eq!(
+            arweave
+                .prune_missing_statuses(temp_dir.0.clone(), None)
+                .await?,
+            0
+        );
+        assert_eq!(
+            arweave
+                .prune_missing_statuses(temp_dir.0.clone(), Some(PathBuf::from(".")))
+                .await?,
+            1
+        );
+        let cutoff = Utc::now() - chrono::Duration::days(7);
+        assert_eq!(arweave.prune_statuses(temp_dir.0.clone(), cutoff).await?, 1);
+        let statuses = arweave.read_all_statuses(temp_dir.0.clone()).await?;
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].id, Base64(vec![3; 32]));
+
+        // Resolved against another base, the remaining status file and the archived status it
+        // replaced both name a missing file.
+        assert_eq!(
+            arweave
+                .prune_missing_statuses(temp_dir.0.clone(), Some(temp_dir.0.clone()))
+                .await?,
+            2
+        );
+        assert!(arweave
+            .read_all_statuses(temp_dir.0.clone())
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_manifest_relative_to() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let root = PathBuf::from("site/");
+        let statuses = ["images/1.png", "meta/1.json"]
+            .iter()
+            .map(|p| Status {
+                file_path: Some(root.join(p)),
+                ..Default::default()
+            })
+            .collect();
+
+        let manifest = arweave.create_manifest_relative_to(statuses, &root)?;
+        let paths = manifest["paths"].as_object().unwrap();
+        assert_eq!(
+            paths.keys().collect::<Vec<_>>(),
+            vec!["images/1.png", "meta/1.json"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_to_manifest() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let status = |path: &str, byte: u8| Status {
+            id: Base64(vec![byte; 32]),
+            file_path: Some(PathBuf::from(path)),
+            content_type: "image/png".to_string(),
+            ..Default::default()
+        };
+        let first_id = Base64(vec![9; 32]);
+        let manifest = arweave.create_manifest(vec![status("0.png", 0), status("1.png", 1)])?;
+        arweave
+            .write_manifest(manifest, first_id.to_string(), temp_dir.0.clone())
+            .await?;
+        let source = temp_dir.0.join(format!("manifest_{}.json", first_id));
+
+        let appended = arweave.create_manifest(vec![status("1.png", 3), status("2.png", 2)])?;
+        let version = arweave
+            .append_to_manifest(
+                source.to_str().unwrap(),
+                appended,
+                temp_dir.0.clone(),
+                (0, 0),
+            )
+            .await?;
+        assert_eq!(version.previous_id, Some(first_id));
+        assert_eq!(version.paths, vec!["1.png", "2.png"]);
+
+        let source = temp_dir.0.join(format!("manifest_{}.json", version.id));
+        let (merged, id) = arweave.load_manifest(source.to_str().unwrap()).await?;
+        assert_eq!(id, Some(version.id.clone()));
+        let paths = merged["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths["0.png"]["id"], Base64(vec![0; 32]).to_string());
+        assert_eq!(paths["1.png"]["id"], Base64(vec![3; 32]).to_string());
+        assert_eq!(paths["2.png"]["content_type"], "image/png");
+
+        let lineage: Vec<ManifestVersion> =
+            serde_json::from_slice(&std::fs::read(temp_dir.0.join(MANIFEST_LINEAGE_FILE_NAME))?)?;
+        assert_eq!(lineage, vec![version]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_manifest_index() -> Result<(), Error> {
+        let statuses = ["index.html", "404.html"]
+            .iter()
+            .map(|p| Status {
+                id: Base64(p.as_bytes().to_vec()),
+                file_path: Some(PathBuf::from(p)),
+                ..Default::default()
+            })
+            .collect();
+        let mut arweave = Arweave::default();
+        let manifest = arweave.create_manifest(statuses)?;
+        assert_eq!(arweave.with_manifest_index(manifest.clone())?, manifest);
+
+        arweave.manifest_index = Some(ManifestIndex {
+            index: Some("index.html".to_string()),
+            fallback: Some("404.html".to_string()),
+        });
+        let indexed = arweave.with_manifest_index(manifest.clone())?;
+        assert_eq!(indexed["index"], json!({"path": "index.html"}));
+        assert_eq!(
+            indexed["fallback"]["id"],
+            manifest["paths"]["404.html"]["id"]
+        );
+        assert_eq!(indexed["version"], "0.2.0");
+
+        let fallback_id = Base64(vec![1; 32]).to_string();
+        arweave.manifest_index = Some(ManifestIndex {
+            index: None,
+            fallback: Some(fallback_id.clone()),
+        });
+        let indexed = arweave.with_manifest_index(manifest.clone())?;
+        assert_eq!(indexed["fallback"]["id"], fallback_id);
+        assert!(indexed.get("index").is_none());
+
+        arweave.manifest_index = Some(ManifestIndex {
+            index: Some("missing.html".to_string()),
+            fallback: None,
+        });
+        assert_matches!(
+            arweave.with_manifest_index(manifest),
+            Err(Error::InvalidManifestPath(_))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reward_multiplier() -> Result<(), Error> {
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let price_terms = arweave.get_price_terms(1.5).await?;
+        assert_eq!(price_terms, (21_000_000, 19_500_000));
+        let last_tx = Base64::from_str("LCwsLCwsLA")?;
+        let transaction = arweave
+            .create_transaction(vec![0; 300 * 1024], None, Some(last_tx), price_terms, true)
+            .await?;
+        assert_eq!(transaction.reward, 40_500_000);
+        assert_eq!(apply_reward_mult(123_456_789_012, 1.5), 185_185_183_518);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_balance() -> Result<(), Error> {
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let required = arweave
+            .check_balance(vec![1, BLOCK_SIZE + 1].into_iter(), (1000, 100))
+            .await?;
+        assert_eq!(required, 2100);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_balance_guard_wallets() -> Result<(), Error> {
+        let key_ring = KeyRing::from_keypair_paths(vec![
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            PathBuf::from(
+                "tests/fixtures/arweave-keyfile-MlV6DeOtRmakDOf6vgOBlif795tcWimgyPsYYNQ8q1Y.json",
+            ),
+        ])
+        .await?;
+        let addresses = key_ring.wallet_addresses()?;
+        let (base_url, _) = serve_http(usize::MAX, move |request| {
+            match addresses
+                .iter()
+                .any(|a| request.starts_with(&format!("GET /wallet/{}/balance ", a)))
+            {
+                true => http_response(200, "600"),
+                false => http_response(500, ""),
+            }
+        });
+        let arweave = Arweave {
+            base_url,
+            key_ring: Some(key_ring),
+            balance_guard: Some(BalanceGuard::new(
+                Duration::from_secs(0),
+                LowBalanceAction::Abort,
+            )),
+            ..Arweave::default()
+        };
+        let guard = arweave.balance_guard.as_ref().unwrap();
+
+        // the balances of both wallets count
+        guard.add_remaining(1200);
+        arweave.check_balance_guard().await?;
+        guard.add_remaining(1);
+        assert_matches!(
+            arweave.check_balance_guard().await,
+            Err(Error::InsufficientBalance { .. })
+        );
+        guard.record_uploaded(1201);
+
+        // every upload stream stops before posting anything, each file costing more than both
+        // balances
+        let price_terms = (2000, 100);
+        let paths = vec![
+            PathBuf::from("tests/fixtures/0.png"),
+            PathBuf::from("tests/fixtures/1.png"),
+        ];
+        let results: Vec<Result<Status, Error>> = upload_files_stream(
+            &arweave,
+            paths.clone().into_iter(),
+            None,
+            None,
+            false,
+            None,
+            price_terms,
+            2,
+        )
+        .collect()
+        .await;
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Err(Error::InsufficientBalance { .. }))));
+        // aborted uploads don't hold on to their cost
+        assert_eq!(guard.remaining(), 0);
+
+        let paths_chunks = arweave.chunk_file_paths(paths.clone().into_iter(), 10_000_000)?;
+        let results: Vec<Result<BundleStatus, Error>> =
+            upload_bundles_stream(&arweave, paths_chunks, Vec::new(), None, price_terms, 2)
+                .collect()
+                .await;
+        assert_matches!(results[..], [Err(Error::InsufficientBalance { .. })]);
+
+        let paths_groups = arweave.group_file_paths_by_hash(paths.into_iter())?;
+        let results: Vec<Result<Status, Error>> =
+            upload_files_dedup_stream(&arweave, paths_groups, None, None, price_terms, 2)
+                .collect()
+                .await;
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Err(Error::InsufficientBalance { .. }))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_price() -> Result<(), Error> {
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            oracle: Arc::new(FixedRate(FiatPrices {
+                ar: 10.0,
+                sol: 20.0,
+            })),
+            ..Arweave::default()
+        };
+        let (base, incremental) = SIMULATED_PRICE_TERMS;
+        let (winstons, fiat_per_ar, fiat_per_sol) = arweave.get_price(&(BLOCK_SIZE * 3)).await?;
+        assert_eq!(winstons, BigUint::from(base + incremental * 2));
+        assert_eq!(fiat_per_ar, BigUint::from(1000u64));
+        assert_eq!(fiat_per_sol, BigUint::from(2000u64));
+
+        assert_eq!(crate::price_for_data_size(0, (1000, 100)), 1000);
+        assert_eq!(
+            crate::price_for_data_size(BLOCK_SIZE + 1, (1000, 100)),
+            1100
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_price_above_f32_precision() -> Result<(), Error> {
+        // neither term is representable as an f32
+        let (base, incremental) = (16_777_217_123u64, 16_777_218_001u64);
+        let (base_url, server) = serve_http(2, move |request| {
+            let winstons = if request.starts_with("GET /price/262144 ") {
+                base
+            } else {
+                base + incremental
+            };
+            http_response(200, winstons.to_string())
+        });
+        let arweave = Arweave {
+            base_url,
+            oracle: Arc::new(FixedRate(FiatPrices {
+                ar: 10.0,
+                sol: 20.0,
+            })),
+            ..Arweave::default()
+        };
+        let (winstons, _, _) = arweave.get_price(&(BLOCK_SIZE * 3)).await?;
+        assert_eq!(
+            winstons,
+            BigUint::from(base as u128 + incremental as u128 * 2)
+        );
+        assert_eq!(server.join().unwrap()?.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_price_cache() -> Result<(), Error> {
+        let (base_url, server) = serve_http(2, |request| {
+            let body = if request.starts_with("GET /price/262144 ") {
+                "1000"
+            } else {
+                "1100"
+            };
+            http_response(200, body)
+        });
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        assert!(Arweave::default().price_cache.is_none());
+        let arweave = Arweave {
+            base_url,
+            price_cache: Some(ResponseCache::new(
+                temp_dir.0.clone(),
+                Duration::from_secs(60),
+            )),
+            ..Arweave::default()
+        };
+        assert_eq!(arweave.get_price_terms(1.0).await?, (1000, 100));
+        // served from the cache, the server only answers two requests
+        assert_eq!(arweave.get_price_terms(1.0).await?, (1000, 100));
+        assert_eq!(server.join().unwrap()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_rewards() -> Result<(), Error> {
+        let price_terms = (u64::MAX, u64::MAX);
+        let reward = crate::price_for_data_size(BLOCK_SIZE * 10, price_terms);
+        assert_eq!(reward, u64::MAX as u128 * 10);
+
+        let transaction = Transaction {
+            reward,
+            quantity: reward,
+            ..Transaction::default()
+        };
+        let json = serde_json::to_string(&transaction)?;
+        assert!(json.contains(&format!("\"reward\":\"{}\"", reward)));
+        assert_eq!(serde_json::from_str::<Transaction>(&json)?.reward, reward);
+
+        let status = Status {
+            reward,
+            ..Status::default()
+        };
+        let status: Status = serde_json::from_str(&serde_json::to_string(&status)?)?;
+        assert_eq!(status.reward, reward);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_unknown_pending_ids() -> Result<(), Error> {
+        let key_ring = KeyRing::from_keypair_paths(vec![
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            PathBuf::from(
+                "tests/fixtures/arweave-keyfile-MlV6DeOtRmakDOf6vgOBlif795tcWimgyPsYYNQ8q1Y.json",
+            ),
+        ])
+        .await?;
+        let addresses = key_ring.wallet_addresses()?;
+        let node = |id: &Base64, block: Value| {
+            json!({"cursor": id.to_string(), "node": {
+                "id": id.to_string(),
+                "tags": [],
+                "data": {"size": "0"},
+                "block": block,
+            }})
+        };
+        let (known_id, unknown_id, mined_id) = (
+            Base64(vec![1; 32]),
+            Base64(vec![2; 32]),
+            Base64(vec![3; 32]),
+        );
+        let response = json!({"data": {"transactions": {
+            "pageInfo": {"hasNextPage": true},
+            "edges": [
+                node(&known_id, Value::Null),
+                node(&unknown_id, Value::Null),
+                node(&mined_id, json!({"id": "block", "height": 1, "timestamp": 1})),
+            ],
+        }}})
+        .to_string();
+        let (base_url, server) = serve_http(1, move |_| http_response(200, &response));
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            base_url,
+            key_ring: Some(key_ring),
+            ..Arweave::default()
+        };
+        let status = Status {
+            id: known_id,
+            status: StatusCode::Submitted,
+            file_path: Some(PathBuf::from("tests/fixtures/0.png")),
+            ..Default::default()
+        };
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+
+        // the page ends with a mined transaction, so no other page is requested
+        let ids = arweave
+            .get_unknown_pending_ids(Some(temp_dir.0.clone()))
+            .await?;
+        assert_eq!(ids, vec![unknown_id]);
+
+        let request = &server.join().unwrap()?[0];
+        assert!(request.starts_with("POST /graphql"));
+        assert!(addresses
+            .iter()
+            .all(|address| request.contains(&address.to_string())));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chain_dedup() -> Result<(), Error> {
+        let id = "kZb9p8hS8MYRKD2WLjbO8A2pBpHQ-ckmVqL8n4C3ZrU";
+        let response = json!({"data": {"transactions": {
+            "pageInfo": {"hasNextPage": false},
+            "edges": [{"cursor": "cursor", "node": {
+                "id": id,
+                "tags": [{"name": "Content-Type", "value": "image/png"}],
+                "data": {"size": "1024"},
+                "block": {"id": "block", "height": 1, "timestamp": 1},
+            }}],
+        }}})
+        .to_string();
+        let (base_url, server) = serve_http(1, move |_| http_response(200, &response));
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            base_url,
+            chain_dedup: true,
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                (0, 0),
+                None,
+            )
+            .await?;
+        assert_eq!(status.id.to_string(), id);
+        assert_eq!(status.status, StatusCode::Confirmed);
+        assert_eq!(status.content_type, "image/png");
+        assert_eq!(
+            arweave.read_status(file_path, temp_dir.0.clone()).await?.id,
+            status.id
+        );
+
+        let request = &server.join().unwrap()?[0];
+        assert!(request.starts_with("POST /graphql"));
+        assert!(request.contains(&crate::hash_file_contents(Path::new(
+            "tests/fixtures/0.png"
+        ))?));
+
+        // The server is gone, so only the status in the log directory can find this upload.
+        let file_path = PathBuf::from("tests/fixtures/1.png");
+        let content_hash = crate::rt::hash_file(&file_path).await?.to_string();
+        let logged = Status {
+            id: Base64(vec![1; 32]),
+            status: StatusCode::Submitted,
+            file_path: Some(file_path.clone()),
+            tags: vec![Tag::<String>::from_utf8_strs(
+                CONTENT_HASH_TAG,
+                &content_hash,
+            )?],
+            ..Default::default()
+        };
+        arweave
+            .write_status(logged, temp_dir.0.clone(), None)
+            .await?;
+        let (paths, uploaded) = arweave
+            .filter_uploaded_paths(vec![file_path.clone()], Some(temp_dir.0.clone()))
+            .await?;
+        assert!(paths.is_empty());
+        assert_eq!(uploaded[0].id, Base64(vec![1; 32]));
+
+        let (data_item, _) = arweave
+            .create_data_item_from_file_path(file_path, Vec::new())
+            .await?;
+        assert!(data_item
+            .tags
+            .iter()
+            .any(|t| t.name == CONTENT_HASH_TAG && t.value == content_hash));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_content_type_override() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.json");
+        let content_types = |tags: &Vec<Tag<Base64>>| -> Vec<String> {
+            tags.iter()
+                .filter(|tag| tag.name.0 == b"Content-Type")
+                .map(|tag| tag.value.to_utf8_string().unwrap())
+                .collect()
+        };
+
+        let transaction = arweave
+            .create_transaction_from_file_path(
+                file_path.clone(),
+                Some(vec![Tag::from_utf8_strs("Content-Type", "text/plain")?]),
+                Some(Base64(vec![0; 32])),
+                (0, 0),
+                true,
+                Some("application/ld+json"),
+            )
+            .await?;
+        assert_eq!(
+            content_types(&transaction.tags),
+            vec!["application/ld+json"]
+        );
+
+        let status = arweave
+            .upload_file_from_path(
+                file_path,
+                Some(temp_dir.0.clone()),
+                None,
+                Some(Base64(vec![0; 32])),
+                (0, 0),
+                Some("application/ld+json"),
+            )
+            .await?;
+        assert_eq!(status.content_type, "application/ld+json");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_status_written_before_post() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let status_path = temp_dir
+            .0
+            .join(crate::hash_file_path(&file_path))
+            .with_extension("json");
+        let (logged_tx, logged_rx) = std::sync::mpsc::channel();
+        let (base_url, server) = serve_http(1, move |_| {
+            logged_tx
+                .send(std::fs::read_to_string(&status_path).unwrap())
+                .unwrap();
+            http_response(400, "")
+        });
+
+        let arweave = Arweave {
+            base_url,
+            ..Arweave::default()
+        };
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                Some(Base64(vec![0; 32])),
+                (0, 0),
+                None,
+            )
+            .await?;
+        assert_matches!(status.status, StatusCode::Failed { .. });
+
+        server.join().unwrap()?;
+        let logged: Status = serde_json::from_str(&logged_rx.recv().unwrap())?;
+        assert_eq!(logged.status, StatusCode::Signed);
+        assert_eq!(logged.id, status.id);
+        assert_eq!(logged.first_submitted, None);
+        assert_eq!(
+            arweave
+                .read_status(file_path, temp_dir.0.clone())
+                .await?
+                .status,
+            status.status
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resume_signed() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let signed_tx_dir = temp_dir.0.join("signed");
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            signed_tx_dir: Some(signed_tx_dir.clone()),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let transaction = arweave
+            .create_transaction_from_file_path(file_path.clone(), None, None, (0, 0), true, None)
+            .await?;
+        let signed_transaction = arweave.sign_transaction(transaction)?;
+        fs::create_dir_all(&signed_tx_dir).await?;
+        let signed_path = arweave
+            .write_signed_transaction(&signed_transaction, &signed_tx_dir)
+            .await?;
+        let status = Status {
+            id: signed_transaction.id.clone(),
+            status: StatusCode::Signed,
+            file_path: Some(file_path.clone()),
+            ..Status::default()
+        };
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+
+        // Persisted transactions are posted again instead of being skipped.
+        assert!(
+            !arweave
+                .is_not_uploaded(file_path.clone(), Some(temp_dir.0.clone()))
+                .await
+        );
+        assert!(!signed_path.exists());
+        let status = arweave
+            .read_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_eq!(
+            arweave.get_status(&status.id).await?.status,
+            StatusCode::Confirmed
+        );
+
+        // Transactions that never reached the network are uploaded again.
+        let status = Status {
+            id: Base64(vec![1; 32]),
+            status: StatusCode::Signed,
+            ..status
+        };
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+        assert!(
+            arweave
+                .is_not_uploaded(file_path.clone(), Some(temp_dir.0.clone()))
+                .await
+        );
+        assert_eq!(
+            arweave
+                .read_status(file_path, temp_dir.0.clone())
+                .await?
+                .status,
+            StatusCode::NotFound
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_upload_from_status() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let file_path = temp_dir.0.join("data.txt");
+        fs::write(&file_path, "tasty").await?;
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                Some(Base64(vec![0; 32])),
+                (0, 0),
+                None,
+            )
+            .await?;
+        assert_eq!(status.data_size, Some(5));
+        assert!(status.data_root.is_some());
+
+        let verification = arweave
+            .verify_upload(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+
+        fs::write(&file_path, "bland").await?;
+        let verification = arweave.verify_upload(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Mismatch);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_compressed_upload() -> Result<(), Error> {
+        use crate::compression::Compression;
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            compression: Some(Compression::Gzip),
+            ..Arweave::default()
+        };
+        let file_path = temp_dir.0.join("data.txt");
+        fs::write(&file_path, "tasty tasty tasty").await?;
+        arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                Some(Base64(vec![0; 32])),
+                (0, 0),
+                None,
+            )
+            .await?;
+
+        let verification = arweave
+            .verify_upload(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+
+        fs::write(&file_path, "bland bland bland").await?;
+        let verification = arweave.verify_upload(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Mismatch);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_manifest_from_log_dir() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            manifest_index: Some(ManifestIndex {
+                index: Some("0.png".to_string()),
+                fallback: None,
+            }),
+            ..Arweave::default()
+        };
+        let root = PathBuf::from("tests/fixtures");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        for file_path in [root.join("0.png"), root.join("1.png")] {
+            arweave
+                .upload_file_from_path(
+                    file_path,
+                    Some(temp_dir.0.clone()),
+                    None,
+                    None,
+                    price_terms,
+                    None,
+                )
+                .await?;
+        }
+        assert_eq!(
+            arweave
+                .post_manifest_from_log_dir(temp_dir.0.clone(), Some(&root), price_terms)
+                .await?,
+            None
+        );
+
+        arweave
+            .update_status(root.join("0.png"), temp_dir.0.clone())
+            .await?;
+        let (id, num_files) = arweave
+            .post_manifest_from_log_dir(temp_dir.0.clone(), Some(&root), price_terms)
+            .await?
+            .unwrap();
+        assert_eq!(num_files, 1);
+        let manifest: Value = serde_json::from_slice(
+            &fs::read(temp_dir.0.join(format!("manifest_{}.json", id))).await?,
+        )?;
+        assert!(manifest["0.png"]["id"].is_string());
+
+        // Bundled files are confirmed through their bundle status.
+        let bundle_dir = TempDir::from_str("./tests/").await?;
+        arweave
+            .upload_files_in_bundles(
+                vec![root.join("0.png"), root.join("1.png")],
+                Some(bundle_dir.0.clone()),
+                None,
+                price_terms,
+                1,
+            )
+            .await?;
+        let bundle_log_dir = format!("{}/", bundle_dir.0.display());
+        for bundle_status in arweave.read_bundle_statuses(&bundle_log_dir).await? {
+            arweave
+                .update_bundle_status(bundle_dir.0.join(format!("{}.json", bundle_status.id)))
+                .await?;
+        }
+        let (_, num_files) = arweave
+            .post_manifest_from_log_dir(bundle_dir.0.clone(), Some(&root), price_terms)
+            .await?
+            .unwrap();
+        assert_eq!(num_files, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_download() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave::default();
+        let mut transaction = arweave.merklize(b"tasty".to_vec())?;
+        transaction.owner = arweave.crypto.keypair_modulus()?;
+        let transaction = arweave.sign_transaction(transaction)?;
+        let header = serde_json::to_string(&transaction)?;
+        let gzipped = crate::compression::Compression::Gzip.compress(b"tasty")?;
+        let item_id = Base64(vec![1; 32]);
+        let bundle_id = Base64(vec![2; 32]);
+        let (bundle_status_request, item_request) = (
+            format!("GET /tx/{}/status", bundle_id),
+            format!("GET /{} ", item_id),
+        );
+        let raw_status = json!({
+            "block_height": 1,
+            "block_indep_hash": Base64(vec![3; 48]).to_string(),
+            "number_of_confirmations": 10,
+        })
+        .to_string();
+        let item_data = gzipped.clone();
+        let mut bundle_status_requests = 0;
+        let (base_url, server) = serve_http(7, move |request| {
+            if request.starts_with(&bundle_status_request) {
+                bundle_status_requests += 1;
+                match bundle_status_requests {
+                    1 => http_response(200, &raw_status),
+                    _ => http_response(202, ""),
+                }
+            } else if request.starts_with(&item_request) {
+                http_response(200, &item_data)
+            } else if request.starts_with("GET /tx/") {
+                http_response(200, &header)
+            } else {
+                http_response(200, "tasty")
+            }
+        });
+        let arweave = Arweave {
+            base_url,
+            ..arweave
+        };
+
+        let file_path = temp_dir.0.join("data.txt");
+        fs::write(&file_path, "tasty").await?;
+        let status = Status {
+            id: transaction.id.clone(),
+            file_path: Some(file_path.clone()),
+            ..Status::default()
+        };
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+
+        let verification = arweave
+            .verify_download(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+
+        fs::write(&file_path, "bland").await?;
+        let verification = arweave
+            .verify_download(file_path, temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Mismatch);
+
+        // Compressed data item of a bundle, confirmed and then pending.
+        let file_path = temp_dir.0.join("bundled.txt");
+        fs::write(&file_path, "tasty").await?;
+        let status = Status {
+            id: item_id,
+            file_path: Some(file_path.clone()),
+            bundle_id: Some(bundle_id),
+            tags: vec![Tag::from_utf8_strs("Content-Encoding", "gzip")?],
+            data_root: Some(arweave.merklize(gzipped.clone())?.data_root),
+            data_size: Some(gzipped.len() as u64),
+            ..Status::default()
+        };
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+        let verification = arweave
+            .verify_download(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+        let verification = arweave
+            .verify_download(file_path, temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Pending);
+        server.join().unwrap()?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_status_webhook() -> Result<(), Error> {
+        let (webhook_url, server) = serve_http(1, |_| http_response(200, ""));
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            webhook_url: Some(webhook_url),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        let status = arweave.update_status(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(status.status, StatusCode::Confirmed);
+
+        let request = &server.join().unwrap()?[0];
+        let body = &request[request.find("{").unwrap()..];
+        let notification: StatusNotification = serde_json::from_str(body)?;
+        assert_eq!(notification.id, status.id);
+        assert_eq!(notification.status, StatusCode::Confirmed);
+        assert_eq!(notification.previous_status, StatusCode::Submitted);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_range() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let data = std::fs::read("tests/fixtures/1mb.bin")?;
+        let transaction = arweave.merklize(data.clone())?;
+        let chunks: Vec<(std::ops::Range<usize>, String)> = (0..transaction.chunks.len())
+            .map(|i| {
+                let node = &transaction.chunks[i];
+                let chunk = serde_json::to_string(&transaction.get_chunk(i).unwrap()).unwrap();
+                (node.min_byte_range..node.max_byte_range, chunk)
+            })
+            .collect();
+        let header = serde_json::to_string(&transaction.clone_with_no_data()?)?;
+        let data_start = 1_000_000;
+        let tx_offset = json!({
+            "size": data.len().to_string(),
+            "offset": (data_start + data.len() - 1).to_string(),
+        })
+        .to_string();
+
+        let (base_url, _) = serve_http(4, move |request| {
+            let path = request.split_whitespace().nth(1).unwrap();
+            let body = if path.ends_with("/offset") {
+                tx_offset.clone()
+            } else if let Some(weave_offset) = path.strip_prefix("/chunk/") {
+                let position = weave_offset.parse::<usize>().unwrap() - data_start;
+                chunks
+                    .iter()
+                    .find(|(range, _)| range.contains(&position))
+                    .unwrap()
+                    .1
+                    .clone()
+            } else {
+                header.clone()
+            };
+            http_response(200, body)
+        });
+
+        let arweave = Arweave {
+            base_url,
+            ..Arweave::default()
+        };
+        let id = Base64(vec![1; 32]);
+        // Spans the boundary between the first and second chunk.
+        let range = arweave.download_range(&id, BLOCK_SIZE - 100, 200).await?;
+        assert_eq!(
+            range,
+            data[BLOCK_SIZE as usize - 100..BLOCK_SIZE as usize + 100]
+        );
+
+        let header = serde_json::to_string(&transaction.clone_with_no_data()?)?;
+        let tx_offset = json!({ "size": data.len().to_string(), "offset": "10" }).to_string();
+        let (base_url, _) = serve_http(2, move |request| {
+            match request
+                .split_whitespace()
+                .nth(1)
+                .unwrap()
+                .ends_with("/offset")
+            {
+                true => http_response(200, &tx_offset),
+                false => http_response(200, &header),
+            }
+        });
+        let arweave = Arweave {
+            base_url,
+            ..Arweave::default()
+        };
+        assert_matches!(
+            arweave.download_range(&id, 0, 100).await,
+            Err(Error::InvalidTxOffset(_))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_transaction() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let data = std::fs::read("tests/fixtures/0.png")?;
+        let mut transaction = arweave.merklize(data.clone())?;
+        transaction.owner = arweave.crypto.keypair_modulus()?;
+        let mut transaction = arweave.sign_transaction(transaction)?;
+        transaction.data = Base64(vec![]);
+        let header = serde_json::to_string(&transaction)?;
+        let id = transaction.id.clone();
+
+        let serve = |header: String, data: Vec<u8>| {
+            serve_http(2, move |request| match request.starts_with("GET /tx/") {
+                true => http_response(200, &header),
+                false => http_response(200, &data),
+            })
+            .0
+        };
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let file_path = temp_dir.0.join("nested/0.png");
+        let arweave = Arweave {
+            base_url: serve(header.clone(), data.clone()),
+            ..Arweave::default()
+        };
+        let verification = arweave
+            .download_transaction(id.clone(), file_path.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+        assert_eq!(fs::read(&file_path).await?, data);
+
+        let arweave = Arweave {
+            base_url: serve(header, data[1..].to_vec()),
+            ..Arweave::default()
+        };
+        let file_path = temp_dir.0.join("1.png");
+        assert_matches!(
+            arweave
+                .download_transaction(id.clone(), file_path.clone())
+                .await,
+            Err(Error::DataMismatch(_))
+        );
+        assert!(!file_path.exists());
+
+        transaction.tags = vec![Tag::from_utf8_strs("Content-Type", "image/png")?];
+        let arweave = Arweave {
+            base_url: serve(serde_json::to_string(&transaction)?, data.clone()),
+            ..Arweave::default()
+        };
+        assert_matches!(
+            arweave.download_transaction(id, file_path.clone()).await,
+            Err(Error::InvalidSignature)
+        );
+        assert!(!file_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_manifest_download_paths() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let id = Base64(vec![1; 32]);
+        let manifest = json!({
+            "manifest": "arweave/paths",
+            "version": "0.1.0",
+            "paths": {"images/0.png": {"id": id.to_string()}}
+        });
+        assert_eq!(
+            arweave.manifest_download_paths(&manifest, Path::new("out"))?,
+            vec![(id.clone(), PathBuf::from("out/images/0.png"))]
+        );
+
+        let manifest = json!({"../0.png": {"id": id.to_string(), "files": []}});
+        assert_matches!(
+            arweave.manifest_download_paths(&manifest, Path::new("out")),
+            Err(Error::InvalidManifestPath(_))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_all_statuses() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        for file_path in ["tests/fixtures/0.png", "tests/fixtures/1.png"] {
+            arweave
+                .upload_file_from_path(
+                    PathBuf::from(file_path),
+                    Some(temp_dir.0.clone()),
+                    None,
+                    None,
+                    price_terms,
+                    None,
+                )
+                .await?;
+        }
+        fs::write(temp_dir.0.join("assets.json"), "{}").await?;
+        // Statuses of uploads without a file path are keyed by id instead.
+        let file_path = PathBuf::from("tests/fixtures/1.png");
+        let mut status = arweave
+            .read_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        fs::remove_file(
+            temp_dir
+                .0
+                .join(crate::hash_file_path(&file_path))
+                .with_extension("json"),
+        )
+        .await?;
+        status.file_path = None;
+        arweave
+            .write_status(status, temp_dir.0.clone(), None)
+            .await?;
+
+        let statuses = arweave.read_all_statuses(temp_dir.0.clone()).await?;
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses.iter().all(|s| s.status == StatusCode::Submitted));
+
+        let updated: Vec<Status> =
+            crate::update_all_statuses_stream(&arweave, temp_dir.0.clone(), 2)?
+                .try_collect()
+                .await?;
+        assert_eq!(updated.len(), 2);
+        let confirmed = arweave
+            .filter_all_statuses(temp_dir.0.clone(), Some(vec![StatusCode::Confirmed]), None)
+            .await?;
+        assert_eq!(confirmed.len(), 2);
+        assert!(arweave
+            .status_summary_all(temp_dir.0.clone())
+            .await?
+            .contains(" Total                     2"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_filter_statuses_by_tags_and_path() -> Result<(), Error> {
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let mut statuses = Vec::new();
+        for file_path in [
+            "tests/fixtures/0.png",
+            "tests/fixtures/1.png",
+            "tests/fixtures/0.json",
+        ] {
+            statuses.push(
+                arweave
+                    .upload_file_from_path(
+                        PathBuf::from(file_path),
+                        None,
+                        Some(vec![Tag::<Base64>::from_utf8_strs("Collection", "apes")?]),
+                        None,
+                        price_terms,
+                        None,
+                    )
+                    .await?,
+            );
+        }
+        assert!(statuses[0]
+            .tags
+            .contains(&Tag::<String>::from_utf8_strs("Content-Type", "image/png")?));
+
+        let pngs = StatusFilter {
+            tags: vec![
+                Tag::<String>::from_utf8_strs("Content-Type", "image/png")?,
+                Tag::<String>::from_utf8_strs("Collection", "apes")?,
+            ],
+            path_pattern: None,
+        };
+        let filtered = arweave.filter_statuses_with(statuses.clone(), None, None, Some(&pngs))?;
+        assert_eq!(filtered.len(), 2);
+
+        let first = StatusFilter {
+            path_pattern: Some(glob::Pattern::new("tests/fixtures/0.*").unwrap()),
+            ..pngs
+        };
+        let filtered = arweave.filter_statuses_with(statuses, None, None, Some(&first))?;
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(
+            filtered[0].file_path,
+            Some(PathBuf::from("tests/fixtures/0.png"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_during_maintenance() -> Result<(), Error> {
+        let serve = |responses: Vec<&'static str>| {
+            let mut responses = responses.into_iter();
+            serve_http(responses.len(), move |_| {
+                responses.next().unwrap().as_bytes().to_vec()
+            })
+            .0
+        };
+        let unavailable = "HTTP/1.1 503 Service Unavailable\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+        let ok = "HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+        let transaction = Transaction {
+            id: Base64(vec![1; 32]),
+            ..Transaction::default()
+        };
+
+        let arweave = Arweave {
+            base_url: serve(vec![unavailable]),
+            adaptive_concurrency: Some(AdaptiveConcurrency::new(4)),
+            ..Arweave::default()
+        };
+        assert_matches!(
+            arweave.post_transaction(&transaction).await,
+            Err(Error::GatewayUnavailable(Some(_)))
+        );
+        // Maintenance doesn't count towards the error rate.
+        assert_eq!(arweave.adaptive_concurrency.as_ref().unwrap().limit(), 4);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let arweave = Arweave {
+            base_url: serve(vec![unavailable, ok]),
+            maintenance: Some(MaintenanceGate::new(Duration::from_secs(30)).with_events(tx)),
+            adaptive_concurrency: Some(AdaptiveConcurrency::new(4)),
+            ..Arweave::default()
+        };
+        arweave.post_transaction(&transaction).await?;
+        assert_eq!(arweave.adaptive_concurrency.as_ref().unwrap().limit(), 4);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            MaintenanceEvent::Paused {
+                retry_after: Some(Duration::from_secs(0))
+            }
+        );
+        assert_matches!(rx.try_recv(), Ok(MaintenanceEvent::Resumed { .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_atomic_asset() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let source_path = temp_dir.0.join("contract.js");
+        fs::write(&source_path, "export function handle(state, action) {}").await?;
+        let source_status = arweave
+            .deploy_contract_source(source_path, Some(temp_dir.0.clone()), price_terms)
+            .await?;
+        assert_eq!(source_status.status, StatusCode::Submitted);
+
+        let init_state = json!({"owner": "owner", "balances": {"owner": 1}});
+        let tags = arweave.contract_tags(&source_status.id, &init_state)?;
+        assert_eq!(
+            tags[2],
+            Tag::<Base64>::from_utf8_strs("Contract-Src", &source_status.id.to_string())?
+        );
+        assert_eq!(
+            serde_json::from_slice::<Value>(&tags[3].value.0)?,
+            init_state
+        );
+
+        let status = arweave
+            .upload_atomic_asset(
+                PathBuf::from("tests/fixtures/0.png"),
+                Some(temp_dir.0.clone()),
+                None,
+                &source_status.id,
+                &init_state,
+                price_terms,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_eq!(status.content_type, "image/png");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_from_url() -> Result<(), Error> {
+        let body = std::fs::read("tests/fixtures/0.png")?;
+        let (base_url, _) = serve_http(2, move |_| http_response(200, &body));
+        let url = base_url.join("bucket/images/0.png").unwrap();
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let status = arweave
+            .upload_file_from_url(
+                url.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_eq!(status.content_type, "image/png");
+        assert_eq!(status.file_path, Some(PathBuf::from(url.as_str())));
+        assert_eq!(
+            arweave
+                .read_status(PathBuf::from(url.as_str()), temp_dir.0.clone())
+                .await?
+                .id,
+            status.id
+        );
+
+        // With a chunk budget the object is uploaded a chunk at a time from a temporary file.
+        let arweave = Arweave {
+            chunk_budget: Some(ChunkBudget::new(2)),
+            ..arweave
+        };
+        let streamed = arweave
+            .upload_file_from_url(url, None, None, None, price_terms)
+            .await?;
+        assert_eq!(streamed.status, StatusCode::Submitted);
+        assert_eq!(streamed.data_root, status.data_root);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seed_chunks() -> Result<(), Error> {
+        let (peer, server) = serve_http(2, |_| http_response(200, ""));
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            seed_peers: vec![peer.clone(), Url::from_str("http://127.0.0.1:1/").unwrap()],
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_eq!(status.seeded_peers, vec![peer.to_string()]);
+        assert_eq!(
+            arweave
+                .read_status(file_path, temp_dir.0.clone())
+                .await?
+                .seeded_peers,
+            status.seeded_peers
+        );
+        let paths: Vec<String> = server
+            .join()
+            .unwrap()?
+            .iter()
+            .map(|request| request.split(' ').nth(1).unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(paths, vec!["/tx/", "/chunk/"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_discover_peers() -> Result<(), Error> {
+        let (peer, server) = serve_http(2, |request| {
+            let body = match request.split(' ').nth(1) {
+                // Lists the server itself, as named by the host header of the request.
+                Some("/peers") => {
+                    let host = request.lines().find_map(|l| l.strip_prefix("host: "));
+                    json!([host, "127.0.0.1:1"]).to_string()
+                }
+                _ => json!({"network": "arweave.N.1"}).to_string(),
+            };
+            http_response(200, body)
+        });
+
+        let arweave = Arweave {
+            base_url: peer.clone(),
+            ..Arweave::default()
+        };
+        assert_eq!(arweave.discover_peers(5).await?, vec![peer]);
+        let paths: Vec<String> = server
+            .join()
+            .unwrap()?
+            .iter()
+            .map(|request| request.split(' ').nth(1).unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(paths, vec!["/peers", "/info"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_offline_signing() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let online = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let offline = Arweave::from_keypair_path(
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            Url::from_str("http://url.com").unwrap(),
+        )
+        .await?;
+
+        let price_terms = online.get_price_terms(1.0).await?;
+        let unsigned_path = online
+            .write_unsigned_transaction(
+                PathBuf::from("tests/fixtures/1mb.bin"),
+                &temp_dir.0,
+                None,
+                None,
+                price_terms,
+            )
+            .await?;
+        let unsigned = online.read_unsigned_transaction(&unsigned_path).await?;
+        assert!(unsigned.transaction.owner.0.is_empty());
+        assert_matches!(
+            online.post_transaction(&unsigned.transaction).await,
+            Err(Error::UnsignedTransaction)
+        );
+
+        let signed_path = offline
+            .sign_transaction_file(&unsigned_path, &temp_dir.0)
+            .await?;
+        let signed = online.read_signed_transaction(&signed_path).await?;
+        assert_eq!(signed.transaction.owner, offline.crypto.keypair_modulus()?);
+        assert_eq!(signed.chunks, unsigned.chunks);
+        online.verify_transaction(&signed.transaction)?;
+        assert_matches!(
+            online.verify_transaction(&unsigned.transaction),
+            Err(Error::UnsignedTransaction)
+        );
+
+        let (id, _) = online.post_signed_transaction_file(&signed_path, 2).await?;
+        assert_eq!(id, signed.transaction.id);
+        assert_eq!(online.get_status(&id).await?.status, StatusCode::Confirmed);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_signed_transaction_file() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let signed_tx_dir = temp_dir.0.join("signed");
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+
+        let data = std::fs::read("tests/fixtures/0.png")?;
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let transaction = arweave
+            .create_transaction(data, None, None, price_terms, true)
+            .await?;
+        let signed_transaction = arweave.sign_transaction(transaction)?;
+        fs::create_dir_all(&signed_tx_dir).await?;
+        let path = arweave
+            .write_signed_transaction(&signed_transaction, &signed_tx_dir)
+            .await?;
+
+        let signed = arweave.read_signed_transaction(&path).await?;
+        assert_eq!(signed.chunks.len(), signed_transaction.chunks.len());
+        assert!(signed.transaction.data.0.is_empty());
+
+        // Corrupted files are rejected before anything is posted.
+        let mut corrupted = arweave.read_signed_transaction(&path).await?;
+        corrupted.chunks[0].chunk.0[0] ^= 1;
+        assert_matches!(
+            arweave.post_signed_transaction(corrupted, 2).await,
+            Err(Error::InvalidDataRoot(_))
+        );
+        let mut corrupted = arweave.read_signed_transaction(&path).await?;
+        corrupted.transaction.id.0[0] ^= 1;
+        assert_matches!(
+            arweave.post_signed_transaction(corrupted, 2).await,
+            Err(Error::InvalidTransactionId(_))
+        );
+
+        let (id, _) = arweave.post_signed_transaction_file(&path, 2).await?;
+        assert_eq!(id, signed_transaction.id);
+        assert!(!path.exists());
+        assert_eq!(arweave.get_status(&id).await?.status, StatusCode::Confirmed);
+
+        // Uploads remove their signed transactions once posted.
+        let arweave = Arweave {
+            signed_tx_dir: Some(signed_tx_dir.clone()),
+            ..arweave
+        };
+        arweave
+            .upload_file_from_path(
+                PathBuf::from("tests/fixtures/1.png"),
+                None,
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert_eq!(std::fs::read_dir(&signed_tx_dir)?.count(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_key_ring_rotation() -> Result<(), Error> {
+        let key_ring = KeyRing::from_keypair_paths(vec![
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            PathBuf::from(
+                "tests/fixtures/arweave-keyfile-MlV6DeOtRmakDOf6vgOBlif795tcWimgyPsYYNQ8q1Y.json",
+            ),
+        ])
+        .await?;
+        let addresses = key_ring.wallet_addresses()?;
+        let arweave = Arweave {
+            key_ring: Some(key_ring),
+            ..Arweave::default()
+        };
+        assert_eq!(arweave.signing_wallet_addresses()?, addresses);
+
+        let transaction = arweave
+            .create_transaction(
+                b"rotate".to_vec(),
+                None,
+                Some(Base64::from_str("LCwsLCwsLA")?),
+                (0, 0),
+                true,
+            )
+            .await?;
+        let signed = (0..3)
+            .map(|_| arweave.sign_upload_transaction(transaction.clone_with_no_data()?))
+            .collect::<Result<Vec<Transaction>, Error>>()?;
+        let owners: Vec<Base64> = signed
+            .iter()
+            .map(|t| Base64(arweave.crypto.hash_sha256(&t.owner.0).unwrap().to_vec()))
+            .collect();
+        assert_eq!(
+            owners,
+            vec![
+                addresses[0].clone(),
+                addresses[1].clone(),
+                addresses[0].clone()
+            ]
+        );
+        for transaction in &signed {
+            arweave.verify_transaction(transaction)?;
+        }
+        let mut tampered = signed[1].clone_with_no_data()?;
+        tampered.owner = signed[0].owner.clone();
+        assert_matches!(
+            arweave.verify_transaction(&tampered),
+            Err(Error::InvalidSignature)
+        );
+        assert_matches!(KeyRing::new(Vec::new()).err(), Some(Error::EmptyKeyRing));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reupload_not_found() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let not_found = |file_path: &str, blocks_ago: i64| Status {
+            id: Base64(vec![1; 32]),
+            status: StatusCode::NotFound,
+            file_path: Some(PathBuf::from(file_path)),
+            first_submitted: Some(
+                Utc::now() - chrono::Duration::seconds(blocks_ago * BLOCK_TIME_SECS as i64),
+            ),
+            ..Status::default()
+        };
+        arweave
+            .write_status(
+                not_found("tests/fixtures/0.png", 5),
+                temp_dir.0.clone(),
+                None,
+            )
+            .await?;
+        arweave
+            .write_status(
+                not_found("tests/fixtures/1.png", 1),
+                temp_dir.0.clone(),
+                None,
+            )
+            .await?;
+
+        let paths = vec![
+            PathBuf::from("tests/fixtures/0.png"),
+            PathBuf::from("tests/fixtures/1.png"),
+            PathBuf::from("tests/fixtures/2.png"),
+        ];
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let reuploaded = arweave
+            .reupload_not_found(
+                paths.clone().into_iter(),
+                temp_dir.0.clone(),
+                3,
+                None,
+                price_terms,
+                2,
+            )
+            .await?;
+        assert_eq!(reuploaded.len(), 1);
+        assert_eq!(reuploaded[0].file_path, Some(paths[0].clone()));
+
+        let status = arweave
+            .read_status(paths[0].clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_ne!(status.id, Base64(vec![1; 32]));
+        assert_eq!(
+            arweave
+                .read_status(paths[1].clone(), temp_dir.0.clone())
+                .await?
+                .status,
+            StatusCode::NotFound
+        );
+        Ok(())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{
-        error::Error,
-        transaction::{Base64, FromUtf8Strs, Tag},
-        utils::TempDir,
-        Arweave, Status,
-    };
-    use futures::future::try_join_all;
-    use glob::glob;
-    use matches::assert_matches;
-    use std::{path::PathBuf, str::FromStr, time::Instant};
-    use tokio::fs;
-    use url::Url;
 
     #[tokio::test]
-    async fn test_cannot_post_unsigned_transaction() -> Result<(), Error> {
-        let arweave = Arweave::from_keypair_path(
-            PathBuf::from(
-                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
-            ),
-            Url::from_str("http://url.com").unwrap(),
+    async fn test_bundle_policy() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            bundle_policy: Some(BundlePolicy::default()),
+            // Bundles and files share its permits without holding two at once.
+            adaptive_concurrency: Some(AdaptiveConcurrency::new(1)),
+            balance_guard: Some(BalanceGuard::new(
+                Duration::from_secs(0),
+                LowBalanceAction::Abort,
+            )),
+            ..Arweave::from_keypair_path(
+                PathBuf::from(
+                    "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+                ),
+                Url::from_str("https://arweave.net").unwrap(),
+            )
+            .await?
+        };
+        let paths = vec![
+            PathBuf::from("tests/fixtures/0.png"),
+            PathBuf::from("tests/fixtures/1mb.bin"),
+            PathBuf::from("tests/fixtures/1.png"),
+        ];
+
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let statuses: Vec<Status> = upload_files_stream(
+            &arweave,
+            paths.clone().into_iter(),
+            None,
+            Some(temp_dir.0.clone()),
+            false,
+            None,
+            price_terms,
+            2,
+        )
+        .try_collect()
+        .await?;
+        assert_eq!(statuses.len(), 3);
+        assert_eq!(arweave.balance_guard.as_ref().unwrap().remaining(), 0);
+        let bundled: Vec<&Status> = statuses.iter().filter(|s| s.bundle_id.is_some()).collect();
+        assert_eq!(bundled.len(), 2);
+        assert_eq!(bundled[0].bundle_id, bundled[1].bundle_id);
+        assert!(bundled.iter().all(
+            |s| s.file_path != Some(paths[1].clone()) && s.id != *s.bundle_id.as_ref().unwrap()
+        ));
+
+        let status = arweave
+            .update_status(paths[0].clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.status, StatusCode::Confirmed);
+        assert_eq!(status.bundle_id, bundled[0].bundle_id);
+        let data = fs::read(&paths[0]).await?;
+        assert_eq!(status.data_size, Some(data.len() as u64));
+        assert_eq!(status.data_root, Some(arweave.merklize(data)?.data_root));
+        let verification = arweave
+            .verify_upload(paths[0].clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(verification.outcome, VerifyOutcome::Verified);
+        let bundle_statuses = arweave
+            .read_bundle_statuses(&format!("{}/", temp_dir.0.display()))
+            .await?;
+        assert_eq!(bundle_statuses.len(), 1);
+        assert_eq!(bundle_statuses[0].status, StatusCode::Submitted);
+        assert_eq!(Some(&bundle_statuses[0].id), bundled[0].bundle_id.as_ref());
+        // The statuses of bundled files keep the tags of their data items and share the reward
+        // of the bundle by data size.
+        let file_statuses = arweave.file_statuses_from_bundle_status(&bundle_statuses[0])?;
+        assert!(file_statuses.iter().all(|s| s
+            .tags
+            .iter()
+            .any(|tag| tag.name == "Content-Type" && tag.value == s.content_type)));
+        let total_size: u64 = file_statuses.iter().map(|s| s.data_size.unwrap()).sum();
+        assert!(file_statuses.iter().all(|s| s.reward
+            == bundle_statuses[0].reward * s.data_size.unwrap() as u128 / total_size as u128));
+        let manifest = arweave.create_manifest_from_bundle_statuses(bundle_statuses)?;
+        assert!(manifest["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .all(|entry| entry.get("data_root").is_none()));
+
+        // Bundles are posted as they fill up.
+        let arweave = Arweave {
+            bundle_policy: Some(BundlePolicy {
+                bundle_size: 1,
+                ..BundlePolicy::default()
+            }),
+            ..arweave
+        };
+        let statuses: Vec<Status> = upload_files_stream(
+            &arweave,
+            paths.clone().into_iter(),
+            None,
+            None,
+            false,
+            None,
+            price_terms,
+            2,
         )
+        .try_collect()
         .await?;
+        let bundled: Vec<&Status> = statuses.iter().filter(|s| s.bundle_id.is_some()).collect();
+        assert_eq!(bundled.len(), 2);
+        assert_ne!(bundled[0].bundle_id, bundled[1].bundle_id);
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn test_failed_and_dropped_statuses() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            base_url: Url::from_str("http://127.0.0.1:1/").unwrap(),
+            ..Arweave::default()
+        };
         let file_path = PathBuf::from("tests/fixtures/0.png");
-        let last_tx = Base64::from_str("LCwsLCwsLA")?;
-        let other_tags = vec![Tag::<Base64>::from_utf8_strs("key2", "value2")?];
-        let transaction = arweave
-            .create_transaction_from_file_path(
-                file_path,
-                Some(other_tags),
-                Some(last_tx),
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                Some(Base64::from_str("LCwsLCwsLA")?),
                 (0, 0),
-                true,
+                None,
             )
             .await?;
+        assert_matches!(&status.status, StatusCode::Failed { reason } if !reason.is_empty());
+        assert!(!status.id.0.is_empty());
+        assert_eq!(
+            arweave
+                .read_status(file_path.clone(), temp_dir.0.clone())
+                .await?,
+            status
+        );
+        assert!(
+            arweave
+                .is_not_uploaded(file_path, Some(temp_dir.0.clone()))
+                .await
+        );
 
-        let error = arweave.post_transaction(&transaction).await.unwrap_err();
-        assert_matches!(error, Error::UnsignedTransaction);
+        assert_eq!(
+            StatusCode::Pending.updated(StatusCode::NotFound),
+            StatusCode::Dropped
+        );
+        assert_eq!(
+            StatusCode::Dropped.updated(StatusCode::Confirmed),
+            StatusCode::Confirmed
+        );
+        assert_eq!(status.status.updated(StatusCode::NotFound), status.status);
+        assert_eq!(
+            StatusCode::Submitted.updated(StatusCode::NotFound),
+            StatusCode::NotFound
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_budget() -> Result<(), Error> {
+        use crate::merkle::{chunk_ranges, MAX_CHUNK_SIZE};
+
+        assert_eq!(chunk_ranges(0), vec![0..0]);
+        assert_eq!(
+            chunk_ranges(2 * MAX_CHUNK_SIZE).last(),
+            Some(&(2 * MAX_CHUNK_SIZE..2 * MAX_CHUNK_SIZE))
+        );
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let file_path = temp_dir.0.join("large.bin");
+        let data: Vec<u8> = (0..3 * MAX_CHUNK_SIZE + 1000).map(|i| i as u8).collect();
+        fs::write(&file_path, &data).await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            chunk_budget: Some(ChunkBudget::new(2)),
+            ..Arweave::default()
+        };
+        let last_tx = Some(Base64(vec![0; 32]));
 
+        let streamed = arweave
+            .create_transaction_from_file_chunks(&file_path, None, last_tx.clone(), (0, 0), true)
+            .await?;
+        let read = arweave
+            .create_transaction(data, None, last_tx, (0, 0), true)
+            .await?;
+        assert!(streamed.data.0.is_empty());
+        assert_eq!(streamed.data_root, read.data_root);
+        assert_eq!(streamed.data_size, read.data_size);
+        assert_eq!(streamed.chunks, read.chunks);
+        assert_eq!(streamed.proofs, read.proofs);
+        assert_eq!(streamed.tags, read.tags);
+
+        let status = arweave
+            .upload_file_from_path(file_path, None, None, None, (0, 0), None)
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert_eq!(arweave.chunk_budget.as_ref().unwrap().available(), 2);
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_create_write_read_status() -> Result<(), Error> {
-        let arweave = Arweave::from_keypair_path(
-            PathBuf::from(
-                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
-            ),
-            Url::from_str("http://url.com").unwrap(),
-        )
-        .await?;
+    async fn test_ipfs_cid_tag() -> Result<(), Error> {
+        use crate::{compression::Compression, ipfs};
 
-        let file_path = PathBuf::from("tests/fixtures/0.png");
-        let last_tx = Base64::from_str("LCwsLCwsLA")?;
-        let other_tags = vec![Tag::<Base64>::from_utf8_strs("key2", "value2")?];
-        let transaction = arweave
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let file_path = temp_dir.0.join("large.bin");
+        let data: Vec<u8> = (0..2 * ipfs::IPFS_CHUNK_SIZE + 100)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        fs::write(&file_path, &data).await?;
+        let mut arweave = Arweave {
+            chunk_budget: Some(ChunkBudget::new(2)),
+            ipfs_cid: true,
+            ..Arweave::default()
+        };
+        let last_tx = Some(Base64(vec![0; 32]));
+        let ipfs_cid = |transaction: &Transaction| -> String {
+            transaction
+                .tags
+                .iter()
+                .find(|tag| tag.name.0 == ipfs::IPFS_ADD_TAG.as_bytes())
+                .map(|tag| tag.value.to_utf8_string().unwrap())
+                .unwrap()
+        };
+
+        let streamed = arweave
+            .create_transaction_from_file_chunks(&file_path, None, last_tx.clone(), (0, 0), true)
+            .await?;
+        let read = arweave
             .create_transaction_from_file_path(
                 file_path.clone(),
-                Some(other_tags),
-                Some(last_tx),
+                None,
+                last_tx.clone(),
                 (0, 0),
                 true,
+                None,
             )
             .await?;
+        assert_eq!(ipfs_cid(&streamed), ipfs::cid(&data, &arweave.crypto)?);
+        assert_eq!(ipfs_cid(&read), ipfs_cid(&streamed));
 
-        let signed_transaction = arweave.sign_transaction(transaction)?;
+        arweave.compression = Some(Compression::Gzip);
+        let compressed = arweave
+            .create_transaction_from_file_path(file_path, None, last_tx, (0, 0), true, None)
+            .await?;
+        assert_eq!(
+            ipfs_cid(&compressed),
+            ipfs::cid(&compressed.data.0, &arweave.crypto)?
+        );
+        assert_ne!(ipfs_cid(&compressed), ipfs_cid(&read));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_progress() -> Result<(), Error> {
+        use crate::merkle::MAX_CHUNK_SIZE;
+
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let file_path = temp_dir.0.join("large.bin");
+        let data: Vec<u8> = (0..3 * MAX_CHUNK_SIZE + 1000).map(|i| i as u8).collect();
+        fs::write(&file_path, &data).await?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            chunk_budget: Some(ChunkBudget::new(2)),
+            progress: Some(tx),
+            ..Arweave::default()
+        };
+
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                (0, 0),
+                None,
+            )
+            .await?;
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            UploadProgress::Started {
+                id: status.id.clone(),
+                files: 1,
+                data_size: data.len() as u64,
+            }
+        );
+        let mut posted = 0;
+        for _ in 0..4 {
+            match rx.try_recv().unwrap() {
+                UploadProgress::ChunkPosted { id, bytes } if id == status.id => posted += bytes,
+                event => panic!("unexpected {:?}", event),
+            }
+        }
+        assert_eq!(posted, data.len() as u64);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            UploadProgress::Finished {
+                id: status.id,
+                posted: true,
+            }
+        );
+
+        let statuses: Vec<Status> = upload_files_stream(
+            &arweave,
+            vec![file_path].into_iter(),
+            None,
+            Some(temp_dir.0.clone()),
+            true,
+            None,
+            (0, 0),
+            1,
+        )
+        .try_collect()
+        .await?;
+        assert!(statuses.is_empty());
+        assert_eq!(rx.try_recv().unwrap(), UploadProgress::Skipped { files: 1 });
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_statuses_with_failed_requests() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            base_url: Url::from_str("http://127.0.0.1:1/").unwrap(),
+            status_updates: StatusUpdatePolicy {
+                buffer: 0,
+                delay: Duration::from_millis(20),
+            },
+            ..Arweave::default()
+        };
+        let paths: Vec<PathBuf> = ["0.png", "missing.png", "1.png"]
+            .iter()
+            .map(|p| PathBuf::from("tests/fixtures").join(p))
+            .collect();
+        for (i, file_path) in [&paths[0], &paths[2]].iter().enumerate() {
+            let status = Status {
+                id: Base64(vec![i as u8; 32]),
+                status: StatusCode::Submitted,
+                file_path: Some(file_path.to_path_buf()),
+                ..Default::default()
+            };
+            arweave
+                .write_status(status, temp_dir.0.clone(), None)
+                .await?;
+        }
+
+        let start = std::time::Instant::now();
+        let statuses = arweave
+            .update_statuses(paths.into_iter(), temp_dir.0.clone())
+            .await?;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[1].id, Base64(vec![1; 32]));
+        assert!(statuses.iter().all(|s| s.status == StatusCode::Submitted));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tx_status_response() -> Result<(), Error> {
+        use crate::status::TxStatusResponse;
+        use reqwest::StatusCode as ResponseStatusCode;
+
+        let body = r#"{"block_height":641606,"block_indep_hash":"2QFRpYd3eHdfJ8C6ngQHEitAmw5vXKs-wJZDrzbsMVhYU-dFVUuugoBl0TMD-Cce","number_of_confirmations":12}"#;
+        let confirmed = TxStatusResponse::from_response(ResponseStatusCode::OK, body)?;
+        assert_matches!(
+            confirmed,
+            TxStatusResponse::Confirmed {
+                block_height: 641606,
+                number_of_confirmations: 12,
+                ..
+            }
+        );
+        assert_eq!(confirmed.status_code(), StatusCode::Confirmed);
+        assert_eq!(confirmed.raw_status().unwrap().number_of_confirmations, 12);
+
+        assert_eq!(
+            TxStatusResponse::from_response(ResponseStatusCode::OK, "Pending")?,
+            TxStatusResponse::Pending
+        );
+        let accepted = TxStatusResponse::from_response(ResponseStatusCode::ACCEPTED, "")?;
+        assert_eq!(accepted, TxStatusResponse::Accepted);
+        assert_eq!(accepted.status_code(), StatusCode::Pending);
+        assert_eq!(
+            TxStatusResponse::from_response(ResponseStatusCode::NOT_FOUND, "Not Found.")?,
+            TxStatusResponse::NotFound
+        );
+        assert_matches!(
+            TxStatusResponse::from_response(ResponseStatusCode::BAD_GATEWAY, ""),
+            Err(Error::UnexpectedStatusResponse(502, _))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_confirmed_chunks() -> Result<(), Error> {
+        let log_dir = TempDir::from_str("./tests/").await?;
+        let export_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/1mb.bin");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(log_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert!(arweave
+            .export_confirmed_chunks(log_dir.0.clone(), &export_dir.0)
+            .await?
+            .is_empty());
+
+        let status = arweave.update_status(file_path, log_dir.0.clone()).await?;
+        let chunks_dirs = arweave
+            .export_confirmed_chunks(log_dir.0.clone(), &export_dir.0)
+            .await?;
+        assert_eq!(chunks_dirs, vec![export_dir.0.join(status.id.to_string())]);
+
+        let data = std::fs::read("tests/fixtures/1mb.bin")?;
+        let transaction = arweave.merklize(data)?;
+        assert_eq!(
+            std::fs::read_dir(&chunks_dirs[0])?.count(),
+            transaction.chunks.len()
+        );
+        let chunk: Value = serde_json::from_str(&std::fs::read_to_string(
+            chunks_dirs[0].join(format!("{}.json", transaction.proofs[0].offset)),
+        )?)?;
+        assert_eq!(chunk["data_root"], transaction.data_root.to_string());
 
         let status = Status {
-            id: signed_transaction.id.clone(),
-            reward: signed_transaction.reward,
-            file_path: Some(file_path.clone()),
-            ..Default::default()
+            data_root: Some(Base64(vec![0; 32])),
+            ..status
         };
+        assert_matches!(
+            arweave.export_chunks(&status, &export_dir.0).await,
+            Err(Error::InvalidDataRoot(_))
+        );
+        Ok(())
+    }
 
-        let temp_log_dir = TempDir::from_str("./tests/").await?;
-        let log_dir = temp_log_dir.0.clone();
+    #[tokio::test]
+    async fn test_status_timestamps() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_millis(100),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert!(status.first_submitted.is_some());
+        assert_eq!(status.last_checked, None);
+        assert!(status.signed_at.unwrap() >= status.created_at);
+        assert!(status.posted_at.unwrap() >= status.signed_at.unwrap());
+        assert!(status.preparation_time().unwrap() >= chrono::Duration::zero());
+
+        assert!(status.posted_at.unwrap() >= status.first_submitted.unwrap());
+        assert!(status.first_submitted.unwrap() >= status.signed_at.unwrap());
+
+        // Time between signing and posting, spent persisting the status, isn't posting time.
+        let submitted_at = Utc::now();
+        let mut posted = Status {
+            signed_at: Some(submitted_at - chrono::Duration::seconds(3)),
+            first_submitted: Some(submitted_at),
+            ..status.clone()
+        };
+        posted.record_post(submitted_at + chrono::Duration::seconds(2), 1000);
+        assert_eq!(posted.bytes_per_sec, Some(500.0));
+        let mut bundle_posted = BundleStatus {
+            first_submitted: Some(submitted_at),
+            ..BundleStatus::default()
+        };
+        bundle_posted.record_post(submitted_at + chrono::Duration::seconds(4), 1000);
+        assert_eq!(bundle_posted.bytes_per_sec, Some(250.0));
+
+        let status = arweave
+            .update_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert!(status.last_checked.is_some());
+        assert_eq!(status.confirmed_at, None);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = arweave
+            .update_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.confirmed_at, status.last_checked);
+        assert!(status.confirmation_latency().unwrap() >= chrono::Duration::milliseconds(100));
+
+        let written =
+            fs::read_to_string(temp_dir.0.join(crate::hash_file_path(&file_path) + ".json"))
+                .await?;
+        let written: Value = serde_json::from_str(&written)?;
+        assert!(written["confirmed_at"].as_str().unwrap().ends_with("Z"));
+
+        let summary = arweave
+            .status_summary(vec![file_path].into_iter(), temp_dir.0.clone())
+            .await?;
+        assert!(summary.contains("Avg to confirm"));
+        assert!(summary.contains("Avg to post"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rfc3339_offsets() -> Result<(), Error> {
+        let status: Status = serde_json::from_value(serde_json::json!({
+            "id": "",
+            "status": "Confirmed",
+            "file_path": null,
+            "content_type": "image/png",
+            "created_at": "2022-01-01T00:00:00Z",
+            "last_modified": "2022-01-01T00:00:00Z",
+            "first_submitted": "2022-01-01T09:00:00+09:00",
+            "confirmed_at": "2022-01-01T00:30:00Z",
+            "reward": 0,
+            "bundlr_receipt": null
+        }))?;
+        assert_eq!(
+            status.confirmation_latency(),
+            Some(chrono::Duration::minutes(30))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_and_upload() -> Result<(), Error> {
+        let watch_dir = TempDir::from_str("./tests/").await?;
+        // an absolute log dir inside the relative watched one, so that status writes are only
+        // told apart from new files once both are normalized
+        let log_dir = watch_dir.0.canonicalize()?.join("logs");
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(600),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+
+        let mut uploaded = Vec::new();
+        let watch = arweave.watch_and_upload(
+            watch_dir.0.clone(),
+            log_dir,
+            None,
+            1.0,
+            Duration::from_millis(100),
+            |status: &Status| uploaded.push(status.clone()),
+        );
+        let write = async {
+            sleep(Duration::from_millis(100)).await;
+            fs::write(watch_dir.0.join("0.txt"), "foo").await?;
+            sleep(Duration::from_millis(500)).await;
+            // modified after its status was created, so uploaded again
+            fs::write(watch_dir.0.join("0.txt"), "bar").await?;
+            sleep(Duration::from_millis(500)).await;
+            Ok::<(), Error>(())
+        };
+        tokio::select! {
+            result = watch => result?,
+            result = write => result?,
+        }
+
+        assert_matches!(
+            uploaded.as_slice(),
+            [s0, s1] if s0.status == StatusCode::Submitted && s0.id != s1.id
+        );
+        assert!(uploaded
+            .iter()
+            .all(|s| s.file_path.as_ref().unwrap().file_name().unwrap() == "0.txt"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_chunk() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave::default();
+        let file_path = PathBuf::from("tests/fixtures/1mb.bin");
 
+        let leaf_index = arweave.create_leaf_index(file_path.clone()).await?;
         arweave
-            .write_status(status.clone(), log_dir.clone(), None)
+            .write_leaf_index(&leaf_index, &file_path, temp_dir.0.clone())
+            .await?;
+        let leaf_index = arweave
+            .read_leaf_index(&file_path, temp_dir.0.clone())
             .await?;
 
-        let read_status = arweave.read_status(file_path, log_dir).await?;
+        let transaction = arweave.merklize(fs::read(&file_path).await?)?;
+        assert_eq!(leaf_index.data_root, transaction.data_root);
+        for index in 0..transaction.chunks.len() {
+            assert_eq!(
+                arweave
+                    .regenerate_chunk(file_path.clone(), &leaf_index, index)
+                    .await?,
+                transaction.get_chunk(index)?
+            );
+        }
+        Ok(())
+    }
 
-        assert_eq!(status, read_status);
+    #[tokio::test]
+    async fn test_write_metaplex_uris() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let dir = temp_dir.0.clone();
+        let paths = vec![dir.join("nft_10.json"), dir.join("nft_2.json")];
+        let manifest = serde_json::json!({
+            paths[0].display().to_string(): {"id": "ten"},
+            paths[1].display().to_string(): {"id": "two"},
+        });
+        let manifest_path = dir.join("manifest_abc.json");
+        fs::write(&manifest_path, manifest.to_string()).await?;
+
+        let index_pattern = Regex::new(r"(\d+)$").unwrap();
+        let uris_path = arweave
+            .write_metaplex_uris(
+                paths.clone().into_iter(),
+                manifest_path.clone(),
+                false,
+                &index_pattern,
+            )
+            .await?;
+        assert_eq!(uris_path, dir.join("uris.json"));
+
+        let uris: Value = serde_json::from_str(&fs::read_to_string(&uris_path).await?)?;
+        let uris = uris.as_object().unwrap();
+        assert_eq!(uris.keys().collect::<Vec<_>>(), vec!["2", "10"]);
+        assert_eq!(uris["2"], "https://arweave.net/two");
+
+        assert_matches!(
+            arweave
+                .write_metaplex_uris(
+                    vec![paths[0].clone(), dir.join("nft.json")].into_iter(),
+                    manifest_path,
+                    false,
+                    &index_pattern,
+                )
+                .await,
+            Err(Error::InvalidFileIndex(_))
+        );
+        Ok(())
+    }
 
+    #[test]
+    fn test_deserialize_bundle_with_invalid_signature() -> Result<(), Error> {
+        let arweave = Arweave::default();
+        let data_item = arweave.sign_data_item(DataItem {
+            data: Base64(b"data".to_vec()),
+            ..DataItem::default()
+        })?;
+        let mut data_items = vec![data_item];
+        let bundle = arweave.create_bundle(data_items.clone())?;
+        assert_eq!(arweave.deserialize_bundle(bundle)?.len(), 1);
+
+        data_items[0].signature.0[0] ^= 1;
+        let bundle = arweave.create_bundle(data_items)?;
+        assert!(matches!(
+            arweave.deserialize_bundle(bundle),
+            Err(Error::InvalidDataItem)
+        ));
         Ok(())
     }
 
@@ -1846,14 +9223,56 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_create_and_deserialize_nested_bundle() -> Result<(), Error> {
+        let arweave = Arweave::from_keypair_path(
+            PathBuf::from(
+                "tests/fixtures/arweave-key-7eV1qae4qVNqsNChg3Scdi-DpOLJPCogct4ixoq1WNg.json",
+            ),
+            Url::from_str("http://url.com").unwrap(),
+        )
+        .await?;
+
+        let mut ids = Vec::new();
+        let mut nested_data_items = Vec::new();
+        for paths in [vec![0, 1], vec![2, 3, 4]] {
+            let paths = paths
+                .into_iter()
+                .map(|i| PathBuf::from(format!("tests/fixtures/{}.png", i)))
+                .collect();
+            let data_items: Vec<DataItem> = arweave
+                .create_data_items_from_file_paths(paths, Vec::new())
+                .await?
+                .into_iter()
+                .map(|(d, _)| d)
+                .collect();
+            ids.extend(data_items.iter().map(|d| d.id.clone()));
+            let nested_data_item = arweave.create_nested_bundle_data_item(data_items)?;
+            assert!(nested_data_item.is_bundle());
+            nested_data_items.push(nested_data_item);
+        }
+
+        let bundle = arweave.create_bundle(nested_data_items)?;
+        assert_eq!(arweave.deserialize_bundle(bundle.clone())?.len(), 2);
+        let data_items = arweave.deserialize_nested_bundle(bundle)?;
+        assert_eq!(
+            data_items.into_iter().map(|d| d.id).collect::<Vec<_>>(),
+            ids
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_price_points() -> Result<(), Error> {
         let mut price = 0 as u64;
         println!("{:>6}  {:>12} {:>12}", "size", "winstons", "incremental");
         println!("{:-<40}", "");
+        let client = reqwest::Client::new();
         for p in 1..10 {
             let size = p * 100 * 256;
-            let new_price = reqwest::get(format!("https://arweave.net/price/{}", size * 1024))
+            let new_price = client
+                .get(format!("https://arweave.net/price/{}", size * 1024))
+                .send()
                 .await?
                 .json::<u64>()
                 .await?;
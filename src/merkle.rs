@@ -1,7 +1,9 @@
 //! Functionality for chunking file data and calculating and verifying root ids.
 
-use crate::{crypto::Provider, error::Error};
+use crate::{crypto::Provider, error::Error, transaction::Base64};
 use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use std::{convert::TryInto, ops::Range};
 
 /// Single struct used for original data chunks (Leaves) and branch nodes (hashes of pairs of child nodes).
 #[derive(Debug, PartialEq, Clone)]
@@ -83,42 +85,59 @@ impl Helpers<usize> for usize {
     }
 }
 
-/// Generates data chunks from which the calculation of root id starts.
-pub fn generate_leaves(data: Vec<u8>, crypto: &Provider) -> Result<Vec<Node>, Error> {
-    let mut data_chunks: Vec<&[u8]> = data.chunks(MAX_CHUNK_SIZE).collect();
-
-    #[allow(unused_assignments)]
-    let mut last_two = Vec::new();
-
-    if data_chunks.len() > 1 && data_chunks.last().unwrap().len() < MIN_CHUNK_SIZE {
-        last_two = data_chunks.split_off(data_chunks.len() - 2).concat();
-        let chunk_size = last_two.len() / 2 + (last_two.len() % 2 != 0) as usize;
-        data_chunks.append(&mut last_two.chunks(chunk_size).collect::<Vec<&[u8]>>());
+/// Returns the byte ranges that data of `data_size` bytes is split into. Chunks are
+/// [`MAX_CHUNK_SIZE`] bytes, except that a last chunk smaller than [`MIN_CHUNK_SIZE`] is evened
+/// out with the one before it and data that ends on a full chunk gets an empty last chunk.
+pub fn chunk_ranges(data_size: usize) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = (0..data_size)
+        .step_by(MAX_CHUNK_SIZE)
+        .map(|start| start..(start + MAX_CHUNK_SIZE).min(data_size))
+        .collect();
+
+    if ranges.len() > 1 && ranges.last().unwrap().len() < MIN_CHUNK_SIZE {
+        let last = ranges.pop().unwrap();
+        let second_last = ranges.pop().unwrap();
+        let last_two_size = last.end - second_last.start;
+        let chunk_size = last_two_size / 2 + (last_two_size % 2 != 0) as usize;
+        ranges.push(second_last.start..second_last.start + chunk_size);
+        ranges.push(second_last.start + chunk_size..last.end);
     }
 
-    if data_chunks.last().unwrap().len() == MAX_CHUNK_SIZE {
-        data_chunks.push(&[]);
+    match ranges.last() {
+        Some(last) if last.len() == MAX_CHUNK_SIZE => ranges.push(data_size..data_size),
+        None => ranges.push(0..0),
+        _ => {}
     }
+    ranges
+}
 
-    let mut leaves = Vec::<Node>::new();
-    let mut min_byte_range = 0;
-    for chunk in data_chunks.into_iter() {
-        let data_hash = crypto.hash_sha256(chunk)?;
-        let max_byte_range = min_byte_range + &chunk.len();
-        let offset = max_byte_range.to_note_vec();
-        let id = crypto.hash_all_sha256(vec![&data_hash, &offset])?;
+/// Generates the leaf for `chunk`, the data from `min_byte_range` to the end of the chunk.
+pub fn generate_leaf(
+    chunk: &[u8],
+    min_byte_range: usize,
+    crypto: &Provider,
+) -> Result<Node, Error> {
+    let data_hash = crypto.hash_sha256(chunk)?;
+    let max_byte_range = min_byte_range + chunk.len();
+    let offset = max_byte_range.to_note_vec();
+    let id = crypto.hash_all_sha256(vec![&data_hash, &offset])?;
 
-        leaves.push(Node {
-            id,
-            data_hash: Some(data_hash),
-            min_byte_range,
-            max_byte_range,
-            left_child: None,
-            right_child: None,
-        });
-        min_byte_range = min_byte_range + &chunk.len();
-    }
-    Ok(leaves)
+    Ok(Node {
+        id,
+        data_hash: Some(data_hash),
+        min_byte_range,
+        max_byte_range,
+        left_child: None,
+        right_child: None,
+    })
+}
+
+/// Generates data chunks from which the calculation of root id starts.
+pub fn generate_leaves(data: Vec<u8>, crypto: &Provider) -> Result<Vec<Node>, Error> {
+    chunk_ranges(data.len())
+        .into_iter()
+        .map(|range| generate_leaf(&data[range.clone()], range.start, crypto))
+        .collect()
 }
 
 /// Hashes together a single branch node from a pair of child nodes.
@@ -203,6 +222,95 @@ pub fn resolve_proofs(node: Node, proof: Option<Proof>) -> Result<Vec<Proof>, Er
     }
 }
 
+/// Id, data hash and byte range of a leaf [`Node`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct LeafEntry {
+    pub id: Base64,
+    pub data_hash: Base64,
+    pub min_byte_range: usize,
+    pub max_byte_range: usize,
+}
+
+/// Leaves of the merkle tree of a file's data, from which the [`Proof`] of any single chunk can be
+/// regenerated without reading or rehashing the rest of the file.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct LeafIndex {
+    pub data_root: Base64,
+    pub data_size: u64,
+    pub leaves: Vec<LeafEntry>,
+}
+
+impl LeafIndex {
+    pub fn from_leaves(leaves: &[Node], data_root: Base64, data_size: u64) -> Self {
+        Self {
+            data_root,
+            data_size,
+            leaves: leaves
+                .iter()
+                .map(|l| LeafEntry {
+                    id: Base64(l.id.to_vec()),
+                    data_hash: Base64(l.data_hash.unwrap_or_default().to_vec()),
+                    min_byte_range: l.min_byte_range,
+                    max_byte_range: l.max_byte_range,
+                })
+                .collect(),
+        }
+    }
+
+    /// Calculates the [`Proof`] for the chunk at `index` by hashing branches up from the leaf
+    /// ids, keeping only the branches on the path to that chunk.
+    pub fn proof(&self, index: usize, crypto: &Provider) -> Result<Proof, Error> {
+        let leaf = self
+            .leaves
+            .get(index)
+            .ok_or(Error::InvalidChunkIndex(index))?;
+
+        let mut nodes = self
+            .leaves
+            .iter()
+            .map(|l| {
+                Ok((
+                    l.id.0
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| Error::InvalidHash)?,
+                    l.max_byte_range,
+                ))
+            })
+            .collect::<Result<Vec<([u8; HASH_SIZE], usize)>, Error>>()?;
+
+        let mut position = index;
+        let mut branches = Vec::new();
+        while nodes.len() > 1 {
+            let mut layer = Vec::with_capacity(nodes.len() / 2 + 1);
+            for (i, pair) in nodes.chunks(2).enumerate() {
+                match pair {
+                    [left, right] => {
+                        let note = left.1.to_note_vec();
+                        let id = crypto.hash_all_sha256(vec![&left.0, &right.0, &note])?;
+                        if i == position / 2 {
+                            branches.push([&left.0[..], &right.0[..], &note[..]].concat());
+                        }
+                        layer.push((id, right.1));
+                    }
+                    [single] => layer.push(*single),
+                    _ => unreachable!(),
+                }
+            }
+            position /= 2;
+            nodes = layer;
+        }
+
+        let mut proof: Vec<u8> = branches.into_iter().rev().flatten().collect();
+        proof.extend(&leaf.data_hash.0);
+        proof.extend(leaf.max_byte_range.to_note_vec());
+        Ok(Proof {
+            offset: leaf.max_byte_range - 1,
+            proof,
+        })
+    }
+}
+
 /// Validates chunk of data against provided [`Proof`].
 pub fn validate_chunk(
     mut root_id: [u8; HASH_SIZE],
@@ -213,6 +321,7 @@ pub fn validate_chunk(
     match chunk {
         Node {
             data_hash: Some(data_hash),
+            min_byte_range,
             max_byte_range,
             ..
         } => {
@@ -243,9 +352,11 @@ pub fn validate_chunk(
                     return Err(Error::InvalidProof.into());
                 }
 
-                // If the offset from the proof is greater than the offset in the data chunk,
-                // then the next id to validate against is from the left.
-                root_id = match max_byte_range > branch_proof.offset() {
+                // If the offset from the proof is greater than the start of the data chunk,
+                // then the next id to validate against is from the left. Comparing the start
+                // rather than the end also finds the empty last chunk of data that ends on a
+                // full chunk.
+                root_id = match min_byte_range >= branch_proof.offset() {
                     true => branch_proof.right_id,
                     false => branch_proof.left_id,
                 }
@@ -253,7 +364,7 @@ pub fn validate_chunk(
 
             // Validate leaf: both id and data_hash are correct.
             let id = crypto.hash_all_sha256(vec![&data_hash, &max_byte_range.to_note_vec()])?;
-            if !(id == root_id) & !(data_hash == leaf_proof.data_hash) {
+            if !(id == root_id) || !(data_hash == leaf_proof.data_hash) {
                 return Err(Error::InvalidProof.into());
             }
         }
@@ -264,10 +375,44 @@ pub fn validate_chunk(
     Ok(())
 }
 
-#[cfg(test)]
+/// Validates a `chunk` downloaded with its `data_path` against `root_id`, returning the range of
+/// the data of the transaction that it covers.
+pub fn validate_downloaded_chunk(
+    root_id: [u8; HASH_SIZE],
+    chunk: &[u8],
+    data_path: &[u8],
+    crypto: &Provider,
+) -> Result<Range<usize>, Error> {
+    let leaf_len = HASH_SIZE + NOTE_SIZE;
+    if chunk.is_empty()
+        || data_path.len() < leaf_len
+        || (data_path.len() - leaf_len) % (HASH_SIZE * 2 + NOTE_SIZE) != 0
+    {
+        return Err(Error::InvalidProof);
+    }
+    let end = LeafProof::try_from_proof_slice(&data_path[data_path.len() - leaf_len..])?.offset();
+    let start = end.checked_sub(chunk.len()).ok_or(Error::InvalidProof)?;
+    let node = Node {
+        id: [0; HASH_SIZE],
+        data_hash: Some(crypto.hash_sha256(chunk)?),
+        min_byte_range: start,
+        max_byte_range: end,
+        left_child: None,
+        right_child: None,
+    };
+    let proof = Proof {
+        offset: end - 1,
+        proof: data_path.to_vec(),
+    };
+    validate_chunk(root_id, node, proof, crypto)?;
+    Ok(start..end)
+}
+
+#[cfg(all(test, feature = "native"))]
 mod tests {
     use super::*;
     use crate::transaction::Base64;
+    use matches::assert_matches;
     use std::{path::PathBuf, str::FromStr};
     use tokio::fs;
 
@@ -382,6 +527,31 @@ mod tests {
         );
         Ok(())
     }
+    #[tokio::test]
+    async fn test_leaf_index_proofs() -> Result<(), Error> {
+        let crypto = Provider::default();
+        for file_path in ["tests/fixtures/1mb.bin", "tests/fixtures/rebar3"] {
+            let data = fs::read(file_path).await?;
+            let data_size = data.len() as u64;
+            let leaves: Vec<Node> = generate_leaves(data, &crypto)?;
+            let root = generate_data_root(leaves.clone(), &crypto)?;
+            let root_id = root.id.clone();
+            let leaf_index = LeafIndex::from_leaves(&leaves, Base64(root_id.to_vec()), data_size);
+            let proofs = resolve_proofs(root, None)?;
+
+            for (i, (chunk, proof)) in leaves.into_iter().zip(proofs.into_iter()).enumerate() {
+                let regenerated = leaf_index.proof(i, &crypto)?;
+                assert_eq!(regenerated, proof);
+                validate_chunk(root_id.clone(), chunk, regenerated, &crypto)?;
+            }
+            assert_matches!(
+                leaf_index.proof(leaf_index.leaves.len(), &crypto),
+                Err(Error::InvalidChunkIndex(_))
+            );
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_validate_chunks() -> Result<(), Error> {
         let crypto = Provider::default();
@@ -399,6 +569,52 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_validate_chunk_with_tampered_leaf() -> Result<(), Error> {
+        let crypto = Provider::default();
+        let data = fs::read("tests/fixtures/1mb.bin").await?;
+        let leaves: Vec<Node> = generate_leaves(data, &crypto)?;
+        let root = generate_data_root(leaves.clone(), &crypto)?;
+        let root_id = root.id.clone();
+        let mut proof = resolve_proofs(root, None)?.remove(1);
+
+        // Branches and chunk still match, only the data hash of the leaf proof differs.
+        let leaf_start = proof.proof.len() - HASH_SIZE - NOTE_SIZE;
+        proof.proof[leaf_start] ^= 1;
+        assert_matches!(
+            validate_chunk(root_id, leaves[1].clone(), proof, &crypto),
+            Err(Error::InvalidProof)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_downloaded_chunk() -> Result<(), Error> {
+        let crypto = Provider::default();
+        let data = fs::read("tests/fixtures/1mb.bin").await?;
+        let leaves: Vec<Node> = generate_leaves(data.clone(), &crypto)?;
+        let root = generate_data_root(leaves.clone(), &crypto)?;
+        let proofs = resolve_proofs(root.clone(), None)?;
+
+        let chunk = &data[leaves[1].min_byte_range..leaves[1].max_byte_range];
+        assert_eq!(
+            validate_downloaded_chunk(root.id, chunk, &proofs[1].proof, &crypto)?,
+            leaves[1].min_byte_range..leaves[1].max_byte_range
+        );
+
+        let mut tampered = chunk.to_vec();
+        tampered[0] ^= 1;
+        assert_matches!(
+            validate_downloaded_chunk(root.id, &tampered, &proofs[1].proof, &crypto),
+            Err(Error::InvalidProof)
+        );
+        assert_matches!(
+            validate_downloaded_chunk(root.id, chunk, &proofs[2].proof, &crypto),
+            Err(Error::InvalidProof)
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_valid_root() -> Result<(), Error> {
         let crypto = Provider::default();
@@ -0,0 +1,79 @@
+//! Prometheus metrics for bulk upload pipelines. Enabled with the `metrics` feature.
+
+use crate::{error::Error, forecast::SpendForecast, status::StatusCode};
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram, register_int_counter, register_int_counter_vec, register_int_gauge,
+    Histogram, IntCounter, IntCounterVec, IntGauge, TextEncoder,
+};
+
+lazy_static! {
+    /// Number of files successfully uploaded, either as transactions or in bundles.
+    pub static ref FILES_UPLOADED: IntCounter = register_int_counter!(
+        "arloader_files_uploaded_total",
+        "Number of files uploaded."
+    )
+    .unwrap();
+
+    /// Number of bytes of transaction data posted to the `tx/` and `chunk/` endpoints.
+    pub static ref BYTES_POSTED: IntCounter = register_int_counter!(
+        "arloader_bytes_posted_total",
+        "Number of bytes of transaction data posted."
+    )
+    .unwrap();
+
+    /// Latency of posting transactions to the `tx/` endpoint.
+    pub static ref POST_LATENCY: Histogram = register_histogram!(
+        "arloader_post_latency_seconds",
+        "Latency of posting transactions in seconds."
+    )
+    .unwrap();
+
+    /// Number of times posting a chunk was retried.
+    pub static ref CHUNK_RETRIES: IntCounter = register_int_counter!(
+        "arloader_chunk_retries_total",
+        "Number of chunk post retries."
+    )
+    .unwrap();
+
+    /// Number of status updates by resulting [`StatusCode`].
+    pub static ref STATUS_UPDATES: IntCounterVec = register_int_counter_vec!(
+        "arloader_status_updates_total",
+        "Number of status updates by status code.",
+        &["status"]
+    )
+    .unwrap();
+
+    /// Projected daily spend in winstons from the latest [`SpendForecast`].
+    pub static ref FORECAST_DAILY_WINSTONS: IntGauge = register_int_gauge!(
+        "arloader_forecast_daily_winstons",
+        "Projected daily spend in winstons."
+    )
+    .unwrap();
+
+    /// Projected weekly spend in winstons from the latest [`SpendForecast`].
+    pub static ref FORECAST_WEEKLY_WINSTONS: IntGauge = register_int_gauge!(
+        "arloader_forecast_weekly_winstons",
+        "Projected weekly spend in winstons."
+    )
+    .unwrap();
+}
+
+/// Sets the forecast gauges to the projections of `forecast`.
+pub fn record_forecast(forecast: &SpendForecast) {
+    FORECAST_DAILY_WINSTONS.set(forecast.daily_winstons as i64);
+    FORECAST_WEEKLY_WINSTONS.set(forecast.weekly_winstons as i64);
+}
+
+/// Increments the status distribution counter for the provided [`StatusCode`].
+pub fn record_status(status: &StatusCode) {
+    STATUS_UPDATES
+        .with_label_values(&[&status.to_string()])
+        .inc();
+}
+
+/// Renders all registered metrics in the Prometheus text exposition format.
+pub fn gather_metrics() -> Result<String, Error> {
+    let metrics = TextEncoder::new().encode_to_string(&prometheus::gather())?;
+    Ok(metrics)
+}
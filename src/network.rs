@@ -0,0 +1,111 @@
+//! Typed responses of the `info` and `tx/pending` endpoints of a node.
+
+use serde::{Deserialize, Serialize};
+
+/// Number of pending transactions at which the mempool is considered saturated. Nodes mine at
+/// most this many transactions into a block (`BLOCK_TX_COUNT_LIMIT` of the Arweave node), so
+/// new transactions wait for at least one more block.
+pub const SATURATED_MEMPOOL_LEN: usize = 1000;
+
+/// Response of the `info` endpoint of a node.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct NetworkInfo {
+    pub network: String,
+    pub version: u64,
+    pub release: u64,
+    /// Height of the current block, -1 if the node hasn't joined the network yet.
+    pub height: i64,
+    /// Independent hash of the current block, `not_joined` if the node hasn't joined the network.
+    pub current: String,
+    pub blocks: u64,
+    pub peers: u64,
+    pub queue_length: u64,
+    pub node_state_latency: u64,
+}
+
+impl NetworkInfo {
+    /// Whether the node is still joining the network, so it can't accept transactions yet.
+    pub fn is_syncing(&self) -> bool {
+        self.height < 0 || self.current == "not_joined"
+    }
+}
+
+/// State of the network from the `info` and `tx/pending` endpoints of a node.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NetworkConditions {
+    pub info: NetworkInfo,
+    pub pending_count: usize,
+}
+
+impl NetworkConditions {
+    pub fn is_mempool_saturated(&self) -> bool {
+        self.pending_count >= SATURATED_MEMPOOL_LEN
+    }
+
+    /// Number of uploads to post at a time instead of `buffer`, divided by the number of blocks
+    /// needed to mine the transactions already pending, since posting faster than that only
+    /// queues more transactions behind them. At least 1.
+    pub fn upload_buffer(&self, buffer: usize) -> usize {
+        let blocks_pending = self.pending_count / SATURATED_MEMPOOL_LEN + 1;
+        (buffer / blocks_pending).max(1)
+    }
+
+    /// Warnings to show before uploading, empty if the network looks healthy.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.info.is_syncing() {
+            warnings.push(format!(
+                "node on {} is still syncing and may reject transactions",
+                self.info.network
+            ));
+        }
+        if self.is_mempool_saturated() {
+            warnings.push(format!(
+                "mempool is saturated with {} pending transactions, uploads may take several blocks to confirm",
+                self.pending_count
+            ));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NetworkConditions, NetworkInfo, SATURATED_MEMPOOL_LEN};
+
+    #[test]
+    fn test_network_conditions() {
+        let info: NetworkInfo = serde_json::from_str(
+            r#"{"network":"arweave.N.1","version":5,"release":53,"height":1014240,
+            "current":"8Z0sSyi9qd_qhUbu3nyNVn9rmx7ATW8KJ5Vn0M6T-a6mq3AfdQDj5AzGr6bAZ7d9",
+            "blocks":1014241,"peers":77,"queue_length":0,"node_state_latency":1}"#,
+        )
+        .unwrap();
+        assert_eq!(info.height, 1014240);
+        assert!(!info.is_syncing());
+
+        let conditions = NetworkConditions {
+            info,
+            pending_count: 12,
+        };
+        assert!(conditions.warnings().is_empty());
+        assert_eq!(conditions.upload_buffer(10), 10);
+
+        let not_joined: NetworkInfo =
+            serde_json::from_str(r#"{"network":"arweave.N.1","height":-1,"current":"not_joined"}"#)
+                .unwrap();
+        let conditions = NetworkConditions {
+            info: not_joined,
+            pending_count: SATURATED_MEMPOOL_LEN,
+        };
+        assert!(conditions.is_mempool_saturated());
+        assert_eq!(conditions.warnings().len(), 2);
+        assert_eq!(conditions.upload_buffer(10), 5);
+        let conditions = NetworkConditions {
+            pending_count: 5 * SATURATED_MEMPOOL_LEN,
+            ..conditions
+        };
+        assert_eq!(conditions.upload_buffer(4), 1);
+    }
+}
@@ -0,0 +1,195 @@
+//! Sources of AR and SOL prices in fiat currencies, used to show the cost of uploads.
+
+use crate::{error::Error, Arweave};
+use futures::future::BoxFuture;
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+use url::Url;
+
+/// Fiat currencies that prices can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+}
+
+impl Currency {
+    /// Lower case ISO 4217 code, as used by CoinGecko.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "usd",
+            Currency::Eur => "eur",
+            Currency::Gbp => "gbp",
+            Currency::Jpy => "jpy",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Jpy => "¥",
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code().to_uppercase())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "usd" => Ok(Currency::Usd),
+            "eur" => Ok(Currency::Eur),
+            "gbp" => Ok(Currency::Gbp),
+            "jpy" => Ok(Currency::Jpy),
+            _ => Err(Error::UnsupportedCurrency(s.to_string())),
+        }
+    }
+}
+
+/// Prices of one AR and one SOL in a fiat [`Currency`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiatPrices {
+    pub ar: f64,
+    pub sol: f64,
+}
+
+/// Source of [`FiatPrices`]. Set it on [`Arweave::oracle`]. Requests can go through
+/// [`Arweave::get_price_with_cache`] so that they are cached along with network prices.
+pub trait PriceOracle: Send + Sync {
+    fn fiat_prices<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        currency: Currency,
+    ) -> BoxFuture<'a, Result<FiatPrices, Error>>;
+}
+
+/// Prices from the [CoinGecko](https://www.coingecko.com/en/api) simple price endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct CoinGecko;
+
+impl PriceOracle for CoinGecko {
+    fn fiat_prices<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        currency: Currency,
+    ) -> BoxFuture<'a, Result<FiatPrices, Error>> {
+        Box::pin(async move {
+            let url = Url::from_str(&format!(
+                "https://api.coingecko.com/api/v3/simple/price?ids=arweave,solana&vs_currencies={}",
+                currency.code()
+            ))?;
+            let (_, body) = arweave
+                .get_price_with_cache(url)
+                .await
+                .map_err(|e| Error::OracleGetPriceError(e))?;
+            let prices: Value = serde_json::from_str(&body)?;
+            let price = |id: &str| {
+                prices[id][currency.code()]
+                    .as_f64()
+                    .ok_or_else(|| Error::OraclePriceNotFound(format!("{}/{}", id, currency)))
+            };
+            Ok(FiatPrices {
+                ar: price("arweave")?,
+                sol: price("solana")?,
+            })
+        })
+    }
+}
+
+/// Prices from the [RedStone](https://redstone.finance) API. RedStone quotes everything in USD,
+/// so other currencies are converted with its USD price of the currency.
+#[derive(Debug, Clone, Default)]
+pub struct Redstone;
+
+impl PriceOracle for Redstone {
+    fn fiat_prices<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        currency: Currency,
+    ) -> BoxFuture<'a, Result<FiatPrices, Error>> {
+        Box::pin(async move {
+            let url = Url::from_str(&format!(
+                "https://api.redstone.finance/prices?symbols=AR,SOL,{}&provider=redstone",
+                currency
+            ))?;
+            let (_, body) = arweave
+                .get_price_with_cache(url)
+                .await
+                .map_err(|e| Error::OracleGetPriceError(e))?;
+            let prices: Value = serde_json::from_str(&body)?;
+            let usd_price = |symbol: &str| {
+                prices[symbol]["value"]
+                    .as_f64()
+                    .ok_or_else(|| Error::OraclePriceNotFound(symbol.to_string()))
+            };
+            let usd_per_unit = match currency {
+                Currency::Usd => 1.0,
+                _ => usd_price(&currency.to_string())?,
+            };
+            Ok(FiatPrices {
+                ar: usd_price("AR")? / usd_per_unit,
+                sol: usd_price("SOL")? / usd_per_unit,
+            })
+        })
+    }
+}
+
+/// Returns the same prices for every [`Currency`], e.g. for offline estimates or tests.
+#[derive(Debug, Clone)]
+pub struct FixedRate(pub FiatPrices);
+
+impl PriceOracle for FixedRate {
+    fn fiat_prices<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _currency: Currency,
+    ) -> BoxFuture<'a, Result<FiatPrices, Error>> {
+        Box::pin(async move { Ok(self.0) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, FiatPrices, FixedRate};
+    use crate::{error::Error, Arweave};
+    use matches::assert_matches;
+    use num_bigint::BigUint;
+    use std::{str::FromStr, sync::Arc};
+
+    #[test]
+    fn test_currency_from_str() -> Result<(), Error> {
+        assert_eq!(Currency::from_str("EUR")?, Currency::Eur);
+        assert_eq!(Currency::Jpy.to_string(), "JPY");
+        assert_matches!(
+            Currency::from_str("chf"),
+            Err(Error::UnsupportedCurrency(_))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fixed_rate_oracle() -> Result<(), Error> {
+        let arweave = Arweave {
+            oracle: Arc::new(FixedRate(FiatPrices {
+                ar: 12.345,
+                sol: 150.0,
+            })),
+            currency: Currency::Eur,
+            ..Arweave::default()
+        };
+        let (ar, sol) = arweave.get_fiat_prices().await?;
+        assert_eq!(ar, BigUint::from(1234u32));
+        assert_eq!(sol, BigUint::from(15000u32));
+        Ok(())
+    }
+}
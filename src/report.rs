@@ -0,0 +1,176 @@
+//! Reports of uploaded files as Markdown or HTML tables.
+
+use crate::{error::Error, status::Status};
+use std::{fmt, str::FromStr};
+use url::Url;
+
+/// Markup that a [`StatusReport`] is rendered as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(Error::InvalidReportFormat(s.to_string())),
+        }
+    }
+}
+
+/// Uploaded file, as a row of a [`StatusReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow {
+    pub file_path: String,
+    pub id: String,
+    pub url: Url,
+    pub content_type: String,
+    pub status: String,
+    pub confirmations: u64,
+}
+
+/// Table of uploaded files, sorted by path, with links to them on `gateway_url`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusReport {
+    pub gateway_url: Url,
+    pub rows: Vec<ReportRow>,
+}
+
+impl StatusReport {
+    pub fn from_statuses(statuses: &[Status], gateway_url: Url) -> Result<Self, Error> {
+        let mut rows = statuses
+            .iter()
+            .map(|status| {
+                Ok(ReportRow {
+                    file_path: status
+                        .file_path
+                        .as_ref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default(),
+                    id: status.id.to_string(),
+                    url: gateway_url.join(&status.id.to_string())?,
+                    content_type: status.content_type.clone(),
+                    status: status.status.to_string(),
+                    confirmations: status
+                        .raw_status
+                        .as_ref()
+                        .map(|r| r.number_of_confirmations)
+                        .unwrap_or(0),
+                })
+            })
+            .collect::<Result<Vec<ReportRow>, Error>>()?;
+        rows.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        Ok(Self { gateway_url, rows })
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut output = String::from(
+            "| file | id | content type | status | confirmations |\n\
+             |------|----|--------------|--------|--------------:|\n",
+        );
+        for row in &self.rows {
+            output.push_str(&format!(
+                "| {} | [{}]({}) | {} | {} | {} |\n",
+                escape_markdown(&row.file_path),
+                row.id,
+                row.url,
+                escape_markdown(&row.content_type),
+                row.status,
+                row.confirmations
+            ));
+        }
+        output
+    }
+
+    fn to_html(&self) -> String {
+        let mut output = String::from(
+            "<table>\n  <thead>\n    <tr><th>file</th><th>id</th><th>content type</th>\
+             <th>status</th><th>confirmations</th></tr>\n  </thead>\n  <tbody>\n",
+        );
+        for row in &self.rows {
+            output.push_str(&format!(
+                "    <tr><td>{}</td><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&row.file_path),
+                escape_html(row.url.as_str()),
+                row.id,
+                escape_html(&row.content_type),
+                row.status,
+                row.confirmations
+            ));
+        }
+        output.push_str("  </tbody>\n</table>\n");
+        output
+    }
+}
+
+impl fmt::Display for StatusReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_markdown())
+    }
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReportFormat, StatusReport};
+    use crate::{
+        status::{Status, StatusCode},
+        transaction::Base64,
+    };
+    use std::{path::PathBuf, str::FromStr};
+    use url::Url;
+
+    #[test]
+    fn test_status_report() {
+        let status = |path: &str, byte: u8| Status {
+            id: Base64(vec![byte; 32]),
+            status: StatusCode::Confirmed,
+            file_path: Some(PathBuf::from(path)),
+            content_type: "image/png".to_string(),
+            ..Status::default()
+        };
+        let statuses = vec![status("b|<1>.png", 2), status("a.png", 1)];
+        let report =
+            StatusReport::from_statuses(&statuses, Url::from_str("https://arweave.net/").unwrap())
+                .unwrap();
+        let id = Base64(vec![1; 32]).to_string();
+        assert_eq!(report.rows[0].file_path, "a.png");
+        assert_eq!(
+            report.rows[0].url.as_str(),
+            format!("https://arweave.net/{}", id)
+        );
+
+        let markdown = report.render(ReportFormat::from_str("md").unwrap());
+        assert!(markdown.contains(&format!("| a.png | [{}](https://arweave.net/{}) |", id, id)));
+        assert!(markdown.contains("b\\|<1>.png"));
+        let html = report.render(ReportFormat::Html);
+        assert!(html.contains(&format!(
+            "<a href=\"https://arweave.net/{}\">{}</a>",
+            id, id
+        )));
+        assert!(html.contains("<td>b|&lt;1&gt;.png</td>"));
+        assert!(ReportFormat::from_str("pdf").is_err());
+    }
+}
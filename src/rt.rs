@@ -0,0 +1,134 @@
+//! Runtime-specific file system access, timers and spawning of tasks, on tokio or async-std.
+
+use crate::error::Error;
+use std::{future::Future, path::Path};
+
+pub use std::time::Duration;
+
+#[cfg(not(feature = "async-std-runtime"))]
+pub use tokio::{fs, time::sleep};
+
+#[cfg(feature = "async-std-runtime")]
+pub use async_std::task::sleep;
+
+/// File system functions with the same signatures as those of [`tokio::fs`].
+#[cfg(feature = "async-std-runtime")]
+pub mod fs {
+    use std::{io, path::Path};
+
+    pub use async_std::fs::File;
+
+    pub async fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+        async_std::fs::copy(from.as_ref(), to.as_ref()).await
+    }
+
+    pub async fn create_dir(path: impl AsRef<Path>) -> io::Result<()> {
+        async_std::fs::create_dir(path.as_ref()).await
+    }
+
+    pub async fn create_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+        async_std::fs::create_dir_all(path.as_ref()).await
+    }
+
+    pub async fn metadata(path: impl AsRef<Path>) -> io::Result<std::fs::Metadata> {
+        async_std::fs::metadata(path.as_ref()).await
+    }
+
+    pub async fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        async_std::fs::read(path.as_ref()).await
+    }
+
+    pub async fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+        async_std::fs::read_to_string(path.as_ref()).await
+    }
+
+    pub async fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+        async_std::fs::remove_file(path.as_ref()).await
+    }
+
+    pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+        async_std::fs::rename(from.as_ref(), to.as_ref()).await
+    }
+
+    pub async fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        async_std::fs::write(path.as_ref(), contents).await
+    }
+}
+
+/// Reads `len` bytes of the file at `path`, starting at `offset`.
+pub async fn read_range(path: impl AsRef<Path>, offset: u64, len: usize) -> Result<Vec<u8>, Error> {
+    #[cfg(feature = "async-std-runtime")]
+    use async_std::io::{prelude::SeekExt, ReadExt};
+    #[cfg(not(feature = "async-std-runtime"))]
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut file = fs::File::open(path.as_ref()).await?;
+    file.seek(std::io::SeekFrom::Start(offset)).await?;
+    let mut data = vec![0; len];
+    file.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+/// Returns the BLAKE3 hash of the contents of the file at `path`, read a block at a time.
+pub async fn hash_file(path: impl AsRef<Path>) -> Result<blake3::Hash, Error> {
+    #[cfg(feature = "async-std-runtime")]
+    use async_std::io::ReadExt;
+    #[cfg(not(feature = "async-std-runtime"))]
+    use tokio::io::AsyncReadExt;
+
+    let mut file = fs::File::open(path.as_ref()).await?;
+    let mut hasher = blake3::Hasher::new();
+    let mut block = vec![0; 65536];
+    loop {
+        match file.read(&mut block).await? {
+            0 => break,
+            n => hasher.update(&block[..n]),
+        };
+    }
+    Ok(hasher.finalize())
+}
+
+/// Writes the body of `response` to a new file at `path` as it is received, returning the number
+/// of bytes written.
+pub async fn download_to_file(
+    mut response: reqwest::Response,
+    path: impl AsRef<Path>,
+) -> Result<u64, Error> {
+    #[cfg(feature = "async-std-runtime")]
+    use async_std::io::WriteExt;
+    #[cfg(not(feature = "async-std-runtime"))]
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = fs::File::create(path.as_ref()).await?;
+    let mut written = 0;
+    while let Some(bytes) = response.chunk().await? {
+        file.write_all(&bytes).await?;
+        written += bytes.len() as u64;
+    }
+    file.flush().await?;
+    Ok(written)
+}
+
+/// Spawns `future` as a background task that is not awaited.
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    #[cfg(not(feature = "async-std-runtime"))]
+    tokio::spawn(future);
+    #[cfg(feature = "async-std-runtime")]
+    async_std::task::spawn(future);
+}
+
+/// Runs blocking or CPU bound work, like hashing large files, on a thread where it doesn't hold up
+/// other tasks.
+pub async fn spawn_blocking<F, T>(f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(not(feature = "async-std-runtime"))]
+    return Ok(tokio::task::spawn_blocking(f).await?);
+    #[cfg(feature = "async-std-runtime")]
+    return Ok(async_std::task::spawn_blocking(f).await);
+}
@@ -0,0 +1,93 @@
+//! Hook for vetting files before they are permanently uploaded.
+
+use crate::error::Error;
+use futures::future::BoxFuture;
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+};
+
+/// Outcome of scanning a file before upload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanVerdict {
+    Allow,
+    /// The file must not be uploaded, for the given reason.
+    Deny(String),
+}
+
+/// Check run on every file before it is uploaded, e.g. a malware, PII or NSFW scanner. Set it on
+/// [`crate::Arweave::scanner`]. Denied files get a [`crate::status::StatusCode::Blocked`] status
+/// and are left out of transactions and bundles, so they are never paid for.
+pub trait FileScanner: Send + Sync {
+    fn scan<'a>(&'a self, file_path: &'a Path) -> BoxFuture<'a, Result<ScanVerdict, Error>>;
+}
+
+/// Any async fn or closure that takes the path of a file is a [`FileScanner`].
+impl<F, Fut> FileScanner for F
+where
+    F: Fn(PathBuf) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<ScanVerdict, Error>> + Send + 'static,
+{
+    fn scan<'a>(&'a self, file_path: &'a Path) -> BoxFuture<'a, Result<ScanVerdict, Error>> {
+        Box::pin(self(file_path.to_path_buf()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScanVerdict;
+    use crate::{error::Error, status::StatusCode, utils::TempDir, Arweave, PathsChunk};
+    use matches::assert_matches;
+    use std::{path::PathBuf, sync::Arc};
+
+    async fn deny_json(file_path: PathBuf) -> Result<ScanVerdict, Error> {
+        match file_path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Ok(ScanVerdict::Deny("metadata not allowed".to_string())),
+            _ => Ok(ScanVerdict::Allow),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_blocks_upload() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            scanner: Some(Arc::new(deny_json)),
+            ..Arweave::default()
+        };
+
+        let status = arweave
+            .upload_file_from_path(
+                PathBuf::from("tests/fixtures/0.json"),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                (0, 0),
+                None,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Blocked);
+        assert_eq!(status.reward, 0);
+        assert_eq!(
+            status.blocked_reason.as_deref(),
+            Some("metadata not allowed")
+        );
+
+        let written = arweave
+            .read_status(PathBuf::from("tests/fixtures/0.json"), temp_dir.0.clone())
+            .await?;
+        assert_eq!(written, status);
+
+        let paths = vec![
+            PathBuf::from("tests/fixtures/0.png"),
+            PathBuf::from("tests/fixtures/0.json"),
+        ];
+        let (allowed, blocked) = arweave.scan_paths_chunk(PathsChunk(paths, 0)).await?;
+        assert_eq!(allowed.0, vec![PathBuf::from("tests/fixtures/0.png")]);
+        assert_eq!(
+            allowed.1,
+            PathBuf::from("tests/fixtures/0.png").metadata()?.len()
+        );
+        assert_matches!(blocked.as_slice(), [s] if s.status == StatusCode::Blocked);
+        Ok(())
+    }
+}
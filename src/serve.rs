@@ -0,0 +1,354 @@
+//! HTTP API for uploading files with a shared wallet. Enabled with the `serve` feature.
+
+use crate::{
+    digest::BatchDigest,
+    error::Error,
+    is_bearer_token, path_under_root,
+    status::Status,
+    transaction::{FromUtf8Strs, Tag},
+    Arweave,
+};
+use hyper::{
+    body::HttpBody,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, convert::Infallible, net::SocketAddr, path::PathBuf, sync::Arc};
+
+/// Largest body of a `POST /upload` request, in bytes.
+pub const MAX_UPLOAD_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Body of a `POST /upload` request.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadRequest {
+    pub file_path: PathBuf,
+    /// Tags added to the upload, by name.
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Uploads files submitted to the API with [`UploadServer::arweave`], writing their statuses to
+/// [`UploadServer::log_dir`].
+///
+/// - `POST /upload` with a json [`UploadRequest`] uploads the file and returns its [`Status`].
+/// - `GET /status/<FILE_PATH>` returns the status of an uploaded file, updated from the network.
+///   The path is percent-encoded, e.g. `/status/images%2F0.png`.
+/// - `GET /summary` returns the [`BatchDigest`] of every upload in the log directory.
+///
+/// Errors are returned as `{"error": "<MESSAGE>"}`.
+pub struct UploadServer {
+    pub arweave: Arweave,
+    pub log_dir: PathBuf,
+    pub reward_mult: f32,
+    /// Directory that submitted files have to be in.
+    pub root_dir: PathBuf,
+    /// Token that requests have to send as `Authorization: Bearer <TOKEN>`.
+    pub token: String,
+}
+
+impl UploadServer {
+    pub fn new(
+        arweave: Arweave,
+        log_dir: PathBuf,
+        reward_mult: f32,
+        root_dir: PathBuf,
+        token: String,
+    ) -> Self {
+        Self {
+            arweave,
+            log_dir,
+            reward_mult,
+            root_dir,
+            token,
+        }
+    }
+
+    /// Serves the API on `addr` until the process is stopped.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), Error> {
+        let server = Arc::new(self);
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                }))
+            }
+        });
+        Server::try_bind(&addr)?.serve(make_service).await?;
+        Ok(())
+    }
+
+    /// Routes `request` to the endpoint for its method and path.
+    pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let authorization = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        if !is_bearer_token(authorization, &self.token) {
+            return json_response(
+                StatusCode::UNAUTHORIZED,
+                json!({ "error": "missing or invalid bearer token" }),
+            );
+        }
+        let path = request.uri().path().to_string();
+        let result = match (request.method(), path.as_str()) {
+            (&Method::POST, "/upload") if !is_json_content_type(&request) => {
+                return json_response(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    json!({ "error": "content type must be application/json" }),
+                )
+            }
+            (&Method::POST, "/upload") => self.upload(request.into_body()).await,
+            (&Method::GET, "/summary") => self.summary().await,
+            (&Method::GET, path) if path.starts_with("/status/") => {
+                self.status(&path["/status/".len()..]).await
+            }
+            _ => return json_response(StatusCode::NOT_FOUND, json!({ "error": "not found" })),
+        };
+        match result {
+            Ok(value) => json_response(StatusCode::OK, value),
+            Err(error) => {
+                json_response(error_status(&error), json!({ "error": error.to_string() }))
+            }
+        }
+    }
+
+    async fn upload(&self, body: Body) -> Result<Value, Error> {
+        let request: UploadRequest = serde_json::from_slice(&read_body(body).await?)?;
+        let file_path = path_under_root(&self.root_dir, &request.file_path)?;
+        let tags = request
+            .tags
+            .iter()
+            .map(|(name, value)| Tag::from_utf8_strs(name, value))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let price_terms = self.arweave.get_price_terms(self.reward_mult).await?;
+        let status = self
+            .arweave
+            .upload_file_from_path(
+                file_path,
+                Some(self.log_dir.clone()),
+                (!tags.is_empty()).then(|| tags),
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        Ok(serde_json::to_value(status)?)
+    }
+
+    async fn status(&self, encoded_path: &str) -> Result<Value, Error> {
+        let file_path = PathBuf::from(
+            percent_decode_str(encoded_path)
+                .decode_utf8_lossy()
+                .as_ref(),
+        );
+        let status: Status = self
+            .arweave
+            .update_status(
+                path_under_root(&self.root_dir, &file_path)?,
+                self.log_dir.clone(),
+            )
+            .await?;
+        Ok(serde_json::to_value(status)?)
+    }
+
+    async fn summary(&self) -> Result<Value, Error> {
+        let digest: BatchDigest = self.arweave.batch_digest(self.log_dir.clone()).await?;
+        Ok(serde_json::to_value(digest)?)
+    }
+}
+
+/// Reads `body`, failing with [`Error::RequestBodyTooLarge`] as soon as it is larger than
+/// [`MAX_UPLOAD_REQUEST_BYTES`].
+async fn read_body(mut body: Body) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > MAX_UPLOAD_REQUEST_BYTES {
+            return Err(Error::RequestBodyTooLarge(MAX_UPLOAD_REQUEST_BYTES));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+fn is_json_content_type(request: &Request<Body>) -> bool {
+    request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map_or(false, |mime| {
+            mime.trim().eq_ignore_ascii_case("application/json")
+        })
+}
+
+fn error_status(error: &Error) -> StatusCode {
+    match error {
+        Error::StatusNotFound => StatusCode::NOT_FOUND,
+        Error::IOError(e) if e.kind() == std::io::ErrorKind::NotFound => StatusCode::NOT_FOUND,
+        Error::SerdeJson(_) | Error::InvalidTags => StatusCode::BAD_REQUEST,
+        Error::PathOutsideRoot(_) => StatusCode::FORBIDDEN,
+        Error::RequestBodyTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn json_response(status: StatusCode, value: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(value.to_string()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UploadServer, MAX_UPLOAD_REQUEST_BYTES};
+    use crate::{
+        error::Error,
+        simulation::SimulatedGateway,
+        status::{Status, StatusCode},
+        utils::TempDir,
+        Arweave,
+    };
+    use hyper::{body::to_bytes, http::request::Builder, Body, Request};
+    use serde_json::Value;
+    use std::{path::PathBuf, sync::Arc, time::Duration};
+
+    async fn request(server: &UploadServer, request: Request<Body>) -> (u16, Value) {
+        let response = server.handle(request).await;
+        let status = response.status().as_u16();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn upload(body: impl Into<Body>) -> Request<Body> {
+        authorized(Request::post("/upload"))
+            .header("Content-Type", "application/json")
+            .body(body.into())
+            .unwrap()
+    }
+
+    fn authorized(builder: Builder) -> Builder {
+        builder.header("Authorization", "Bearer token")
+    }
+
+    #[tokio::test]
+    async fn test_upload_server() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            ..Arweave::default()
+        };
+        let server = UploadServer::new(
+            arweave,
+            temp_dir.0.clone(),
+            1.0,
+            PathBuf::from("tests/fixtures"),
+            "token".to_string(),
+        );
+
+        let (code, body) = request(
+            &server,
+            upload(r#"{"file_path": "tests/fixtures/0.png", "tags": {"App-Name": "arloader"}}"#),
+        )
+        .await;
+        assert_eq!(code, 200);
+        let status: Status = serde_json::from_value(body).unwrap();
+
+        let (code, body) = request(
+            &server,
+            authorized(Request::get("/status/tests%2Ffixtures%2F0.png"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, 200);
+        let updated: Status = serde_json::from_value(body).unwrap();
+        assert_eq!(updated.id, status.id);
+        assert_eq!(updated.status, StatusCode::Confirmed);
+
+        let (code, body) = request(
+            &server,
+            authorized(Request::get("/summary"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, 200);
+        assert_eq!(body["files"], 1);
+
+        let (code, _) = request(
+            &server,
+            authorized(Request::get("/status/tests%2Ffixtures%2F1.png"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, 404);
+        let (code, _) = request(&server, upload("{}")).await;
+        assert_eq!(code, 400);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upload_server_rejects_requests() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let server = UploadServer::new(
+            Arweave::default(),
+            temp_dir.0.clone(),
+            1.0,
+            PathBuf::from("tests/fixtures"),
+            "token".to_string(),
+        );
+
+        for authorization in [None, Some("Bearer other"), Some("token")] {
+            let mut builder = Request::get("/summary");
+            if let Some(authorization) = authorization {
+                builder = builder.header("Authorization", authorization);
+            }
+            let (code, _) = request(&server, builder.body(Body::empty()).unwrap()).await;
+            assert_eq!(code, 401);
+        }
+
+        let (code, _) = request(
+            &server,
+            authorized(Request::post("/upload"))
+                .header("Content-Type", "text/plain")
+                .body(Body::from(r#"{"file_path": "tests/fixtures/0.png"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, 415);
+
+        let (code, _) = request(&server, upload(vec![b' '; MAX_UPLOAD_REQUEST_BYTES + 1])).await;
+        assert_eq!(code, 413);
+
+        for file_path in ["Cargo.toml", "tests/fixtures/../../Cargo.toml"] {
+            let (code, _) = request(
+                &server,
+                upload(format!(r#"{{"file_path": "{}"}}"#, file_path)),
+            )
+            .await;
+            assert_eq!(code, 403);
+        }
+        let (code, _) = request(
+            &server,
+            authorized(Request::get("/status/Cargo.toml"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, 403);
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+//! In-memory stand-in for a gateway, used to rehearse uploads without spending AR.
+
+use crate::{
+    error::Error,
+    price_for_data_size,
+    rt::sleep,
+    status::TxStatusResponse,
+    transaction::{Base64, Chunk, DownloadedChunk, Transaction, TxOffset},
+    transport::{ArweaveTransport, EMPTY_GRAPHQL_RESPONSE},
+    Arweave,
+};
+use futures::future::BoxFuture;
+use reqwest::StatusCode as ResponseStatusCode;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Price terms returned while simulating, roughly those of the network at the time of writing.
+pub const SIMULATED_PRICE_TERMS: (u64, u64) = (14_000_000, 13_000_000);
+
+/// Delay added to posts to the simulated gateway by the cli.
+pub const SIMULATED_LATENCY_MILLIS: u64 = 200;
+
+/// Accepts transactions and chunks posted by [`crate::Arweave`] when
+/// [`crate::Arweave::simulation`] is set, reporting them as pending until `confirm_after` has
+/// elapsed and confirmed afterwards. Reading, chunking, signing and status logging all run as
+/// they would against the network. Headers of posted transactions can be fetched, but not their
+/// data or chunks, and GraphQL queries find nothing.
+#[derive(Debug)]
+pub struct SimulatedGateway {
+    /// Time after which a posted transaction is reported as confirmed.
+    pub confirm_after: Duration,
+    /// Delay added to every post, to approximate network latency.
+    pub latency: Duration,
+    posted: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl SimulatedGateway {
+    pub fn new(confirm_after: Duration, latency: Duration) -> Self {
+        Self {
+            confirm_after,
+            latency,
+            posted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn post_transaction(&self, transaction: &Transaction) -> Result<(), Error> {
+        sleep(self.latency).await;
+        self.posted.lock().unwrap().insert(
+            transaction.id.to_string(),
+            (
+                Instant::now(),
+                serde_json::to_string(&transaction.clone_with_no_data()?)?,
+            ),
+        );
+        Ok(())
+    }
+
+    pub async fn post_chunk(&self, _chunk: &Chunk) -> Result<(), Error> {
+        sleep(self.latency).await;
+        Ok(())
+    }
+
+    /// Returns a fixed anchor, since simulated transactions are never checked against blocks.
+    pub fn tx_anchor(&self) -> Base64 {
+        Base64(vec![0; 32])
+    }
+
+    pub fn status(&self, id: &Base64) -> TxStatusResponse {
+        let posted_at = self
+            .posted
+            .lock()
+            .unwrap()
+            .get(&id.to_string())
+            .map(|(posted_at, _)| *posted_at);
+        match posted_at {
+            None => TxStatusResponse::NotFound,
+            Some(posted_at) if posted_at.elapsed() < self.confirm_after => {
+                TxStatusResponse::Pending
+            }
+            Some(posted_at) => TxStatusResponse::Confirmed {
+                block_height: 0,
+                block_indep_hash: Base64(vec![0; 48]),
+                number_of_confirmations: (posted_at.elapsed().as_secs()
+                    - self.confirm_after.as_secs())
+                    / 120
+                    + 1,
+            },
+        }
+    }
+}
+
+impl ArweaveTransport for SimulatedGateway {
+    /// Prices follow [`SIMULATED_PRICE_TERMS`].
+    fn get_price<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        bytes: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>> {
+        Box::pin(async move { Ok(price_for_data_size(bytes, SIMULATED_PRICE_TERMS) as u64) })
+    }
+
+    fn get_tx_anchor<'a>(&'a self, _arweave: &'a Arweave) -> BoxFuture<'a, Result<Base64, Error>> {
+        Box::pin(async move { Ok(self.tx_anchor()) })
+    }
+
+    fn post_transaction<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        transaction: &'a Transaction,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(self.post_transaction(transaction))
+    }
+
+    fn post_chunk<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        chunk: &'a Chunk,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(self.post_chunk(chunk))
+    }
+
+    fn get_tx_status<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxStatusResponse, Error>> {
+        Box::pin(async move { Ok(self.status(id)) })
+    }
+
+    fn get_transaction<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<(ResponseStatusCode, String), Error>> {
+        Box::pin(async move {
+            let key = id.to_string();
+            let header = self
+                .posted
+                .lock()
+                .unwrap()
+                .get(&key)
+                .map(|(_, header)| header.clone());
+            match (header, self.status(id)) {
+                (Some(header), TxStatusResponse::Confirmed { .. }) => {
+                    Ok((ResponseStatusCode::OK, header))
+                }
+                (Some(_), _) => Ok((ResponseStatusCode::ACCEPTED, "Pending".to_string())),
+                (None, _) => Ok((ResponseStatusCode::NOT_FOUND, String::new())),
+            }
+        })
+    }
+
+    fn get_transaction_data<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _id: &'a Base64,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn get_tx_offset<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxOffset, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn get_chunk<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _offset: u64,
+    ) -> BoxFuture<'a, Result<DownloadedChunk, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn query_graphql<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _query: &'a Value,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async { Ok(EMPTY_GRAPHQL_RESPONSE.to_string()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SimulatedGateway;
+    use crate::{error::Error, status::StatusCode, utils::TempDir, Arweave};
+    use std::{path::PathBuf, sync::Arc, time::Duration};
+    use tokio::time::sleep;
+
+    #[tokio::test]
+    async fn test_simulated_upload() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_millis(100),
+                Duration::from_millis(0),
+            ))),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        assert!(matches!(
+            arweave.get_verified_transaction_data(&status.id).await,
+            Err(Error::UnexpectedStatusResponse(404, _))
+        ));
+
+        let status = arweave
+            .update_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.status, StatusCode::Pending);
+
+        sleep(Duration::from_millis(100)).await;
+        let status = arweave.update_status(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(status.status, StatusCode::Confirmed);
+        let transaction = arweave.get_transaction(&status.id).await?;
+        assert_eq!(transaction.data_root, status.data_root.unwrap());
+        assert!(arweave
+            .get_wallet_transactions(None, None)
+            .await?
+            .transactions
+            .is_empty());
+        Ok(())
+    }
+}
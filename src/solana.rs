@@ -6,7 +6,13 @@ use futures::future::try_join;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use solana_sdk::{
-    hash::Hash, pubkey::Pubkey, signature::Signer, signer::keypair, system_transaction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+    signer::keypair,
+    system_transaction,
+    transaction::Transaction,
 };
 use std::str::FromStr;
 
@@ -25,10 +31,120 @@ pub const SOL_AR_BASE_URL: &str = "https://arloader.io/";
 /// Minimum SOL transaction amount.
 pub const FLOOR: u64 = 5000;
 
+/// SPL Token program, which token transfers are made through.
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Associated Token Account program, which derives the token account of a wallet for a mint.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Mint of USDC on Solana main net.
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// Decimals of USDC.
+pub const USDC_DECIMALS: u8 = 6;
+
+/// Index of the `TransferChecked` instruction of the SPL Token program.
+const TRANSFER_CHECKED: u8 = 12;
+
+/// SPL token to pay in instead of SOL, identified by its mint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplToken {
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+impl SplToken {
+    pub fn new(mint: &str, decimals: u8) -> Result<Self, Error> {
+        Ok(Self {
+            mint: Pubkey::from_str(mint)?,
+            decimals,
+        })
+    }
+
+    pub fn usdc() -> Self {
+        Self::new(USDC_MINT, USDC_DECIMALS).unwrap()
+    }
+
+    /// Converts an amount in whole tokens to the token's base units, e.g. 1.5 USDC to 1,500,000.
+    pub fn to_base_units(&self, amount: f64) -> u64 {
+        (amount * 10f64.powi(self.decimals as i32)).round() as u64
+    }
+
+    /// Converts `lamports` to the token's base units at `cents_per_sol`, the price of SOL in
+    /// hundredths of the currency the token is pegged to, rounding up so that payments are never
+    /// short.
+    pub fn base_units_for_lamports(&self, lamports: u64, cents_per_sol: u64) -> u64 {
+        let numerator = lamports as u128 * cents_per_sol as u128 * 10u128.pow(self.decimals as u32);
+        let denominator = 1_000_000_000u128 * 100;
+        ((numerator + denominator - 1) / denominator) as u64
+    }
+
+    /// Returns the associated token account holding this token for `wallet`.
+    pub fn token_account(&self, wallet: &Pubkey) -> Pubkey {
+        let token_program_id = Pubkey::from_str(TOKEN_PROGRAM_ID).unwrap();
+        Pubkey::find_program_address(
+            &[
+                wallet.as_ref(),
+                token_program_id.as_ref(),
+                self.mint.as_ref(),
+            ],
+            &Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).unwrap(),
+        )
+        .0
+    }
+
+    /// Creates a `TransferChecked` instruction moving `amount` base units from the token account
+    /// of `owner` to that of `to_wallet`. The destination token account must already exist.
+    pub fn transfer_instruction(
+        &self,
+        owner: &Pubkey,
+        to_wallet: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let mut data = vec![TRANSFER_CHECKED];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(self.decimals);
+
+        Instruction {
+            program_id: Pubkey::from_str(TOKEN_PROGRAM_ID).unwrap(),
+            accounts: vec![
+                AccountMeta::new(self.token_account(owner), false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.token_account(to_wallet), false),
+                AccountMeta::new_readonly(*owner, true),
+            ],
+            data,
+        }
+    }
+}
+
+/// Parses `usdc` or `<MINT>:<DECIMALS>`.
+impl FromStr for SplToken {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("usdc") {
+            return Ok(Self::usdc());
+        }
+        let (mint, decimals) = s
+            .split_once(':')
+            .ok_or_else(|| Error::InvalidSplToken(s.to_string()))?;
+        let decimals = decimals
+            .parse::<u8>()
+            .map_err(|_| Error::InvalidSplToken(s.to_string()))?;
+        Self::new(mint, decimals)
+    }
+}
+
 /// Returns recent blockhash neeed to create transaction.
 pub async fn get_recent_blockhash(base_url: url::Url) -> Result<Hash, Error> {
-    let client = reqwest::Client::new();
+    get_recent_blockhash_with_client(&reqwest::Client::new(), base_url).await
+}
 
+/// Like [`get_recent_blockhash`], sending requests with `client`.
+pub async fn get_recent_blockhash_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+) -> Result<Hash, Error> {
     let mut config = serde_json::Map::new();
     config.insert(
         "commitment".to_string(),
@@ -58,8 +174,15 @@ pub async fn get_sol_wallet_balance(
     base_url: url::Url,
     keypair: &keypair::Keypair,
 ) -> Result<u64, Error> {
-    let client = reqwest::Client::new();
+    get_sol_wallet_balance_with_client(&reqwest::Client::new(), base_url, keypair).await
+}
 
+/// Like [`get_sol_wallet_balance`], sending requests with `client`.
+pub async fn get_sol_wallet_balance_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    keypair: &keypair::Keypair,
+) -> Result<u64, Error> {
     let mut config = serde_json::Map::new();
     config.insert("commitment".to_string(), json!("confirmed".to_string()));
 
@@ -83,8 +206,15 @@ pub async fn get_sol_wallet_balance(
 
 /// Airdrops tokens from devnet for testing purposes.
 pub async fn request_airdrop(base_url: url::Url, keypair: &keypair::Keypair) -> Result<(), Error> {
-    let client = reqwest::Client::new();
+    request_airdrop_with_client(&reqwest::Client::new(), base_url, keypair).await
+}
 
+/// Like [`request_airdrop`], sending requests with `client`.
+pub async fn request_airdrop_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    keypair: &keypair::Keypair,
+) -> Result<(), Error> {
     let mut config = serde_json::Map::new();
     config.insert("commitment".to_string(), json!("confirmed".to_string()));
 
@@ -106,10 +236,21 @@ pub async fn create_sol_transaction(
     base_url: url::Url,
     from_keypair: &keypair::Keypair,
     lamports: u64,
+) -> Result<String, Error> {
+    create_sol_transaction_with_client(&reqwest::Client::new(), base_url, from_keypair, lamports)
+        .await
+}
+
+/// Like [`create_sol_transaction`], sending requests with `client`.
+pub async fn create_sol_transaction_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    from_keypair: &keypair::Keypair,
+    lamports: u64,
 ) -> Result<String, Error> {
     let (recent_blockhash, balance) = try_join(
-        get_recent_blockhash(base_url.clone()),
-        get_sol_wallet_balance(base_url, from_keypair),
+        get_recent_blockhash_with_client(client, base_url.clone()),
+        get_sol_wallet_balance_with_client(client, base_url, from_keypair),
     )
     .await?;
 
@@ -128,14 +269,116 @@ pub async fn create_sol_transaction(
     Ok(bs58::encode(serialized).into_string())
 }
 
+/// Returns the balance, in base units, of the token account of `wallet` for `token`.
+pub async fn get_token_balance(
+    base_url: url::Url,
+    wallet: &Pubkey,
+    token: &SplToken,
+) -> Result<u64, Error> {
+    get_token_balance_with_client(&reqwest::Client::new(), base_url, wallet, token).await
+}
+
+/// Like [`get_token_balance`], sending requests with `client`.
+pub async fn get_token_balance_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    wallet: &Pubkey,
+    token: &SplToken,
+) -> Result<u64, Error> {
+    let post_object = PostObject {
+        method: String::from("getTokenAccountBalance"),
+        params: vec![json!(
+            bs58::encode(token.token_account(wallet)).into_string()
+        )],
+        ..Default::default()
+    };
+
+    let result: Value = client
+        .post(base_url)
+        .json(&post_object)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    // Token accounts that don't exist yet have no balance.
+    let balance = result["result"]["value"]["amount"]
+        .as_str()
+        .and_then(|a| a.parse::<u64>().ok())
+        .unwrap_or(0);
+    Ok(balance)
+}
+
+/// Creates Solana transaction that pays `amount` base units of `token` instead of SOL, built
+/// with a token transfer instruction in place of [`system_transaction::transfer`]. Transaction
+/// fees are still paid in SOL by `from_keypair`.
+pub async fn create_spl_token_transaction(
+    base_url: url::Url,
+    from_keypair: &keypair::Keypair,
+    token: &SplToken,
+    amount: u64,
+) -> Result<String, Error> {
+    create_spl_token_transaction_with_client(
+        &reqwest::Client::new(),
+        base_url,
+        from_keypair,
+        token,
+        amount,
+    )
+    .await
+}
+
+/// Like [`create_spl_token_transaction`], sending requests with `client`.
+pub async fn create_spl_token_transaction_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    from_keypair: &keypair::Keypair,
+    token: &SplToken,
+    amount: u64,
+) -> Result<String, Error> {
+    let (recent_blockhash, balance) = try_join(
+        get_recent_blockhash_with_client(client, base_url.clone()),
+        get_token_balance_with_client(client, base_url, &from_keypair.pubkey(), token),
+    )
+    .await?;
+
+    if balance < amount {
+        return Err(Error::InsufficientTokenFunds);
+    }
+
+    let instruction = token.transfer_instruction(
+        &from_keypair.pubkey(),
+        &Pubkey::from_str(SOL_AR_PUBKEY).unwrap(),
+        amount,
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&from_keypair.pubkey()),
+        &[from_keypair],
+        recent_blockhash,
+    );
+    let serialized = bincode::serialize(&transaction)?;
+
+    Ok(bs58::encode(serialized).into_string())
+}
+
 /// Submits Solana transaction and required transaction elements and gets back signed AR transaction.
 pub async fn get_sol_ar_signature(
     base_url: url::Url,
     deep_hash_item: DeepHashItem,
     sol_tx: String,
 ) -> Result<SigResponse, Error> {
-    let client = reqwest::Client::new();
+    get_sol_ar_signature_with_client(&reqwest::Client::new(), base_url, deep_hash_item, sol_tx)
+        .await
+}
 
+/// Like [`get_sol_ar_signature`], sending requests with `client`.
+pub async fn get_sol_ar_signature_with_client(
+    client: &reqwest::Client,
+    base_url: url::Url,
+    deep_hash_item: DeepHashItem,
+    sol_tx: String,
+) -> Result<SigResponse, Error> {
     let tx_data = TxData {
         deep_hash_item,
         sol_tx,
@@ -224,4 +467,36 @@ mod tests {
         println!("{}", balance);
         Ok(())
     }
+
+    #[test]
+    fn test_spl_transfer_instruction() -> Result<(), Error> {
+        let token = SplToken::usdc();
+        assert_eq!(token.to_base_units(1.5), 1_500_000);
+
+        let owner = Pubkey::new_unique();
+        let to_wallet = Pubkey::new_unique();
+        let instruction = token.transfer_instruction(&owner, &to_wallet, 1_500_000);
+        assert_eq!(instruction.program_id, Pubkey::from_str(TOKEN_PROGRAM_ID)?);
+        assert_eq!(instruction.data[0], 12);
+        assert_eq!(instruction.data[1..9], 1_500_000u64.to_le_bytes());
+        assert_eq!(instruction.data[9], USDC_DECIMALS);
+        assert_eq!(instruction.accounts[1].pubkey, token.mint);
+        assert_eq!(instruction.accounts[3].pubkey, owner);
+        assert!(instruction.accounts[3].is_signer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_spl_token_from_str() -> Result<(), Error> {
+        assert_eq!(SplToken::from_str("usdc")?, SplToken::usdc());
+        assert_eq!(
+            SplToken::from_str(&format!("{}:9", USDC_MINT))?,
+            SplToken::new(USDC_MINT, 9)?
+        );
+        assert!(SplToken::from_str(USDC_MINT).is_err());
+
+        // 5000 lamports at $150.25 per SOL is $0.00075125, rounded up to 752 USDC base units.
+        assert_eq!(SplToken::usdc().base_units_for_lamports(5000, 15025), 752);
+        Ok(())
+    }
 }
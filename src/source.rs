@@ -0,0 +1,236 @@
+//! Sources that uploads read data from instead of local files, like S3 buckets and urls.
+
+use crate::error::Error;
+use serde::Deserialize;
+use std::{fmt, str::FromStr};
+use url::Url;
+
+/// Endpoint that buckets are listed and objects fetched from, unless another is set with
+/// [`S3Bucket::with_endpoint`].
+pub const S3_DEFAULT_ENDPOINT: &str = "https://s3.amazonaws.com/";
+
+/// Where objects to upload are read from, parsed from either an http(s) url or an
+/// `s3://<bucket>/<prefix>` uri.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadSource {
+    Url(Url),
+    S3(S3Bucket),
+}
+
+impl UploadSource {
+    /// Returns the urls of the objects to upload, listing the bucket for S3 sources.
+    pub async fn urls(&self, client: &reqwest::Client) -> Result<Vec<Url>, Error> {
+        match self {
+            Self::Url(url) => Ok(vec![url.clone()]),
+            Self::S3(bucket) => bucket.list_object_urls(client).await,
+        }
+    }
+}
+
+impl FromStr for UploadSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("s3://") {
+            return Ok(Self::S3(S3Bucket::from_str(s)?));
+        }
+        let url = Url::from_str(s)?;
+        match url.scheme() {
+            "http" | "https" => Ok(Self::Url(url)),
+            _ => Err(Error::InvalidUploadSource(s.to_string())),
+        }
+    }
+}
+
+/// Objects in an S3 bucket, or a bucket of a compatible service, whose keys start with `prefix`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Bucket {
+    pub endpoint: Url,
+    pub bucket: String,
+    pub prefix: String,
+}
+
+impl S3Bucket {
+    /// Lists the bucket at `endpoint` instead of [`S3_DEFAULT_ENDPOINT`], e.g. a regional
+    /// endpoint or that of a compatible service.
+    pub fn with_endpoint(mut self, endpoint: Url) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    /// Returns the path style url of the object at `key`.
+    pub fn object_url(&self, key: &str) -> Url {
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut()
+            .expect("endpoints are http(s) urls")
+            .pop_if_empty()
+            .push(&self.bucket)
+            .extend(key.split('/'));
+        url
+    }
+
+    /// Lists the keys of the objects under `prefix`, following continuation tokens until all of
+    /// them have been returned. Requests aren't signed, so the bucket has to allow public
+    /// listing.
+    pub async fn list_keys(&self, client: &reqwest::Client) -> Result<Vec<String>, Error> {
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut url = self.object_url("");
+            url.path_segments_mut()
+                .expect("endpoints are http(s) urls")
+                .pop_if_empty();
+            url.query_pairs_mut()
+                .append_pair("list-type", "2")
+                .append_pair("prefix", &self.prefix);
+            if let Some(token) = &continuation_token {
+                url.query_pairs_mut()
+                    .append_pair("continuation-token", token);
+            }
+            let body = client
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            let (page_keys, next_token) = parse_list_objects(&body)?;
+            keys.extend(page_keys);
+            match next_token {
+                Some(token) => continuation_token = Some(token),
+                None => return Ok(keys),
+            }
+        }
+    }
+
+    /// Lists the urls of the objects under `prefix`, leaving out folder placeholders.
+    pub async fn list_object_urls(&self, client: &reqwest::Client) -> Result<Vec<Url>, Error> {
+        Ok(self
+            .list_keys(client)
+            .await?
+            .into_iter()
+            .filter(|k| !k.ends_with('/'))
+            .map(|k| self.object_url(&k))
+            .collect())
+    }
+}
+
+impl FromStr for S3Bucket {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = s
+            .strip_prefix("s3://")
+            .ok_or_else(|| Error::InvalidUploadSource(s.to_string()))?;
+        let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+        if bucket.is_empty() {
+            return Err(Error::InvalidUploadSource(s.to_string()));
+        }
+        Ok(Self {
+            endpoint: Url::from_str(S3_DEFAULT_ENDPOINT).unwrap(),
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for S3Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "s3://{}/{}", self.bucket, self.prefix)
+    }
+}
+
+/// Body of a `ListObjectsV2` response, with the fields used to page through the keys.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ListBucketResult {
+    #[serde(default)]
+    is_truncated: bool,
+    #[serde(default)]
+    contents: Vec<ListedObject>,
+    next_continuation_token: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ListedObject {
+    key: String,
+}
+
+/// Returns the keys in a `ListObjectsV2` response and the token for the next page, if the
+/// response is truncated.
+pub fn parse_list_objects(body: &str) -> Result<(Vec<String>, Option<String>), Error> {
+    let result: ListBucketResult = quick_xml::de::from_str(body)
+        .map_err(|e| Error::InvalidUploadSource(format!("bucket listing: {}", e)))?;
+    let keys = result.contents.into_iter().map(|o| o.key).collect();
+    let next_token = result
+        .is_truncated
+        .then_some(result.next_continuation_token)
+        .flatten();
+    Ok((keys, next_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_list_objects, S3Bucket, UploadSource};
+    use std::str::FromStr;
+    use url::Url;
+
+    #[test]
+    fn test_upload_sources() {
+        let bucket = S3Bucket::from_str("s3://my-bucket/images/2022").unwrap();
+        assert_eq!(bucket.bucket, "my-bucket");
+        assert_eq!(bucket.prefix, "images/2022");
+        assert_eq!(
+            bucket.object_url("images/2022/a b#1.png").as_str(),
+            "https://s3.amazonaws.com/my-bucket/images/2022/a%20b%231.png"
+        );
+        let bucket = bucket.with_endpoint(Url::from_str("http://localhost:9000").unwrap());
+        assert_eq!(
+            bucket.object_url("0.png").as_str(),
+            "http://localhost:9000/my-bucket/0.png"
+        );
+        assert!(S3Bucket::from_str("s3:///images").is_err());
+
+        assert_eq!(
+            UploadSource::from_str("https://example.com/0.png").unwrap(),
+            UploadSource::Url(Url::from_str("https://example.com/0.png").unwrap())
+        );
+        assert!(matches!(
+            UploadSource::from_str("s3://my-bucket").unwrap(),
+            UploadSource::S3(S3Bucket { prefix, .. }) if prefix.is_empty()
+        ));
+        assert!(UploadSource::from_str("ftp://example.com/0.png").is_err());
+
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListBucketResult>
+                <Name>my-bucket</Name>
+                <IsTruncated>true</IsTruncated>
+                <Contents><Key>images/</Key></Contents>
+                <Contents><Key>images/0.png</Key></Contents>
+                <Contents>
+                    <Key>images/R&amp;D.png</Key>
+                    <Size>100</Size>
+                    <StorageClass>STANDARD</StorageClass>
+                </Contents>
+                <!-- <Key>not/a/key</Key> -->
+                <Contents><Key><![CDATA[images/<1>.png]]></Key></Contents>
+                <NextContinuationToken>token&amp;1</NextContinuationToken>
+            </ListBucketResult>"#;
+        let (keys, next_token) = parse_list_objects(body).unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                "images/",
+                "images/0.png",
+                "images/R&D.png",
+                "images/<1>.png"
+            ]
+        );
+        assert_eq!(next_token, Some("token&1".to_string()));
+        let (_, next_token) =
+            parse_list_objects(&body.replace("<IsTruncated>true", "<IsTruncated>false")).unwrap();
+        assert_eq!(next_token, None);
+        assert!(parse_list_objects("<ListBucketResult>").is_err());
+    }
+}
@@ -1,15 +1,92 @@
 //! Data structures for reporting transaction statuses.
 
+use crate::bundlr::BundlrReceipt;
+use crate::error::Error;
 use crate::solana::SigResponse;
-use crate::transaction::Base64;
+use crate::transaction::{Base64, Tag};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::{cmp::Eq, fmt, hash::Hash, path::PathBuf};
+use std::{
+    cmp::Eq,
+    fmt,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
 
 const STRFTIME: &str = "%Y-%m-%d %H:%M:%S";
 
+/// Serializes optional timestamps as RFC 3339 strings in UTC. Timestamps with other offsets are
+/// converted to UTC when deserialized.
+mod rfc3339 {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => {
+                serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::Millis, true))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|t| t.with_timezone(&Utc))
+                    .map_err(de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+/// Serializes optional file paths as strings, or as the bytes of the path on Unix and its wide
+/// characters on Windows if it isn't valid UTF-8, so that any path that can be uploaded can be
+/// logged.
+mod file_path {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::{ffi::OsString, path::PathBuf};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FilePath {
+        Utf8(String),
+        Os(OsString),
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<PathBuf>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => match value.to_str() {
+                Some(value) => serializer.serialize_str(value),
+                None => value.as_os_str().serialize(serializer),
+            },
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<PathBuf>, D::Error> {
+        Ok(
+            Option::<FilePath>::deserialize(deserializer)?.map(|file_path| match file_path {
+                FilePath::Utf8(file_path) => PathBuf::from(file_path),
+                FilePath::Os(file_path) => PathBuf::from(file_path),
+            }),
+        )
+    }
+}
+
 /// Status as reported directly from the network.
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -19,6 +96,72 @@ pub struct RawStatus {
     pub number_of_confirmations: u64,
 }
 
+/// Response of the `tx/{id}/status` endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxStatusResponse {
+    /// Received by the gateway but not yet seen by the nodes it forwards to.
+    Accepted,
+    /// In the mempool, waiting to be mined.
+    Pending,
+    Confirmed {
+        block_height: u64,
+        block_indep_hash: Base64,
+        number_of_confirmations: u64,
+    },
+    NotFound,
+}
+
+impl TxStatusResponse {
+    /// Parses the status code and body of a response from the `tx/{id}/status` endpoint.
+    pub fn from_response(status: reqwest::StatusCode, body: &str) -> Result<Self, Error> {
+        match status {
+            reqwest::StatusCode::OK if body == "Pending" => Ok(Self::Pending),
+            reqwest::StatusCode::OK => Ok(serde_json::from_str::<RawStatus>(body)?.into()),
+            reqwest::StatusCode::ACCEPTED => Ok(Self::Accepted),
+            reqwest::StatusCode::NOT_FOUND => Ok(Self::NotFound),
+            _ => Err(Error::UnexpectedStatusResponse(
+                status.as_u16(),
+                body.to_string(),
+            )),
+        }
+    }
+
+    /// [`StatusCode`] recorded in statuses for the response. Accepted transactions are recorded
+    /// as [`StatusCode::Pending`].
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Accepted | Self::Pending => StatusCode::Pending,
+            Self::Confirmed { .. } => StatusCode::Confirmed,
+            Self::NotFound => StatusCode::NotFound,
+        }
+    }
+
+    pub fn raw_status(&self) -> Option<RawStatus> {
+        match self {
+            Self::Confirmed {
+                block_height,
+                block_indep_hash,
+                number_of_confirmations,
+            } => Some(RawStatus {
+                block_height: *block_height,
+                block_indep_hash: block_indep_hash.clone(),
+                number_of_confirmations: *number_of_confirmations,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<RawStatus> for TxStatusResponse {
+    fn from(raw_status: RawStatus) -> Self {
+        Self::Confirmed {
+            block_height: raw_status.block_height,
+            block_indep_hash: raw_status.block_indep_hash,
+            number_of_confirmations: raw_status.number_of_confirmations,
+        }
+    }
+}
+
 /// Indicates transaction status on the network, from Submitted to Confirmed.
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone, Eq, Hash)]
 pub enum StatusCode {
@@ -27,6 +170,47 @@ pub enum StatusCode {
     Pending,
     Confirmed,
     NotFound,
+    /// Denied by the [`crate::scan::FileScanner`] and never uploaded.
+    Blocked,
+    /// Signed but not posted, because posting the transaction or its chunks failed.
+    Failed {
+        reason: String,
+    },
+    /// Seen as pending by the network but no longer found, e.g. after being evicted from the
+    /// mempool.
+    Dropped,
+    /// Signed and logged before being posted, so that the id is never lost. A status that is
+    /// still signed was interrupted while posting, and the transaction may or may not have
+    /// reached the network until the status is updated.
+    Signed,
+}
+
+impl StatusCode {
+    /// Returns true if `other` is the same kind of status, ignoring the reason of
+    /// [`StatusCode::Failed`].
+    pub fn is_kind_of(&self, other: &StatusCode) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Returns true if the transaction didn't make it to the network and the file needs to be
+    /// uploaded again.
+    pub fn needs_reupload(&self) -> bool {
+        matches!(
+            self,
+            StatusCode::NotFound | StatusCode::Failed { .. } | StatusCode::Dropped
+        )
+    }
+
+    /// Returns the status to record when the network reports `reported` for a transaction with
+    /// this status. Pending transactions that can no longer be found are
+    /// [`StatusCode::Dropped`], and failed or dropped transactions stay so while not found.
+    pub fn updated(&self, reported: StatusCode) -> StatusCode {
+        match (self, reported) {
+            (StatusCode::Pending, StatusCode::NotFound) => StatusCode::Dropped,
+            (StatusCode::Failed { .. } | StatusCode::Dropped, StatusCode::NotFound) => self.clone(),
+            (_, reported) => reported,
+        }
+    }
 }
 
 impl std::fmt::Display for StatusCode {
@@ -36,31 +220,99 @@ impl std::fmt::Display for StatusCode {
             StatusCode::Pending => write!(f, "Pending"),
             StatusCode::Confirmed => write!(f, "Confirmed"),
             StatusCode::NotFound => write!(f, "NotFound"),
+            StatusCode::Blocked => write!(f, "Blocked"),
+            StatusCode::Failed { .. } => write!(f, "Failed"),
+            StatusCode::Dropped => write!(f, "Dropped"),
+            StatusCode::Signed => write!(f, "Signed"),
         }
     }
 }
 pub struct FilterElements<'a> {
     pub raw_status: &'a Option<RawStatus>,
     pub status: &'a StatusCode,
+    pub file_paths: Vec<&'a Path>,
+    pub tags: &'a [Tag<String>],
 }
 pub trait Filterable {
     fn get_filter_elements(&self) -> FilterElements;
 }
 
+/// Criteria beyond status code and confirmations that [`crate::Arweave::filter_statuses_with`]
+/// matches statuses against. A status matches if it has every tag in `tags` and, with a
+/// `path_pattern`, one of its file paths matches the pattern. Bundle statuses match on the paths
+/// of the files in the bundle and have no tags.
+#[derive(Debug, Clone, Default)]
+pub struct StatusFilter {
+    pub tags: Vec<Tag<String>>,
+    pub path_pattern: Option<glob::Pattern>,
+}
+
+impl StatusFilter {
+    pub fn matches(&self, elements: &FilterElements) -> bool {
+        let has_tags = self.tags.iter().all(|tag| elements.tags.contains(tag));
+        let has_path = match &self.path_pattern {
+            Some(pattern) => elements.file_paths.iter().any(|p| pattern.matches_path(p)),
+            None => true,
+        };
+        has_tags && has_path
+    }
+}
+
 /// Data structure for tracking transaction statuses.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Status {
     pub id: Base64,
     pub status: StatusCode,
+    #[serde(default, with = "file_path")]
     pub file_path: Option<PathBuf>,
     pub content_type: String,
     pub created_at: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
-    pub reward: u64,
+    /// When the transaction was first posted.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub first_submitted: Option<DateTime<Utc>>,
+    /// When the status was last requested from the network.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub last_checked: Option<DateTime<Utc>>,
+    /// When the status was first found to be [`StatusCode::Confirmed`].
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// When the transaction was signed, after the file was read, hashed and chunked. Kept at
+    /// the precision of `created_at` so that short preparation times can be measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<DateTime<Utc>>,
+    /// When the transaction and its chunks had been posted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posted_at: Option<DateTime<Utc>>,
+    /// Bytes of data posted per second, from `first_submitted` to `posted_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_per_sec: Option<f64>,
+    pub reward: u128,
     #[serde(flatten)]
     pub raw_status: Option<RawStatus>,
     #[serde(flatten)]
     pub sol_sig: Option<SigResponse>,
+    pub bundlr_receipt: Option<BundlrReceipt>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    /// Peers from [`crate::Arweave::seed_peers`] that accepted the transaction and its chunks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seeded_peers: Vec<String>,
+    /// Bundle the file was uploaded in as a data item, if any, whose status is tracked in
+    /// place of that of the data item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<Base64>,
+    /// Tags the file was uploaded with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag<String>>,
+    /// Merkle root of the data of the transaction, so that the file can be verified against the
+    /// transaction without fetching its header. For files uploaded in bundles, it is calculated
+    /// in the same way from the data of their data item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_root: Option<Base64>,
+    /// Size of the data of the transaction in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_size: Option<u64>,
 }
 
 impl Default for Status {
@@ -72,14 +324,66 @@ impl Default for Status {
             content_type: mime_guess::mime::OCTET_STREAM.to_string(),
             created_at: Utc::now(),
             last_modified: Utc::now(),
+            first_submitted: None,
+            last_checked: None,
+            confirmed_at: None,
+            signed_at: None,
+            posted_at: None,
+            bytes_per_sec: None,
             reward: 0,
             raw_status: None,
             sol_sig: None,
+            bundlr_receipt: None,
+            blocked_reason: None,
+            seeded_peers: Vec::new(),
+            bundle_id: None,
+            tags: Vec::new(),
+            data_root: None,
+            data_size: None,
         }
     }
 }
 
 impl Status {
+    /// Records a status check at `checked_at`, setting `confirmed_at` the first time the status
+    /// is [`StatusCode::Confirmed`].
+    pub fn record_check(&mut self, checked_at: DateTime<Utc>) {
+        self.last_modified = checked_at;
+        self.last_checked = Some(checked_at);
+        if self.status == StatusCode::Confirmed && self.confirmed_at.is_none() {
+            self.confirmed_at = Some(checked_at);
+        }
+    }
+
+    /// Time from first submission to confirmation, if both have been recorded.
+    pub fn confirmation_latency(&self) -> Option<Duration> {
+        Some(self.confirmed_at? - self.first_submitted?)
+    }
+
+    /// Time spent reading, hashing and signing the file before it was posted.
+    pub fn preparation_time(&self) -> Option<Duration> {
+        Some(self.signed_at? - self.created_at)
+    }
+
+    /// Time spent posting the transaction and its chunks, leaving out writing the status.
+    pub fn posting_time(&self) -> Option<Duration> {
+        Some(self.posted_at? - self.first_submitted?)
+    }
+
+    /// Size in bytes of the uploaded data, which is what the reward was paid for, or for statuses
+    /// written before it was recorded, the size of the file on disk if it still exists.
+    pub fn uploaded_size(&self) -> Option<u64> {
+        self.data_size
+            .or_else(|| Some(self.file_path.as_ref()?.metadata().ok()?.len()))
+    }
+
+    /// Records that `data_size` bytes were posted at `posted_at`, setting `bytes_per_sec` from
+    /// `first_submitted`.
+    pub fn record_post(&mut self, posted_at: DateTime<Utc>, data_size: u64) {
+        self.posted_at = Some(posted_at);
+        self.bytes_per_sec = throughput(data_size, self.posting_time());
+    }
+
     pub fn header_string(&self, output_format: &OutputFormat) -> String {
         match output_format {
             OutputFormat::Display => {
@@ -98,6 +402,8 @@ impl Filterable for Status {
         FilterElements {
             raw_status: &self.raw_status,
             status: &self.status,
+            file_paths: self.file_path.iter().map(PathBuf::as_path).collect(),
+            tags: &self.tags,
         }
     }
 }
@@ -152,6 +458,28 @@ impl VerboseDisplay for Status {
             "last_modified:",
             self.last_modified.format(STRFTIME).to_string()
         )?;
+        for (label, timestamp) in [
+            ("signed_at:", self.signed_at),
+            ("posted_at:", self.posted_at),
+        ] {
+            if let Some(timestamp) = timestamp {
+                writeln!(
+                    w,
+                    "{:<15} {}",
+                    label,
+                    timestamp.format(STRFTIME).to_string()
+                )?;
+            }
+        }
+        if let Some(bytes_per_sec) = self.bytes_per_sec {
+            writeln!(w, "{:<15} {:.0}", "bytes/sec:", bytes_per_sec)?;
+        }
+        write_timestamps(
+            w,
+            self.first_submitted,
+            self.last_checked,
+            self.confirmed_at,
+        )?;
         if let Some(raw_status) = &self.raw_status {
             writeln!(w, "{:<15} {}", "height:", raw_status.block_height)?;
             writeln!(w, "{:<15} {}", "indep_hash:", raw_status.block_indep_hash)?;
@@ -161,10 +489,41 @@ impl VerboseDisplay for Status {
                 "confirms:", raw_status.number_of_confirmations
             )?;
         };
+        if let Some(receipt) = &self.bundlr_receipt {
+            writeln!(w, "{:<15} {}", "receipt_ts:", receipt.timestamp)?;
+            writeln!(w, "{:<15} {}", "deadline:", receipt.deadline_height)?;
+        };
+        if let Some(blocked_reason) = &self.blocked_reason {
+            writeln!(w, "{:<15} {}", "blocked:", blocked_reason)?;
+        };
         writeln!(w, "")
     }
 }
 
+/// Writes the submission, check and confirmation timestamps that have been recorded.
+fn write_timestamps(
+    w: &mut dyn fmt::Write,
+    first_submitted: Option<DateTime<Utc>>,
+    last_checked: Option<DateTime<Utc>>,
+    confirmed_at: Option<DateTime<Utc>>,
+) -> fmt::Result {
+    for (label, timestamp) in [
+        ("submitted:", first_submitted),
+        ("last_checked:", last_checked),
+        ("confirmed_at:", confirmed_at),
+    ] {
+        if let Some(timestamp) = timestamp {
+            writeln!(
+                w,
+                "{:<15} {}",
+                label,
+                timestamp.format(STRFTIME).to_string()
+            )?;
+        }
+    }
+    Ok(())
+}
+
 /// Data structure for tracking bundle statuses.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct BundleStatus {
@@ -175,11 +534,44 @@ pub struct BundleStatus {
     pub data_size: u64,
     pub created_at: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
-    pub reward: u64,
+    /// When the transaction was first posted.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub first_submitted: Option<DateTime<Utc>>,
+    /// When the status was last requested from the network.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub last_checked: Option<DateTime<Utc>>,
+    /// When the status was first found to be [`StatusCode::Confirmed`].
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "rfc3339")]
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub reward: u128,
     #[serde(flatten)]
     pub raw_status: Option<RawStatus>,
     #[serde(flatten)]
     pub sol_sig: Option<SigResponse>,
+    /// Statuses of files left out of the bundle by the [`crate::scan::FileScanner`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked: Vec<Status>,
+    /// Peers from [`crate::Arweave::seed_peers`] that accepted the transaction and its chunks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seeded_peers: Vec<String>,
+    /// When the bundle transaction was signed, after its data items were created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<DateTime<Utc>>,
+    /// When the bundle transaction and its chunks had been posted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posted_at: Option<DateTime<Utc>>,
+    /// Bytes of data posted per second, from `first_submitted` to `posted_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_per_sec: Option<f64>,
+}
+
+/// Bytes per second of posting `data_size` bytes in `posting_time`.
+fn throughput(data_size: u64, posting_time: Option<Duration>) -> Option<f64> {
+    posting_time
+        .and_then(|d| d.to_std().ok())
+        .map(|d| d.as_secs_f64())
+        .filter(|secs| *secs > 0.0)
+        .map(|secs| data_size as f64 / secs)
 }
 
 impl Default for BundleStatus {
@@ -192,14 +584,49 @@ impl Default for BundleStatus {
             data_size: 0,
             created_at: Utc::now(),
             last_modified: Utc::now(),
+            first_submitted: None,
+            last_checked: None,
+            confirmed_at: None,
             reward: 0,
             raw_status: None,
             sol_sig: None,
+            blocked: Vec::new(),
+            seeded_peers: Vec::new(),
+            signed_at: None,
+            posted_at: None,
+            bytes_per_sec: None,
         }
     }
 }
 
 impl BundleStatus {
+    /// Time spent posting the bundle transaction and its chunks.
+    pub fn posting_time(&self) -> Option<Duration> {
+        Some(self.posted_at? - self.first_submitted?)
+    }
+
+    /// Records that `data_size` bytes were posted at `posted_at`, setting `bytes_per_sec` from
+    /// `first_submitted`.
+    pub fn record_post(&mut self, posted_at: DateTime<Utc>, data_size: u64) {
+        self.posted_at = Some(posted_at);
+        self.bytes_per_sec = throughput(data_size, self.posting_time());
+    }
+
+    /// Records a status check at `checked_at`, setting `confirmed_at` the first time the status
+    /// is [`StatusCode::Confirmed`].
+    pub fn record_check(&mut self, checked_at: DateTime<Utc>) {
+        self.last_modified = checked_at;
+        self.last_checked = Some(checked_at);
+        if self.status == StatusCode::Confirmed && self.confirmed_at.is_none() {
+            self.confirmed_at = Some(checked_at);
+        }
+    }
+
+    /// Time from first submission to confirmation, if both have been recorded.
+    pub fn confirmation_latency(&self) -> Option<Duration> {
+        Some(self.confirmed_at? - self.first_submitted?)
+    }
+
     pub fn header_string(&self, output_format: &OutputFormat) -> String {
         match output_format {
             OutputFormat::Display => {
@@ -218,6 +645,12 @@ impl Filterable for BundleStatus {
         FilterElements {
             raw_status: &self.raw_status,
             status: &self.status,
+            file_paths: self
+                .file_paths
+                .as_object()
+                .map(|paths| paths.keys().map(Path::new).collect())
+                .unwrap_or_default(),
+            tags: &[],
         }
     }
 }
@@ -262,6 +695,12 @@ impl VerboseDisplay for BundleStatus {
             "last_modified:",
             self.last_modified.format(STRFTIME).to_string()
         )?;
+        write_timestamps(
+            w,
+            self.first_submitted,
+            self.last_checked,
+            self.confirmed_at,
+        )?;
         if let Some(raw_status) = &self.raw_status {
             writeln!(w, "{:<15} {}", "height:", raw_status.block_height)?;
             writeln!(w, "{:<15} {}", "indep_hash:", raw_status.block_indep_hash)?;
@@ -275,17 +714,126 @@ impl VerboseDisplay for BundleStatus {
     }
 }
 
+/// Outcome of checking an uploaded file against the transaction recorded in its [`Status`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Eq, Hash)]
+pub enum VerifyOutcome {
+    Verified,
+    Mismatch,
+    Pending,
+    NotFound,
+    /// Downloaded without a transaction to check against, as for data items in bundles.
+    Unverified,
+}
+
+impl std::fmt::Display for VerifyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyOutcome::Verified => write!(f, "Verified"),
+            VerifyOutcome::Mismatch => write!(f, "Mismatch"),
+            VerifyOutcome::Pending => write!(f, "Pending"),
+            VerifyOutcome::NotFound => write!(f, "NotFound"),
+            VerifyOutcome::Unverified => write!(f, "Unverified"),
+        }
+    }
+}
+
+/// Data structure for reporting the verification of an uploaded file.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Verification {
+    pub id: Base64,
+    pub file_path: PathBuf,
+    pub outcome: VerifyOutcome,
+}
+
+impl Verification {
+    pub fn header_string(&self, output_format: &OutputFormat) -> String {
+        match output_format {
+            OutputFormat::Display => {
+                format!(
+                    " {:<30}  {:<43}  {:<9}
+{:-<86}",
+                    "path", "id", "outcome", ""
+                )
+            }
+            _ => format!("{}", ""),
+        }
+    }
+}
+
+impl QuietDisplay for Verification {}
+impl VerboseDisplay for Verification {}
+
+impl std::fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            " {:<30}  {:<43}  {:<9}",
+            self.file_path.display().to_string(),
+            self.id,
+            self.outcome.to_string(),
+        )
+    }
+}
+
+/// Payload posted to [`crate::Arweave::webhook_url`] when a status moves to
+/// [`StatusCode::Confirmed`], [`StatusCode::NotFound`] or [`StatusCode::Dropped`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct StatusNotification {
+    pub file_path: Option<PathBuf>,
+    pub id: Base64,
+    pub status: StatusCode,
+    pub previous_status: StatusCode,
+}
+
+/// Version of a manifest posted by [`crate::Arweave::append_to_manifest`], recorded in the
+/// [`crate::MANIFEST_LINEAGE_FILE_NAME`] of a log directory, oldest first.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ManifestVersion {
+    pub id: Base64,
+    /// Manifest the version was created from, if its id is known.
+    pub previous_id: Option<Base64>,
+    /// Paths added or replaced by the version.
+    pub paths: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Controls output format, including quiet, verbose and json formats.
 #[derive(Debug, Clone, Copy)]
 pub enum OutputFormat {
     Display,
+    /// One json object per line, so that output can be parsed line by line.
     Json,
-    JsonCompact,
     DisplayQuiet,
     DisplayVerbose,
+    /// Same as [`OutputFormat::Json`], kept for callers of the pretty and compact json formats
+    /// that json replaced.
+    #[deprecated(note = "use `OutputFormat::Json`, which prints compact json")]
+    JsonCompact,
 }
 
+#[allow(deprecated)]
 impl OutputFormat {
+    pub fn is_json(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::JsonCompact)
+    }
+
+    /// Prints a message that isn't a record, to stderr for json so that every line of stdout is
+    /// a record.
+    pub fn print_message<D: fmt::Display>(&self, message: D) {
+        if self.is_json() {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Prints the header of a table of records, which json doesn't have.
+    pub fn print_header(&self, header: &str) {
+        if !self.is_json() {
+            println!("{}", header);
+        }
+    }
+
     pub fn formatted_string<T>(&self, item: &T) -> String
     where
         T: Serialize + fmt::Display + QuietDisplay + VerboseDisplay,
@@ -302,14 +850,9 @@ impl OutputFormat {
                 VerboseDisplay::write_str(item, &mut s).unwrap();
                 s
             }
-            OutputFormat::Json => {
-                let mut string = serde_json::to_string_pretty(item).unwrap();
-                ",\n".chars().for_each(|c| string.push(c));
-                string
-            }
-            OutputFormat::JsonCompact => {
-                let mut string = serde_json::to_value(item).unwrap().to_string();
-                ",\n".chars().for_each(|c| string.push(c));
+            OutputFormat::Json | OutputFormat::JsonCompact => {
+                let mut string = serde_json::to_string(item).unwrap();
+                string.push('\n');
                 string
             }
         }
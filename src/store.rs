@@ -0,0 +1,430 @@
+//! Storage of upload [`Status`]es behind [`StatusStore`], in files of the log directory by default.
+
+use crate::{error::Error, file_stem_is_status, rt::fs, status::Status, STATUS_ARCHIVE_FILE_NAME};
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use glob::glob;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// Predicate of the statuses kept by [`StatusStore::retain_statuses`].
+pub type StatusFilterFn<'a> = dyn Fn(&Status) -> bool + Send + Sync + 'a;
+
+/// Where [`crate::Arweave`] writes and reads statuses. Set it on [`crate::Arweave::status_store`]
+/// to share statuses between upload workers through a database or key-value store, with
+/// `log_dir` as the namespace of their keys.
+///
+/// Statuses are keyed by `log_dir` and by a key unique within it, the BLAKE3 hash of the file
+/// path of the upload or `txid_<ID>` for uploads without one. Bundle statuses aren't statuses of
+/// the store and are always written as files in `log_dir`.
+pub trait StatusStore: Send + Sync {
+    /// Writes `status`, replacing any status with the same key.
+    fn write_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+        status: &'a Status,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Returns the status with `key`, or `None` if there isn't one.
+    fn read_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Status>, Error>>;
+
+    /// Returns a stream of every status in `log_dir`.
+    fn list_statuses<'a>(
+        &'a self,
+        log_dir: PathBuf,
+    ) -> Result<BoxStream<'a, Result<Status, Error>>, Error>;
+
+    /// Removes the statuses in `log_dir` that `keep` returns false for. Returns the number of
+    /// statuses removed.
+    fn retain_statuses<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        keep: &'a StatusFilterFn<'a>,
+    ) -> BoxFuture<'a, Result<usize, Error>>;
+
+    /// Moves the statuses in `log_dir` into fewer records, e.g. an archive of status files.
+    /// Returns the number of statuses compacted, 0 for stores that don't need compacting, which
+    /// do nothing by default.
+    fn compact_statuses<'a>(&'a self, _log_dir: &'a Path) -> BoxFuture<'a, Result<usize, Error>> {
+        Box::pin(async { Ok(0) })
+    }
+}
+
+/// Writes statuses to `<log_dir>/<key>.json`, reading them from the status archive of
+/// [`crate::Arweave::compact_statuses`] if they aren't there.
+///
+/// Archives are parsed once and kept until their modification time or size changes, so reading
+/// many statuses from a compacted log directory doesn't parse its archive for each of them.
+#[derive(Debug, Default)]
+pub struct FileStatusStore {
+    archives: Mutex<HashMap<PathBuf, ArchiveEntry>>,
+}
+
+type ArchiveEntry = ((SystemTime, u64), Arc<BTreeMap<String, Status>>);
+
+impl Clone for FileStatusStore {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl FileStatusStore {
+    /// Returns the status archive of `log_dir`, parsing it only if it has changed since it was
+    /// last read.
+    async fn status_archive(&self, log_dir: &Path) -> Result<Arc<BTreeMap<String, Status>>, Error> {
+        let metadata = match fs::metadata(log_dir.join(STATUS_ARCHIVE_FILE_NAME)).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Arc::new(BTreeMap::new()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let version = (metadata.modified()?, metadata.len());
+        if let Some((cached_version, archive)) = self.archives.lock().unwrap().get(log_dir) {
+            if *cached_version == version {
+                return Ok(archive.clone());
+            }
+        }
+        let archive = Arc::new(read_status_archive(log_dir).await?);
+        self.archives
+            .lock()
+            .unwrap()
+            .insert(log_dir.to_path_buf(), (version, archive.clone()));
+        Ok(archive)
+    }
+}
+
+impl StatusStore for FileStatusStore {
+    fn write_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+        status: &'a Status,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            fs::write(
+                log_dir.join(key).with_extension("json"),
+                serde_json::to_string(status)?,
+            )
+            .await?;
+            Ok(())
+        })
+    }
+
+    fn read_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Status>, Error>> {
+        Box::pin(async move {
+            let status_path = log_dir.join(key).with_extension("json");
+            if status_path.exists() {
+                let data = fs::read_to_string(status_path).await?;
+                Ok(Some(serde_json::from_str(&data)?))
+            } else {
+                Ok(self.status_archive(log_dir).await?.get(key).cloned())
+            }
+        })
+    }
+
+    /// Archived statuses follow those in status files, leaving out any that have been written
+    /// again since.
+    fn list_statuses<'a>(
+        &'a self,
+        log_dir: PathBuf,
+    ) -> Result<BoxStream<'a, Result<Status, Error>>, Error> {
+        let status_paths = status_file_paths(&log_dir)?;
+        let file_stems: HashSet<String> = status_paths
+            .iter()
+            .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from))
+            .collect();
+        let archived = stream::once(async move { read_status_archive(&log_dir).await })
+            .map(move |archive| {
+                let statuses: Vec<Result<Status, Error>> = match archive {
+                    Ok(archive) => archive
+                        .into_iter()
+                        .filter(|(stem, _)| !file_stems.contains(stem))
+                        .map(|(_, status)| Ok(status))
+                        .collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                stream::iter(statuses)
+            })
+            .flatten();
+        Ok(stream::iter(status_paths)
+            .then(|status_path| async move {
+                let data = fs::read_to_string(status_path).await?;
+                Ok(serde_json::from_str::<Status>(&data)?)
+            })
+            .chain(archived)
+            .boxed())
+    }
+
+    fn retain_statuses<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        keep: &'a StatusFilterFn<'a>,
+    ) -> BoxFuture<'a, Result<usize, Error>> {
+        Box::pin(retain_file_statuses(log_dir, keep))
+    }
+
+    /// Moves every status file in `log_dir` into its status archive, a single file that reads
+    /// fall back to, so that log directories of large collections don't hold thousands of files.
+    /// Statuses written after compaction go to status files again and take precedence over
+    /// archived ones until the next compaction. Returns the number of archived statuses.
+    fn compact_statuses<'a>(&'a self, log_dir: &'a Path) -> BoxFuture<'a, Result<usize, Error>> {
+        Box::pin(async move {
+            let mut archive = read_status_archive(log_dir).await?;
+            let status_paths = status_file_paths(log_dir)?;
+            for status_path in &status_paths {
+                let data = fs::read_to_string(status_path).await?;
+                let file_stem = status_path.file_stem().unwrap().to_string_lossy();
+                archive.insert(file_stem.to_string(), serde_json::from_str(&data)?);
+            }
+            write_status_archive(log_dir, &archive).await?;
+            for status_path in status_paths {
+                fs::remove_file(status_path).await?;
+            }
+            Ok(archive.len())
+        })
+    }
+}
+
+/// Returns the paths of the [`Status`] files in `log_dir`, leaving out its status archive.
+pub(crate) fn status_file_paths(log_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    Ok(glob(&log_dir.join("*.json").to_string_lossy())?
+        .filter_map(Result::ok)
+        .filter(|p| file_stem_is_status(p))
+        .collect())
+}
+
+/// Reads the statuses archived by [`crate::Arweave::compact_statuses`], keyed by the stems of their
+/// status files. Returns an empty archive if `log_dir` hasn't been compacted.
+async fn read_status_archive(log_dir: &Path) -> Result<BTreeMap<String, Status>, Error> {
+    let archive_path = log_dir.join(STATUS_ARCHIVE_FILE_NAME);
+    if !archive_path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let data = fs::read_to_string(archive_path).await?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+/// Replaces the status archive of `log_dir` atomically.
+async fn write_status_archive(
+    log_dir: &Path,
+    archive: &BTreeMap<String, Status>,
+) -> Result<(), Error> {
+    let archive_path = log_dir.join(STATUS_ARCHIVE_FILE_NAME);
+    let temp_path = archive_path.with_extension("json.tmp");
+    fs::write(&temp_path, serde_json::to_string(archive)?).await?;
+    fs::rename(&temp_path, &archive_path).await?;
+    Ok(())
+}
+
+/// Removes the statuses in `log_dir`, both status files and archived, that `keep` returns false
+/// for. Returns the number of statuses removed.
+async fn retain_file_statuses(log_dir: &Path, keep: &StatusFilterFn<'_>) -> Result<usize, Error> {
+    let mut removed = 0;
+    for status_path in status_file_paths(log_dir)? {
+        let data = fs::read_to_string(&status_path).await?;
+        if !keep(&serde_json::from_str::<Status>(&data)?) {
+            fs::remove_file(status_path).await?;
+            removed += 1;
+        }
+    }
+
+    let mut archive = read_status_archive(log_dir).await?;
+    let archived = archive.len();
+    archive.retain(|_, status| keep(status));
+    if archive.len() < archived {
+        removed += archived - archive.len();
+        write_status_archive(log_dir, &archive).await?;
+    }
+    Ok(removed)
+}
+
+/// Keeps statuses in memory, for tests and short-lived processes that don't need them
+/// afterwards.
+#[derive(Debug, Default)]
+pub struct MemoryStatusStore {
+    statuses: Mutex<BTreeMap<PathBuf, BTreeMap<String, Status>>>,
+}
+
+impl StatusStore for MemoryStatusStore {
+    fn write_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+        status: &'a Status,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.statuses
+            .lock()
+            .unwrap()
+            .entry(log_dir.to_path_buf())
+            .or_default()
+            .insert(key.to_string(), status.clone());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn read_status<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Status>, Error>> {
+        let status = self
+            .statuses
+            .lock()
+            .unwrap()
+            .get(log_dir)
+            .and_then(|statuses| statuses.get(key).cloned());
+        Box::pin(async { Ok(status) })
+    }
+
+    fn list_statuses<'a>(
+        &'a self,
+        log_dir: PathBuf,
+    ) -> Result<BoxStream<'a, Result<Status, Error>>, Error> {
+        let statuses: Vec<Result<Status, Error>> = self
+            .statuses
+            .lock()
+            .unwrap()
+            .get(&log_dir)
+            .map(|statuses| statuses.values().cloned().map(Ok).collect())
+            .unwrap_or_default();
+        Ok(stream::iter(statuses).boxed())
+    }
+
+    fn retain_statuses<'a>(
+        &'a self,
+        log_dir: &'a Path,
+        keep: &'a StatusFilterFn<'a>,
+    ) -> BoxFuture<'a, Result<usize, Error>> {
+        let removed = match self.statuses.lock().unwrap().get_mut(log_dir) {
+            Some(statuses) => {
+                let len = statuses.len();
+                statuses.retain(|_, status| keep(status));
+                len - statuses.len()
+            }
+            None => 0,
+        };
+        Box::pin(async move { Ok(removed) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileStatusStore, MemoryStatusStore, StatusStore};
+    use crate::{
+        error::Error,
+        simulation::SimulatedGateway,
+        status::{Status, StatusCode},
+        transaction::Base64,
+        utils::TempDir,
+        Arweave,
+    };
+    use std::{path::PathBuf, sync::Arc, time::Duration};
+
+    #[tokio::test]
+    async fn test_memory_status_store() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let arweave = Arweave {
+            simulation: Some(Arc::new(SimulatedGateway::new(
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+            ))),
+            status_store: Arc::new(MemoryStatusStore::default()),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert!(!temp_dir
+            .0
+            .join(crate::hash_file_path(&file_path))
+            .with_extension("json")
+            .exists());
+
+        let read = arweave
+            .read_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(read.id, status.id);
+        let updated = arweave.update_status(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(updated.status, StatusCode::Confirmed);
+        let statuses = arweave.read_all_statuses(temp_dir.0.clone()).await?;
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].status, StatusCode::Confirmed);
+        assert!(matches!(
+            arweave
+                .read_status(PathBuf::from("tests/fixtures/1.png"), temp_dir.0.clone())
+                .await,
+            Err(Error::StatusNotFound)
+        ));
+
+        assert_eq!(arweave.compact_statuses(temp_dir.0.clone()).await?, 0);
+        assert_eq!(
+            arweave
+                .prune_statuses(temp_dir.0.clone(), chrono::Utc::now())
+                .await?,
+            1
+        );
+        assert!(arweave
+            .read_all_statuses(temp_dir.0.clone())
+            .await?
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_status_store_reads_archive_once() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let store = Arc::new(FileStatusStore::default());
+        let arweave = Arweave {
+            status_store: store.clone(),
+            ..Arweave::default()
+        };
+        for i in 0..3u8 {
+            let status = Status {
+                id: Base64(vec![i; 32]),
+                file_path: Some(PathBuf::from(format!("tests/fixtures/{}.png", i))),
+                ..Default::default()
+            };
+            arweave
+                .write_status(status, temp_dir.0.clone(), None)
+                .await?;
+        }
+        arweave.compact_statuses(temp_dir.0.clone()).await?;
+
+        let key = crate::hash_file_path(&PathBuf::from("tests/fixtures/0.png"));
+        assert!(store.read_status(&temp_dir.0, &key).await?.is_some());
+        let first = store.status_archive(&temp_dir.0).await?;
+        let second = store.status_archive(&temp_dir.0).await?;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        arweave
+            .prune_statuses(temp_dir.0.clone(), chrono::Utc::now())
+            .await?;
+        assert!(store.read_status(&temp_dir.0, &key).await?.is_none());
+        Ok(())
+    }
+}
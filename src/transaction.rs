@@ -5,7 +5,7 @@ use crate::{
     merkle::{Node, Proof},
 };
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 
 /// Transaction data structure per [Arweave transaction spec](https://docs.arweave.org/developers/server/http-api#transaction-format).
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
@@ -17,13 +17,13 @@ pub struct Transaction {
     pub tags: Vec<Tag<Base64>>,
     pub target: Base64,
     #[serde(with = "stringify")]
-    pub quantity: u64,
+    pub quantity: u128,
     pub data_root: Base64,
     pub data: Base64,
     #[serde(with = "stringify")]
     pub data_size: u64,
     #[serde(with = "stringify")]
-    pub reward: u64,
+    pub reward: u128,
     pub signature: Base64,
     #[serde(skip)]
     pub chunks: Vec<Node>,
@@ -40,7 +40,21 @@ pub struct Chunk {
     data_path: Base64,
     #[serde(with = "stringify")]
     pub offset: usize,
-    chunk: Base64,
+    pub(crate) chunk: Base64,
+}
+
+impl Chunk {
+    /// Creates a chunk from its data and [`Proof`], e.g. one regenerated from a
+    /// [`crate::merkle::LeafIndex`].
+    pub fn new(data_root: Base64, data_size: u64, proof: Proof, chunk: Vec<u8>) -> Self {
+        Self {
+            data_root,
+            data_size,
+            data_path: Base64(proof.proof),
+            offset: proof.offset,
+            chunk: Base64(chunk),
+        }
+    }
 }
 
 /// Serializes and deserializes numbers represented as Strings. Used for `quantity`, `data_size`
@@ -102,6 +116,70 @@ impl Transaction {
     }
 }
 
+/// Signed [`Transaction`] header with the [`Chunk`]s of its data, written to disk so that the
+/// transaction can be posted again without re-reading and re-hashing the data.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SignedTransaction {
+    pub transaction: Transaction,
+    pub chunks: Vec<Chunk>,
+}
+
+impl SignedTransaction {
+    pub fn from_transaction(signed_transaction: &Transaction) -> Result<Self, Error> {
+        if signed_transaction.id.0.is_empty() {
+            return Err(Error::UnsignedTransaction);
+        }
+        Ok(Self {
+            transaction: signed_transaction.clone_with_no_data()?,
+            chunks: (0..signed_transaction.chunks.len())
+                .map(|i| signed_transaction.get_chunk(i))
+                .collect::<Result<Vec<Chunk>, Error>>()?,
+        })
+    }
+}
+
+/// Chunk of the data of a transaction as returned by the `chunk/{offset}` endpoint of a node,
+/// with the proof of its position in the data in `data_path`.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct DownloadedChunk {
+    pub chunk: Base64,
+    pub data_path: Base64,
+}
+
+/// Position of the data of a transaction in the weave, as returned by the `tx/{id}/offset`
+/// endpoint of a node. `offset` is the weave offset of the last byte of the data.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct TxOffset {
+    #[serde(with = "stringify")]
+    pub size: u64,
+    #[serde(with = "stringify")]
+    pub offset: u64,
+}
+
+/// Unsigned [`Transaction`] header with the [`Chunk`]s of its data, written to disk so that it
+/// can be signed on a machine without network access. The header has no owner, which is set to
+/// the modulus of the signing keypair by [`crate::Arweave::sign_unsigned_transaction`].
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct UnsignedTransaction {
+    pub transaction: Transaction,
+    pub chunks: Vec<Chunk>,
+}
+
+impl UnsignedTransaction {
+    pub fn from_transaction(transaction: &Transaction) -> Result<Self, Error> {
+        let mut header = transaction.clone_with_no_data()?;
+        header.owner = Base64::default();
+        header.id = Base64::default();
+        header.signature = Base64::default();
+        Ok(Self {
+            transaction: header,
+            chunks: (0..transaction.chunks.len())
+                .map(|i| transaction.get_chunk(i))
+                .collect::<Result<Vec<Chunk>, Error>>()?,
+        })
+    }
+}
+
 /// Implemented on [`Transaction`] to create root [`DeepHashItem`]s used by
 /// [`crate::crypto::Provider::deep_hash`] in the creation of a transaction
 /// signatures.
@@ -186,6 +264,289 @@ impl FromUtf8Strs<Tag<String>> for Tag<String> {
     }
 }
 
+impl From<&Tag<Base64>> for Tag<String> {
+    /// Decodes the name and value of a transaction tag, replacing invalid utf-8.
+    fn from(tag: &Tag<Base64>) -> Self {
+        Self {
+            name: String::from_utf8_lossy(&tag.name.0).to_string(),
+            value: String::from_utf8_lossy(&tag.value.0).to_string(),
+        }
+    }
+}
+
+/// Maximum number of tags of a transaction or data item.
+pub const MAX_TAGS: usize = 128;
+
+/// Maximum size in bytes of the name of a tag.
+pub const MAX_TAG_NAME_BYTES: usize = 1024;
+
+/// Maximum size in bytes of the value of a tag.
+pub const MAX_TAG_VALUE_BYTES: usize = 3072;
+
+/// Maximum total size in bytes of the names and values of the tags of a transaction.
+pub const MAX_TX_TAGS_BYTES: usize = 2048;
+
+/// Maximum total size in bytes of the names and values of the tags of a data item.
+pub const MAX_DATA_ITEM_TAGS_BYTES: usize = 4096;
+
+/// Checks `tags` against the protocol limits on their number, the size of each name and value
+/// and their total size, `max_total_bytes`, so that tags the network would reject fail before
+/// the transaction is signed.
+pub fn validate_tags<T>(tags: &[Tag<T>], max_total_bytes: usize) -> Result<(), Error>
+where
+    T: AsRef<[u8]>,
+{
+    if tags.len() > MAX_TAGS {
+        return Err(Error::TagLimitExceeded(format!(
+            "{} tags, more than {}",
+            tags.len(),
+            MAX_TAGS
+        )));
+    }
+    let mut total_bytes = 0;
+    for (i, tag) in tags.iter().enumerate() {
+        let (name, value) = (tag.name.as_ref(), tag.value.as_ref());
+        let tag_name = String::from_utf8_lossy(&name[..name.len().min(32)]).to_string();
+        if name.len() > MAX_TAG_NAME_BYTES {
+            return Err(Error::TagLimitExceeded(format!(
+                "name of tag {} ({}...) is {} bytes, more than {}",
+                i,
+                tag_name,
+                name.len(),
+                MAX_TAG_NAME_BYTES
+            )));
+        }
+        if value.len() > MAX_TAG_VALUE_BYTES {
+            return Err(Error::TagLimitExceeded(format!(
+                "value of tag {} ({}) is {} bytes, more than {}",
+                i,
+                tag_name,
+                value.len(),
+                MAX_TAG_VALUE_BYTES
+            )));
+        }
+        total_bytes += name.len() + value.len();
+    }
+    if total_bytes > max_total_bytes {
+        return Err(Error::TagLimitExceeded(format!(
+            "tags are {} bytes in total, more than {}",
+            total_bytes, max_total_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Pattern for deriving [`Tag`]s from the directory structure of file paths, e.g.
+/// `collection/{Collection}/{Edition}` tags `collection/apes/1/0.png` with `Collection=apes`
+/// and `Edition=1`. Components in braces capture the directory name as the tag value, `*`
+/// matches any directory and all other components must match exactly. The pattern is matched
+/// against the trailing directories of each file path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagPattern(Vec<TagPatternComponent>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum TagPatternComponent {
+    Any,
+    Capture(String),
+    Literal(String),
+}
+
+impl FromStr for TagPattern {
+    type Err = Error;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let components = str
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .map(|c| {
+                if c == "*" {
+                    Ok(TagPatternComponent::Any)
+                } else if let Some(name) = c.strip_prefix('{').and_then(|c| c.strip_suffix('}')) {
+                    match name.is_empty() || name.contains(&['{', '}'][..]) {
+                        true => Err(Error::InvalidTagPattern),
+                        false => Ok(TagPatternComponent::Capture(name.to_string())),
+                    }
+                } else if c.contains(&['{', '}'][..]) {
+                    Err(Error::InvalidTagPattern)
+                } else {
+                    Ok(TagPatternComponent::Literal(c.to_string()))
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if components.is_empty() {
+            return Err(Error::InvalidTagPattern);
+        }
+        Ok(Self(components))
+    }
+}
+
+impl TagPattern {
+    /// Returns the tags captured from the directories of `file_path`, or an empty [`Vec`] if
+    /// the pattern doesn't match.
+    pub fn tags_for_path<T>(&self, file_path: &Path) -> Result<Vec<T>, Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        let dirs: Vec<String> = file_path
+            .parent()
+            .map(|p| {
+                p.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if dirs.len() < self.0.len() {
+            return Ok(Vec::new());
+        }
+
+        let mut tags = Vec::new();
+        for (component, dir) in self.0.iter().zip(&dirs[dirs.len() - self.0.len()..]) {
+            match component {
+                TagPatternComponent::Any => {}
+                TagPatternComponent::Capture(name) => tags.push(T::from_utf8_strs(name, dir)?),
+                TagPatternComponent::Literal(literal) if literal == dir => {}
+                TagPatternComponent::Literal(_) => return Ok(Vec::new()),
+            }
+        }
+        Ok(tags)
+    }
+}
+
+/// Disposition gateways are asked to serve uploaded files with through a `Content-Disposition`
+/// tag, so that browsers open or download them under their original file names. Gateways serve
+/// paths of manifests with the tags of the transactions they point to, so the tags also apply to
+/// files opened through manifests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentDisposition {
+    Inline,
+    Attachment,
+}
+
+impl FromStr for ContentDisposition {
+    type Err = Error;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "inline" => Ok(ContentDisposition::Inline),
+            "attachment" => Ok(ContentDisposition::Attachment),
+            _ => Err(Error::InvalidContentDisposition(str.to_string())),
+        }
+    }
+}
+
+impl ContentDisposition {
+    /// Returns the value of a `Content-Disposition` header for `file_name`, as described in
+    /// [RFC 6266](https://www.rfc-editor.org/rfc/rfc6266). Names that aren't plain ASCII get an
+    /// ASCII `filename` fallback and a percent-encoded UTF-8 `filename*`.
+    pub fn header_value(&self, file_name: &str) -> String {
+        let disposition = match self {
+            ContentDisposition::Inline => "inline",
+            ContentDisposition::Attachment => "attachment",
+        };
+        let fallback: String = file_name
+            .chars()
+            .map(|c| match c {
+                ' '..='~' if c != '"' && c != '\\' && c != '%' => c,
+                _ => '_',
+            })
+            .collect();
+        if fallback == file_name {
+            return format!("{}; filename=\"{}\"", disposition, file_name);
+        }
+        let encoded: String = file_name
+            .bytes()
+            .map(|b| match b {
+                b'a'..=b'z'
+                | b'A'..=b'Z'
+                | b'0'..=b'9'
+                | b'!'
+                | b'#'
+                | b'$'
+                | b'&'
+                | b'+'
+                | b'-'
+                | b'.'
+                | b'^'
+                | b'_'
+                | b'`'
+                | b'|'
+                | b'~' => (b as char).to_string(),
+                _ => format!("%{:02X}", b),
+            })
+            .collect();
+        format!(
+            "{}; filename=\"{}\"; filename*=UTF-8''{}",
+            disposition, fallback, encoded
+        )
+    }
+
+    /// Returns `Content-Disposition` and `File-Name` tags for the file name of `file_path`.
+    pub fn tags_for_path<T>(&self, file_path: &Path) -> Result<Vec<T>, Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        let file_name = match file_path.file_name() {
+            Some(file_name) => file_name.to_string_lossy(),
+            None => return Ok(Vec::new()),
+        };
+        Ok(vec![
+            T::from_utf8_strs("Content-Disposition", &self.header_value(&file_name))?,
+            T::from_utf8_strs("File-Name", &file_name)?,
+        ])
+    }
+}
+
+/// Application that transactions are stamped with through `App-Name` and `App-Version` tags,
+/// along with the `Unix-Time` they were created at, so that uploads can be queried by the
+/// application that made them. Defaults to `arloader` and the version of the crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppTags {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl Default for AppTags {
+    fn default() -> Self {
+        Self {
+            name: String::from("arloader"),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        }
+    }
+}
+
+impl FromStr for AppTags {
+    type Err = Error;
+    /// Parses `NAME` or `NAME/VERSION`.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (name, version) = match str.split_once('/') {
+            Some((name, version)) => (name, Some(version.to_string())),
+            None => (str, None),
+        };
+        if name.is_empty() || version.as_deref() == Some("") {
+            return Err(Error::InvalidAppTags(str.to_string()));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            version,
+        })
+    }
+}
+
+impl AppTags {
+    /// Returns the `App-Name`, `App-Version` and `Unix-Time` tags for a transaction created at
+    /// `unix_time`.
+    pub fn tags<T>(&self, unix_time: i64) -> Result<Vec<T>, Error>
+    where
+        T: FromUtf8Strs<T>,
+    {
+        let mut tags = vec![T::from_utf8_strs("App-Name", &self.name)?];
+        if let Some(version) = &self.version {
+            tags.push(T::from_utf8_strs("App-Version", version)?);
+        }
+        tags.push(T::from_utf8_strs("Unix-Time", &unix_time.to_string())?);
+        Ok(tags)
+    }
+}
+
 impl<'a> ToItems<'a, Vec<Tag<Base64>>> for Vec<Tag<Base64>> {
     fn to_deep_hash_item(&'a self) -> Result<DeepHashItem, Error> {
         if self.len() > 0 {
@@ -244,6 +605,12 @@ impl Base64 {
     }
 }
 
+impl AsRef<[u8]> for Base64 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl Serialize for Base64 {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.collect_str(&format!("{}", &self))
@@ -307,9 +674,13 @@ impl Uploader {
 
 #[cfg(test)]
 mod tests {
-    use super::{Base64, DeepHashItem, Error, FromUtf8Strs, Tag, ToItems};
+    use super::{
+        validate_tags, AppTags, Base64, ContentDisposition, DeepHashItem, Error, FromUtf8Strs, Tag,
+        TagPattern, ToItems, MAX_DATA_ITEM_TAGS_BYTES, MAX_TAGS, MAX_TX_TAGS_BYTES,
+    };
+    use matches::assert_matches;
     use serde_json;
-    use std::str::FromStr;
+    use std::{path::PathBuf, str::FromStr};
 
     #[test]
     fn test_deserialize_base64() -> Result<(), Error> {
@@ -371,4 +742,104 @@ mod tests {
         assert_eq!(deep_hash_item, deep_hash_item_actual);
         Ok(())
     }
+
+    #[test]
+    fn test_validate_tags() -> Result<(), Error> {
+        let tags = vec![Tag::<Base64>::from_utf8_strs("App-Name", "arloader-test")?; MAX_TAGS];
+        validate_tags(&tags, MAX_DATA_ITEM_TAGS_BYTES)?;
+        assert_matches!(
+            validate_tags(&tags, MAX_TX_TAGS_BYTES),
+            Err(Error::TagLimitExceeded(_))
+        );
+        let too_many = vec![Tag::<String>::from_utf8_strs("a", "b")?; MAX_TAGS + 1];
+        assert_matches!(
+            validate_tags(&too_many, MAX_TX_TAGS_BYTES),
+            Err(Error::TagLimitExceeded(_))
+        );
+        let long_name = vec![Tag::<String>::from_utf8_strs(&"a".repeat(1025), "b")?];
+        assert_matches!(
+            validate_tags(&long_name, MAX_DATA_ITEM_TAGS_BYTES),
+            Err(Error::TagLimitExceeded(e)) if e.starts_with("name of tag 0")
+        );
+        let long_value = vec![Tag::<String>::from_utf8_strs("Input", &"a".repeat(3073))?];
+        assert_matches!(
+            validate_tags(&long_value, MAX_DATA_ITEM_TAGS_BYTES),
+            Err(Error::TagLimitExceeded(e)) if e.starts_with("value of tag 0 (Input)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_pattern() -> Result<(), Error> {
+        let pattern = TagPattern::from_str("collection/{Collection}/*/{Edition}")?;
+
+        let tags: Vec<Tag<String>> =
+            pattern.tags_for_path(&PathBuf::from("assets/collection/apes/hd/1/0.png"))?;
+        assert_eq!(
+            tags,
+            vec![
+                Tag::<String>::from_utf8_strs("Collection", "apes")?,
+                Tag::<String>::from_utf8_strs("Edition", "1")?,
+            ]
+        );
+
+        let tags: Vec<Tag<String>> =
+            pattern.tags_for_path(&PathBuf::from("assets/other/apes/hd/1/0.png"))?;
+        assert!(tags.is_empty());
+        let tags: Vec<Tag<String>> = pattern.tags_for_path(&PathBuf::from("apes/1/0.png"))?;
+        assert!(tags.is_empty());
+
+        assert_matches!(TagPattern::from_str("a/{}"), Err(Error::InvalidTagPattern));
+        assert_matches!(TagPattern::from_str("a/{b"), Err(Error::InvalidTagPattern));
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_disposition() -> Result<(), Error> {
+        let tags: Vec<Tag<String>> = ContentDisposition::Attachment
+            .tags_for_path(&PathBuf::from("reports/q1 report.pdf"))?;
+        assert_eq!(
+            tags,
+            vec![
+                Tag::<String>::from_utf8_strs(
+                    "Content-Disposition",
+                    "attachment; filename=\"q1 report.pdf\""
+                )?,
+                Tag::<String>::from_utf8_strs("File-Name", "q1 report.pdf")?,
+            ]
+        );
+
+        assert_eq!(
+            ContentDisposition::Inline.header_value("résumé \"final\".pdf"),
+            "inline; filename=\"r_sum_ _final_.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9%20%22final%22.pdf"
+        );
+        assert_matches!(
+            ContentDisposition::from_str("download"),
+            Err(Error::InvalidContentDisposition(_))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_app_tags() -> Result<(), Error> {
+        let tags: Vec<Tag<String>> = AppTags::default().tags(1660000000)?;
+        assert_eq!(
+            tags[0],
+            Tag::<String>::from_utf8_strs("App-Name", "arloader")?
+        );
+        assert_eq!(tags[1].name, "App-Version");
+        assert_eq!(
+            tags[2],
+            Tag::<String>::from_utf8_strs("Unix-Time", "1660000000")?
+        );
+
+        let app_tags = AppTags::from_str("gallery")?;
+        assert_eq!(app_tags.tags::<Tag<String>>(0)?.len(), 2);
+        assert_eq!(
+            AppTags::from_str("gallery/2.1.0")?.version.as_deref(),
+            Some("2.1.0")
+        );
+        assert_matches!(AppTags::from_str("/1.0"), Err(Error::InvalidAppTags(_)));
+        Ok(())
+    }
 }
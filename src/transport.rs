@@ -0,0 +1,500 @@
+//! Requests to the endpoints of a gateway, behind [`ArweaveTransport`].
+
+use crate::{
+    error::Error,
+    fetch_tx_anchor, rt,
+    status::TxStatusResponse,
+    transaction::{Base64, Chunk, DownloadedChunk, Transaction, TxOffset},
+    Arweave,
+};
+use futures::future::BoxFuture;
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    StatusCode as ResponseStatusCode,
+};
+use serde_json::Value;
+#[cfg(feature = "test-util")]
+use std::{collections::HashMap, sync::Mutex};
+use tracing::debug;
+
+/// Endpoints of a gateway that [`Arweave`] uploads and updates statuses with. Set it on
+/// [`Arweave::transport`].
+pub trait ArweaveTransport: Send + Sync {
+    /// Returns the price in winstons of uploading `bytes` bytes of data.
+    fn get_price<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        bytes: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>>;
+
+    /// Returns an anchor to use as `last_tx` for a new transaction.
+    fn get_tx_anchor<'a>(&'a self, arweave: &'a Arweave) -> BoxFuture<'a, Result<Base64, Error>>;
+
+    fn post_transaction<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        transaction: &'a Transaction,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    fn post_chunk<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        chunk: &'a Chunk,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    fn get_tx_status<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxStatusResponse, Error>>;
+
+    /// Returns the response status and body of the header of the transaction with `id`, `200`
+    /// with its json once it has been mined and `202` while it is pending.
+    fn get_transaction<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<(ResponseStatusCode, String), Error>>;
+
+    /// Returns the data of the transaction with `id` as stored, before any `Content-Encoding` is
+    /// decoded.
+    fn get_transaction_data<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>>;
+
+    fn get_tx_offset<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxOffset, Error>>;
+
+    /// Returns the chunk that covers the byte at weave offset `offset`.
+    fn get_chunk<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<DownloadedChunk, Error>>;
+
+    /// Posts `query` to the GraphQL endpoint, returning the body of the response.
+    fn query_graphql<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        query: &'a Value,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+}
+
+/// GraphQL response without any transactions, returned by gateways that don't index any.
+pub const EMPTY_GRAPHQL_RESPONSE: &str =
+    r#"{"data":{"transactions":{"pageInfo":{"hasNextPage":false},"edges":[]}}}"#;
+
+/// Sends requests to [`Arweave::base_url`] with [`Arweave::client`]. Price responses go through
+/// [`Arweave::cache`], anchors through [`Arweave::anchor_cache`] and posts are
+/// limited by [`Arweave::host_limiter`] and [`Arweave::maintenance`] if set.
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport;
+
+impl ArweaveTransport for HttpTransport {
+    fn get_price<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        bytes: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("price/")?.join(&bytes.to_string())?;
+            let (_, body) = arweave
+                .get_price_with_cache(url)
+                .await
+                .map_err(|e| Error::ArweaveGetPriceError(e))?;
+            Ok(serde_json::from_str::<u64>(&body)?)
+        })
+    }
+
+    /// With an [`Arweave::anchor_cache`], the cached anchor is reused until it expires and is
+    /// refreshed in the background once it is past half its time to live.
+    fn get_tx_anchor<'a>(&'a self, arweave: &'a Arweave) -> BoxFuture<'a, Result<Base64, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("tx_anchor")?;
+            let anchor_cache = match &arweave.anchor_cache {
+                Some(anchor_cache) => anchor_cache,
+                None => return fetch_tx_anchor(&arweave.client, url).await,
+            };
+
+            match anchor_cache.get() {
+                Some((anchor, age)) if age < anchor_cache.ttl / 2 => Ok(anchor),
+                Some((anchor, _)) => {
+                    if anchor_cache.start_refresh() {
+                        let (client, anchor_cache) = (arweave.client.clone(), anchor_cache.clone());
+                        rt::spawn(async move {
+                            match fetch_tx_anchor(&client, url).await {
+                                Ok(anchor) => anchor_cache.insert(anchor),
+                                Err(e) => {
+                                    debug!("failed to refresh tx_anchor: {}", e);
+                                    anchor_cache.cancel_refresh();
+                                }
+                            }
+                        });
+                    }
+                    Ok(anchor)
+                }
+                None => {
+                    let anchor = fetch_tx_anchor(&arweave.client, url).await?;
+                    anchor_cache.insert(anchor.clone());
+                    Ok(anchor)
+                }
+            }
+        })
+    }
+
+    fn post_transaction<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        transaction: &'a Transaction,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("tx/")?;
+            let _permit = arweave.acquire_host_permit(&url).await;
+            let resp = arweave
+                .send_post(
+                    arweave
+                        .client
+                        .post(url)
+                        .json(transaction)
+                        .header(&ACCEPT, "application/json")
+                        .header(&CONTENT_TYPE, "application/json"),
+                )
+                .await?;
+            debug!("post_transaction {:?}", &resp);
+            if resp.status() != reqwest::StatusCode::OK {
+                let status = resp.status().as_u16();
+                return Err(Error::UnexpectedStatusResponse(status, resp.text().await?));
+            }
+            Ok(())
+        })
+    }
+
+    fn post_chunk<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        chunk: &'a Chunk,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("chunk/")?;
+            let _permit = arweave.acquire_host_permit(&url).await;
+            arweave
+                .send_post(
+                    arweave
+                        .client
+                        .post(url)
+                        .json(chunk)
+                        .header(&ACCEPT, "application/json")
+                        .header(&CONTENT_TYPE, "application/json"),
+                )
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn get_tx_status<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxStatusResponse, Error>> {
+        Box::pin(async move {
+            // Statuses change from one poll to the next, so they are never cached.
+            let url = arweave.base_url.join(&format!("tx/{}/status", id))?;
+            let resp = arweave.client.get(url).send().await?;
+            let resp_status = resp.status();
+            TxStatusResponse::from_response(resp_status, &resp.text().await?)
+        })
+    }
+
+    /// Responses go through [`Arweave::cache`].
+    fn get_transaction<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<(ResponseStatusCode, String), Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("tx/")?.join(&id.to_string())?;
+            Ok(arweave.get_with_cache(url).await?)
+        })
+    }
+
+    fn get_transaction_data<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join(&id.to_string())?;
+            let resp = arweave.client.get(url).send().await?.error_for_status()?;
+            Ok(resp.bytes().await?.to_vec())
+        })
+    }
+
+    fn get_tx_offset<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxOffset, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join(&format!("tx/{}/offset", id))?;
+            let resp = arweave.client.get(url).send().await?.error_for_status()?;
+            Ok(resp.json::<TxOffset>().await?)
+        })
+    }
+
+    fn get_chunk<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<DownloadedChunk, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join(&format!("chunk/{}", offset))?;
+            let resp = arweave.client.get(url).send().await?.error_for_status()?;
+            Ok(resp.json::<DownloadedChunk>().await?)
+        })
+    }
+
+    fn query_graphql<'a>(
+        &'a self,
+        arweave: &'a Arweave,
+        query: &'a Value,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async move {
+            let url = arweave.base_url.join("graphql")?;
+            let resp = arweave
+                .client
+                .post(url)
+                .json(query)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(resp.text().await?)
+        })
+    }
+}
+
+/// Price per byte charged by a [`MockGateway`] by default.
+#[cfg(feature = "test-util")]
+pub const MOCK_WINSTONS_PER_BYTE: u64 = 50;
+
+/// Answers transport requests in process, for tests. Prices are `winstons_per_byte` for every
+/// byte and anchors are always `anchor`. Posted transactions are reported as pending for the
+/// first `pending_polls` status requests and as confirmed afterwards, and transactions that
+/// were never posted as not found, so that results don't depend on timing. Headers of posted
+/// transactions can be fetched, but not their data or chunks, and GraphQL queries find
+/// nothing.
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub struct MockGateway {
+    pub winstons_per_byte: u64,
+    pub anchor: Base64,
+    pub pending_polls: u64,
+    transactions: Mutex<Vec<Transaction>>,
+    chunk_offsets: Mutex<Vec<usize>>,
+    polls: Mutex<HashMap<String, u64>>,
+}
+
+#[cfg(feature = "test-util")]
+impl Default for MockGateway {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl MockGateway {
+    pub fn new(pending_polls: u64) -> Self {
+        Self {
+            winstons_per_byte: MOCK_WINSTONS_PER_BYTE,
+            anchor: Base64(vec![1; 32]),
+            pending_polls,
+            transactions: Mutex::new(Vec::new()),
+            chunk_offsets: Mutex::new(Vec::new()),
+            polls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Headers of the transactions posted so far, in order, without their data.
+    pub fn posted_transactions(&self) -> Vec<Transaction> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|t| t.clone_with_no_data().unwrap())
+            .collect()
+    }
+
+    /// Offsets of the chunks posted so far, in order.
+    pub fn posted_chunk_offsets(&self) -> Vec<usize> {
+        self.chunk_offsets.lock().unwrap().clone()
+    }
+
+    fn status(&self, id: &Base64) -> TxStatusResponse {
+        let mut polls = self.polls.lock().unwrap();
+        let count = match polls.get_mut(&id.to_string()) {
+            Some(count) => count,
+            None => return TxStatusResponse::NotFound,
+        };
+        *count += 1;
+        if *count <= self.pending_polls {
+            TxStatusResponse::Pending
+        } else {
+            TxStatusResponse::Confirmed {
+                block_height: 0,
+                block_indep_hash: Base64(vec![0; 48]),
+                number_of_confirmations: *count - self.pending_polls,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl ArweaveTransport for MockGateway {
+    fn get_price<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        bytes: u64,
+    ) -> BoxFuture<'a, Result<u64, Error>> {
+        Box::pin(async move { Ok(bytes * self.winstons_per_byte) })
+    }
+
+    fn get_tx_anchor<'a>(&'a self, _arweave: &'a Arweave) -> BoxFuture<'a, Result<Base64, Error>> {
+        Box::pin(async move { Ok(self.anchor.clone()) })
+    }
+
+    fn post_transaction<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        transaction: &'a Transaction,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            self.polls
+                .lock()
+                .unwrap()
+                .insert(transaction.id.to_string(), 0);
+            self.transactions
+                .lock()
+                .unwrap()
+                .push(transaction.clone_with_no_data()?);
+            Ok(())
+        })
+    }
+
+    fn post_chunk<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        chunk: &'a Chunk,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            self.chunk_offsets.lock().unwrap().push(chunk.offset);
+            Ok(())
+        })
+    }
+
+    fn get_tx_status<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxStatusResponse, Error>> {
+        Box::pin(async move { Ok(self.status(id)) })
+    }
+
+    fn get_transaction<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        id: &'a Base64,
+    ) -> BoxFuture<'a, Result<(ResponseStatusCode, String), Error>> {
+        Box::pin(async move {
+            let transactions = self.transactions.lock().unwrap();
+            match transactions.iter().find(|t| &t.id == id) {
+                Some(transaction) => Ok((
+                    ResponseStatusCode::OK,
+                    serde_json::to_string(&transaction.clone_with_no_data()?)?,
+                )),
+                None => Ok((ResponseStatusCode::NOT_FOUND, String::new())),
+            }
+        })
+    }
+
+    fn get_transaction_data<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _id: &'a Base64,
+    ) -> BoxFuture<'a, Result<Vec<u8>, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn get_tx_offset<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _id: &'a Base64,
+    ) -> BoxFuture<'a, Result<TxOffset, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn get_chunk<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _offset: u64,
+    ) -> BoxFuture<'a, Result<DownloadedChunk, Error>> {
+        Box::pin(async { Err(Error::UnexpectedStatusResponse(404, String::new())) })
+    }
+
+    fn query_graphql<'a>(
+        &'a self,
+        _arweave: &'a Arweave,
+        _query: &'a Value,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async { Ok(EMPTY_GRAPHQL_RESPONSE.to_string()) })
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::MockGateway;
+    use crate::{error::Error, status::StatusCode, utils::TempDir, Arweave};
+    use std::{path::PathBuf, sync::Arc};
+
+    #[tokio::test]
+    async fn test_mock_gateway() -> Result<(), Error> {
+        let temp_dir = TempDir::from_str("./tests/").await?;
+        let mock = Arc::new(MockGateway::new(1));
+        let arweave = Arweave {
+            transport: mock.clone(),
+            ..Arweave::default()
+        };
+        let file_path = PathBuf::from("tests/fixtures/0.png");
+
+        let price_terms = arweave.get_price_terms(1.0).await?;
+        assert_eq!(price_terms, (256 * 1024 * 50, 256 * 1024 * 50));
+
+        let status = arweave
+            .upload_file_from_path(
+                file_path.clone(),
+                Some(temp_dir.0.clone()),
+                None,
+                None,
+                price_terms,
+                None,
+            )
+            .await?;
+        assert_eq!(status.status, StatusCode::Submitted);
+        let posted = mock.posted_transactions();
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0].id, status.id);
+        assert_eq!(posted[0].last_tx, mock.anchor);
+        assert_eq!(arweave.get_transaction(&status.id).await?.id, status.id);
+
+        let status = arweave
+            .update_status(file_path.clone(), temp_dir.0.clone())
+            .await?;
+        assert_eq!(status.status, StatusCode::Pending);
+        let status = arweave.update_status(file_path, temp_dir.0.clone()).await?;
+        assert_eq!(status.status, StatusCode::Confirmed);
+        Ok(())
+    }
+}
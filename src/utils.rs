@@ -1,10 +1,18 @@
-//! Async [`TempDir`] for testing.
+//! Async [`TempDir`] and a local HTTP server for testing.
 
-use crate::error::Error;
+use crate::{error::Error, rt::fs};
 use base64::{self, encode_config};
 use ring::rand::{SecureRandom, SystemRandom};
 use std::{fs as fsstd, path::PathBuf};
-use tokio::fs;
+#[cfg(test)]
+use std::{
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    thread::{self, JoinHandle},
+};
+#[cfg(test)]
+use url::Url;
 
 /// Tuple struct with a [`PathBuf`] in it.
 pub struct TempDir(pub PathBuf);
@@ -37,3 +45,75 @@ impl Drop for TempDir {
         }
     }
 }
+
+/// Answers `requests` HTTP requests on a local port in a background thread, one per connection,
+/// with the response `respond` returns for the text of each, e.g. from [`http_response`].
+/// Returns the base url of the server and a handle that joins with the requests it received.
+#[cfg(test)]
+pub(crate) fn serve_http<F>(
+    requests: usize,
+    mut respond: F,
+) -> (Url, JoinHandle<io::Result<Vec<String>>>)
+where
+    F: FnMut(&str) -> Vec<u8> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = Url::from_str(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let handle = thread::spawn(move || {
+        let mut received = Vec::new();
+        for _ in 0..requests {
+            let (mut stream, _) = listener.accept()?;
+            let request = read_http_request(&mut stream)?;
+            stream.write_all(&respond(&request))?;
+            received.push(request);
+        }
+        Ok(received)
+    });
+    (base_url, handle)
+}
+
+/// Returns a response with `status` and `body` that closes the connection.
+#[cfg(test)]
+pub(crate) fn http_response(status: u16, body: impl AsRef<[u8]>) -> Vec<u8> {
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or_default();
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        status,
+        reason,
+        body.as_ref().len()
+    )
+    .into_bytes();
+    response.extend_from_slice(body.as_ref());
+    response
+}
+
+/// Reads a request up to the end of the body given by its `content-length` header.
+#[cfg(test)]
+fn read_http_request(stream: &mut TcpStream) -> io::Result<String> {
+    let mut request = Vec::new();
+    let mut buf = [0; 65536];
+    loop {
+        let n = stream.read(&mut buf)?;
+        request.extend_from_slice(&buf[..n]);
+        if let Some(header_len) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            let content_len = String::from_utf8_lossy(&request[..header_len])
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .and_then(|len| len.trim().parse::<usize>().ok())
+                })
+                .unwrap_or(0);
+            if request.len() >= header_len + 4 + content_len {
+                break;
+            }
+        }
+        if n == 0 {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&request).into_owned())
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "native")]
+
 use arloader::{
     crypto::Provider,
     error::Error,
@@ -55,7 +57,7 @@ async fn test_post_transaction() -> Result<(), Error> {
     airdrop(&arweave).await?;
     let file_path = PathBuf::from("tests/fixtures/0.png");
     let transaction = arweave
-        .create_transaction_from_file_path(file_path, None, None, (0, 0), true)
+        .create_transaction_from_file_path(file_path, None, None, (0, 0), true, None)
         .await?;
 
     let signed_transaction = arweave.sign_transaction(transaction)?;
@@ -86,7 +88,14 @@ async fn test_upload_file_from_path() -> Result<(), Error> {
     let log_dir = temp_log_dir.0.clone();
 
     let status = arweave
-        .upload_file_from_path(file_path.clone(), Some(log_dir.clone()), None, None, (0, 0))
+        .upload_file_from_path(
+            file_path.clone(),
+            Some(log_dir.clone()),
+            None,
+            None,
+            (0, 0),
+            None,
+        )
         .await?;
 
     let read_status = arweave.read_status(file_path, log_dir.clone()).await?;
@@ -110,7 +119,14 @@ async fn test_update_status() -> Result<(), Error> {
     let log_dir = temp_log_dir.0.clone();
 
     let _ = arweave
-        .upload_file_from_path(file_path.clone(), Some(log_dir.clone()), None, None, (0, 0))
+        .upload_file_from_path(
+            file_path.clone(),
+            Some(log_dir.clone()),
+            None,
+            None,
+            (0, 0),
+            None,
+        )
         .await?;
 
     let read_status = arweave
@@ -264,7 +280,8 @@ async fn test_filter_statuses() -> Result<(), Error> {
     // to fetch their raw statuses from the server.
     let paths_iter = glob("tests/fixtures/[5-9]*.png")?.filter_map(Result::ok);
     let transactions = try_join_all(
-        paths_iter.map(|p| arweave.create_transaction_from_file_path(p, None, None, (0, 0), true)),
+        paths_iter
+            .map(|p| arweave.create_transaction_from_file_path(p, None, None, (0, 0), true, None)),
     )
     .await?;
     let _ = try_join_all(
@@ -339,9 +356,9 @@ async fn test_upload_files_stream() -> Result<(), Error> {
     let mut _tags_iter = Some(iter::repeat(Some(Vec::<Tag<Base64>>::new())));
     _tags_iter = None;
 
-    let mut stream = upload_files_stream(&arweave, paths_iter, None, None, None, (0, 0), 3);
+    let mut stream = upload_files_stream(&arweave, paths_iter, None, None, false, None, (0, 0), 3);
 
-    let output_format = OutputFormat::JsonCompact;
+    let output_format = OutputFormat::Json;
 
     let mut counter = 0;
     while let Some(Ok(status)) = stream.next().await {
@@ -354,6 +371,43 @@ async fn test_upload_files_stream() -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn test_upload_files_stream_with_mock_gateway() -> Result<(), Error> {
+    use arloader::transport::MockGateway;
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+
+    let mock = Arc::new(MockGateway::new(0));
+    let arweave = Arweave {
+        transport: mock.clone(),
+        ..get_arweave().await?
+    };
+    let paths_iter = glob("tests/fixtures/[0-9]*.png")?.filter_map(Result::ok);
+    let temp_log_dir = TempDir::from_str("./tests/").await?;
+    let log_dir = temp_log_dir.0.clone();
+
+    let price_terms = arweave.get_price_terms(1.0).await?;
+    let statuses: Vec<Status> = upload_files_stream(
+        &arweave,
+        paths_iter,
+        None,
+        Some(log_dir.clone()),
+        false,
+        None,
+        price_terms,
+        3,
+    )
+    .try_collect()
+    .await?;
+    assert_eq!(mock.posted_transactions().len(), statuses.len());
+
+    let paths_iter = glob("tests/fixtures/[0-9]*.png")?.filter_map(Result::ok);
+    let statuses = arweave.update_statuses(paths_iter, log_dir).await?;
+    assert!(statuses.iter().all(|s| s.status == StatusCode::Confirmed));
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_upload_file_from_path_with_sol() -> Result<(), Error> {
     let solana_url = "https://api.devnet.solana.com/".parse::<Url>()?;